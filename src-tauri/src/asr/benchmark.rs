@@ -0,0 +1,87 @@
+//! Provider 基准测试
+//!
+//! 将同一段音频依次送入多个 Provider，记录耗时和识别结果，方便用户对比不同 Provider
+//! 对自己口音的识别效果。
+
+use serde::Serialize;
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+use super::codec::AudioEncoder;
+use super::provider::{AsrError, AsrProvider, AsrResult};
+
+/// 每次发送的样本数（16kHz 下 200ms）
+const CHUNK_SAMPLES: usize = 3200;
+
+/// 单个 Provider 的测试结果
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkResult {
+    pub provider_id: String,
+    pub display_name: String,
+    /// 识别文本（失败时为空）
+    pub text: String,
+    /// 从开始发送音频到拿到最终结果的耗时
+    pub latency_ms: u64,
+    /// 识别置信度（Provider 支持时）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 把一段 16kHz 单声道 PCM 音频整体送入 Provider，返回最后一个识别结果
+///
+/// 音频按 Provider 声明的编码格式发送。基准测试和录音失败后的重试共用。
+pub async fn transcribe_samples(
+    provider: &dyn AsrProvider,
+    samples: &[i16],
+) -> (Result<(), AsrError>, Option<AsrResult>) {
+    let mut encoder = match AudioEncoder::new(provider.audio_codec()) {
+        Ok(encoder) => encoder,
+        Err(e) => return (Err(AsrError::Configuration(e)), None),
+    };
+    let (audio_tx, audio_rx) = mpsc::channel::<Vec<u8>>(samples.len() / CHUNK_SAMPLES + 2);
+    let (result_tx, mut result_rx) = mpsc::channel(32);
+
+    // 音频一次性入队，通道关闭即表示录音结束
+    for chunk in samples.chunks(CHUNK_SAMPLES) {
+        let data = encoder.encode(chunk);
+        if !data.is_empty() {
+            let _ = audio_tx.send(data).await;
+        }
+    }
+    let tail = encoder.finish();
+    if !tail.is_empty() {
+        let _ = audio_tx.send(tail).await;
+    }
+    drop(audio_tx);
+
+    let collect = async {
+        let mut last = None;
+        while let Some(result) = result_rx.recv().await {
+            last = Some(result);
+        }
+        last
+    };
+    tokio::join!(provider.transcribe_stream(audio_rx, result_tx), collect)
+}
+
+/// 用一段 16kHz 单声道 PCM 音频测试单个 Provider
+pub async fn run(provider: &dyn AsrProvider, samples: &[i16]) -> BenchmarkResult {
+    let started = Instant::now();
+    let (outcome, last) = transcribe_samples(provider, samples).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let (text, confidence) = last
+        .map(|r| (r.text, r.confidence))
+        .unwrap_or_default();
+
+    BenchmarkResult {
+        provider_id: provider.id().to_string(),
+        display_name: provider.display_name().to_string(),
+        text,
+        latency_ms,
+        confidence,
+        error: outcome.err().map(|e| e.to_string()),
+    }
+}
@@ -1,21 +1,19 @@
-use crate::asr::protocol::{AsrConfig, AsrResponse};
+use crate::asr::codec::AudioCodec;
+use crate::asr::protocol::{AsrConfig, AsrResponse, RequestConfig};
+use crate::asr::provider::{AsrSegment, WordTiming};
+use crate::asr::transport::{Connection, Transport, WebSocketTransport};
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
-use futures_util::{SinkExt, StreamExt};
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
 use std::borrow::Cow;
 use std::io::{Read, Write};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
-use tokio_tungstenite::{
-    connect_async,
-    tungstenite::{
-        http::{Request, Uri},
-        Message,
-    },
-};
+use tokio_tungstenite::tungstenite::http::{Request, Uri};
 
 // 豆包流式语音识别模型 2.0 API 端点
 const VOLCENGINE_ASR_URL: &str = "wss://openspeech.bytedance.com/api/v3/sauc/bigmodel";
@@ -33,18 +31,52 @@ const MESSAGE_TYPE_AUDIO_ONLY: u8 = 0x02;
 const MESSAGE_SERIAL_JSON: u8 = 0x01;
 const MESSAGE_COMPRESS_GZIP: u8 = 0x01;
 const MESSAGE_COMPRESS_NONE: u8 = 0x00;
+const MESSAGE_TYPE_ERROR: u8 = 0x0f;
 
-/// ASR 结果，包含文本和是否是 prefetch
+/// ASR 结果，包含文本、是否是 prefetch 和词级时间戳
 #[derive(Clone, Debug)]
 pub struct AsrResult {
     pub text: String,
     pub is_prefetch: bool,
+    pub words: Vec<WordTiming>,
+    pub segments: Vec<AsrSegment>,
+}
+
+/// 断线重连的初始等待时间
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_millis(500);
+/// 断线重连的最长等待时间
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(8);
+/// 单次断线最多重连次数
+const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+
+/// 一次录音会话的流式状态，跨连接保留
+#[derive(Default)]
+struct StreamSession {
+    /// 本次录音的全部音频（含断线期间缓存的部分），重连后完整重放
+    audio: Vec<Vec<u8>>,
+    /// 麦克风音频是否已全部收到
+    audio_done: bool,
+}
+
+/// 单个连接的结束原因
+enum ConnectionEnd {
+    /// 服务端在收到结束标记后关闭连接
+    Completed,
+    /// 结果接收方已关闭，无需继续
+    ResultReceiverClosed,
+    /// 连接异常断开，需要重连
+    Dropped(String),
+    /// 服务端返回错误，不再重连
+    Failed(String),
 }
 
 pub struct AsrClient {
     app_id: String,
     access_token: String,
     secret_key: String,
+    request: RequestConfig,
+    codec: AudioCodec,
+    transport: Arc<dyn Transport>,
 }
 
 impl AsrClient {
@@ -53,9 +85,30 @@ impl AsrClient {
             app_id,
             access_token,
             secret_key,
+            request: RequestConfig::default(),
+            codec: AudioCodec::Pcm,
+            transport: Arc::new(WebSocketTransport),
         }
     }
 
+    /// 替换传输层（录制 / 回放 / 测试）
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// 设置识别请求参数（标点、ITN、分句等）
+    pub fn with_request_config(mut self, request: RequestConfig) -> Self {
+        self.request = request;
+        self
+    }
+
+    /// 设置上传音频的编码（需与送入的音频数据一致）
+    pub fn with_audio_codec(mut self, codec: AudioCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
     fn generate_signature(&self, string_to_sign: &str) -> String {
         let mut mac = HmacSha256::new_from_slice(self.secret_key.as_bytes())
             .expect("HMAC can take key of any size");
@@ -210,13 +263,8 @@ impl AsrClient {
         }
     }
 
-    /// 连接并流式传输音频数据
-    /// result_tx 发送 AsrResult，包含 prefetch 状态
-    pub async fn connect_and_stream(
-        &self,
-        mut audio_rx: mpsc::Receiver<Vec<u8>>,
-        result_tx: mpsc::Sender<AsrResult>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// 建立经过鉴权的连接
+    async fn connect(&self) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
         let connect_id = uuid::Uuid::new_v4().to_string();
 
         let uri: Uri = VOLCENGINE_ASR_URL.parse()?;
@@ -246,83 +294,223 @@ impl AsrClient {
 
         log::info!("Connecting to ASR service");
 
-        let (ws_stream, _response) = connect_async(request).await?;
+        let connection = self.transport.connect(request).await?;
         log::info!("WebSocket connected");
+        Ok(connection)
+    }
+
+    /// 解析服务器错误帧，返回错误码和错误信息
+    fn parse_error(data: &[u8]) -> Option<(u32, String)> {
+        if data.len() < 4 || data[1] >> 4 != MESSAGE_TYPE_ERROR {
+            return None;
+        }
+        let header_size = (data[0] & 0x0f) as usize * 4;
+        let payload = data.get(header_size..)?;
+        if payload.len() < 8 {
+            return None;
+        }
+        let code = u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]);
+        let message = String::from_utf8_lossy(&payload[8..]).to_string();
+        Some((code, message))
+    }
+
+    /// 测试凭据是否有效：完成握手并发送初始化配置，检查服务端是否返回错误
+    pub async fn test_connection(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Connection {
+            mut sink,
+            mut source,
+        } = self.connect().await?;
+
+        let config_json = serde_json::to_vec(&AsrConfig::new(self.request.clone(), self.codec))?;
+        let init_msg = Self::build_seed_message(MESSAGE_TYPE_FULL_CLIENT, &config_json, true);
+        sink.send(init_msg).await?;
+        sink.send(Self::build_finish_message()).await?;
+
+        // 等待服务端首个响应，错误帧表示鉴权或配置有误
+        let first = tokio::time::timeout(Duration::from_secs(5), source.next()).await;
+        let _ = sink.close().await;
+
+        match first {
+            Ok(Some(Ok(data))) => match Self::parse_error(&data) {
+                Some((code, message)) => Err(format!("服务端错误 {}: {}", code, message).into()),
+                None => Ok(()),
+            },
+            Ok(Some(Err(e))) => Err(e),
+            // 超时或连接正常关闭：握手已成功，视为凭据有效
+            _ => Ok(()),
+        }
+    }
+
+    /// 连接并流式传输音频数据
+    /// result_tx 发送 AsrResult，包含 prefetch 状态
+    ///
+    /// 连接中途断开时按指数退避重连，新连接从头重放本次录音的全部音频，
+    /// 服务端返回的仍是整段文本，同一次录音不会丢失内容。
+    pub async fn connect_and_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut session = StreamSession::default();
+        let mut connection = self.open_session(&session).await?;
+
+        loop {
+            match self
+                .run_connection(connection, &mut audio_rx, &result_tx, &mut session)
+                .await
+            {
+                ConnectionEnd::Completed | ConnectionEnd::ResultReceiverClosed => break,
+                ConnectionEnd::Failed(message) => return Err(message.into()),
+                ConnectionEnd::Dropped(reason) => {
+                    log::warn!("ASR connection dropped: {}", reason);
+                    connection = self.reconnect(&mut audio_rx, &mut session).await?;
+                }
+            }
+        }
+
+        log::info!("ASR session completed");
+        Ok(())
+    }
 
-        let (mut write, mut read) = ws_stream.split();
+    /// 建立连接、发送初始化配置，并重放当前连接需要的音频
+    async fn open_session(
+        &self,
+        session: &StreamSession,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let mut connection = self.connect().await?;
 
-        // 发送初始化配置
-        let config = AsrConfig::default();
-        let config_json = serde_json::to_vec(&config)?;
+        let config_json = serde_json::to_vec(&AsrConfig::new(self.request.clone(), self.codec))?;
         let init_msg = Self::build_seed_message(MESSAGE_TYPE_FULL_CLIENT, &config_json, true);
-        write.send(Message::Binary(init_msg)).await?;
+        connection.sink.send(init_msg).await?;
 
-        let (stop_tx, mut stop_rx) = mpsc::channel::<()>(1);
+        if !session.audio.is_empty() {
+            log::info!("Replaying {} buffered audio chunks", session.audio.len());
+        }
+        for chunk in &session.audio {
+            connection.sink.send(Self::build_audio_message(chunk)).await?;
+        }
+        if session.audio_done {
+            connection.sink.send(Self::build_finish_message()).await?;
+        }
+        Ok(connection)
+    }
 
-        // 发送音频数据的任务
-        let send_task = tokio::spawn(async move {
+    /// 按指数退避重连，等待期间继续缓存麦克风音频
+    async fn reconnect(
+        &self,
+        audio_rx: &mut mpsc::Receiver<Vec<u8>>,
+        session: &mut StreamSession,
+    ) -> Result<Connection, Box<dyn std::error::Error + Send + Sync>> {
+        let mut delay = RECONNECT_INITIAL_DELAY;
+        for attempt in 1..=MAX_RECONNECT_ATTEMPTS {
+            log::info!(
+                "Reconnecting to ASR service in {:?} (attempt {}/{})",
+                delay,
+                attempt,
+                MAX_RECONNECT_ATTEMPTS
+            );
+            let sleep = tokio::time::sleep(delay);
+            tokio::pin!(sleep);
             loop {
                 tokio::select! {
-                    audio_data = audio_rx.recv() => {
-                        match audio_data {
-                            Some(data) => {
-                                let audio_msg = Self::build_audio_message(&data);
-                                if write.send(Message::Binary(audio_msg)).await.is_err() {
-                                    break;
-                                }
+                    _ = &mut sleep => break,
+                    audio_data = audio_rx.recv(), if !session.audio_done => match audio_data {
+                        Some(data) => session.audio.push(data),
+                        None => session.audio_done = true,
+                    },
+                }
+            }
+
+            match self.open_session(session).await {
+                Ok(connection) => {
+                    log::info!("ASR connection resumed");
+                    return Ok(connection);
+                }
+                Err(e) => log::warn!("Reconnect attempt {} failed: {}", attempt, e),
+            }
+            delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+        }
+        Err(format!("ASR 连接中断，重连 {} 次均失败", MAX_RECONNECT_ATTEMPTS).into())
+    }
+
+    /// 在单个连接上收发数据，直到会话完成或连接断开
+    async fn run_connection(
+        &self,
+        connection: Connection,
+        audio_rx: &mut mpsc::Receiver<Vec<u8>>,
+        result_tx: &mpsc::Sender<AsrResult>,
+        session: &mut StreamSession,
+    ) -> ConnectionEnd {
+        let Connection {
+            sink: mut write,
+            source: mut read,
+        } = connection;
+
+        let end = loop {
+            tokio::select! {
+                audio_data = audio_rx.recv(), if !session.audio_done => {
+                    match audio_data {
+                        Some(data) => {
+                            let audio_msg = Self::build_audio_message(&data);
+                            session.audio.push(data);
+                            if let Err(e) = write.send(audio_msg).await {
+                                break ConnectionEnd::Dropped(e.to_string());
                             }
-                            None => {
-                                log::info!("Audio channel closed, sending finish message");
-                                let _ = write.send(Message::Binary(Self::build_finish_message())).await;
-                                break;
+                        }
+                        None => {
+                            log::info!("Audio channel closed, sending finish message");
+                            session.audio_done = true;
+                            if let Err(e) = write.send(Self::build_finish_message()).await {
+                                break ConnectionEnd::Dropped(e.to_string());
                             }
                         }
                     }
-                    _ = stop_rx.recv() => {
-                        let _ = write.send(Message::Binary(Self::build_finish_message())).await;
-                        break;
-                    }
                 }
-            }
-        });
-
-        // 接收识别结果的任务
-        let recv_task = tokio::spawn(async move {
-            while let Some(msg) = read.next().await {
-                match msg {
-                    Ok(Message::Binary(data)) => {
-                        if let Some(response) = Self::parse_response(&data) {
-                            if response.is_success() {
-                                let result_text = response.get_text();
-                                if !result_text.is_empty() {
-                                    let result = AsrResult {
-                                        text: result_text,
-                                        is_prefetch: response.is_prefetch(),
-                                    };
-                                    if result_tx.send(result).await.is_err() {
-                                        break;
-                                    }
-                                }
+                msg = read.next() => {
+                    match msg {
+                        Some(Ok(data)) => {
+                            // 服务端错误（鉴权、配额等）重连也无法恢复
+                            if let Some((code, message)) = Self::parse_error(&data) {
+                                break ConnectionEnd::Failed(format!(
+                                    "服务端错误 {}: {}",
+                                    code, message
+                                ));
+                            }
+                            let Some(response) = Self::parse_response(&data) else {
+                                continue;
+                            };
+                            if !response.is_success() {
+                                continue;
+                            }
+                            let result_text = response.get_text();
+                            if result_text.is_empty() {
+                                continue;
+                            }
+                            let result = AsrResult {
+                                text: result_text,
+                                is_prefetch: response.is_prefetch(),
+                                words: response.get_words(),
+                                segments: response.get_segments(),
+                            };
+                            if result_tx.send(result).await.is_err() {
+                                break ConnectionEnd::ResultReceiverClosed;
                             }
                         }
+                        Some(Err(e)) => {
+                            log::error!("WebSocket error: {}", e);
+                            break ConnectionEnd::Dropped(e.to_string());
+                        }
+                        // 发送结束标记后服务端关闭连接，说明会话正常结束
+                        None if session.audio_done => break ConnectionEnd::Completed,
+                        None => break ConnectionEnd::Dropped("连接被服务端关闭".to_string()),
                     }
-                    Ok(Message::Close(_)) => {
-                        log::info!("WebSocket connection closed");
-                        break;
-                    }
-                    Err(e) => {
-                        log::error!("WebSocket error: {}", e);
-                        break;
-                    }
-                    _ => {}
                 }
             }
-            drop(stop_tx);
-        });
-
-        let _ = tokio::join!(send_task, recv_task);
-        log::info!("ASR session completed");
+        };
 
-        Ok(())
+        if !matches!(end, ConnectionEnd::Dropped(_)) {
+            let _ = write.close().await;
+        }
+        end
     }
 }
@@ -0,0 +1,186 @@
+//! 上传音频编码
+//!
+//! 录音统一为 16kHz 单声道 PCM，发送给 Provider 前按其声明的编码格式转换。
+//! 网络较差时 Ogg Opus 可将上行带宽从 256kbps 降到约 24kbps。
+
+#[cfg(feature = "opus")]
+use ogg::writing::{PacketWriteEndInfo, PacketWriter};
+use serde::{Deserialize, Serialize};
+
+/// 采样率（与录音一致）
+#[cfg(feature = "opus")]
+const SAMPLE_RATE: u32 = 16000;
+/// Opus 帧长（16kHz 下 20ms）
+#[cfg(feature = "opus")]
+const OPUS_FRAME_SAMPLES: usize = 320;
+/// Ogg Opus 的粒度位置固定按 48kHz 计算
+#[cfg(feature = "opus")]
+const GRANULE_PER_FRAME: u64 = 960;
+/// 语音编码码率
+#[cfg(feature = "opus")]
+const OPUS_BITRATE: i32 = 24000;
+/// 单个 Opus 包的最大字节数
+#[cfg(feature = "opus")]
+const MAX_PACKET_SIZE: usize = 4000;
+
+/// Provider 接收的音频编码格式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioCodec {
+    /// 16kHz/16bit/单声道 PCM（小端）
+    #[default]
+    Pcm,
+    /// Ogg 封装的 Opus
+    Opus,
+}
+
+/// 流式音频编码器
+pub enum AudioEncoder {
+    Pcm,
+    #[cfg(feature = "opus")]
+    Opus(Box<OggOpusEncoder>),
+}
+
+impl AudioEncoder {
+    pub fn new(codec: AudioCodec) -> Result<Self, String> {
+        match codec {
+            AudioCodec::Pcm => Ok(Self::Pcm),
+            #[cfg(feature = "opus")]
+            AudioCodec::Opus => Ok(Self::Opus(Box::new(OggOpusEncoder::new()?))),
+            #[cfg(not(feature = "opus"))]
+            AudioCodec::Opus => Err("Opus support is not enabled in this build".to_string()),
+        }
+    }
+
+    /// 编码一块 PCM 样本，返回可直接发送的数据（可能为空，表示数据不足一帧）
+    pub fn encode(&mut self, samples: &[i16]) -> Vec<u8> {
+        match self {
+            Self::Pcm => bytemuck::cast_slice(samples).to_vec(),
+            #[cfg(feature = "opus")]
+            Self::Opus(encoder) => encoder.encode(samples),
+        }
+    }
+
+    /// 结束编码，返回剩余的数据
+    pub fn finish(&mut self) -> Vec<u8> {
+        match self {
+            Self::Pcm => Vec::new(),
+            #[cfg(feature = "opus")]
+            Self::Opus(encoder) => encoder.finish(),
+        }
+    }
+}
+
+/// Ogg Opus 流式编码器
+#[cfg(feature = "opus")]
+pub struct OggOpusEncoder {
+    encoder: opus::Encoder,
+    writer: PacketWriter<'static, Vec<u8>>,
+    serial: u32,
+    /// 尚未凑满一帧的样本
+    pending: Vec<i16>,
+    granule: u64,
+    packet: Vec<u8>,
+}
+
+#[cfg(feature = "opus")]
+impl OggOpusEncoder {
+    pub fn new() -> Result<Self, String> {
+        let mut encoder =
+            opus::Encoder::new(SAMPLE_RATE, opus::Channels::Mono, opus::Application::Voip)
+                .map_err(|e| format!("Failed to create Opus encoder: {}", e))?;
+        encoder
+            .set_bitrate(opus::Bitrate::Bits(OPUS_BITRATE))
+            .map_err(|e| format!("Failed to set Opus bitrate: {}", e))?;
+        let lookahead = encoder.get_lookahead().unwrap_or(0).max(0) as u16;
+
+        let mut this = Self {
+            encoder,
+            writer: PacketWriter::new(Vec::new()),
+            serial: rand_serial(),
+            pending: Vec::with_capacity(OPUS_FRAME_SAMPLES * 2),
+            granule: 0,
+            packet: vec![0; MAX_PACKET_SIZE],
+        };
+        this.write_headers(lookahead * 3)?;
+        Ok(this)
+    }
+
+    /// 写入 OpusHead 和 OpusTags 头（各占一页）
+    fn write_headers(&mut self, pre_skip: u16) -> Result<(), String> {
+        let mut head = Vec::with_capacity(19);
+        head.extend_from_slice(b"OpusHead");
+        head.push(1); // 版本
+        head.push(1); // 声道数
+        head.extend_from_slice(&pre_skip.to_le_bytes());
+        head.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        head.extend_from_slice(&0i16.to_le_bytes()); // 输出增益
+        head.push(0); // 声道映射
+
+        let vendor = b"speaky";
+        let mut tags = Vec::with_capacity(16 + vendor.len());
+        tags.extend_from_slice(b"OpusTags");
+        tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+        tags.extend_from_slice(vendor);
+        tags.extend_from_slice(&0u32.to_le_bytes()); // 无用户注释
+
+        for header in [head, tags] {
+            self.writer
+                .write_packet(header, self.serial, PacketWriteEndInfo::EndPage, 0)
+                .map_err(|e| format!("Failed to write Ogg header: {}", e))?;
+        }
+        Ok(())
+    }
+
+    pub fn encode(&mut self, samples: &[i16]) -> Vec<u8> {
+        self.pending.extend_from_slice(samples);
+        let frames = self.pending.len() / OPUS_FRAME_SAMPLES;
+        for index in 0..frames {
+            // 每次调用的最后一帧结束当前页，使数据能立即发出
+            let end = if index + 1 == frames {
+                PacketWriteEndInfo::EndPage
+            } else {
+                PacketWriteEndInfo::NormalPacket
+            };
+            let start = index * OPUS_FRAME_SAMPLES;
+            self.write_frame(start, end);
+        }
+        self.pending.drain(..frames * OPUS_FRAME_SAMPLES);
+        self.take_output()
+    }
+
+    pub fn finish(&mut self) -> Vec<u8> {
+        // 最后不足一帧的部分补零，并标记流结束
+        self.pending.resize(OPUS_FRAME_SAMPLES, 0);
+        self.write_frame(0, PacketWriteEndInfo::EndStream);
+        self.pending.clear();
+        self.take_output()
+    }
+
+    fn write_frame(&mut self, start: usize, end: PacketWriteEndInfo) {
+        let frame = &self.pending[start..start + OPUS_FRAME_SAMPLES];
+        match self.encoder.encode(frame, &mut self.packet) {
+            Ok(len) => {
+                self.granule += GRANULE_PER_FRAME;
+                let packet = self.packet[..len].to_vec();
+                if let Err(e) = self
+                    .writer
+                    .write_packet(packet, self.serial, end, self.granule)
+                {
+                    log::error!("Failed to write Ogg page: {}", e);
+                }
+            }
+            Err(e) => log::error!("Opus encode error: {}", e),
+        }
+    }
+
+    fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(self.writer.inner_mut())
+    }
+}
+
+/// Ogg 逻辑流序列号
+#[cfg(feature = "opus")]
+fn rand_serial() -> u32 {
+    uuid::Uuid::new_v4().as_u128() as u32
+}
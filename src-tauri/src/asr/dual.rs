@@ -0,0 +1,62 @@
+//! 双 Provider 对比模式
+//!
+//! 同一段音频同时送入主 Provider 和对比 Provider，会话结束后按策略选出最终文本，
+//! 两份结果都保存在历史记录中，便于比较哪个 Provider 更可靠。
+
+use serde::{Deserialize, Serialize};
+
+/// 最终结果的选择策略
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DualSelection {
+    /// 两者都有置信度时取置信度高的，否则取较长的文本
+    #[default]
+    Auto,
+    /// 始终使用主 Provider 的结果，对比结果仅记录
+    Primary,
+}
+
+/// 单个 Provider 的识别结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TranscriptCandidate {
+    /// Provider ID
+    pub provider: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    /// 是否被选为最终结果
+    #[serde(default)]
+    pub selected: bool,
+}
+
+impl TranscriptCandidate {
+    pub fn new(provider: String, text: String, confidence: Option<f32>) -> Self {
+        Self {
+            provider,
+            text,
+            confidence,
+            selected: false,
+        }
+    }
+}
+
+/// 是否应改用对比 Provider 的结果（平局时保留主 Provider）
+pub fn prefer_secondary(
+    primary: &TranscriptCandidate,
+    secondary: &TranscriptCandidate,
+    strategy: DualSelection,
+) -> bool {
+    if secondary.text.trim().is_empty() {
+        return false;
+    }
+    if primary.text.trim().is_empty() {
+        return true;
+    }
+    match strategy {
+        DualSelection::Primary => false,
+        DualSelection::Auto => match (primary.confidence, secondary.confidence) {
+            (Some(p), Some(s)) => s > p,
+            _ => secondary.text.trim().chars().count() > primary.text.trim().chars().count(),
+        },
+    }
+}
@@ -0,0 +1,91 @@
+//! 单次识别会话的性能指标
+//!
+//! 用于比较不同 Provider / 模型的速度：音频时长、解码耗时、实时率（RTF）、
+//! 首个中间结果延迟和上传字节数。
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 16kHz / 16bit / 单声道 PCM 每毫秒的字节数
+const BYTES_PER_MS: u64 = 32;
+
+/// 单次会话指标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionMetrics {
+    /// Provider ID
+    pub provider: String,
+    /// 录音时长
+    pub audio_ms: u64,
+    /// 从停止录音到拿到最终结果的耗时
+    pub decode_ms: u64,
+    /// 实时率 = decode_ms / audio_ms（越小越快）
+    pub rtf: f32,
+    /// 从开始录音到首个非空结果的耗时（没有结果时为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_partial_ms: Option<u64>,
+    /// 送入 Provider 的音频字节数
+    pub bytes_uploaded: u64,
+}
+
+impl SessionMetrics {
+    pub fn new(
+        provider: String,
+        bytes_uploaded: u64,
+        decode_ms: u64,
+        first_partial_ms: Option<u64>,
+    ) -> Self {
+        let audio_ms = bytes_uploaded / BYTES_PER_MS;
+        let rtf = if audio_ms > 0 {
+            decode_ms as f32 / audio_ms as f32
+        } else {
+            0.0
+        };
+        Self {
+            provider,
+            audio_ms,
+            decode_ms,
+            rtf,
+            first_partial_ms,
+            bytes_uploaded,
+        }
+    }
+}
+
+/// 按 Provider 汇总的统计
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderStats {
+    pub provider: String,
+    pub sessions: usize,
+    pub total_audio_ms: u64,
+    pub avg_decode_ms: u64,
+    pub avg_rtf: f32,
+    /// 有中间结果的会话的平均首结果延迟
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_first_partial_ms: Option<u64>,
+}
+
+/// 按 Provider 汇总会话指标
+pub fn summarize<'a>(metrics: impl IntoIterator<Item = &'a SessionMetrics>) -> Vec<ProviderStats> {
+    let mut groups: BTreeMap<&str, Vec<&SessionMetrics>> = BTreeMap::new();
+    for m in metrics {
+        groups.entry(m.provider.as_str()).or_default().push(m);
+    }
+
+    groups
+        .into_iter()
+        .map(|(provider, sessions)| {
+            let count = sessions.len();
+            let first_partials: Vec<u64> =
+                sessions.iter().filter_map(|m| m.first_partial_ms).collect();
+            ProviderStats {
+                provider: provider.to_string(),
+                sessions: count,
+                total_audio_ms: sessions.iter().map(|m| m.audio_ms).sum(),
+                avg_decode_ms: sessions.iter().map(|m| m.decode_ms).sum::<u64>() / count as u64,
+                avg_rtf: sessions.iter().map(|m| m.rtf).sum::<f32>() / count as f32,
+                avg_first_partial_ms: (!first_partials.is_empty())
+                    .then(|| first_partials.iter().sum::<u64>() / first_partials.len() as u64),
+            }
+        })
+        .collect()
+}
@@ -1,7 +1,13 @@
+pub mod benchmark;
 pub mod client;
+pub mod codec;
+pub mod dual;
+pub mod metrics;
 pub mod model_manager;
 pub mod protocol;
 pub mod provider;
 pub mod providers;
+pub mod registry;
+pub mod transport;
 
 pub use provider::{AsrProvider, ModelDownloadable};
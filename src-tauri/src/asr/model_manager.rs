@@ -1,16 +1,23 @@
 //! 模型下载管理模块
 //!
-//! 提供模型文件下载功能，支持断点续传和进度报告。
+//! 提供模型文件下载功能，支持断点续传、大文件分段并行下载和进度报告。下载完成后校验 SHA-256，
+//! 校验值固定在各模型定义旁，不一致或没有固定校验值时下载失败。
 
 use futures::StreamExt;
-use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc;
 
 use crate::asr::provider::{AsrError, DownloadProgress};
 
+/// 文件不小于该大小且服务端支持 Range 时分段并行下载
+const PARALLEL_MIN_SIZE: u64 = 32 * 1024 * 1024;
+/// 并行下载的连接数
+const PARALLEL_CONNECTIONS: u64 = 4;
+
 /// 下载文件到指定路径
 ///
 /// # 参数
@@ -18,6 +25,7 @@ use crate::asr::provider::{AsrError, DownloadProgress};
 /// - `temp_path`: 临时文件路径
 /// - `dest_path`: 最终目标路径
 /// - `model_id`: 模型 ID（用于进度报告）
+/// - `sha256`: 固定的 SHA-256 校验值（十六进制），None 时拒绝下载
 /// - `progress_tx`: 进度发送通道
 /// - `cancel_flag`: 取消标志
 pub async fn download_file(
@@ -25,13 +33,133 @@ pub async fn download_file(
     temp_path: &Path,
     dest_path: &Path,
     model_id: &str,
+    sha256: Option<&str>,
     progress_tx: mpsc::Sender<DownloadProgress>,
     cancel_flag: Arc<AtomicBool>,
 ) -> Result<(), AsrError> {
+    let expected = sha256.ok_or_else(|| {
+        AsrError::ModelDownload(format!("{} 没有固定的 SHA-256 校验值，无法下载", url))
+    })?;
     let client = reqwest::Client::new();
+    let progress = Progress::new(model_id, progress_tx);
+
+    // 已有单连接下载的临时文件时继续单连接续传，否则大文件尝试分段并行下载
+    let ranged_size = if temp_path.exists() {
+        None
+    } else {
+        probe_ranged_size(&client, url).await
+    };
+    let total_size = match ranged_size {
+        Some(total) if total >= PARALLEL_MIN_SIZE => {
+            download_parallel(&client, url, temp_path, total, &progress, &cancel_flag).await?;
+            total
+        }
+        _ => download_single(&client, url, temp_path, &progress, &cancel_flag).await?,
+    };
+
+    // 校验完整性，不一致时删除临时文件，下次重新下载
+    let actual = file_sha256(temp_path).await?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        let _ = std::fs::remove_file(temp_path);
+        return Err(AsrError::ChecksumMismatch(format!(
+            "{} 期望 {}，实际 {}",
+            model_id, expected, actual
+        )));
+    }
+    log::info!("模型校验通过: {}", model_id);
+
+    // 重命名完成的文件
+    std::fs::rename(temp_path, dest_path)
+        .map_err(|e| AsrError::ModelDownload(format!("重命名文件失败: {}", e)))?;
+
+    // 发送完成进度
+    progress.finish(total_size).await;
+
+    log::info!("模型下载完成: {:?}", dest_path);
+    Ok(())
+}
+
+/// 下载进度（多个连接共享）
+struct Progress {
+    model_id: String,
+    tx: mpsc::Sender<DownloadProgress>,
+    total: AtomicU64,
+    downloaded: AtomicU64,
+    last_percent: AtomicU32,
+}
 
+impl Progress {
+    fn new(model_id: &str, tx: mpsc::Sender<DownloadProgress>) -> Self {
+        Self {
+            model_id: model_id.to_string(),
+            tx,
+            total: AtomicU64::new(0),
+            downloaded: AtomicU64::new(0),
+            last_percent: AtomicU32::new(0),
+        }
+    }
+
+    /// 记录新下载的字节数，限制进度更新频率（每 1% 更新一次）
+    async fn advance(&self, bytes: u64) {
+        let downloaded = self.downloaded.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        let total = self.total.load(Ordering::Relaxed);
+        let percent = if total > 0 {
+            ((downloaded as f32 / total as f32) * 100.0) as u32
+        } else {
+            0
+        };
+        if self.last_percent.swap(percent, Ordering::Relaxed) != percent {
+            self.send(downloaded, total, percent as f32).await;
+        }
+    }
+
+    async fn finish(&self, total: u64) {
+        self.send(total, total, 100.0).await;
+    }
+
+    async fn send(&self, downloaded_bytes: u64, total_bytes: u64, percent: f32) {
+        let _ = self
+            .tx
+            .send(DownloadProgress {
+                model_id: self.model_id.clone(),
+                downloaded_bytes,
+                total_bytes,
+                percent,
+            })
+            .await;
+    }
+}
+
+/// 查询文件大小；服务端不支持 Range 请求时返回 None
+async fn probe_ranged_size(client: &reqwest::Client, url: &str) -> Option<u64> {
+    let response = client.head(url).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let headers = response.headers();
+    let accepts_ranges = headers
+        .get(reqwest::header::ACCEPT_RANGES)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("bytes"));
+    if !accepts_ranges {
+        return None;
+    }
+    headers
+        .get(reqwest::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+/// 单连接下载（支持断点续传），返回文件总大小
+async fn download_single(
+    client: &reqwest::Client,
+    url: &str,
+    temp_path: &Path,
+    progress: &Progress,
+    cancel_flag: &AtomicBool,
+) -> Result<u64, AsrError> {
     // 检查已下载的大小（用于断点续传）
-    let mut downloaded: u64 = if temp_path.exists() {
+    let downloaded: u64 = if temp_path.exists() {
         std::fs::metadata(temp_path)
             .map(|m| m.len())
             .unwrap_or(0)
@@ -72,19 +200,105 @@ pub async fn download_file(
     } else {
         response.content_length().unwrap_or(0)
     };
+    progress.total.store(total_size, Ordering::Relaxed);
+    progress.downloaded.store(downloaded, Ordering::Relaxed);
 
+    write_stream(response, temp_path, progress, cancel_flag).await?;
+    Ok(total_size)
+}
+
+/// 分段并行下载：每段写入单独的分段文件（各自断点续传），全部完成后按顺序合并到临时文件
+async fn download_parallel(
+    client: &reqwest::Client,
+    url: &str,
+    temp_path: &Path,
+    total_size: u64,
+    progress: &Progress,
+    cancel_flag: &AtomicBool,
+) -> Result<(), AsrError> {
+    progress.total.store(total_size, Ordering::Relaxed);
+    let part_size = total_size.div_ceil(PARALLEL_CONNECTIONS);
+    let parts: Vec<(PathBuf, u64, u64)> = (0..PARALLEL_CONNECTIONS)
+        .map(|i| {
+            let start = i * part_size;
+            let end = ((i + 1) * part_size).min(total_size) - 1;
+            (temp_path.with_extension(format!("part{}", i)), start, end)
+        })
+        .filter(|(_, start, _)| *start < total_size)
+        .collect();
+    log::info!("分 {} 段并行下载 {} 字节", parts.len(), total_size);
+
+    futures::future::try_join_all(parts.iter().map(|(path, start, end)| {
+        download_part(client, url, path, *start, *end, progress, cancel_flag)
+    }))
+    .await?;
+
+    // 合并分段
+    let mut file = tokio::fs::File::create(temp_path)
+        .await
+        .map_err(|e| AsrError::ModelDownload(format!("打开文件失败: {}", e)))?;
+    for (path, _, _) in &parts {
+        let mut part = tokio::fs::File::open(path).await?;
+        tokio::io::copy(&mut part, &mut file)
+            .await
+            .map_err(|e| AsrError::ModelDownload(format!("合并分段失败: {}", e)))?;
+    }
+    file.flush().await.map_err(|e| AsrError::ModelDownload(format!("刷新文件失败: {}", e)))?;
+    for (path, _, _) in &parts {
+        let _ = std::fs::remove_file(path);
+    }
+    Ok(())
+}
+
+/// 下载 `[start, end]` 字节区间到分段文件
+async fn download_part(
+    client: &reqwest::Client,
+    url: &str,
+    path: &Path,
+    start: u64,
+    end: u64,
+    progress: &Progress,
+    cancel_flag: &AtomicBool,
+) -> Result<(), AsrError> {
+    let existing = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    progress.advance(existing).await;
+    if start + existing > end {
+        return Ok(());
+    }
+
+    let response = client
+        .get(url)
+        .header("Range", format!("bytes={}-{}", start + existing, end))
+        .send()
+        .await
+        .map_err(|e| AsrError::ModelDownload(format!("请求失败: {}", e)))?;
+    if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        return Err(AsrError::ModelDownload(format!(
+            "分段下载失败: HTTP {}",
+            response.status()
+        )));
+    }
+
+    write_stream(response, path, progress, cancel_flag).await
+}
+
+/// 把响应体追加写入文件
+async fn write_stream(
+    response: reqwest::Response,
+    path: &Path,
+    progress: &Progress,
+    cancel_flag: &AtomicBool,
+) -> Result<(), AsrError> {
     // 打开文件（追加模式）
     let mut file = tokio::fs::OpenOptions::new()
         .create(true)
         .append(true)
-        .open(temp_path)
+        .open(path)
         .await
         .map_err(|e| AsrError::ModelDownload(format!("打开文件失败: {}", e)))?;
 
     // 流式下载
     let mut stream = response.bytes_stream();
-    let mut last_progress_percent: u32 = 0;
-
     while let Some(chunk_result) = stream.next().await {
         // 检查取消标志
         if cancel_flag.load(Ordering::SeqCst) {
@@ -92,52 +306,36 @@ pub async fn download_file(
             return Err(AsrError::ModelDownload("下载已取消".into()));
         }
 
-        let chunk = chunk_result.map_err(|e| AsrError::ModelDownload(format!("读取数据失败: {}", e)))?;
+        let chunk = chunk_result
+            .map_err(|e| AsrError::ModelDownload(format!("读取数据失败: {}", e)))?;
 
         file.write_all(&chunk)
             .await
             .map_err(|e| AsrError::ModelDownload(format!("写入文件失败: {}", e)))?;
 
-        downloaded += chunk.len() as u64;
-
-        // 限制进度更新频率（每 1% 更新一次）
-        let current_percent = if total_size > 0 {
-            ((downloaded as f32 / total_size as f32) * 100.0) as u32
-        } else {
-            0
-        };
-
-        if current_percent != last_progress_percent {
-            last_progress_percent = current_percent;
-            let _ = progress_tx
-                .send(DownloadProgress {
-                    model_id: model_id.to_string(),
-                    downloaded_bytes: downloaded,
-                    total_bytes: total_size,
-                    percent: current_percent as f32,
-                })
-                .await;
-        }
+        progress.advance(chunk.len() as u64).await;
     }
 
     // 确保写入完成
     file.flush().await.map_err(|e| AsrError::ModelDownload(format!("刷新文件失败: {}", e)))?;
-    drop(file);
-
-    // 重命名完成的文件
-    std::fs::rename(temp_path, dest_path)
-        .map_err(|e| AsrError::ModelDownload(format!("重命名文件失败: {}", e)))?;
-
-    // 发送完成进度
-    let _ = progress_tx
-        .send(DownloadProgress {
-            model_id: model_id.to_string(),
-            downloaded_bytes: total_size,
-            total_bytes: total_size,
-            percent: 100.0,
-        })
-        .await;
-
-    log::info!("模型下载完成: {:?}", dest_path);
     Ok(())
 }
+
+/// 计算文件的 SHA-256（十六进制小写）
+async fn file_sha256(path: &Path) -> Result<String, AsrError> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut file = std::fs::File::open(&path)?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher)?;
+        Ok::<String, AsrError>(
+            hasher
+                .finalize()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        )
+    })
+    .await
+    .map_err(|e| AsrError::ModelDownload(format!("校验任务失败: {}", e)))?
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::asr::codec::AudioCodec;
+use crate::asr::provider::{AsrSegment, WordTiming};
+
 /// 豆包 ASR 请求配置
 #[derive(Serialize, Debug, Clone)]
 pub struct AsrConfig {
@@ -29,28 +32,79 @@ pub struct RequestConfig {
     pub enable_itn: bool,
     pub result_type: String,
     pub show_utterances: bool,
+    /// 热词等识别上下文
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corpus: Option<Corpus>,
 }
 
-impl Default for AsrConfig {
-    fn default() -> Self {
+/// 识别上下文
+#[derive(Serialize, Debug, Clone)]
+pub struct Corpus {
+    /// JSON 字符串，如 `{"hotwords":[{"word":"豆包"}]}`
+    pub context: String,
+}
+
+impl Corpus {
+    /// 由热词列表构建
+    pub fn from_hotwords(hotwords: &[String]) -> Option<Self> {
+        if hotwords.is_empty() {
+            return None;
+        }
+        let words: Vec<_> = hotwords
+            .iter()
+            .map(|word| serde_json::json!({ "word": word }))
+            .collect();
+        Some(Self {
+            context: serde_json::json!({ "hotwords": words }).to_string(),
+        })
+    }
+}
+
+impl AudioConfig {
+    /// 按上传编码生成音频参数（Opus 使用 Ogg 封装）
+    pub fn for_codec(codec: AudioCodec) -> Self {
+        let (format, codec) = match codec {
+            AudioCodec::Pcm => ("pcm", "pcm"),
+            AudioCodec::Opus => ("ogg", "opus"),
+        };
+        Self {
+            format: format.to_string(),
+            codec: codec.to_string(),
+            rate: 16000,
+            bits: 16,
+            channel: 1,
+        }
+    }
+}
+
+impl AsrConfig {
+    /// 使用指定的识别请求参数和上传编码创建配置
+    pub fn new(request: RequestConfig, codec: AudioCodec) -> Self {
         Self {
             user: UserConfig {
                 uid: uuid::Uuid::new_v4().to_string(),
             },
-            audio: AudioConfig {
-                format: "pcm".to_string(),
-                codec: "pcm".to_string(),
-                rate: 16000,
-                bits: 16,
-                channel: 1,
-            },
-            request: RequestConfig {
-                model_name: "bigmodel".to_string(),
-                enable_punc: true,
-                enable_itn: true,
-                result_type: "single".to_string(),
-                show_utterances: false,
-            },
+            audio: AudioConfig::for_codec(codec),
+            request,
+        }
+    }
+}
+
+impl Default for AsrConfig {
+    fn default() -> Self {
+        Self::new(RequestConfig::default(), AudioCodec::Pcm)
+    }
+}
+
+impl Default for RequestConfig {
+    fn default() -> Self {
+        Self {
+            model_name: "bigmodel".to_string(),
+            enable_punc: true,
+            enable_itn: true,
+            result_type: "single".to_string(),
+            show_utterances: false,
+            corpus: None,
         }
     }
 }
@@ -90,6 +144,8 @@ pub struct AsrResultSingle {
     #[serde(default)]
     pub prefetch: bool,
     #[serde(default)]
+    utterances: Vec<Utterance>,
+    #[serde(default)]
     additions: Option<serde_json::Value>,
 }
 
@@ -116,6 +172,39 @@ pub struct Utterance {
     end_time: u64,
     #[serde(default)]
     definite: bool,
+    #[serde(default)]
+    words: Vec<UtteranceWord>,
+}
+
+/// 分句中的字词（时间单位为毫秒）
+#[derive(Deserialize, Debug, Clone)]
+pub struct UtteranceWord {
+    text: String,
+    start_time: u64,
+    end_time: u64,
+}
+
+impl Utterance {
+    /// 分句内的词级时间戳，服务端未返回字词时以整句作为一个词
+    fn word_timings(&self) -> Vec<WordTiming> {
+        if self.words.is_empty() {
+            return vec![WordTiming {
+                text: self.text.clone(),
+                start: self.start_time as f32 / 1000.0,
+                end: self.end_time as f32 / 1000.0,
+                confidence: None,
+            }];
+        }
+        self.words
+            .iter()
+            .map(|w| WordTiming {
+                text: w.text.clone(),
+                start: w.start_time as f32 / 1000.0,
+                end: w.end_time as f32 / 1000.0,
+                confidence: None,
+            })
+            .collect()
+    }
 }
 
 impl AsrResponse {
@@ -134,6 +223,35 @@ impl AsrResponse {
         }
     }
 
+    /// 词级时间戳（需在请求中开启 show_utterances）
+    pub fn get_words(&self) -> Vec<WordTiming> {
+        self.utterances().iter().flat_map(Utterance::word_timings).collect()
+    }
+
+    /// 按分句划分的带时间戳分段（需在请求中开启 show_utterances）
+    pub fn get_segments(&self) -> Vec<AsrSegment> {
+        self.utterances()
+            .iter()
+            .filter(|u| !u.text.is_empty())
+            .map(|u| AsrSegment {
+                text: u.text.clone(),
+                start: u.start_time as f32 / 1000.0,
+                end: u.end_time as f32 / 1000.0,
+                confidence: None,
+            })
+            .collect()
+    }
+
+    fn utterances(&self) -> &[Utterance] {
+        match &self.result {
+            Some(AsrResultWrapper::Single(r)) => &r.utterances,
+            Some(AsrResultWrapper::Array(results)) => {
+                results.first().map(|r| r.utterances.as_slice()).unwrap_or_default()
+            }
+            _ => &[],
+        }
+    }
+
     /// 检查是否是 prefetch 结果（预取结果通常是最终结果）
     pub fn is_prefetch(&self) -> bool {
         match &self.result {
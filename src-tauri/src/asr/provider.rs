@@ -5,16 +5,73 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::mpsc;
 
+use crate::asr::codec::AudioCodec;
+
+/// 停止录音后等待最终结果的默认时长（流式 Provider 录音期间已基本识别完成）
+const DEFAULT_RESULT_TIMEOUT: Duration = Duration::from_millis(2000);
+
 /// ASR 识别结果（统一格式）
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct AsrResult {
     /// 识别出的文本
     pub text: String,
     /// 是否是最终结果（false 表示中间结果/prefetch）
     pub is_final: bool,
+    /// 带时间戳的分段（Provider 支持时填充）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<AsrSegment>,
+    /// 整体置信度 (0.0 - 1.0)，Provider 不支持时为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    /// 词级时间戳（Provider 支持时填充，可用于字幕导出等）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTiming>,
+    /// 自动检测出的语言代码（如 "en"），仅在识别语言为 auto 且 Provider 支持时填充
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// 词级时间戳
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WordTiming {
+    /// 词文本（中文通常为单字）
+    pub text: String,
+    /// 开始时间（秒）
+    pub start: f32,
+    /// 结束时间（秒）
+    pub end: f32,
+    /// 词置信度 (0.0 - 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/// 识别结果分段
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AsrSegment {
+    /// 分段文本
+    pub text: String,
+    /// 开始时间（秒）
+    pub start: f32,
+    /// 结束时间（秒）
+    pub end: f32,
+    /// 分段置信度 (0.0 - 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+impl AsrSegment {
+    /// 按时长加权计算多个分段的整体置信度
+    pub fn overall_confidence(segments: &[AsrSegment]) -> Option<f32> {
+        let (weighted, total) = segments
+            .iter()
+            .filter_map(|s| s.confidence.map(|c| (c, (s.end - s.start).max(0.01))))
+            .fold((0.0, 0.0), |(w, t), (c, d)| (w + c * d, t + d));
+        (total > 0.0).then(|| weighted / total)
+    }
 }
 
 /// ASR Provider 错误类型
@@ -30,6 +87,8 @@ pub enum AsrError {
     ModelNotFound(String),
     #[error("模型下载失败: {0}")]
     ModelDownload(String),
+    #[error("模型校验失败: {0}")]
+    ChecksumMismatch(String),
     #[error("IO 错误: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -41,6 +100,8 @@ pub enum AsrError {
 pub enum ProviderStatus {
     /// 就绪可用
     Ready,
+    /// 可用，但部分设置无法生效（如请求的 GPU 后端未编译进当前构建）
+    Degraded(String),
     /// 需要配置
     NeedsConfiguration,
     /// 需要下载模型
@@ -73,14 +134,27 @@ pub trait AsrProvider: Send + Sync {
 
     /// 检查是否已就绪
     fn is_ready(&self) -> bool {
-        matches!(self.status(), ProviderStatus::Ready)
+        matches!(self.status(), ProviderStatus::Ready | ProviderStatus::Degraded(_))
     }
 
     /// 验证配置是否有效
     fn validate(&self) -> Result<(), AsrError>;
 
+    /// 期望接收的音频编码，录音转发时按此格式编码
+    fn audio_codec(&self) -> AudioCodec {
+        AudioCodec::Pcm
+    }
+
+    /// 停止录音后等待最终结果的最长时间
+    ///
+    /// 录音结束后才上传音频的 Provider 需覆盖为请求超时与重试的总预算，
+    /// `audio_secs` 为本次录音时长。
+    fn result_timeout(&self, _audio_secs: f32) -> Duration {
+        DEFAULT_RESULT_TIMEOUT
+    }
+
     /// 流式语音识别
-    /// - audio_rx: 接收 16kHz/16bit/单声道 PCM 音频数据
+    /// - audio_rx: 接收按 `audio_codec()` 编码的音频数据（默认 16kHz/16bit/单声道 PCM）
     /// - result_tx: 发送识别结果
     async fn transcribe_stream(
         &self,
@@ -130,6 +204,8 @@ pub struct DownloadProgress {
 #[async_trait]
 pub trait ModelDownloadable: AsrProvider {
     /// 获取可用模型列表
+    ///
+    /// 下载时校验固定的 SHA-256，没有固定校验值的模型无法下载，只在已手动放入模型目录时列出。
     fn available_models(&self) -> Vec<ModelInfo>;
 
     /// 获取已下载模型列表
@@ -0,0 +1,315 @@
+//! 阿里云智能语音交互（NLS）实时语音识别 Provider
+//!
+//! 通过 NLS 实时语音识别 WebSocket 协议流式上传 PCM 音频。识别语言由控制台中
+//! 项目（appkey）绑定的模型决定。
+
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use super::url_encode;
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+
+/// 等待 TranscriptionStarted 的最长时间
+const START_TIMEOUT: Duration = Duration::from_secs(5);
+/// 音频结束后等待 TranscriptionCompleted 的最长时间
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 阿里云 NLS 配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AlibabaNlsConfig {
+    /// 项目 Appkey
+    #[serde(default)]
+    pub appkey: String,
+    /// 访问令牌（控制台获取，有效期有限）
+    #[serde(default)]
+    pub token: String,
+    /// 网关地址
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+}
+
+fn default_endpoint() -> String {
+    "wss://nls-gateway-cn-shanghai.aliyuncs.com/ws/v1".to_string()
+}
+
+impl Default for AlibabaNlsConfig {
+    fn default() -> Self {
+        Self {
+            appkey: String::new(),
+            token: String::new(),
+            endpoint: default_endpoint(),
+        }
+    }
+}
+
+impl AlibabaNlsConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.appkey.is_empty() && !self.token.is_empty()
+    }
+}
+
+/// 服务端事件
+#[derive(Deserialize, Debug)]
+struct ServerEvent {
+    header: EventHeader,
+    #[serde(default)]
+    payload: Option<EventPayload>,
+}
+
+#[derive(Deserialize, Debug)]
+struct EventHeader {
+    name: String,
+    #[serde(default)]
+    status: i32,
+    #[serde(default)]
+    status_text: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct EventPayload {
+    /// 句子序号
+    #[serde(default)]
+    index: u32,
+    #[serde(default)]
+    result: String,
+    #[serde(default)]
+    confidence: Option<f32>,
+}
+
+/// 32 位十六进制 ID（NLS 要求的 message_id / task_id 格式）
+fn new_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// 构建指令消息
+fn command(name: &str, task_id: &str, appkey: &str, payload: serde_json::Value) -> String {
+    json!({
+        "header": {
+            "message_id": new_id(),
+            "task_id": task_id,
+            "namespace": "SpeechTranscriber",
+            "name": name,
+            "appkey": appkey,
+        },
+        "payload": payload,
+    })
+    .to_string()
+}
+
+/// 阿里云 NLS Provider
+pub struct AlibabaNlsProvider {
+    config: AlibabaNlsConfig,
+}
+
+impl AlibabaNlsProvider {
+    pub fn new(config: AlibabaNlsConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for AlibabaNlsProvider {
+    fn id(&self) -> &str {
+        "alibaba"
+    }
+
+    fn display_name(&self) -> &str {
+        "阿里云"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if !self.config.is_configured() {
+            return Err(AsrError::Configuration("Appkey 和 Token 不能为空".into()));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let url = format!(
+            "{}?token={}",
+            self.config.endpoint,
+            url_encode(&self.config.token)
+        );
+        log::info!("Connecting to Alibaba Cloud NLS");
+        let (ws_stream, _response) = connect_async(url)
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+        log::info!("Alibaba Cloud NLS WebSocket connected");
+
+        let (mut write, mut read) = ws_stream.split();
+
+        let task_id = new_id();
+        let start = command(
+            "StartTranscription",
+            &task_id,
+            &self.config.appkey,
+            json!({
+                "format": "pcm",
+                "sample_rate": 16000,
+                "enable_intermediate_result": true,
+                "enable_punctuation_prediction": true,
+                "enable_inverse_text_normalization": true,
+            }),
+        );
+        write
+            .send(Message::Text(start))
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+
+        // 等待服务端确认开始识别
+        loop {
+            let msg = tokio::time::timeout(START_TIMEOUT, read.next())
+                .await
+                .map_err(|_| AsrError::Connection("等待 TranscriptionStarted 超时".into()))?;
+            let text = match msg {
+                Some(Ok(Message::Text(text))) => text,
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => return Err(AsrError::Connection(e.to_string())),
+                None => return Err(AsrError::Connection("连接已关闭".into())),
+            };
+            let Ok(event) = serde_json::from_str::<ServerEvent>(&text) else {
+                continue;
+            };
+            match event.header.name.as_str() {
+                "TranscriptionStarted" => break,
+                "TaskFailed" => {
+                    return Err(AsrError::Connection(format!(
+                        "{} ({})",
+                        event.header.status_text, event.header.status
+                    )))
+                }
+                _ => continue,
+            }
+        }
+
+        // 发送音频，结束时发送 StopTranscription
+        let appkey = self.config.appkey.clone();
+        let stop_task_id = task_id.clone();
+        let (audio_done_tx, mut audio_done_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            while let Some(chunk) = audio_rx.recv().await {
+                if write.send(Message::Binary(chunk)).await.is_err() {
+                    return;
+                }
+            }
+
+            log::info!("Audio channel closed, stopping transcription");
+            let stop = command("StopTranscription", &stop_task_id, &appkey, json!({}));
+            let _ = write.send(Message::Text(stop)).await;
+            let _ = audio_done_tx.send(());
+        });
+
+        // 句子序号 -> 文本
+        let mut sentences: BTreeMap<u32, String> = BTreeMap::new();
+        let mut confidences: Vec<f32> = Vec::new();
+        let mut last_text = String::new();
+        let mut audio_done = false;
+
+        loop {
+            let next = if audio_done {
+                match tokio::time::timeout(FINALIZE_TIMEOUT, read.next()).await {
+                    Ok(msg) => msg,
+                    Err(_) => {
+                        log::warn!("Alibaba Cloud NLS finalize timeout");
+                        break;
+                    }
+                }
+            } else {
+                tokio::select! {
+                    msg = read.next() => msg,
+                    _ = &mut audio_done_rx => {
+                        audio_done = true;
+                        continue;
+                    }
+                }
+            };
+
+            let Some(msg) = next else {
+                log::info!("Alibaba Cloud NLS connection closed");
+                break;
+            };
+
+            let text = match msg {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(e) => return Err(AsrError::Connection(e.to_string())),
+            };
+
+            let Ok(event) = serde_json::from_str::<ServerEvent>(&text) else {
+                continue;
+            };
+
+            match event.header.name.as_str() {
+                "TranscriptionResultChanged" | "SentenceEnd" => {
+                    if let Some(payload) = event.payload {
+                        if event.header.name == "SentenceEnd" {
+                            confidences.extend(payload.confidence);
+                        }
+                        sentences.insert(payload.index, payload.result);
+                    }
+                }
+                "TranscriptionCompleted" => break,
+                "TaskFailed" => {
+                    return Err(AsrError::Transcription(format!(
+                        "{} ({})",
+                        event.header.status_text, event.header.status
+                    )));
+                }
+                _ => continue,
+            }
+
+            let full_text: String = sentences.values().map(String::as_str).collect();
+            if full_text != last_text && !full_text.is_empty() {
+                last_text = full_text.clone();
+                let result = AsrResult {
+                    text: full_text,
+                    is_final: false,
+                    ..Default::default()
+                };
+                if result_tx.send(result).await.is_err() {
+                    break;
+                }
+            }
+        }
+
+        // 发送最终结果
+        let final_text: String = sentences.values().map(String::as_str).collect();
+        let final_text = final_text.trim().to_string();
+        if !final_text.is_empty() {
+            let confidence = (!confidences.is_empty())
+                .then(|| confidences.iter().sum::<f32>() / confidences.len() as f32);
+            let _ = result_tx
+                .send(AsrResult {
+                    text: final_text,
+                    is_final: true,
+                    confidence,
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("Alibaba Cloud NLS session completed");
+        Ok(())
+    }
+}
@@ -0,0 +1,622 @@
+//! Amazon Transcribe 流式语音识别 Provider
+//!
+//! 通过 HTTP/2 双向流调用 StartStreamTranscription：请求体是逐块签名（SigV4）的
+//! event-stream 音频事件，响应体是 event-stream 编码的 TranscriptEvent。
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::region_locale;
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 签名使用的服务名
+const SERVICE: &str = "transcribe";
+/// 逐块签名时请求体的哈希占位值
+const STREAMING_PAYLOAD: &str = "STREAMING-AWS4-HMAC-SHA256-EVENTS";
+/// 未指定语言时使用的识别语言
+const DEFAULT_LANGUAGE: &str = "zh-CN";
+/// 建立连接的超时时间
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Amazon Transcribe 配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AwsTranscribeConfig {
+    /// 区域（如 "us-east-1"、"ap-northeast-1"）
+    #[serde(default = "default_region")]
+    pub region: String,
+    /// Access Key ID
+    #[serde(default)]
+    pub access_key_id: String,
+    /// Secret Access Key
+    #[serde(default)]
+    pub secret_access_key: String,
+    /// 临时凭证的 Session Token（可选）
+    #[serde(default)]
+    pub session_token: Option<String>,
+    /// 自定义端点（VPC 终端节点等），为空时按区域生成
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// 识别语言（可选）
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+fn default_region() -> String {
+    "us-east-1".to_string()
+}
+
+impl Default for AwsTranscribeConfig {
+    fn default() -> Self {
+        Self {
+            region: default_region(),
+            access_key_id: String::new(),
+            secret_access_key: String::new(),
+            session_token: None,
+            endpoint: None,
+            language: None,
+        }
+    }
+}
+
+impl AwsTranscribeConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.region.is_empty()
+            && !self.access_key_id.is_empty()
+            && !self.secret_access_key.is_empty()
+    }
+
+    fn language_code(&self) -> &str {
+        self.language
+            .as_deref()
+            .map_or(DEFAULT_LANGUAGE, region_locale)
+    }
+
+    fn host(&self) -> String {
+        match self.endpoint {
+            Some(ref endpoint) => endpoint
+                .trim_start_matches("https://")
+                .trim_end_matches('/')
+                .to_string(),
+            None => format!("transcribestreaming.{}.amazonaws.com", self.region),
+        }
+    }
+}
+
+// ==================== event-stream 编码 ====================
+
+/// event-stream 头部值
+enum HeaderValue<'a> {
+    Bytes(&'a [u8]),
+    String(&'a str),
+    Timestamp(i64),
+}
+
+/// 编码单个头部：名称长度 + 名称 + 类型 + 值
+fn encode_header(buf: &mut Vec<u8>, name: &str, value: HeaderValue) {
+    buf.push(name.len() as u8);
+    buf.extend_from_slice(name.as_bytes());
+    match value {
+        HeaderValue::Bytes(bytes) => {
+            buf.push(6);
+            buf.extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+            buf.extend_from_slice(bytes);
+        }
+        HeaderValue::String(s) => {
+            buf.push(7);
+            buf.extend_from_slice(&(s.len() as u16).to_be_bytes());
+            buf.extend_from_slice(s.as_bytes());
+        }
+        HeaderValue::Timestamp(millis) => {
+            buf.push(8);
+            buf.extend_from_slice(&millis.to_be_bytes());
+        }
+    }
+}
+
+/// 编码完整消息：总长度 + 头部长度 + 前导 CRC + 头部 + 负载 + 消息 CRC
+fn encode_message(headers: &[u8], payload: &[u8]) -> Vec<u8> {
+    let total_len = 12 + headers.len() + payload.len() + 4;
+    let mut message = Vec::with_capacity(total_len);
+    message.extend_from_slice(&(total_len as u32).to_be_bytes());
+    message.extend_from_slice(&(headers.len() as u32).to_be_bytes());
+    let prelude_crc = crc32fast::hash(&message);
+    message.extend_from_slice(&prelude_crc.to_be_bytes());
+    message.extend_from_slice(headers);
+    message.extend_from_slice(payload);
+    let message_crc = crc32fast::hash(&message);
+    message.extend_from_slice(&message_crc.to_be_bytes());
+    message
+}
+
+/// 已解码的消息
+struct EventMessage {
+    headers: Vec<(String, String)>,
+    payload: Vec<u8>,
+}
+
+impl EventMessage {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// 从缓冲区解码一条完整消息，数据不足时返回 None
+fn decode_message(buf: &mut Vec<u8>) -> Result<Option<EventMessage>, AsrError> {
+    if buf.len() < 12 {
+        return Ok(None);
+    }
+    let total_len = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+    let headers_len = u32::from_be_bytes([buf[4], buf[5], buf[6], buf[7]]) as usize;
+    if total_len < 16 + headers_len {
+        return Err(AsrError::Transcription("Invalid event-stream message length".into()));
+    }
+    if buf.len() < total_len {
+        return Ok(None);
+    }
+
+    let message: Vec<u8> = buf.drain(..total_len).collect();
+    let expected_crc = u32::from_be_bytes([
+        message[total_len - 4],
+        message[total_len - 3],
+        message[total_len - 2],
+        message[total_len - 1],
+    ]);
+    if crc32fast::hash(&message[..total_len - 4]) != expected_crc {
+        return Err(AsrError::Transcription("event-stream CRC mismatch".into()));
+    }
+
+    let mut headers = Vec::new();
+    let mut raw = &message[12..12 + headers_len];
+    while !raw.is_empty() {
+        let name_len = raw[0] as usize;
+        let name = String::from_utf8_lossy(&raw[1..1 + name_len]).to_string();
+        let value_type = raw[1 + name_len];
+        raw = &raw[2 + name_len..];
+        let value = match value_type {
+            // 字符串和字节数组：2 字节长度 + 数据
+            6 | 7 => {
+                let len = u16::from_be_bytes([raw[0], raw[1]]) as usize;
+                let value = String::from_utf8_lossy(&raw[2..2 + len]).to_string();
+                raw = &raw[2 + len..];
+                value
+            }
+            // 布尔值没有值部分
+            0 | 1 => (value_type == 0).to_string(),
+            2 => {
+                raw = &raw[1..];
+                String::new()
+            }
+            3 => {
+                raw = &raw[2..];
+                String::new()
+            }
+            4 => {
+                raw = &raw[4..];
+                String::new()
+            }
+            5 | 8 => {
+                raw = &raw[8..];
+                String::new()
+            }
+            9 => {
+                raw = &raw[16..];
+                String::new()
+            }
+            other => {
+                return Err(AsrError::Transcription(format!(
+                    "Unknown event-stream header type: {}",
+                    other
+                )))
+            }
+        };
+        headers.push((name, value));
+    }
+
+    Ok(Some(EventMessage {
+        headers,
+        payload: message[12 + headers_len..total_len - 4].to_vec(),
+    }))
+}
+
+// ==================== SigV4 签名 ====================
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// 请求签名状态（音频事件的签名依赖上一条签名）
+struct Signer {
+    signing_key: Vec<u8>,
+    scope: String,
+    prior_signature: String,
+}
+
+impl Signer {
+    fn new(config: &AwsTranscribeConfig, now: DateTime<Utc>) -> Self {
+        let date = now.format("%Y%m%d").to_string();
+        let k_date = hmac(
+            format!("AWS4{}", config.secret_access_key).as_bytes(),
+            date.as_bytes(),
+        );
+        let k_region = hmac(&k_date, config.region.as_bytes());
+        let k_service = hmac(&k_region, SERVICE.as_bytes());
+        Self {
+            signing_key: hmac(&k_service, b"aws4_request"),
+            scope: format!("{}/{}/{}/aws4_request", date, config.region, SERVICE),
+            prior_signature: String::new(),
+        }
+    }
+
+    /// 签名 HTTP 请求，返回 Authorization 头部
+    fn sign_request(
+        &mut self,
+        access_key_id: &str,
+        amz_date: &str,
+        headers: &[(&str, String)],
+    ) -> String {
+        let mut sorted: Vec<(String, &str)> = headers
+            .iter()
+            .map(|(name, value)| (name.to_lowercase(), value.trim()))
+            .collect();
+        sorted.sort();
+
+        let canonical_headers: String = sorted
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value))
+            .collect();
+        let signed_headers = sorted
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "POST\n/stream-transcription\n\n{}\n{}\n{}",
+            canonical_headers, signed_headers, STREAMING_PAYLOAD
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            self.scope,
+            sha256_hex(canonical_request.as_bytes())
+        );
+        let signature = hex(&hmac(&self.signing_key, string_to_sign.as_bytes()));
+        self.prior_signature = signature.clone();
+
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            access_key_id, self.scope, signed_headers, signature
+        )
+    }
+
+    /// 将音频事件（空负载表示结束）包装为带签名的外层消息
+    fn sign_event(&mut self, event: &[u8]) -> Vec<u8> {
+        let now = Utc::now();
+        let mut date_header = Vec::new();
+        encode_header(
+            &mut date_header,
+            ":date",
+            HeaderValue::Timestamp(now.timestamp_millis()),
+        );
+
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256-PAYLOAD\n{}\n{}\n{}\n{}\n{}",
+            now.format("%Y%m%dT%H%M%SZ"),
+            self.scope,
+            self.prior_signature,
+            sha256_hex(&date_header),
+            sha256_hex(event)
+        );
+        let signature = hmac(&self.signing_key, string_to_sign.as_bytes());
+        self.prior_signature = hex(&signature);
+
+        let mut headers = date_header;
+        encode_header(
+            &mut headers,
+            ":chunk-signature",
+            HeaderValue::Bytes(&signature),
+        );
+        encode_message(&headers, event)
+    }
+}
+
+/// 编码 AudioEvent
+fn audio_event(pcm: &[u8]) -> Vec<u8> {
+    let mut headers = Vec::new();
+    encode_header(&mut headers, ":content-type", HeaderValue::String("application/octet-stream"));
+    encode_header(&mut headers, ":event-type", HeaderValue::String("AudioEvent"));
+    encode_header(&mut headers, ":message-type", HeaderValue::String("event"));
+    encode_message(&headers, pcm)
+}
+
+// ==================== 识别结果 ====================
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct TranscriptEvent {
+    transcript: Transcript,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct Transcript {
+    #[serde(default)]
+    results: Vec<TranscriptResult>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct TranscriptResult {
+    result_id: String,
+    #[serde(default)]
+    is_partial: bool,
+    #[serde(default)]
+    alternatives: Vec<Alternative>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct Alternative {
+    #[serde(default)]
+    transcript: String,
+    #[serde(default)]
+    items: Vec<Item>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct Item {
+    #[serde(default)]
+    confidence: Option<f32>,
+}
+
+/// 异常事件负载
+#[derive(Deserialize, Debug)]
+struct ExceptionPayload {
+    #[serde(default, alias = "Message")]
+    message: String,
+}
+
+/// 按结果 ID 累积的转写状态
+#[derive(Default)]
+struct TranscriptState {
+    /// (结果 ID, 文本)，部分结果会被同 ID 的后续结果替换
+    results: Vec<(String, String)>,
+    confidences: Vec<f32>,
+    separator: &'static str,
+}
+
+impl TranscriptState {
+    fn update(&mut self, result: TranscriptResult) {
+        let Some(alternative) = result.alternatives.into_iter().next() else {
+            return;
+        };
+        if !result.is_partial {
+            self.confidences
+                .extend(alternative.items.iter().filter_map(|i| i.confidence));
+        }
+        match self.results.iter_mut().find(|(id, _)| *id == result.result_id) {
+            Some(entry) => entry.1 = alternative.transcript,
+            None => self.results.push((result.result_id, alternative.transcript)),
+        }
+    }
+
+    fn full_text(&self) -> String {
+        self.results
+            .iter()
+            .map(|(_, text)| text.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(self.separator)
+    }
+
+    fn confidence(&self) -> Option<f32> {
+        if self.confidences.is_empty() {
+            return None;
+        }
+        Some(self.confidences.iter().sum::<f32>() / self.confidences.len() as f32)
+    }
+}
+
+/// Amazon Transcribe Provider
+pub struct AwsTranscribeProvider {
+    config: AwsTranscribeConfig,
+}
+
+impl AwsTranscribeProvider {
+    pub fn new(config: AwsTranscribeConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for AwsTranscribeProvider {
+    fn id(&self) -> &str {
+        "aws_transcribe"
+    }
+
+    fn display_name(&self) -> &str {
+        "Amazon Transcribe"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if self.config.region.is_empty() {
+            return Err(AsrError::Configuration("区域不能为空".into()));
+        }
+        if self.config.access_key_id.is_empty() || self.config.secret_access_key.is_empty() {
+            return Err(AsrError::Configuration("AWS 凭证不能为空".into()));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let host = self.config.host();
+        let language_code = self.config.language_code().to_string();
+
+        let mut headers: Vec<(&str, String)> = vec![
+            ("content-type", "application/vnd.amazon.eventstream".to_string()),
+            ("host", host.clone()),
+            ("x-amz-content-sha256", STREAMING_PAYLOAD.to_string()),
+            ("x-amz-date", amz_date.clone()),
+            ("x-amzn-transcribe-language-code", language_code.clone()),
+            ("x-amzn-transcribe-media-encoding", "pcm".to_string()),
+            ("x-amzn-transcribe-sample-rate", "16000".to_string()),
+        ];
+        if let Some(ref token) = self.config.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+
+        let mut signer = Signer::new(&self.config, now);
+        let authorization = signer.sign_request(&self.config.access_key_id, &amz_date, &headers);
+
+        // 请求体：逐块签名的音频事件，结束时发送空事件
+        let (body_tx, body_rx) = mpsc::channel::<Vec<u8>>(32);
+        tokio::spawn(async move {
+            while let Some(chunk) = audio_rx.recv().await {
+                let message = signer.sign_event(&audio_event(&chunk));
+                if body_tx.send(message).await.is_err() {
+                    return;
+                }
+            }
+            log::info!("Audio channel closed, sending end of stream");
+            let _ = body_tx.send(signer.sign_event(&[])).await;
+        });
+        let body_stream = futures_util::stream::unfold(body_rx, |mut rx| async move {
+            rx.recv()
+                .await
+                .map(|chunk| (Ok::<_, std::io::Error>(chunk), rx))
+        });
+
+        let client = reqwest::Client::builder()
+            .http2_prior_knowledge()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+
+        let mut request = client
+            .post(format!("https://{}/stream-transcription", host))
+            .header("Authorization", authorization)
+            .body(reqwest::Body::wrap_stream(body_stream));
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            request = request.header(*name, value);
+        }
+
+        log::info!("Connecting to Amazon Transcribe ({})", self.config.region);
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AsrError::Connection(format!("HTTP {}: {}", status, body)));
+        }
+        log::info!("Amazon Transcribe stream established");
+
+        let mut state = TranscriptState {
+            separator: if language_code.starts_with("zh") || language_code.starts_with("ja") {
+                ""
+            } else {
+                " "
+            },
+            ..Default::default()
+        };
+        let mut last_text = String::new();
+        let mut buffer = Vec::new();
+        let mut stream = response.bytes_stream();
+
+        'outer: while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| AsrError::Connection(e.to_string()))?;
+            buffer.extend_from_slice(&chunk);
+
+            while let Some(message) = decode_message(&mut buffer)? {
+                match message.header(":message-type") {
+                    Some("event") if message.header(":event-type") == Some("TranscriptEvent") => {
+                        let event: TranscriptEvent = serde_json::from_slice(&message.payload)
+                            .map_err(|e| AsrError::Transcription(e.to_string()))?;
+                        for result in event.transcript.results {
+                            state.update(result);
+                        }
+                    }
+                    Some("exception") | Some("error") => {
+                        let kind = message
+                            .header(":exception-type")
+                            .or(message.header(":error-code"))
+                            .unwrap_or("Unknown")
+                            .to_string();
+                        let detail = serde_json::from_slice::<ExceptionPayload>(&message.payload)
+                            .map(|p| p.message)
+                            .unwrap_or_default();
+                        return Err(AsrError::Transcription(format!("{}: {}", kind, detail)));
+                    }
+                    _ => continue,
+                }
+
+                let full_text = state.full_text();
+                if full_text != last_text && !full_text.is_empty() {
+                    last_text = full_text.clone();
+                    let result = AsrResult {
+                        text: full_text,
+                        is_final: false,
+                        ..Default::default()
+                    };
+                    if result_tx.send(result).await.is_err() {
+                        break 'outer;
+                    }
+                }
+            }
+        }
+
+        // 发送最终结果
+        let final_text = state.full_text();
+        if !final_text.is_empty() {
+            let _ = result_tx
+                .send(AsrResult {
+                    text: final_text,
+                    is_final: true,
+                    confidence: state.confidence(),
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("Amazon Transcribe session completed");
+        Ok(())
+    }
+}
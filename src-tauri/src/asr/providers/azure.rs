@@ -0,0 +1,437 @@
+//! Azure Speech 语音识别 Provider
+//!
+//! 通过 Azure Speech 服务的 WebSocket 协议流式上传 PCM 音频（与官方 Speech SDK 相同的协议）。
+//! 文本消息和二进制消息都带有 HTTP 风格的头部，用 `Path` 区分消息类型。
+
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{
+        client::IntoClientRequest, http::HeaderValue, protocol::frame::coding::CloseCode, Message,
+    },
+};
+
+use super::region_locale;
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+use crate::text::profanity::ProfanityMode;
+
+/// 音频结束后等待服务端返回 turn.end 的最长时间
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+/// 未指定语言时使用的识别语言
+const DEFAULT_LOCALE: &str = "zh-CN";
+
+/// Azure Speech 配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AzureSpeechConfig {
+    /// 订阅密钥
+    #[serde(default)]
+    pub subscription_key: String,
+    /// 服务区域（如 "eastasia"、"chinaeast2"）
+    #[serde(default = "default_region")]
+    pub region: String,
+    /// 自定义端点（私有部署或 Azure 中国区），为空时按区域生成
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// 识别语言（可选）
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 服务端脏话过滤（运行时由全局设置注入）
+    #[serde(skip)]
+    pub profanity_filter: Option<ProfanityMode>,
+}
+
+fn default_region() -> String {
+    "eastasia".to_string()
+}
+
+impl Default for AzureSpeechConfig {
+    fn default() -> Self {
+        Self {
+            subscription_key: String::new(),
+            region: default_region(),
+            endpoint: None,
+            language: None,
+            profanity_filter: None,
+        }
+    }
+}
+
+impl AzureSpeechConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.subscription_key.is_empty() && (!self.region.is_empty() || self.endpoint.is_some())
+    }
+
+    fn locale(&self) -> String {
+        self.language
+            .as_deref()
+            .map_or(DEFAULT_LOCALE, region_locale)
+            .to_string()
+    }
+
+    fn url(&self) -> String {
+        let base = self.endpoint.clone().unwrap_or_else(|| {
+            format!(
+                "wss://{}.stt.speech.microsoft.com/speech/recognition/conversation/cognitiveservices/v1",
+                self.region
+            )
+        });
+        let profanity = match self.profanity_filter {
+            Some(ProfanityMode::Mask) => "masked",
+            Some(ProfanityMode::Remove) => "removed",
+            None => "raw",
+        };
+        format!(
+            "{}?language={}&format=detailed&profanity={}",
+            base,
+            self.locale(),
+            profanity
+        )
+    }
+}
+
+/// speech.hypothesis 消息体
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct Hypothesis {
+    #[serde(default)]
+    text: String,
+}
+
+/// speech.phrase 消息体（detailed 格式）
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct Phrase {
+    recognition_status: String,
+    #[serde(default)]
+    display_text: Option<String>,
+    #[serde(default, rename = "NBest")]
+    n_best: Vec<PhraseCandidate>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PhraseCandidate {
+    #[serde(default)]
+    confidence: f32,
+    #[serde(default)]
+    display: String,
+}
+
+/// 已识别的句子
+#[derive(Default)]
+struct TranscriptState {
+    phrases: Vec<String>,
+    confidences: Vec<f32>,
+    /// 当前句子的中间结果
+    hypothesis: String,
+    /// 中日文句子之间不加空格
+    separator: &'static str,
+}
+
+impl TranscriptState {
+    fn full_text(&self) -> String {
+        self.phrases
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(self.hypothesis.as_str()))
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(self.separator)
+    }
+
+    fn confidence(&self) -> Option<f32> {
+        if self.confidences.is_empty() {
+            return None;
+        }
+        Some(self.confidences.iter().sum::<f32>() / self.confidences.len() as f32)
+    }
+}
+
+/// 生成消息头部
+fn headers(path: &str, request_id: &str, content_type: &str) -> String {
+    format!(
+        "Path: {}\r\nX-RequestId: {}\r\nX-Timestamp: {}\r\nContent-Type: {}\r\n",
+        path,
+        request_id,
+        Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        content_type
+    )
+}
+
+/// 二进制音频消息：2 字节大端头部长度 + 头部 + 音频数据
+fn audio_message(request_id: &str, audio: &[u8]) -> Vec<u8> {
+    let header = headers("audio", request_id, "audio/x-wav");
+    let mut message = Vec::with_capacity(2 + header.len() + audio.len());
+    message.extend_from_slice(&(header.len() as u16).to_be_bytes());
+    message.extend_from_slice(header.as_bytes());
+    message.extend_from_slice(audio);
+    message
+}
+
+/// 流式 WAV 头部（16kHz / 16bit / 单声道，长度未知时填 0）
+fn wav_header() -> Vec<u8> {
+    const SAMPLE_RATE: u32 = 16000;
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&0u32.to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    header.extend_from_slice(&1u16.to_le_bytes()); // 单声道
+    header.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    header.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    header.extend_from_slice(&2u16.to_le_bytes());
+    header.extend_from_slice(&16u16.to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&0u32.to_le_bytes());
+    header
+}
+
+/// 解析文本消息，返回 (Path, 消息体)
+fn parse_text_message(text: &str) -> Option<(&str, &str)> {
+    let (head, body) = text.split_once("\r\n\r\n")?;
+    let path = head.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("path").then(|| value.trim())
+    })?;
+    Some((path, body))
+}
+
+/// Azure Speech Provider
+pub struct AzureSpeechProvider {
+    config: AzureSpeechConfig,
+}
+
+impl AzureSpeechProvider {
+    pub fn new(config: AzureSpeechConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for AzureSpeechProvider {
+    fn id(&self) -> &str {
+        "azure"
+    }
+
+    fn display_name(&self) -> &str {
+        "Azure Speech"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if self.config.subscription_key.is_empty() {
+            return Err(AsrError::Configuration("订阅密钥不能为空".into()));
+        }
+        if self.config.region.is_empty() && self.config.endpoint.is_none() {
+            return Err(AsrError::Configuration("服务区域不能为空".into()));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let connection_id = uuid::Uuid::new_v4().simple().to_string().to_uppercase();
+        let request_id = uuid::Uuid::new_v4().simple().to_string().to_uppercase();
+
+        let mut request = self
+            .config
+            .url()
+            .into_client_request()
+            .map_err(|e| AsrError::Configuration(format!("无效的端点: {}", e)))?;
+        let key = HeaderValue::from_str(&self.config.subscription_key)
+            .map_err(|e| AsrError::Configuration(format!("无效的订阅密钥: {}", e)))?;
+        request
+            .headers_mut()
+            .insert("Ocp-Apim-Subscription-Key", key);
+        request.headers_mut().insert(
+            "X-ConnectionId",
+            HeaderValue::from_str(&connection_id)
+                .map_err(|e| AsrError::Configuration(e.to_string()))?,
+        );
+
+        log::info!("Connecting to Azure Speech ({})", self.config.region);
+        let (ws_stream, _response) = connect_async(request)
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+        log::info!("Azure Speech WebSocket connected");
+
+        let (mut write, mut read) = ws_stream.split();
+
+        let speech_config = json!({
+            "context": {
+                "system": { "name": "Speaky", "version": env!("CARGO_PKG_VERSION") },
+                "os": { "platform": std::env::consts::OS },
+                "audio": { "source": { "type": "Microphones" } },
+            }
+        });
+        let message = format!(
+            "{}\r\n{}",
+            headers("speech.config", &request_id, "application/json"),
+            speech_config
+        );
+        write
+            .send(Message::Text(message))
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+
+        // 发送音频：首个消息带 WAV 头部，结束时发送空音频消息
+        let (audio_done_tx, mut audio_done_rx) = tokio::sync::oneshot::channel::<()>();
+        let audio_request_id = request_id.clone();
+        tokio::spawn(async move {
+            let header = wav_header();
+            let message = audio_message(&audio_request_id, &header);
+            if write.send(Message::Binary(message)).await.is_err() {
+                return;
+            }
+
+            while let Some(chunk) = audio_rx.recv().await {
+                let message = audio_message(&audio_request_id, &chunk);
+                if write.send(Message::Binary(message)).await.is_err() {
+                    return;
+                }
+            }
+
+            log::info!("Audio channel closed, sending end of audio");
+            let _ = write
+                .send(Message::Binary(audio_message(&audio_request_id, &[])))
+                .await;
+            let _ = audio_done_tx.send(());
+        });
+
+        let locale = self.config.locale();
+        let mut state = TranscriptState {
+            separator: if locale.starts_with("zh") || locale.starts_with("ja") {
+                ""
+            } else {
+                " "
+            },
+            ..Default::default()
+        };
+        let mut last_text = String::new();
+        let mut audio_done = false;
+
+        loop {
+            let next = if audio_done {
+                match tokio::time::timeout(FINALIZE_TIMEOUT, read.next()).await {
+                    Ok(msg) => msg,
+                    Err(_) => {
+                        log::warn!("Azure Speech finalize timeout");
+                        break;
+                    }
+                }
+            } else {
+                tokio::select! {
+                    msg = read.next() => msg,
+                    _ = &mut audio_done_rx => {
+                        audio_done = true;
+                        continue;
+                    }
+                }
+            };
+
+            let Some(msg) = next else {
+                log::info!("Azure Speech connection closed");
+                break;
+            };
+
+            let text = match msg {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(frame)) => {
+                    // 认证失败、配额不足等错误通过关闭帧返回
+                    if let Some(frame) = frame {
+                        if frame.code != CloseCode::Normal {
+                            return Err(AsrError::Connection(frame.reason.to_string()));
+                        }
+                    }
+                    break;
+                }
+                Ok(_) => continue,
+                Err(e) => return Err(AsrError::Connection(e.to_string())),
+            };
+
+            let Some((path, body)) = parse_text_message(&text) else {
+                continue;
+            };
+
+            match path {
+                "speech.hypothesis" => {
+                    if let Ok(hypothesis) = serde_json::from_str::<Hypothesis>(body) {
+                        state.hypothesis = hypothesis.text;
+                    }
+                }
+                "speech.phrase" => {
+                    let Ok(phrase) = serde_json::from_str::<Phrase>(body) else {
+                        continue;
+                    };
+                    state.hypothesis.clear();
+                    if phrase.recognition_status != "Success" {
+                        log::debug!("Azure Speech phrase status: {}", phrase.recognition_status);
+                        continue;
+                    }
+                    let best = phrase.n_best.first();
+                    let display = phrase
+                        .display_text
+                        .or_else(|| best.map(|c| c.display.clone()))
+                        .unwrap_or_default();
+                    if !display.is_empty() {
+                        state.phrases.push(display);
+                        if let Some(candidate) = best {
+                            state.confidences.push(candidate.confidence);
+                        }
+                    }
+                }
+                "turn.end" => break,
+                _ => continue,
+            }
+
+            let full_text = state.full_text();
+            if full_text != last_text && !full_text.is_empty() {
+                last_text = full_text.clone();
+                let result = AsrResult {
+                    text: full_text,
+                    is_final: false,
+                    ..Default::default()
+                };
+                if result_tx.send(result).await.is_err() {
+                    break;
+                }
+            }
+        }
+
+        // 发送最终结果（未完成的中间结果也一并输出）
+        let final_text = state.full_text();
+        if !final_text.is_empty() {
+            let _ = result_tx
+                .send(AsrResult {
+                    text: final_text,
+                    is_final: true,
+                    confidence: state.confidence(),
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("Azure Speech session completed");
+        Ok(())
+    }
+}
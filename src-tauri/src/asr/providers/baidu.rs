@@ -0,0 +1,265 @@
+//! 百度短语音识别 Provider
+//!
+//! 松开快捷键后将整段 PCM 上传到百度短语音识别 REST 接口（单次最长 60 秒）。
+//! access_token 由 API Key / Secret Key 换取，有效期 30 天，在 Provider 内部缓存并在
+//! 过期前或服务端报告失效时自动刷新。
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+
+/// 鉴权接口
+const TOKEN_URL: &str = "https://aip.baidubce.com/oauth/2.0/token";
+/// 标准版识别接口
+const ASR_URL: &str = "https://vop.baidu.com/server_api";
+/// 单次请求超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// 提前刷新 token 的余量
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(24 * 60 * 60);
+/// 接口支持的最长音频（16kHz 16bit 单声道 60 秒）
+const MAX_AUDIO_BYTES: usize = 16000 * 2 * 60;
+/// token 无效或过期的错误码
+const TOKEN_ERROR_CODES: &[i64] = &[3302, 110, 111];
+
+/// 缓存的 access_token（每次录音都会新建 Provider，需全局共享）
+static TOKEN_CACHE: LazyLock<Arc<Mutex<Option<CachedToken>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+
+#[derive(Clone)]
+struct CachedToken {
+    /// 换取 token 时使用的 API Key，配置变更后不再复用
+    api_key: String,
+    token: String,
+    expires_at: Instant,
+}
+
+/// 百度语音配置
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BaiduConfig {
+    /// API Key
+    #[serde(default)]
+    pub api_key: String,
+    /// Secret Key
+    #[serde(default)]
+    pub secret_key: String,
+    /// 识别模型 ID（dev_pid），为空时按识别语言选择
+    #[serde(default)]
+    pub dev_pid: Option<u32>,
+    /// 识别语言（可选）
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+impl BaiduConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.api_key.is_empty() && !self.secret_key.is_empty()
+    }
+
+    fn dev_pid(&self) -> u32 {
+        if let Some(pid) = self.dev_pid {
+            return pid;
+        }
+        match self.language.as_deref() {
+            Some("en") => 1737,
+            Some("yue") => 1637,
+            _ => 1537,
+        }
+    }
+}
+
+/// 鉴权响应
+#[derive(Deserialize)]
+struct TokenResponse {
+    #[serde(default)]
+    access_token: Option<String>,
+    #[serde(default)]
+    expires_in: u64,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// 识别响应
+#[derive(Deserialize)]
+struct RecognitionResponse {
+    err_no: i64,
+    #[serde(default)]
+    err_msg: String,
+    #[serde(default)]
+    result: Vec<String>,
+}
+
+/// 百度语音 Provider
+pub struct BaiduProvider {
+    config: BaiduConfig,
+    client: reqwest::Client,
+}
+
+impl BaiduProvider {
+    pub fn new(config: BaiduConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// 获取 access_token，缓存有效时直接复用
+    async fn access_token(&self) -> Result<String, AsrError> {
+        if let Some(cached) = TOKEN_CACHE.lock().clone() {
+            if cached.api_key == self.config.api_key
+                && cached.expires_at > Instant::now() + TOKEN_REFRESH_MARGIN
+            {
+                return Ok(cached.token);
+            }
+        }
+
+        log::info!("Refreshing Baidu access token");
+        let response: TokenResponse = self
+            .client
+            .post(TOKEN_URL)
+            .query(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", self.config.api_key.as_str()),
+                ("client_secret", self.config.secret_key.as_str()),
+            ])
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| AsrError::Connection(format!("解析鉴权响应失败: {}", e)))?;
+
+        let token = response.access_token.ok_or_else(|| {
+            AsrError::Configuration(format!(
+                "获取 access_token 失败: {}",
+                response.error_description.unwrap_or_default()
+            ))
+        })?;
+
+        *TOKEN_CACHE.lock() = Some(CachedToken {
+            api_key: self.config.api_key.clone(),
+            token: token.clone(),
+            expires_at: Instant::now() + Duration::from_secs(response.expires_in),
+        });
+        Ok(token)
+    }
+
+    /// 发送一次识别请求
+    async fn recognize(&self, audio: &[u8], token: &str) -> Result<RecognitionResponse, AsrError> {
+        let body = json!({
+            "format": "pcm",
+            "rate": 16000,
+            "channel": 1,
+            "cuid": "speaky",
+            "token": token,
+            "dev_pid": self.config.dev_pid(),
+            "speech": STANDARD.encode(audio),
+            "len": audio.len(),
+        });
+
+        self.client
+            .post(ASR_URL)
+            .timeout(REQUEST_TIMEOUT)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| AsrError::Transcription(format!("解析响应失败: {}", e)))
+    }
+}
+
+#[async_trait]
+impl AsrProvider for BaiduProvider {
+    fn id(&self) -> &str {
+        "baidu"
+    }
+
+    fn display_name(&self) -> &str {
+        "百度语音"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn result_timeout(&self, _audio_secs: f32) -> Duration {
+        // 最多两轮获取 token + 识别（token 失效时重试一次）
+        REQUEST_TIMEOUT * 4
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if !self.config.is_configured() {
+            return Err(AsrError::Configuration(
+                "API Key 和 Secret Key 不能为空".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        // 累积所有音频数据
+        let mut audio_buffer = Vec::new();
+        while let Some(chunk) = audio_rx.recv().await {
+            audio_buffer.extend(chunk);
+        }
+
+        if audio_buffer.is_empty() {
+            return Ok(());
+        }
+        if audio_buffer.len() > MAX_AUDIO_BYTES {
+            log::warn!("Audio exceeds Baidu 60s limit, truncating");
+            audio_buffer.truncate(MAX_AUDIO_BYTES);
+        }
+
+        let token = self.access_token().await?;
+        let mut response = self.recognize(&audio_buffer, &token).await?;
+
+        // token 被服务端判定失效时刷新后重试一次
+        if TOKEN_ERROR_CODES.contains(&response.err_no) {
+            log::warn!("Baidu access token rejected ({}), retrying", response.err_no);
+            *TOKEN_CACHE.lock() = None;
+            let token = self.access_token().await?;
+            response = self.recognize(&audio_buffer, &token).await?;
+        }
+
+        if response.err_no != 0 {
+            return Err(AsrError::Transcription(format!(
+                "{} ({})",
+                response.err_msg, response.err_no
+            )));
+        }
+
+        let text = response.result.concat().trim().to_string();
+        if !text.is_empty() {
+            let _ = result_tx
+                .send(AsrResult {
+                    text,
+                    is_final: true,
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("Baidu session completed");
+        Ok(())
+    }
+}
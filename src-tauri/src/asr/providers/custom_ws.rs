@@ -0,0 +1,304 @@
+//! 自定义 WebSocket 语音识别 Provider
+//!
+//! 用于接入内部自建的流式识别网关：连接地址、请求头和消息格式都来自配置。
+//!
+//! - 连接后发送 `start_message`，音频结束后发送 `end_message`（均可选）
+//! - 音频默认以二进制帧发送；配置 `audio_message` 时改为文本帧，其中 `{audio}`
+//!   替换为 base64 编码的 PCM
+//! - 消息模板中的 `{language}` 替换为识别语言
+//! - 响应字段用点号路径指定（如 `result.text`、`results.0.transcript`）
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{
+        client::IntoClientRequest,
+        http::{HeaderName, HeaderValue},
+        Message,
+    },
+};
+
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+
+/// 音频结束后等待服务端输出剩余结果的最长时间
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 自定义 WebSocket 配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CustomWsConfig {
+    /// 连接地址（ws:// 或 wss://）
+    #[serde(default)]
+    pub url: String,
+    /// 额外的请求头（如鉴权）
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// 连接后发送的开始消息模板（可选）
+    #[serde(default)]
+    pub start_message: Option<String>,
+    /// 音频消息模板（可选，为空时直接发送二进制 PCM）
+    #[serde(default)]
+    pub audio_message: Option<String>,
+    /// 音频结束后发送的结束消息模板（可选）
+    #[serde(default)]
+    pub end_message: Option<String>,
+    /// 识别文本字段路径
+    #[serde(default = "default_text_field")]
+    pub text_field: String,
+    /// 句子结束标志字段路径（可选，为空时每条响应都视为完整的累计文本）
+    #[serde(default)]
+    pub final_field: Option<String>,
+    /// 错误信息字段路径（可选，字段存在且非空时结束识别）
+    #[serde(default)]
+    pub error_field: Option<String>,
+    /// 识别语言（可选）
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+fn default_text_field() -> String {
+    "text".to_string()
+}
+
+impl Default for CustomWsConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            headers: BTreeMap::new(),
+            start_message: None,
+            audio_message: None,
+            end_message: None,
+            text_field: default_text_field(),
+            final_field: None,
+            error_field: None,
+            language: None,
+        }
+    }
+}
+
+impl CustomWsConfig {
+    pub fn is_configured(&self) -> bool {
+        self.url.starts_with("ws://") || self.url.starts_with("wss://")
+    }
+
+    /// 替换消息模板中的 `{language}`
+    fn render(&self, template: &str) -> String {
+        template.replace("{language}", self.language.as_deref().unwrap_or(""))
+    }
+}
+
+/// 按点号路径读取 JSON 字段
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    value.pointer(&format!("/{}", path.replace('.', "/")))
+}
+
+/// 自定义 WebSocket Provider
+pub struct CustomWsProvider {
+    config: CustomWsConfig,
+}
+
+impl CustomWsProvider {
+    pub fn new(config: CustomWsConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for CustomWsProvider {
+    fn id(&self) -> &str {
+        "custom_ws"
+    }
+
+    fn display_name(&self) -> &str {
+        "自定义 WebSocket"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if !self.config.is_configured() {
+            return Err(AsrError::Configuration(
+                "连接地址必须以 ws:// 或 wss:// 开头".into(),
+            ));
+        }
+        if self.config.text_field.is_empty() {
+            return Err(AsrError::Configuration("识别文本字段路径不能为空".into()));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let mut request = self
+            .config
+            .url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| AsrError::Configuration(format!("无效的连接地址: {}", e)))?;
+        for (name, value) in &self.config.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| AsrError::Configuration(format!("无效的请求头 {}: {}", name, e)))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| AsrError::Configuration(format!("无效的请求头值: {}", e)))?;
+            request.headers_mut().insert(name, value);
+        }
+
+        log::info!("Connecting to custom WebSocket ASR: {}", self.config.url);
+        let (ws_stream, _response) = connect_async(request)
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+        log::info!("Custom WebSocket ASR connected");
+
+        let (mut write, mut read) = ws_stream.split();
+
+        if let Some(ref start) = self.config.start_message {
+            write
+                .send(Message::Text(self.config.render(start)))
+                .await
+                .map_err(|e| AsrError::Connection(e.to_string()))?;
+        }
+
+        // 发送音频，结束时发送结束消息
+        let audio_template = self.config.audio_message.as_ref().map(|t| self.config.render(t));
+        let end_message = self.config.end_message.as_ref().map(|t| self.config.render(t));
+        let (audio_done_tx, mut audio_done_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            while let Some(chunk) = audio_rx.recv().await {
+                let message = match audio_template {
+                    Some(ref template) => {
+                        Message::Text(template.replace("{audio}", &STANDARD.encode(&chunk)))
+                    }
+                    None => Message::Binary(chunk),
+                };
+                if write.send(message).await.is_err() {
+                    return;
+                }
+            }
+
+            log::info!("Audio channel closed, sending end message");
+            if let Some(end) = end_message {
+                let _ = write.send(Message::Text(end)).await;
+            }
+            let _ = audio_done_tx.send(());
+        });
+
+        // 已结束的句子 + 当前句子的中间结果
+        let mut committed = String::new();
+        let mut current = String::new();
+        let mut last_text = String::new();
+        let mut audio_done = false;
+
+        loop {
+            let next = if audio_done {
+                match tokio::time::timeout(FINALIZE_TIMEOUT, read.next()).await {
+                    Ok(msg) => msg,
+                    Err(_) => {
+                        log::info!("Custom WebSocket ASR finalize timeout");
+                        break;
+                    }
+                }
+            } else {
+                tokio::select! {
+                    msg = read.next() => msg,
+                    _ = &mut audio_done_rx => {
+                        audio_done = true;
+                        continue;
+                    }
+                }
+            };
+
+            let Some(msg) = next else {
+                log::info!("Custom WebSocket ASR connection closed");
+                break;
+            };
+
+            let text = match msg {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(e) => return Err(AsrError::Connection(e.to_string())),
+            };
+
+            let Ok(response) = serde_json::from_str::<Value>(&text) else {
+                log::debug!("Ignoring non-JSON message: {}", text);
+                continue;
+            };
+
+            if let Some(ref path) = self.config.error_field {
+                match lookup(&response, path) {
+                    Some(Value::Null) | None => {}
+                    Some(Value::String(s)) if s.is_empty() => {}
+                    Some(Value::String(s)) => return Err(AsrError::Transcription(s.clone())),
+                    Some(other) => return Err(AsrError::Transcription(other.to_string())),
+                }
+            }
+
+            let Some(sentence) = lookup(&response, &self.config.text_field).and_then(Value::as_str)
+            else {
+                continue;
+            };
+
+            match self.config.final_field {
+                Some(ref path) => {
+                    let is_final = match lookup(&response, path) {
+                        Some(Value::Bool(b)) => *b,
+                        Some(Value::Number(n)) => n.as_i64().unwrap_or(0) != 0,
+                        _ => false,
+                    };
+                    if is_final {
+                        committed.push_str(sentence);
+                        current.clear();
+                    } else {
+                        current = sentence.to_string();
+                    }
+                }
+                None => current = sentence.to_string(),
+            }
+
+            let full_text = format!("{}{}", committed, current);
+            if full_text != last_text && !full_text.is_empty() {
+                last_text = full_text.clone();
+                let result = AsrResult {
+                    text: full_text,
+                    is_final: false,
+                    ..Default::default()
+                };
+                if result_tx.send(result).await.is_err() {
+                    break;
+                }
+            }
+        }
+
+        // 发送最终结果
+        let final_text = format!("{}{}", committed, current).trim().to_string();
+        if !final_text.is_empty() {
+            let _ = result_tx
+                .send(AsrResult {
+                    text: final_text,
+                    is_final: true,
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("Custom WebSocket ASR session completed");
+        Ok(())
+    }
+}
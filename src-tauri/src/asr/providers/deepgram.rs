@@ -0,0 +1,318 @@
+//! Deepgram 实时语音识别 Provider
+//!
+//! 通过 Deepgram Live Streaming WebSocket API 上传 PCM 音频，中间结果（interim）
+//! 作为非最终 `AsrResult` 发送，实时输入可以边说边上屏。
+
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, http::HeaderValue, Message},
+};
+
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+use crate::text::profanity::ProfanityMode;
+
+/// 音频结束后等待服务端关闭连接的最长时间
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Deepgram 配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeepgramConfig {
+    /// API Key
+    #[serde(default)]
+    pub api_key: String,
+    /// WebSocket 端点
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    /// 模型名称
+    #[serde(default = "default_model")]
+    pub model: String,
+    /// 识别语言（可选）
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 服务端脏话过滤（运行时由全局设置注入）
+    #[serde(skip)]
+    pub profanity_filter: Option<ProfanityMode>,
+}
+
+fn default_endpoint() -> String {
+    "wss://api.deepgram.com/v1/listen".to_string()
+}
+
+fn default_model() -> String {
+    "nova-2".to_string()
+}
+
+impl Default for DeepgramConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            endpoint: default_endpoint(),
+            model: default_model(),
+            language: None,
+            profanity_filter: None,
+        }
+    }
+}
+
+impl DeepgramConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    fn url(&self) -> String {
+        let mut url = format!(
+            "{}?model={}&encoding=linear16&sample_rate=16000&channels=1\
+             &interim_results=true&punctuate=true&smart_format=true",
+            self.endpoint, self.model
+        );
+        if let Some(ref language) = self.language {
+            url.push_str(&format!("&language={}", language));
+        }
+        // Deepgram 只支持开关，命中词会被替换为 * 号
+        if self.profanity_filter.is_some() {
+            url.push_str("&profanity_filter=true");
+        }
+        url
+    }
+}
+
+/// 服务端消息（只解析需要的字段）
+#[derive(Deserialize, Debug)]
+struct ServerMessage {
+    #[serde(rename = "type")]
+    message_type: String,
+    #[serde(default)]
+    channel: Option<Channel>,
+    #[serde(default)]
+    is_final: bool,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Channel {
+    #[serde(default)]
+    alternatives: Vec<Alternative>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Alternative {
+    #[serde(default)]
+    transcript: String,
+    #[serde(default)]
+    confidence: f32,
+}
+
+/// 已确定的片段 + 当前中间结果
+#[derive(Default)]
+struct TranscriptState {
+    finals: Vec<String>,
+    confidences: Vec<f32>,
+    interim: String,
+    separator: &'static str,
+}
+
+impl TranscriptState {
+    fn full_text(&self) -> String {
+        self.finals
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(self.interim.as_str()))
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(self.separator)
+    }
+
+    fn confidence(&self) -> Option<f32> {
+        if self.confidences.is_empty() {
+            return None;
+        }
+        Some(self.confidences.iter().sum::<f32>() / self.confidences.len() as f32)
+    }
+}
+
+/// Deepgram Provider
+pub struct DeepgramProvider {
+    config: DeepgramConfig,
+}
+
+impl DeepgramProvider {
+    pub fn new(config: DeepgramConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for DeepgramProvider {
+    fn id(&self) -> &str {
+        "deepgram"
+    }
+
+    fn display_name(&self) -> &str {
+        "Deepgram"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if self.config.api_key.is_empty() {
+            return Err(AsrError::Configuration("API Key 不能为空".into()));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let mut request = self
+            .config
+            .url()
+            .into_client_request()
+            .map_err(|e| AsrError::Configuration(format!("无效的端点: {}", e)))?;
+        let auth = HeaderValue::from_str(&format!("Token {}", self.config.api_key))
+            .map_err(|e| AsrError::Configuration(format!("无效的 API Key: {}", e)))?;
+        request.headers_mut().insert("Authorization", auth);
+
+        log::info!("Connecting to Deepgram ({})", self.config.model);
+        let (ws_stream, _response) = connect_async(request)
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+        log::info!("Deepgram WebSocket connected");
+
+        let (mut write, mut read) = ws_stream.split();
+
+        // 发送音频，结束时通知服务端输出剩余结果并关闭连接
+        let (audio_done_tx, mut audio_done_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            while let Some(chunk) = audio_rx.recv().await {
+                if write.send(Message::Binary(chunk)).await.is_err() {
+                    return;
+                }
+            }
+
+            log::info!("Audio channel closed, closing Deepgram stream");
+            let close = json!({ "type": "CloseStream" });
+            let _ = write.send(Message::Text(close.to_string())).await;
+            let _ = audio_done_tx.send(());
+        });
+
+        let separator = match self.config.language.as_deref() {
+            Some(lang) if lang.starts_with("zh") || lang.starts_with("ja") => "",
+            _ => " ",
+        };
+        let mut state = TranscriptState {
+            separator,
+            ..Default::default()
+        };
+        let mut last_text = String::new();
+        let mut audio_done = false;
+
+        loop {
+            let next = if audio_done {
+                match tokio::time::timeout(FINALIZE_TIMEOUT, read.next()).await {
+                    Ok(msg) => msg,
+                    Err(_) => {
+                        log::warn!("Deepgram finalize timeout");
+                        break;
+                    }
+                }
+            } else {
+                tokio::select! {
+                    msg = read.next() => msg,
+                    _ = &mut audio_done_rx => {
+                        audio_done = true;
+                        continue;
+                    }
+                }
+            };
+
+            let Some(msg) = next else {
+                log::info!("Deepgram connection closed");
+                break;
+            };
+
+            let text = match msg {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(e) => return Err(AsrError::Connection(e.to_string())),
+            };
+
+            let Ok(message) = serde_json::from_str::<ServerMessage>(&text) else {
+                continue;
+            };
+
+            match message.message_type.as_str() {
+                "Results" => {
+                    let Some(alternative) = message
+                        .channel
+                        .and_then(|c| c.alternatives.into_iter().next())
+                    else {
+                        continue;
+                    };
+                    if message.is_final {
+                        state.interim.clear();
+                        if !alternative.transcript.trim().is_empty() {
+                            state.finals.push(alternative.transcript);
+                            state.confidences.push(alternative.confidence);
+                        }
+                    } else {
+                        state.interim = alternative.transcript;
+                    }
+                }
+                "Error" => {
+                    return Err(AsrError::Transcription(
+                        message.description.unwrap_or(text.clone()),
+                    ));
+                }
+                _ => continue,
+            }
+
+            let full_text = state.full_text();
+            if full_text != last_text && !full_text.is_empty() {
+                last_text = full_text.clone();
+                let result = AsrResult {
+                    text: full_text,
+                    is_final: false,
+                    ..Default::default()
+                };
+                if result_tx.send(result).await.is_err() {
+                    break;
+                }
+            }
+        }
+
+        // 发送最终结果
+        let final_text = state.full_text();
+        if !final_text.is_empty() {
+            let _ = result_tx
+                .send(AsrResult {
+                    text: final_text,
+                    is_final: true,
+                    confidence: state.confidence(),
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("Deepgram session completed");
+        Ok(())
+    }
+}
@@ -4,13 +4,18 @@
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 
 use crate::asr::client::AsrClient;
+use crate::asr::codec::AudioCodec;
+use crate::asr::protocol::{Corpus, RequestConfig};
 use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+use crate::asr::transport::{RecordingTransport, ReplayTransport, WebSocketTransport};
 
 /// 豆包 ASR 配置
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DoubaoConfig {
     /// 应用 ID
     #[serde(default)]
@@ -21,12 +26,96 @@ pub struct DoubaoConfig {
     /// 密钥（可选，用于 HMAC 签名）
     #[serde(default)]
     pub secret_key: String,
+    /// 是否由服务端添加标点
+    #[serde(default = "default_true")]
+    pub enable_punc: bool,
+    /// 是否启用逆文本规范化（如 "一百二十" -> "120"）
+    #[serde(default = "default_true")]
+    pub enable_itn: bool,
+    /// 是否返回分句（utterances）详情
+    #[serde(default)]
+    pub show_utterances: bool,
+    /// 结果返回方式 ("single": 增量返回, "full": 全量返回)
+    #[serde(default = "default_result_type")]
+    pub result_type: String,
+    /// 上传音频编码（"pcm" 或 "opus"，网络较差时 Opus 可大幅减少上行流量）
+    #[serde(default)]
+    pub audio_codec: AudioCodec,
+    /// 热词（运行时由统一的热词设置填充）
+    #[serde(skip)]
+    pub hotwords: Vec<String>,
+    /// 调试：把收发的二进制帧录制到该文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record_frames_path: Option<String>,
+    /// 调试：不连接服务端，从该录制文件回放服务端帧
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay_frames_path: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_result_type() -> String {
+    "single".to_string()
+}
+
+impl Default for DoubaoConfig {
+    fn default() -> Self {
+        Self {
+            app_id: String::new(),
+            access_token: String::new(),
+            secret_key: String::new(),
+            enable_punc: true,
+            enable_itn: true,
+            show_utterances: false,
+            result_type: default_result_type(),
+            audio_codec: AudioCodec::Pcm,
+            hotwords: Vec::new(),
+            record_frames_path: None,
+            replay_frames_path: None,
+        }
+    }
 }
 
 impl DoubaoConfig {
     pub fn is_configured(&self) -> bool {
         !self.app_id.is_empty() && !self.access_token.is_empty()
     }
+
+    /// 转换为协议层的识别请求参数
+    pub fn request_config(&self) -> RequestConfig {
+        RequestConfig {
+            enable_punc: self.enable_punc,
+            enable_itn: self.enable_itn,
+            show_utterances: self.show_utterances,
+            result_type: self.result_type.clone(),
+            corpus: Corpus::from_hotwords(&self.hotwords),
+            ..RequestConfig::default()
+        }
+    }
+
+    /// 创建使用该配置的 ASR 客户端
+    pub fn client(&self) -> AsrClient {
+        let client = AsrClient::new(
+            self.app_id.clone(),
+            self.access_token.clone(),
+            self.secret_key.clone(),
+        )
+        .with_request_config(self.request_config())
+        .with_audio_codec(self.audio_codec);
+
+        if let Some(ref path) = self.replay_frames_path {
+            client.with_transport(Arc::new(ReplayTransport::new(PathBuf::from(path))))
+        } else if let Some(ref path) = self.record_frames_path {
+            client.with_transport(Arc::new(RecordingTransport::new(
+                Arc::new(WebSocketTransport),
+                PathBuf::from(path),
+            )))
+        } else {
+            client
+        }
+    }
 }
 
 /// 豆包语音识别 Provider
@@ -58,6 +147,10 @@ impl AsrProvider for DoubaoProvider {
         }
     }
 
+    fn audio_codec(&self) -> AudioCodec {
+        self.config.audio_codec
+    }
+
     fn validate(&self) -> Result<(), AsrError> {
         if self.config.app_id.is_empty() {
             return Err(AsrError::Configuration("App ID 不能为空".into()));
@@ -65,6 +158,12 @@ impl AsrProvider for DoubaoProvider {
         if self.config.access_token.is_empty() {
             return Err(AsrError::Configuration("Access Token 不能为空".into()));
         }
+        if !matches!(self.config.result_type.as_str(), "single" | "full") {
+            return Err(AsrError::Configuration(format!(
+                "不支持的 result_type: {}",
+                self.config.result_type
+            )));
+        }
         Ok(())
     }
 
@@ -75,11 +174,7 @@ impl AsrProvider for DoubaoProvider {
     ) -> Result<(), AsrError> {
         self.validate()?;
 
-        let client = AsrClient::new(
-            self.config.app_id.clone(),
-            self.config.access_token.clone(),
-            self.config.secret_key.clone(),
-        );
+        let client = self.config.client();
 
         // 创建内部结果通道，转换格式
         let (internal_tx, mut internal_rx) =
@@ -92,6 +187,9 @@ impl AsrProvider for DoubaoProvider {
                 let result = AsrResult {
                     text: internal_result.text,
                     is_final: !internal_result.is_prefetch,
+                    words: internal_result.words,
+                    segments: internal_result.segments,
+                    ..Default::default()
                 };
                 if result_tx_clone.send(result).await.is_err() {
                     break;
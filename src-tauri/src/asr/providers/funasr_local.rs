@@ -0,0 +1,147 @@
+//! FunASR Paraformer 本地语音识别 Provider
+//!
+//! 通过 sherpa-onnx (ONNX Runtime) 运行 Paraformer 中文模型，普通话识别效果明显好于
+//! Whisper tiny/base。录音过程中按秒重新解码输出中间结果。
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sherpa_rs::paraformer::{ParaformerConfig, ParaformerRecognizer};
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+
+use super::sherpa::{self, ModelSpec, SherpaModel, SAMPLE_RATE};
+use crate::asr::provider::{
+    AsrError, AsrProvider, AsrResult, DownloadProgress, ModelDownloadable, ModelInfo,
+    ProviderStatus,
+};
+
+/// Paraformer 模型
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FunAsrModel {
+    #[default]
+    ParaformerZh,
+    ParaformerZhSmall,
+}
+
+impl SherpaModel for FunAsrModel {
+    const DIR: &'static str = "funasr";
+    const FILES: &'static [&'static str] = &["tokens.txt", "model.int8.onnx"];
+    const MODELS: &'static [ModelSpec<Self>] = &[
+        ModelSpec {
+            model: Self::ParaformerZh,
+            id: "sherpa-onnx-paraformer-zh-2023-09-14",
+            name: "Paraformer 中文",
+            size_bytes: 227_000_000,
+            checksums: &[],
+        },
+        ModelSpec {
+            model: Self::ParaformerZhSmall,
+            id: "sherpa-onnx-paraformer-zh-small-2024-03-09",
+            name: "Paraformer 中文 Small",
+            size_bytes: 79_000_000,
+            checksums: &[],
+        },
+    ];
+}
+
+/// FunASR 本地配置
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FunAsrLocalConfig {
+    /// 模型
+    #[serde(default)]
+    pub model: FunAsrModel,
+    /// 自定义模型目录（需包含 model.int8.onnx 和 tokens.txt）
+    #[serde(default)]
+    pub model_path: Option<PathBuf>,
+}
+
+/// FunASR 本地 Provider
+pub struct FunAsrLocalProvider {
+    config: FunAsrLocalConfig,
+}
+
+impl FunAsrLocalProvider {
+    pub fn new(config: FunAsrLocalConfig) -> Self {
+        Self { config }
+    }
+
+    /// 获取模型目录
+    fn model_dir(&self) -> PathBuf {
+        sherpa::model_dir(&self.config.model, self.config.model_path.as_deref())
+    }
+}
+
+#[async_trait]
+impl AsrProvider for FunAsrLocalProvider {
+    fn id(&self) -> &str {
+        "funasr_local"
+    }
+
+    fn display_name(&self) -> &str {
+        "FunASR 本地"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        sherpa::status(&self.config.model, &self.model_dir())
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        sherpa::validate(&self.config.model, &self.model_dir())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let model_dir = self.model_dir();
+        let config = ParaformerConfig {
+            model: model_dir.join("model.int8.onnx").to_string_lossy().to_string(),
+            tokens: model_dir.join("tokens.txt").to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        tokio::task::spawn_blocking(move || {
+            let mut recognizer = ParaformerRecognizer::new(config)
+                .map_err(|e| AsrError::Transcription(format!("模型加载失败: {}", e)))?;
+            sherpa::transcribe_blocking(audio_rx, result_tx, |samples| {
+                recognizer.transcribe(SAMPLE_RATE, samples).text
+            })
+        })
+        .await
+        .map_err(|e| AsrError::Transcription(format!("任务执行失败: {}", e)))??;
+
+        log::info!("FunASR session completed");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ModelDownloadable for FunAsrLocalProvider {
+    fn available_models(&self) -> Vec<ModelInfo> {
+        sherpa::available_models(&self.config.model)
+    }
+
+    fn models_dir(&self) -> PathBuf {
+        sherpa::models_dir(FunAsrModel::DIR)
+    }
+
+    async fn download_model(
+        &self,
+        model_id: &str,
+        progress_tx: mpsc::Sender<DownloadProgress>,
+    ) -> Result<PathBuf, AsrError> {
+        sherpa::download_model::<FunAsrModel>(model_id, progress_tx).await
+    }
+
+    async fn delete_model(&self, model_id: &str) -> Result<(), AsrError> {
+        sherpa::delete_model::<FunAsrModel>(model_id)
+    }
+
+    fn cancel_download(&self) {
+        sherpa::cancel_download::<FunAsrModel>();
+    }
+}
@@ -0,0 +1,362 @@
+//! 讯飞语音听写（IAT）Provider
+//!
+//! 通过讯飞 IAT WebSocket 接口流式上传 PCM 音频，开启动态修正（wpgs）获取中间结果。
+//! 单次会话最长 60 秒音频。
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::Utc;
+use futures_util::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use super::url_encode;
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 音频结束后等待最终结果的最长时间
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+/// 服务端静音检测的尾部时长（毫秒），设为最大值，由用户松开快捷键结束
+const VAD_EOS_MS: u32 = 10000;
+
+/// 讯飞配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IflytekConfig {
+    /// APPID
+    #[serde(default)]
+    pub app_id: String,
+    /// APIKey
+    #[serde(default)]
+    pub api_key: String,
+    /// APISecret
+    #[serde(default)]
+    pub api_secret: String,
+    /// 接口地址
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    /// 识别语言（可选）
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+fn default_endpoint() -> String {
+    "wss://iat-api.xfyun.cn/v2/iat".to_string()
+}
+
+impl Default for IflytekConfig {
+    fn default() -> Self {
+        Self {
+            app_id: String::new(),
+            api_key: String::new(),
+            api_secret: String::new(),
+            endpoint: default_endpoint(),
+            language: None,
+        }
+    }
+}
+
+impl IflytekConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.app_id.is_empty() && !self.api_key.is_empty() && !self.api_secret.is_empty()
+    }
+
+    /// 讯飞的语言代码（小语种需要在控制台单独开通）
+    fn language_code(&self) -> &str {
+        match self.language.as_deref() {
+            Some("en") => "en_us",
+            Some("ja") => "ja_jp",
+            Some("ko") => "ko_kr",
+            Some("ru") => "ru-ru",
+            Some("fr") => "fr_fr",
+            Some("es") => "es_es",
+            Some("de") => "de_DE",
+            _ => "zh_cn",
+        }
+    }
+
+    /// 生成带鉴权参数的连接地址
+    fn signed_url(&self) -> Result<String, AsrError> {
+        let rest = self
+            .endpoint
+            .strip_prefix("wss://")
+            .or_else(|| self.endpoint.strip_prefix("ws://"))
+            .ok_or_else(|| AsrError::Configuration("接口地址必须以 wss:// 开头".into()))?;
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let path = format!("/{}", path);
+
+        let date = Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        let signature_origin = format!("host: {}\ndate: {}\nGET {} HTTP/1.1", host, date, path);
+        let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes())
+            .map_err(|e| AsrError::Configuration(e.to_string()))?;
+        mac.update(signature_origin.as_bytes());
+        let signature = STANDARD.encode(mac.finalize().into_bytes());
+
+        let authorization = STANDARD.encode(format!(
+            "api_key=\"{}\", algorithm=\"hmac-sha256\", headers=\"host date request-line\", signature=\"{}\"",
+            self.api_key, signature
+        ));
+
+        Ok(format!(
+            "{}?authorization={}&date={}&host={}",
+            self.endpoint,
+            url_encode(&authorization),
+            url_encode(&date),
+            url_encode(host)
+        ))
+    }
+}
+
+/// 服务端响应
+#[derive(Deserialize, Debug)]
+struct Response {
+    code: i32,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    data: Option<ResponseData>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ResponseData {
+    /// 2 表示最后一个结果
+    status: i32,
+    #[serde(default)]
+    result: Option<RecognitionResult>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RecognitionResult {
+    /// 结果序号
+    sn: u32,
+    /// 动态修正："apd" 追加，"rpl" 替换 rg 范围内的结果
+    #[serde(default)]
+    pgs: Option<String>,
+    #[serde(default)]
+    rg: Option<[u32; 2]>,
+    #[serde(default)]
+    ws: Vec<WordSlot>,
+}
+
+#[derive(Deserialize, Debug)]
+struct WordSlot {
+    #[serde(default)]
+    cw: Vec<CandidateWord>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CandidateWord {
+    #[serde(default)]
+    w: String,
+}
+
+impl RecognitionResult {
+    fn text(&self) -> String {
+        self.ws
+            .iter()
+            .filter_map(|slot| slot.cw.first())
+            .map(|cw| cw.w.as_str())
+            .collect()
+    }
+}
+
+/// 讯飞 Provider
+pub struct IflytekProvider {
+    config: IflytekConfig,
+}
+
+impl IflytekProvider {
+    pub fn new(config: IflytekConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for IflytekProvider {
+    fn id(&self) -> &str {
+        "iflytek"
+    }
+
+    fn display_name(&self) -> &str {
+        "讯飞听写"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if !self.config.is_configured() {
+            return Err(AsrError::Configuration(
+                "APPID、APIKey 和 APISecret 不能为空".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let url = self.config.signed_url()?;
+        log::info!("Connecting to iFlytek IAT");
+        let (ws_stream, _response) = connect_async(url)
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+        log::info!("iFlytek WebSocket connected");
+
+        let (mut write, mut read) = ws_stream.split();
+
+        // 发送音频：首帧带业务参数，结束时发送 status = 2 的空帧
+        let app_id = self.config.app_id.clone();
+        let language = self.config.language_code().to_string();
+        let (audio_done_tx, mut audio_done_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            let mut first = true;
+            while let Some(chunk) = audio_rx.recv().await {
+                let data = json!({
+                    "status": if first { 0 } else { 1 },
+                    "format": "audio/L16;rate=16000",
+                    "encoding": "raw",
+                    "audio": STANDARD.encode(&chunk),
+                });
+                let frame = if first {
+                    json!({
+                        "common": { "app_id": app_id },
+                        "business": {
+                            "language": language,
+                            "domain": "iat",
+                            "accent": "mandarin",
+                            "dwa": "wpgs",
+                            "vad_eos": VAD_EOS_MS,
+                        },
+                        "data": data,
+                    })
+                } else {
+                    json!({ "data": data })
+                };
+                first = false;
+                if write.send(Message::Text(frame.to_string())).await.is_err() {
+                    return;
+                }
+            }
+
+            log::info!("Audio channel closed, sending last frame");
+            let last = json!({
+                "data": {
+                    "status": 2,
+                    "format": "audio/L16;rate=16000",
+                    "encoding": "raw",
+                    "audio": "",
+                }
+            });
+            let _ = write.send(Message::Text(last.to_string())).await;
+            let _ = audio_done_tx.send(());
+        });
+
+        // 结果序号 -> 文本
+        let mut sentences: BTreeMap<u32, String> = BTreeMap::new();
+        let mut last_text = String::new();
+        let mut audio_done = false;
+
+        loop {
+            let next = if audio_done {
+                match tokio::time::timeout(FINALIZE_TIMEOUT, read.next()).await {
+                    Ok(msg) => msg,
+                    Err(_) => {
+                        log::warn!("iFlytek finalize timeout");
+                        break;
+                    }
+                }
+            } else {
+                tokio::select! {
+                    msg = read.next() => msg,
+                    _ = &mut audio_done_rx => {
+                        audio_done = true;
+                        continue;
+                    }
+                }
+            };
+
+            let Some(msg) = next else {
+                log::info!("iFlytek connection closed");
+                break;
+            };
+
+            let text = match msg {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(e) => return Err(AsrError::Connection(e.to_string())),
+            };
+
+            let Ok(response) = serde_json::from_str::<Response>(&text) else {
+                continue;
+            };
+            if response.code != 0 {
+                return Err(AsrError::Transcription(format!(
+                    "{} ({})",
+                    response.message, response.code
+                )));
+            }
+            let Some(data) = response.data else {
+                continue;
+            };
+
+            if let Some(result) = data.result {
+                if result.pgs.as_deref() == Some("rpl") {
+                    if let Some([start, end]) = result.rg {
+                        sentences.retain(|sn, _| *sn < start || *sn > end);
+                    }
+                }
+                sentences.insert(result.sn, result.text());
+            }
+
+            let full_text: String = sentences.values().map(String::as_str).collect();
+            if full_text != last_text && !full_text.is_empty() {
+                last_text = full_text.clone();
+                let result = AsrResult {
+                    text: full_text,
+                    is_final: false,
+                    ..Default::default()
+                };
+                if result_tx.send(result).await.is_err() {
+                    break;
+                }
+            }
+
+            if data.status == 2 {
+                break;
+            }
+        }
+
+        // 发送最终结果
+        let final_text: String = sentences.values().map(String::as_str).collect();
+        let final_text = final_text.trim().to_string();
+        if !final_text.is_empty() {
+            let _ = result_tx
+                .send(AsrResult {
+                    text: final_text,
+                    is_final: true,
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("iFlytek session completed");
+        Ok(())
+    }
+}
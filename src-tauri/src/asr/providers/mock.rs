@@ -0,0 +1,142 @@
+//! 演示用 Mock Provider
+//!
+//! 不需要凭据和网络，按脚本定时回放中间结果和最终结果，
+//! 用于演示或验证界面、实时输入、后处理等完整链路。
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+
+/// 脚本中的一步
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MockStep {
+    /// 距离上一步的延迟（毫秒）
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// 截至该步的完整识别文本
+    pub text: String,
+    /// 是否为最终结果
+    #[serde(default)]
+    pub is_final: bool,
+}
+
+/// Mock Provider 配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MockConfig {
+    /// 回放脚本
+    #[serde(default = "default_script")]
+    pub script: Vec<MockStep>,
+}
+
+fn default_script() -> Vec<MockStep> {
+    let step = |delay_ms, text: &str, is_final| MockStep {
+        delay_ms,
+        text: text.to_string(),
+        is_final,
+    };
+    vec![
+        step(400, "你好", false),
+        step(300, "你好，这是", false),
+        step(300, "你好，这是一段演示", false),
+        step(400, "你好，这是一段演示文本。", true),
+    ]
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            script: default_script(),
+        }
+    }
+}
+
+/// Mock Provider
+pub struct MockProvider {
+    config: MockConfig,
+}
+
+impl MockProvider {
+    pub fn new(config: MockConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for MockProvider {
+    fn id(&self) -> &str {
+        "mock"
+    }
+
+    fn display_name(&self) -> &str {
+        "演示模式"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        ProviderStatus::Ready
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if self.config.script.is_empty() {
+            return Err(AsrError::Configuration("演示脚本不能为空".into()));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        // 丢弃音频，通道关闭即表示录音结束
+        let (audio_done_tx, mut audio_done_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            while audio_rx.recv().await.is_some() {}
+            let _ = audio_done_tx.send(());
+        });
+
+        let mut last_text = String::new();
+        let mut audio_done = false;
+        for step in &self.config.script {
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_millis(step.delay_ms)) => {}
+                // 录音提前结束：跳过剩余脚本
+                _ = &mut audio_done_rx => {
+                    audio_done = true;
+                    break;
+                }
+            }
+
+            last_text = step.text.clone();
+            let result = AsrResult {
+                text: step.text.clone(),
+                is_final: step.is_final,
+                ..Default::default()
+            };
+            if result_tx.send(result).await.is_err() {
+                return Ok(());
+            }
+        }
+
+        // 等待录音结束后发送最终结果
+        if !audio_done {
+            let _ = audio_done_rx.await;
+        }
+        if !last_text.is_empty() {
+            let _ = result_tx
+                .send(AsrResult {
+                    text: last_text,
+                    is_final: true,
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("Mock ASR session completed");
+        Ok(())
+    }
+}
@@ -1,9 +1,91 @@
 //! ASR Provider 实现模块
 
+mod alibaba;
+mod aws_transcribe;
+mod azure;
+mod baidu;
+mod custom_ws;
+mod deepgram;
 mod doubao;
+#[cfg(feature = "sherpa")]
+mod funasr_local;
+mod iflytek;
+mod mock;
+#[cfg(feature = "sherpa")]
+mod moonshine;
+mod openai_realtime;
+#[cfg(feature = "sherpa")]
+mod sense_voice;
+#[cfg(feature = "sherpa")]
+mod sherpa;
+mod soniox;
+mod speaches;
+mod tencent;
+#[cfg(feature = "vosk")]
+mod vosk;
 mod whisper_api;
 mod whisper_local;
+mod whisper_server;
 
+pub use alibaba::{AlibabaNlsConfig, AlibabaNlsProvider};
+pub use aws_transcribe::{AwsTranscribeConfig, AwsTranscribeProvider};
+pub use azure::{AzureSpeechConfig, AzureSpeechProvider};
+pub use baidu::{BaiduConfig, BaiduProvider};
+pub use custom_ws::{CustomWsConfig, CustomWsProvider};
+pub use deepgram::{DeepgramConfig, DeepgramProvider};
 pub use doubao::{DoubaoConfig, DoubaoProvider};
-pub use whisper_api::{WhisperApiConfig, WhisperApiProvider};
-pub use whisper_local::{WhisperLocalConfig, WhisperLocalProvider, WhisperModelSize};
+#[cfg(feature = "sherpa")]
+pub use funasr_local::{FunAsrLocalConfig, FunAsrLocalProvider, FunAsrModel};
+pub use iflytek::{IflytekConfig, IflytekProvider};
+pub use mock::{MockConfig, MockProvider};
+#[cfg(feature = "sherpa")]
+pub use moonshine::{MoonshineLocalConfig, MoonshineModel, MoonshineProvider};
+pub use openai_realtime::{OpenAiRealtimeConfig, OpenAiRealtimeProvider};
+#[cfg(feature = "sherpa")]
+pub use sense_voice::{SenseVoiceLocalConfig, SenseVoiceModel, SenseVoiceProvider};
+#[cfg(feature = "sherpa")]
+pub use sherpa::SherpaModel;
+pub use soniox::{SonioxConfig, SonioxProvider};
+pub use speaches::{SpeachesConfig, SpeachesProvider};
+pub use tencent::{TencentAsrConfig, TencentAsrProvider};
+#[cfg(feature = "vosk")]
+pub use vosk::{VoskConfig, VoskModel, VoskProvider};
+pub use whisper_api::{
+    find_whisper_api_preset, whisper_api_presets, WhisperApiConfig, WhisperApiPreset,
+    WhisperApiProvider,
+};
+pub use whisper_local::{
+    WhisperBenchmark, WhisperGpuBackend, WhisperLocalConfig, WhisperLocalProvider,
+    WhisperModelSize,
+};
+pub use whisper_server::{WhisperServerConfig, WhisperServerProvider};
+
+/// 将统一语言设置（如 "zh"）转换为带地区的语言代码（如 "zh-CN"）
+///
+/// 云服务（Azure、AWS）要求带地区的语言代码，未知语言原样返回。
+pub(crate) fn region_locale(language: &str) -> &str {
+    match language {
+        "zh" => "zh-CN",
+        "en" => "en-US",
+        "ja" => "ja-JP",
+        "ko" => "ko-KR",
+        "es" => "es-ES",
+        "fr" => "fr-FR",
+        "de" => "de-DE",
+        "ru" => "ru-RU",
+        other => other,
+    }
+}
+
+/// 查询参数百分号编码
+pub(crate) fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
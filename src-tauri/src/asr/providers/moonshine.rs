@@ -0,0 +1,158 @@
+//! Moonshine 本地语音识别 Provider
+//!
+//! 通过 sherpa-onnx 运行 Moonshine 模型。模型体积小、解码开销随音频长度线性增长，
+//! 内存不足以运行 Whisper base 的低配机器也能离线听写。目前仅支持英文。
+//! 录音过程中按秒重新解码输出中间结果。
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sherpa_rs::moonshine::{MoonshineConfig, MoonshineRecognizer};
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+
+use super::sherpa::{self, ModelSpec, SherpaModel, SAMPLE_RATE};
+use crate::asr::provider::{
+    AsrError, AsrProvider, AsrResult, DownloadProgress, ModelDownloadable, ModelInfo,
+    ProviderStatus,
+};
+
+/// Moonshine 模型
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MoonshineModel {
+    #[default]
+    TinyEn,
+    BaseEn,
+}
+
+impl SherpaModel for MoonshineModel {
+    const DIR: &'static str = "moonshine";
+    const FILES: &'static [&'static str] = &[
+        "tokens.txt",
+        "preprocess.onnx",
+        "encode.int8.onnx",
+        "uncached_decode.int8.onnx",
+        "cached_decode.int8.onnx",
+    ];
+    const MODELS: &'static [ModelSpec<Self>] = &[
+        ModelSpec {
+            model: Self::TinyEn,
+            id: "sherpa-onnx-moonshine-tiny-en-int8",
+            name: "Moonshine Tiny 英文",
+            size_bytes: 103_000_000,
+            checksums: &[],
+        },
+        ModelSpec {
+            model: Self::BaseEn,
+            id: "sherpa-onnx-moonshine-base-en-int8",
+            name: "Moonshine Base 英文",
+            size_bytes: 241_000_000,
+            checksums: &[],
+        },
+    ];
+}
+
+/// Moonshine 配置
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MoonshineLocalConfig {
+    /// 模型
+    #[serde(default)]
+    pub model: MoonshineModel,
+    /// 自定义模型目录（需包含 preprocess/encode/decode 模型和 tokens.txt）
+    #[serde(default)]
+    pub model_path: Option<PathBuf>,
+}
+
+/// Moonshine Provider
+pub struct MoonshineProvider {
+    config: MoonshineLocalConfig,
+}
+
+impl MoonshineProvider {
+    pub fn new(config: MoonshineLocalConfig) -> Self {
+        Self { config }
+    }
+
+    /// 获取模型目录
+    fn model_dir(&self) -> PathBuf {
+        sherpa::model_dir(&self.config.model, self.config.model_path.as_deref())
+    }
+}
+
+#[async_trait]
+impl AsrProvider for MoonshineProvider {
+    fn id(&self) -> &str {
+        "moonshine"
+    }
+
+    fn display_name(&self) -> &str {
+        "Moonshine 本地"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        sherpa::status(&self.config.model, &self.model_dir())
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        sherpa::validate(&self.config.model, &self.model_dir())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let model_dir = self.model_dir();
+        let path = |file: &str| model_dir.join(file).to_string_lossy().to_string();
+        let config = MoonshineConfig {
+            preprocessor: path("preprocess.onnx"),
+            encoder: path("encode.int8.onnx"),
+            uncached_decoder: path("uncached_decode.int8.onnx"),
+            cached_decoder: path("cached_decode.int8.onnx"),
+            tokens: path("tokens.txt"),
+            ..Default::default()
+        };
+
+        tokio::task::spawn_blocking(move || {
+            let mut recognizer = MoonshineRecognizer::new(config)
+                .map_err(|e| AsrError::Transcription(format!("模型加载失败: {}", e)))?;
+            sherpa::transcribe_blocking(audio_rx, result_tx, |samples| {
+                recognizer.transcribe(SAMPLE_RATE, samples).text
+            })
+        })
+        .await
+        .map_err(|e| AsrError::Transcription(format!("任务执行失败: {}", e)))??;
+
+        log::info!("Moonshine session completed");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ModelDownloadable for MoonshineProvider {
+    fn available_models(&self) -> Vec<ModelInfo> {
+        sherpa::available_models(&self.config.model)
+    }
+
+    fn models_dir(&self) -> PathBuf {
+        sherpa::models_dir(MoonshineModel::DIR)
+    }
+
+    async fn download_model(
+        &self,
+        model_id: &str,
+        progress_tx: mpsc::Sender<DownloadProgress>,
+    ) -> Result<PathBuf, AsrError> {
+        sherpa::download_model::<MoonshineModel>(model_id, progress_tx).await
+    }
+
+    async fn delete_model(&self, model_id: &str) -> Result<(), AsrError> {
+        sherpa::delete_model::<MoonshineModel>(model_id)
+    }
+
+    fn cancel_download(&self) {
+        sherpa::cancel_download::<MoonshineModel>();
+    }
+}
@@ -0,0 +1,351 @@
+//! OpenAI Realtime 语音识别 Provider
+//!
+//! 通过 OpenAI Realtime WebSocket API 流式上传 PCM 音频，获取低延迟的增量识别结果。
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, http::HeaderValue, Message},
+};
+
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+use crate::audio::dsp::LinearResampler;
+
+/// Realtime API 要求的采样率（pcm16, 24kHz, 单声道）
+const REALTIME_SAMPLE_RATE: u32 = 24000;
+/// 音频结束后等待剩余转写完成的最长时间
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// OpenAI Realtime 配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OpenAiRealtimeConfig {
+    /// API Key
+    #[serde(default)]
+    pub api_key: String,
+    /// WebSocket 端点
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    /// 转写模型名称
+    #[serde(default = "default_model")]
+    pub model: String,
+    /// 识别语言（可选）
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+fn default_endpoint() -> String {
+    "wss://api.openai.com/v1/realtime?intent=transcription".to_string()
+}
+
+fn default_model() -> String {
+    "gpt-4o-transcribe".to_string()
+}
+
+impl Default for OpenAiRealtimeConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            endpoint: default_endpoint(),
+            model: default_model(),
+            language: None,
+        }
+    }
+}
+
+impl OpenAiRealtimeConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+}
+
+/// Realtime 服务端事件（只解析需要的字段）
+#[derive(Deserialize, Debug)]
+struct ServerEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    item_id: Option<String>,
+    #[serde(default)]
+    delta: Option<String>,
+    #[serde(default)]
+    transcript: Option<String>,
+    #[serde(default)]
+    error: Option<ServerError>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ServerError {
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    message: String,
+}
+
+/// 按语音片段（item）累积的转写状态
+#[derive(Default)]
+struct TranscriptState {
+    /// 按提交顺序排列的 item ID
+    order: Vec<String>,
+    /// 每个 item 当前的文本（增量或最终）
+    texts: HashMap<String, String>,
+    /// 已提交但尚未完成转写的 item
+    pending: HashSet<String>,
+}
+
+impl TranscriptState {
+    fn ensure_item(&mut self, item_id: &str) {
+        if !self.texts.contains_key(item_id) {
+            self.order.push(item_id.to_string());
+            self.texts.insert(item_id.to_string(), String::new());
+        }
+    }
+
+    fn full_text(&self) -> String {
+        self.order
+            .iter()
+            .filter_map(|id| self.texts.get(id))
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// OpenAI Realtime Provider
+pub struct OpenAiRealtimeProvider {
+    config: OpenAiRealtimeConfig,
+}
+
+impl OpenAiRealtimeProvider {
+    pub fn new(config: OpenAiRealtimeConfig) -> Self {
+        Self { config }
+    }
+
+    /// 构建会话配置消息
+    fn session_update(&self) -> serde_json::Value {
+        let mut transcription = json!({ "model": self.config.model });
+        if let Some(ref lang) = self.config.language {
+            transcription["language"] = json!(lang);
+        }
+
+        json!({
+            "type": "transcription_session.update",
+            "session": {
+                "input_audio_format": "pcm16",
+                "input_audio_transcription": transcription,
+                "turn_detection": { "type": "server_vad" },
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl AsrProvider for OpenAiRealtimeProvider {
+    fn id(&self) -> &str {
+        "openai_realtime"
+    }
+
+    fn display_name(&self) -> &str {
+        "OpenAI Realtime"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if self.config.api_key.is_empty() {
+            return Err(AsrError::Configuration("API Key 不能为空".into()));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let mut request = self
+            .config
+            .endpoint
+            .as_str()
+            .into_client_request()
+            .map_err(|e| AsrError::Configuration(format!("无效的端点: {}", e)))?;
+        let auth = HeaderValue::from_str(&format!("Bearer {}", self.config.api_key))
+            .map_err(|e| AsrError::Configuration(format!("无效的 API Key: {}", e)))?;
+        request.headers_mut().insert("Authorization", auth);
+        request
+            .headers_mut()
+            .insert("OpenAI-Beta", HeaderValue::from_static("realtime=v1"));
+
+        log::info!("Connecting to OpenAI Realtime API");
+        let (ws_stream, _response) = connect_async(request)
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+        log::info!("OpenAI Realtime WebSocket connected");
+
+        let (mut write, mut read) = ws_stream.split();
+
+        write
+            .send(Message::Text(self.session_update().to_string()))
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+
+        // 发送音频：16kHz -> 24kHz 重采样后 base64 编码
+        let (audio_done_tx, mut audio_done_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            let mut resampler = LinearResampler::new(16000, REALTIME_SAMPLE_RATE);
+            while let Some(chunk) = audio_rx.recv().await {
+                let samples: Vec<i16> = chunk
+                    .chunks_exact(2)
+                    .map(|c| i16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                let resampled = resampler.process(&samples);
+                let bytes: &[u8] = bytemuck::cast_slice(&resampled);
+                let event = json!({
+                    "type": "input_audio_buffer.append",
+                    "audio": STANDARD.encode(bytes),
+                });
+                if write.send(Message::Text(event.to_string())).await.is_err() {
+                    return;
+                }
+            }
+
+            // 提交剩余的音频缓冲
+            log::info!("Audio channel closed, committing input buffer");
+            let commit = json!({ "type": "input_audio_buffer.commit" });
+            let _ = write.send(Message::Text(commit.to_string())).await;
+            let _ = audio_done_tx.send(());
+        });
+
+        let mut state = TranscriptState::default();
+        let mut last_text = String::new();
+        let mut audio_done = false;
+        // 最后一次提交尚未被服务端确认（committed 或 commit_empty）
+        let mut awaiting_commit = false;
+
+        loop {
+            // 音频发送完成后，等待所有已提交片段转写完成
+            let next = if audio_done {
+                if !awaiting_commit && state.pending.is_empty() {
+                    break;
+                }
+                match tokio::time::timeout(FINALIZE_TIMEOUT, read.next()).await {
+                    Ok(msg) => msg,
+                    Err(_) => {
+                        log::warn!("OpenAI Realtime finalize timeout");
+                        break;
+                    }
+                }
+            } else {
+                tokio::select! {
+                    msg = read.next() => msg,
+                    _ = &mut audio_done_rx => {
+                        audio_done = true;
+                        awaiting_commit = true;
+                        continue;
+                    }
+                }
+            };
+
+            let Some(msg) = next else {
+                log::info!("OpenAI Realtime connection closed");
+                break;
+            };
+
+            let text = match msg {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(e) => return Err(AsrError::Connection(e.to_string())),
+            };
+
+            let Ok(event) = serde_json::from_str::<ServerEvent>(&text) else {
+                continue;
+            };
+
+            match event.event_type.as_str() {
+                "input_audio_buffer.committed" => {
+                    if audio_done {
+                        awaiting_commit = false;
+                    }
+                    if let Some(id) = event.item_id {
+                        state.ensure_item(&id);
+                        state.pending.insert(id);
+                    }
+                }
+                "conversation.item.input_audio_transcription.delta" => {
+                    if let (Some(id), Some(delta)) = (event.item_id, event.delta) {
+                        state.ensure_item(&id);
+                        if let Some(t) = state.texts.get_mut(&id) {
+                            t.push_str(&delta);
+                        }
+                    }
+                }
+                "conversation.item.input_audio_transcription.completed" => {
+                    if let Some(id) = event.item_id {
+                        state.ensure_item(&id);
+                        state
+                            .texts
+                            .insert(id.clone(), event.transcript.unwrap_or_default());
+                        state.pending.remove(&id);
+                    }
+                }
+                "error" => {
+                    let error = event.error.unwrap_or(ServerError {
+                        code: None,
+                        message: text.clone(),
+                    });
+                    // 缓冲区为空时提交会报错，可以忽略
+                    if error.code.as_deref() == Some("input_audio_buffer_commit_empty") {
+                        awaiting_commit = false;
+                        continue;
+                    }
+                    return Err(AsrError::Transcription(error.message));
+                }
+                _ => continue,
+            }
+
+            let full_text = state.full_text();
+            if full_text != last_text && !full_text.is_empty() {
+                last_text = full_text.clone();
+                let result = AsrResult {
+                    text: full_text,
+                    is_final: false,
+                    ..Default::default()
+                };
+                if result_tx.send(result).await.is_err() {
+                    break;
+                }
+            }
+        }
+
+        // 发送最终结果
+        let final_text = state.full_text();
+        if !final_text.is_empty() {
+            let _ = result_tx
+                .send(AsrResult {
+                    text: final_text,
+                    is_final: true,
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("OpenAI Realtime session completed");
+        Ok(())
+    }
+}
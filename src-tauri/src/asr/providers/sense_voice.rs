@@ -0,0 +1,152 @@
+//! SenseVoice 本地语音识别 Provider
+//!
+//! 通过 sherpa-onnx 运行 SenseVoice-Small：多语言（中/英/日/韩/粤）、速度快、
+//! 自带标点（ITN）。录音过程中按秒重新解码输出中间结果。
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sherpa_rs::sense_voice::{SenseVoiceConfig, SenseVoiceRecognizer};
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+
+use super::sherpa::{self, ModelSpec, SherpaModel, SAMPLE_RATE};
+use crate::asr::provider::{
+    AsrError, AsrProvider, AsrResult, DownloadProgress, ModelDownloadable, ModelInfo,
+    ProviderStatus,
+};
+
+/// 模型支持的语言
+const SUPPORTED_LANGUAGES: &[&str] = &["zh", "en", "ja", "ko", "yue"];
+
+/// SenseVoice 模型
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SenseVoiceModel {
+    #[default]
+    Small,
+}
+
+impl SherpaModel for SenseVoiceModel {
+    const DIR: &'static str = "sense_voice";
+    const FILES: &'static [&'static str] = &["tokens.txt", "model.int8.onnx"];
+    const MODELS: &'static [ModelSpec<Self>] = &[ModelSpec {
+        model: Self::Small,
+        id: "sherpa-onnx-sense-voice-zh-en-ja-ko-yue-2024-07-17",
+        name: "SenseVoice Small",
+        size_bytes: 239_000_000,
+        checksums: &[],
+    }];
+}
+
+/// SenseVoice 配置
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SenseVoiceLocalConfig {
+    /// 模型
+    #[serde(default)]
+    pub model: SenseVoiceModel,
+    /// 自定义模型目录（需包含 model.int8.onnx 和 tokens.txt）
+    #[serde(default)]
+    pub model_path: Option<PathBuf>,
+    /// 识别语言（可选，不支持的语言按自动检测处理）
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// SenseVoice Provider
+pub struct SenseVoiceProvider {
+    config: SenseVoiceLocalConfig,
+}
+
+impl SenseVoiceProvider {
+    pub fn new(config: SenseVoiceLocalConfig) -> Self {
+        Self { config }
+    }
+
+    /// 获取模型目录
+    fn model_dir(&self) -> PathBuf {
+        sherpa::model_dir(&self.config.model, self.config.model_path.as_deref())
+    }
+
+    fn language(&self) -> String {
+        match self.config.language.as_deref() {
+            Some(lang) if SUPPORTED_LANGUAGES.contains(&lang) => lang.to_string(),
+            _ => "auto".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for SenseVoiceProvider {
+    fn id(&self) -> &str {
+        "sense_voice"
+    }
+
+    fn display_name(&self) -> &str {
+        "SenseVoice 本地"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        sherpa::status(&self.config.model, &self.model_dir())
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        sherpa::validate(&self.config.model, &self.model_dir())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let model_dir = self.model_dir();
+        let config = SenseVoiceConfig {
+            model: model_dir.join("model.int8.onnx").to_string_lossy().to_string(),
+            tokens: model_dir.join("tokens.txt").to_string_lossy().to_string(),
+            language: self.language(),
+            use_itn: true,
+            ..Default::default()
+        };
+
+        tokio::task::spawn_blocking(move || {
+            let mut recognizer = SenseVoiceRecognizer::new(config)
+                .map_err(|e| AsrError::Transcription(format!("模型加载失败: {}", e)))?;
+            sherpa::transcribe_blocking(audio_rx, result_tx, |samples| {
+                recognizer.transcribe(SAMPLE_RATE, samples).text
+            })
+        })
+        .await
+        .map_err(|e| AsrError::Transcription(format!("任务执行失败: {}", e)))??;
+
+        log::info!("SenseVoice session completed");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ModelDownloadable for SenseVoiceProvider {
+    fn available_models(&self) -> Vec<ModelInfo> {
+        sherpa::available_models(&self.config.model)
+    }
+
+    fn models_dir(&self) -> PathBuf {
+        sherpa::models_dir(SenseVoiceModel::DIR)
+    }
+
+    async fn download_model(
+        &self,
+        model_id: &str,
+        progress_tx: mpsc::Sender<DownloadProgress>,
+    ) -> Result<PathBuf, AsrError> {
+        sherpa::download_model::<SenseVoiceModel>(model_id, progress_tx).await
+    }
+
+    async fn delete_model(&self, model_id: &str) -> Result<(), AsrError> {
+        sherpa::delete_model::<SenseVoiceModel>(model_id)
+    }
+
+    fn cancel_download(&self) {
+        sherpa::cancel_download::<SenseVoiceModel>();
+    }
+}
@@ -0,0 +1,257 @@
+//! sherpa-onnx 本地模型的公共部分
+//!
+//! Paraformer、SenseVoice 等非流式 ONNX 模型共用的模型表、下载管理与模拟流式识别：
+//! 录音过程中每累积一段新音频就对已有音频整体解码一次，输出中间结果。
+//! 各 Provider 只需通过 [`SherpaModel`] 提供自己的模型数据。
+
+use directories::ProjectDirs;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
+use tokio::sync::mpsc;
+
+use crate::asr::provider::{AsrError, AsrResult, DownloadProgress, ModelInfo, ProviderStatus};
+
+/// 输入采样率
+pub(super) const SAMPLE_RATE: u32 = 16000;
+/// 每累积多少新样本做一次中间解码（1 秒）
+const PARTIAL_INTERVAL: usize = SAMPLE_RATE as usize;
+/// 超过该长度后不再做中间解码（整体解码耗时随音频增长）
+const MAX_PARTIAL_SAMPLES: usize = SAMPLE_RATE as usize * 30;
+
+/// 各模型目录的下载取消标志（每次命令都会新建 Provider，需全局共享）
+static CANCEL_FLAGS: LazyLock<Mutex<HashMap<&'static str, Arc<AtomicBool>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 模型表中的一项
+pub struct ModelSpec<M: 'static> {
+    pub model: M,
+    /// 模型 ID（也是本地目录名和 Hugging Face 仓库名）
+    pub id: &'static str,
+    /// 显示名称（不含大小）
+    pub name: &'static str,
+    /// 模型大小（字节）
+    pub size_bytes: u64,
+    /// 各模型文件的 SHA-256
+    pub checksums: &'static [(&'static str, &'static str)],
+}
+
+/// sherpa-onnx 模型枚举，提供该类模型的存储目录、文件列表和模型表
+pub trait SherpaModel: Clone + PartialEq + 'static {
+    /// 存储目录名（~/.config/speaky/models/<DIR>/）
+    const DIR: &'static str;
+    /// 模型文件（tokens 较小，放在前面先下载）
+    const FILES: &'static [&'static str];
+    /// 所有可用的模型，覆盖全部枚举值
+    const MODELS: &'static [ModelSpec<Self>];
+
+    /// 模型表中的对应项
+    fn spec(&self) -> &'static ModelSpec<Self> {
+        Self::MODELS
+            .iter()
+            .find(|spec| spec.model == *self)
+            .expect("model table covers every variant")
+    }
+
+    /// 模型 ID（也是本地目录名）
+    fn id(&self) -> &'static str {
+        self.spec().id
+    }
+
+    /// 从模型 ID 解析
+    fn from_id(id: &str) -> Option<Self> {
+        Self::MODELS
+            .iter()
+            .find(|spec| spec.id == id)
+            .map(|spec| spec.model.clone())
+    }
+}
+
+/// 模型存储目录: ~/.config/speaky/models/<name>/
+pub(super) fn models_dir(name: &str) -> PathBuf {
+    ProjectDirs::from("com", "speaky", "Speaky")
+        .map(|dirs| dirs.config_dir().join("models").join(name))
+        .unwrap_or_else(|| PathBuf::from("./models").join(name))
+}
+
+/// 检查模型目录中的文件是否都已下载
+fn is_downloaded(dir: &Path, files: &[&str]) -> bool {
+    files.iter().all(|file| {
+        std::fs::metadata(dir.join(file))
+            .map(|m| m.len() > 0)
+            .unwrap_or(false)
+    })
+}
+
+/// 检查模型文件是否都有固定的 SHA-256
+fn is_pinned(files: &[&str], checksums: &[(&str, &str)]) -> bool {
+    files
+        .iter()
+        .all(|file| checksums.iter().any(|(name, _)| name == file))
+}
+
+/// 模型的下载取消标志
+fn cancel_flag<M: SherpaModel>() -> Arc<AtomicBool> {
+    CANCEL_FLAGS.lock().entry(M::DIR).or_default().clone()
+}
+
+/// 使用的模型目录：自定义目录优先，否则为存储目录下的模型 ID
+pub(super) fn model_dir<M: SherpaModel>(model: &M, model_path: Option<&Path>) -> PathBuf {
+    model_path
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| models_dir(M::DIR).join(model.id()))
+}
+
+/// 模型是否已就绪
+pub(super) fn status<M: SherpaModel>(model: &M, dir: &Path) -> ProviderStatus {
+    if !is_downloaded(dir, M::FILES) {
+        ProviderStatus::NeedsModelDownload {
+            model: model.id().to_string(),
+            size_mb: model.spec().size_bytes / 1_000_000,
+        }
+    } else {
+        ProviderStatus::Ready
+    }
+}
+
+/// 检查模型文件是否齐全
+pub(super) fn validate<M: SherpaModel>(model: &M, dir: &Path) -> Result<(), AsrError> {
+    if !is_downloaded(dir, M::FILES) {
+        return Err(AsrError::ModelNotFound(format!(
+            "需要先下载 {} 模型",
+            model.id()
+        )));
+    }
+    Ok(())
+}
+
+/// 模型列表（没有固定校验值且未手动放入的模型不列出）
+pub(super) fn available_models<M: SherpaModel>(selected: &M) -> Vec<ModelInfo> {
+    let dir = models_dir(M::DIR);
+    M::MODELS
+        .iter()
+        .map(|spec| (spec, is_downloaded(&dir.join(spec.id), M::FILES)))
+        .filter(|(spec, downloaded)| *downloaded || is_pinned(M::FILES, spec.checksums))
+        .map(|(spec, downloaded)| ModelInfo {
+            id: spec.id.to_string(),
+            name: format!("{} ({} MB)", spec.name, spec.size_bytes / 1_000_000),
+            size_bytes: spec.size_bytes,
+            is_downloaded: downloaded,
+            is_selected: spec.model == *selected,
+        })
+        .collect()
+}
+
+/// 从 Hugging Face 仓库逐个下载模型文件
+///
+/// 小文件放在前面，进度条主要反映最后的模型文件。每个文件按模型表中固定的 SHA-256 校验。
+pub(super) async fn download_model<M: SherpaModel>(
+    model_id: &str,
+    progress_tx: mpsc::Sender<DownloadProgress>,
+) -> Result<PathBuf, AsrError> {
+    let model = M::from_id(model_id)
+        .ok_or_else(|| AsrError::ModelNotFound(format!("未知模型: {}", model_id)))?;
+    let spec = model.spec();
+
+    // 重置取消标志
+    let cancel_flag = cancel_flag::<M>();
+    cancel_flag.store(false, Ordering::SeqCst);
+
+    let dir = models_dir(M::DIR).join(model_id);
+    std::fs::create_dir_all(&dir)?;
+
+    for file in M::FILES {
+        let dest_path = dir.join(file);
+        if is_downloaded(&dir, &[file]) {
+            continue;
+        }
+        let url = format!(
+            "https://huggingface.co/csukuangfj/{}/resolve/main/{}",
+            spec.id, file
+        );
+        crate::asr::model_manager::download_file(
+            &url,
+            &dest_path.with_extension("tmp"),
+            &dest_path,
+            model_id,
+            spec.checksums
+                .iter()
+                .find(|(name, _)| name == file)
+                .map(|(_, sha256)| *sha256),
+            progress_tx.clone(),
+            cancel_flag.clone(),
+        )
+        .await?;
+    }
+    Ok(dir)
+}
+
+/// 删除已下载的模型
+pub(super) fn delete_model<M: SherpaModel>(model_id: &str) -> Result<(), AsrError> {
+    let path = models_dir(M::DIR).join(model_id);
+    if path.exists() {
+        std::fs::remove_dir_all(&path)?;
+        log::info!("已删除模型: {:?}", path);
+    }
+    Ok(())
+}
+
+/// 取消正在进行的下载
+pub(super) fn cancel_download<M: SherpaModel>() {
+    cancel_flag::<M>().store(true, Ordering::SeqCst);
+}
+
+/// 模拟流式识别（在阻塞线程中调用）
+///
+/// `decode` 对完整音频（f32, 16kHz）解码并返回文本。
+pub(super) fn transcribe_blocking(
+    mut audio_rx: mpsc::Receiver<Vec<u8>>,
+    result_tx: mpsc::Sender<AsrResult>,
+    mut decode: impl FnMut(&[f32]) -> String,
+) -> Result<(), AsrError> {
+    let mut samples: Vec<f32> = Vec::new();
+    let mut last_decoded = 0;
+    let mut last_text = String::new();
+
+    while let Some(chunk) = audio_rx.blocking_recv() {
+        samples.extend(
+            chunk
+                .chunks_exact(2)
+                .map(|c| i16::from_le_bytes([c[0], c[1]]) as f32 / 32768.0),
+        );
+
+        if samples.len() > MAX_PARTIAL_SAMPLES || samples.len() - last_decoded < PARTIAL_INTERVAL {
+            continue;
+        }
+        last_decoded = samples.len();
+
+        let text = decode(&samples).trim().to_string();
+        if text != last_text && !text.is_empty() {
+            last_text = text.clone();
+            let result = AsrResult {
+                text,
+                is_final: false,
+                ..Default::default()
+            };
+            if result_tx.blocking_send(result).is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    if samples.is_empty() {
+        return Ok(());
+    }
+
+    let text = decode(&samples).trim().to_string();
+    if !text.is_empty() {
+        let _ = result_tx.blocking_send(AsrResult {
+            text,
+            is_final: true,
+            ..Default::default()
+        });
+    }
+    Ok(())
+}
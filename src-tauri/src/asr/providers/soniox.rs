@@ -0,0 +1,288 @@
+//! Soniox 实时语音识别 Provider
+//!
+//! 通过 Soniox WebSocket API 流式上传 PCM 音频。每条响应包含若干 token：
+//! 已确定的 token（`is_final`）累加保存，未确定的 token 只在下一条响应前有效。
+//! 中英混说时通过语言提示同时传入中文和英文。
+
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, AsrSegment, ProviderStatus};
+
+/// 音频结束后等待 finished 响应的最长时间
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Soniox 配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SonioxConfig {
+    /// API Key
+    #[serde(default)]
+    pub api_key: String,
+    /// WebSocket 地址
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    /// 模型名称
+    #[serde(default = "default_model")]
+    pub model: String,
+    /// 识别语言（可选，为空时默认提示中文和英文）
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+fn default_endpoint() -> String {
+    "wss://stt-rt.soniox.com/transcribe-websocket".to_string()
+}
+
+fn default_model() -> String {
+    "stt-rt-preview".to_string()
+}
+
+impl Default for SonioxConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            endpoint: default_endpoint(),
+            model: default_model(),
+            language: None,
+        }
+    }
+}
+
+impl SonioxConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    /// 语言提示（中英混说是最常见的场景，始终附带英文）
+    fn language_hints(&self) -> Vec<String> {
+        match self.language.as_deref() {
+            Some("en") => vec!["en".to_string()],
+            Some(lang) => vec![lang.to_string(), "en".to_string()],
+            None => vec!["zh".to_string(), "en".to_string()],
+        }
+    }
+}
+
+/// 服务端响应
+#[derive(Deserialize, Debug)]
+struct Response {
+    #[serde(default)]
+    tokens: Vec<Token>,
+    #[serde(default)]
+    finished: bool,
+    #[serde(default)]
+    error_code: Option<i32>,
+    #[serde(default)]
+    error_message: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Token {
+    text: String,
+    #[serde(default)]
+    start_ms: Option<u64>,
+    #[serde(default)]
+    end_ms: Option<u64>,
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    is_final: bool,
+}
+
+/// Soniox Provider
+pub struct SonioxProvider {
+    config: SonioxConfig,
+}
+
+impl SonioxProvider {
+    pub fn new(config: SonioxConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for SonioxProvider {
+    fn id(&self) -> &str {
+        "soniox"
+    }
+
+    fn display_name(&self) -> &str {
+        "Soniox"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if !self.config.is_configured() {
+            return Err(AsrError::Configuration("API Key 不能为空".into()));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        log::info!("Connecting to Soniox ({})", self.config.model);
+        let (ws_stream, _response) = connect_async(self.config.endpoint.as_str())
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+        log::info!("Soniox WebSocket connected");
+
+        let (mut write, mut read) = ws_stream.split();
+
+        // 首条消息为会话配置
+        let start = json!({
+            "api_key": self.config.api_key,
+            "model": self.config.model,
+            "audio_format": "pcm_s16le",
+            "sample_rate": 16000,
+            "num_channels": 1,
+            "language_hints": self.config.language_hints(),
+        });
+        write
+            .send(Message::Text(start.to_string()))
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+
+        // 发送音频，结束时发送空帧通知服务端
+        let (audio_done_tx, mut audio_done_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            while let Some(chunk) = audio_rx.recv().await {
+                if write.send(Message::Binary(chunk)).await.is_err() {
+                    return;
+                }
+            }
+
+            log::info!("Audio channel closed, sending end of stream");
+            let _ = write.send(Message::Binary(Vec::new())).await;
+            let _ = audio_done_tx.send(());
+        });
+
+        let mut final_tokens: Vec<Token> = Vec::new();
+        let mut pending = String::new();
+        let mut last_text = String::new();
+        let mut audio_done = false;
+
+        loop {
+            let next = if audio_done {
+                match tokio::time::timeout(FINALIZE_TIMEOUT, read.next()).await {
+                    Ok(msg) => msg,
+                    Err(_) => {
+                        log::warn!("Soniox finalize timeout");
+                        break;
+                    }
+                }
+            } else {
+                tokio::select! {
+                    msg = read.next() => msg,
+                    _ = &mut audio_done_rx => {
+                        audio_done = true;
+                        continue;
+                    }
+                }
+            };
+
+            let Some(msg) = next else {
+                log::info!("Soniox connection closed");
+                break;
+            };
+
+            let text = match msg {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(e) => return Err(AsrError::Connection(e.to_string())),
+            };
+
+            let Ok(response) = serde_json::from_str::<Response>(&text) else {
+                continue;
+            };
+            if let Some(code) = response.error_code {
+                return Err(AsrError::Transcription(format!(
+                    "{} ({})",
+                    response.error_message.unwrap_or_default(),
+                    code
+                )));
+            }
+
+            pending.clear();
+            for token in response.tokens {
+                if token.is_final {
+                    final_tokens.push(token);
+                } else {
+                    pending.push_str(&token.text);
+                }
+            }
+
+            let full_text: String = final_tokens
+                .iter()
+                .map(|t| t.text.as_str())
+                .chain(std::iter::once(pending.as_str()))
+                .collect();
+            if full_text != last_text && !full_text.is_empty() {
+                last_text = full_text.clone();
+                let result = AsrResult {
+                    text: full_text,
+                    is_final: false,
+                    ..Default::default()
+                };
+                if result_tx.send(result).await.is_err() {
+                    break;
+                }
+            }
+
+            if response.finished {
+                break;
+            }
+        }
+
+        // 发送最终结果（未确定的 token 也一并输出）
+        let final_text: String = final_tokens
+            .iter()
+            .map(|t| t.text.as_str())
+            .chain(std::iter::once(pending.as_str()))
+            .collect();
+        let final_text = final_text.trim().to_string();
+        if !final_text.is_empty() {
+            let segments: Vec<AsrSegment> = final_tokens
+                .iter()
+                .filter_map(|t| {
+                    Some(AsrSegment {
+                        text: t.text.trim().to_string(),
+                        start: t.start_ms? as f32 / 1000.0,
+                        end: t.end_ms? as f32 / 1000.0,
+                        confidence: t.confidence,
+                    })
+                })
+                .collect();
+            let confidence = AsrSegment::overall_confidence(&segments);
+            let _ = result_tx
+                .send(AsrResult {
+                    text: final_text,
+                    is_final: true,
+                    segments,
+                    confidence,
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("Soniox session completed");
+        Ok(())
+    }
+}
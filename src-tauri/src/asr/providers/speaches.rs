@@ -0,0 +1,264 @@
+//! faster-whisper (speaches) Provider
+//!
+//! speaches 基于 faster-whisper，吞吐量明显高于 OpenAI 格式的通用接口，并额外支持
+//! 服务端 VAD 过滤和词级时间戳。开启词级时间戳时，结果中的分段为逐词分段。
+
+use async_trait::async_trait;
+use reqwest::multipart;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::whisper_api::pcm_to_wav;
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, AsrSegment, ProviderStatus};
+
+/// 输入音频采样率
+const SAMPLE_RATE: u32 = 16000;
+/// 单次请求超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// speaches 配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpeachesConfig {
+    /// 服务地址
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    /// 模型名称
+    #[serde(default = "default_model")]
+    pub model: String,
+    /// API Key（可选，服务端开启鉴权时需要）
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// 启用服务端 VAD 过滤静音段
+    #[serde(default = "default_true")]
+    pub vad_filter: bool,
+    /// 返回词级时间戳
+    #[serde(default)]
+    pub word_timestamps: bool,
+    /// 识别语言（可选）
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+fn default_base_url() -> String {
+    "http://127.0.0.1:8000".to_string()
+}
+
+fn default_model() -> String {
+    "Systran/faster-whisper-small".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for SpeachesConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_base_url(),
+            model: default_model(),
+            api_key: None,
+            vad_filter: true,
+            word_timestamps: false,
+            language: None,
+        }
+    }
+}
+
+impl SpeachesConfig {
+    pub fn is_configured(&self) -> bool {
+        (self.base_url.starts_with("http://") || self.base_url.starts_with("https://"))
+            && !self.model.is_empty()
+    }
+}
+
+/// 分段
+#[derive(Deserialize)]
+struct Segment {
+    #[serde(default)]
+    start: f32,
+    #[serde(default)]
+    end: f32,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    avg_logprob: Option<f32>,
+}
+
+/// 词级时间戳
+#[derive(Deserialize)]
+struct Word {
+    #[serde(default)]
+    start: f32,
+    #[serde(default)]
+    end: f32,
+    #[serde(default)]
+    word: String,
+    #[serde(default)]
+    probability: Option<f32>,
+}
+
+/// verbose_json 响应
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    segments: Vec<Segment>,
+    #[serde(default)]
+    words: Vec<Word>,
+}
+
+/// speaches Provider
+pub struct SpeachesProvider {
+    config: SpeachesConfig,
+    client: reqwest::Client,
+}
+
+impl SpeachesProvider {
+    pub fn new(config: SpeachesConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for SpeachesProvider {
+    fn id(&self) -> &str {
+        "speaches"
+    }
+
+    fn display_name(&self) -> &str {
+        "faster-whisper (speaches)"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn result_timeout(&self, _audio_secs: f32) -> Duration {
+        REQUEST_TIMEOUT
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if !self.config.is_configured() {
+            return Err(AsrError::Configuration(
+                "服务地址必须以 http:// 或 https:// 开头，且模型不能为空".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        // 累积所有音频数据
+        let mut audio_buffer = Vec::new();
+        while let Some(chunk) = audio_rx.recv().await {
+            audio_buffer.extend(chunk);
+        }
+
+        if audio_buffer.is_empty() {
+            return Ok(());
+        }
+
+        let wav_data = pcm_to_wav(&audio_buffer, SAMPLE_RATE, 1, 16);
+        let file_part = multipart::Part::bytes(wav_data)
+            .file_name("audio.wav")
+            .mime_str("audio/wav")
+            .map_err(|e| AsrError::Transcription(e.to_string()))?;
+
+        let mut form = multipart::Form::new()
+            .part("file", file_part)
+            .text("model", self.config.model.clone())
+            .text("response_format", "verbose_json")
+            .text("vad_filter", self.config.vad_filter.to_string());
+        if self.config.word_timestamps {
+            form = form.text("timestamp_granularities[]", "word");
+        }
+        if let Some(ref lang) = self.config.language {
+            form = form.text("language", lang.clone());
+        }
+
+        let url = format!(
+            "{}/v1/audio/transcriptions",
+            self.config.base_url.trim_end_matches('/')
+        );
+        let mut request = self
+            .client
+            .post(&url)
+            .timeout(REQUEST_TIMEOUT)
+            .multipart(form);
+        if let Some(ref key) = self.config.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AsrError::Transcription(format!(
+                "API 请求失败 ({}): {}",
+                status, error_text
+            )));
+        }
+
+        let result: TranscriptionResponse = response
+            .json()
+            .await
+            .map_err(|e| AsrError::Transcription(format!("解析响应失败: {}", e)))?;
+
+        let segments: Vec<AsrSegment> = if self.config.word_timestamps && !result.words.is_empty()
+        {
+            result
+                .words
+                .into_iter()
+                .map(|w| AsrSegment {
+                    text: w.word.trim().to_string(),
+                    start: w.start,
+                    end: w.end,
+                    confidence: w.probability,
+                })
+                .collect()
+        } else {
+            result
+                .segments
+                .into_iter()
+                .map(|s| AsrSegment {
+                    text: s.text.trim().to_string(),
+                    start: s.start,
+                    end: s.end,
+                    confidence: s.avg_logprob.map(|lp| lp.exp().clamp(0.0, 1.0)),
+                })
+                .collect()
+        };
+        let confidence = AsrSegment::overall_confidence(&segments);
+
+        let _ = result_tx
+            .send(AsrResult {
+                text: result.text.trim().to_string(),
+                is_final: true,
+                segments,
+                confidence,
+                ..Default::default()
+            })
+            .await;
+
+        log::info!("speaches session completed");
+        Ok(())
+    }
+}
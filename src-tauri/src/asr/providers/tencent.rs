@@ -0,0 +1,306 @@
+//! 腾讯云实时语音识别 Provider
+//!
+//! 通过腾讯云实时语音识别 WebSocket 接口流式上传 PCM 音频。鉴权参数放在 URL 中，
+//! 签名为按参数名排序后的请求串的 HMAC-SHA1。
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use futures_util::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha1::Sha1;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use super::url_encode;
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+use crate::text::profanity::ProfanityMode;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// 服务地址
+const HOST: &str = "asr.cloud.tencent.com";
+/// 签名有效期（秒）
+const SIGNATURE_TTL_SECS: i64 = 24 * 60 * 60;
+/// 音频结束后等待最终结果的最长时间
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 腾讯云配置
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TencentAsrConfig {
+    /// AppID
+    #[serde(default)]
+    pub app_id: String,
+    /// SecretId
+    #[serde(default)]
+    pub secret_id: String,
+    /// SecretKey
+    #[serde(default)]
+    pub secret_key: String,
+    /// 引擎模型类型（如 "16k_zh"、"16k_zh_large"），为空时按识别语言选择
+    #[serde(default)]
+    pub engine_model_type: Option<String>,
+    /// 识别语言（可选）
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 服务端脏话过滤（运行时由全局设置注入）
+    #[serde(skip)]
+    pub profanity_filter: Option<ProfanityMode>,
+}
+
+impl TencentAsrConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.app_id.is_empty() && !self.secret_id.is_empty() && !self.secret_key.is_empty()
+    }
+
+    fn engine_model_type(&self) -> String {
+        if let Some(ref engine) = self.engine_model_type {
+            return engine.clone();
+        }
+        match self.language.as_deref() {
+            Some("en") => "16k_en",
+            Some("ja") => "16k_ja",
+            Some("ko") => "16k_ko",
+            _ => "16k_zh",
+        }
+        .to_string()
+    }
+
+    /// 生成带签名的连接地址
+    fn signed_url(&self, voice_id: &str) -> Result<String, AsrError> {
+        let timestamp = chrono::Utc::now().timestamp();
+        let mut params = BTreeMap::new();
+        params.insert("engine_model_type", self.engine_model_type());
+        params.insert("expired", (timestamp + SIGNATURE_TTL_SECS).to_string());
+        // 0 不过滤，1 过滤（删除），2 替换为 *
+        let filter_dirty = match self.profanity_filter {
+            Some(ProfanityMode::Remove) => "1",
+            Some(ProfanityMode::Mask) => "2",
+            None => "0",
+        };
+        params.insert("filter_dirty", filter_dirty.to_string());
+        params.insert("needvad", "1".to_string());
+        params.insert(
+            "nonce",
+            (uuid::Uuid::new_v4().as_u128() % 1_000_000_000).to_string(),
+        );
+        params.insert("secretid", self.secret_id.clone());
+        params.insert("timestamp", timestamp.to_string());
+        params.insert("voice_format", "1".to_string());
+        params.insert("voice_id", voice_id.to_string());
+
+        let query = params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+        let sign_origin = format!("{}/asr/v2/{}?{}", HOST, self.app_id, query);
+
+        let mut mac = HmacSha1::new_from_slice(self.secret_key.as_bytes())
+            .map_err(|e| AsrError::Configuration(e.to_string()))?;
+        mac.update(sign_origin.as_bytes());
+        let signature = STANDARD.encode(mac.finalize().into_bytes());
+
+        Ok(format!(
+            "wss://{}&signature={}",
+            sign_origin,
+            url_encode(&signature)
+        ))
+    }
+}
+
+/// 服务端响应
+#[derive(Deserialize, Debug)]
+struct Response {
+    code: i32,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    result: Option<SentenceResult>,
+    /// 1 表示识别结束
+    #[serde(default, rename = "final")]
+    is_final: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct SentenceResult {
+    /// 句子序号
+    index: u32,
+    #[serde(default)]
+    voice_text_str: String,
+}
+
+/// 腾讯云 Provider
+pub struct TencentAsrProvider {
+    config: TencentAsrConfig,
+}
+
+impl TencentAsrProvider {
+    pub fn new(config: TencentAsrConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for TencentAsrProvider {
+    fn id(&self) -> &str {
+        "tencent"
+    }
+
+    fn display_name(&self) -> &str {
+        "腾讯云"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if !self.config.is_configured() {
+            return Err(AsrError::Configuration(
+                "AppID、SecretId 和 SecretKey 不能为空".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let voice_id = uuid::Uuid::new_v4().to_string();
+        let url = self.config.signed_url(&voice_id)?;
+        log::info!("Connecting to Tencent Cloud ASR (voice_id: {})", voice_id);
+        let (ws_stream, _response) = connect_async(url)
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+        log::info!("Tencent Cloud ASR WebSocket connected");
+
+        let (mut write, mut read) = ws_stream.split();
+
+        // 握手结果：code 非 0 表示鉴权或参数错误
+        match read.next().await {
+            Some(Ok(Message::Text(text))) => {
+                if let Ok(response) = serde_json::from_str::<Response>(&text) {
+                    if response.code != 0 {
+                        return Err(AsrError::Connection(format!(
+                            "{} ({})",
+                            response.message, response.code
+                        )));
+                    }
+                }
+            }
+            Some(Err(e)) => return Err(AsrError::Connection(e.to_string())),
+            _ => return Err(AsrError::Connection("握手失败".into())),
+        }
+
+        // 发送音频，结束时发送 end 消息
+        let (audio_done_tx, mut audio_done_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            while let Some(chunk) = audio_rx.recv().await {
+                if write.send(Message::Binary(chunk)).await.is_err() {
+                    return;
+                }
+            }
+
+            log::info!("Audio channel closed, sending end message");
+            let end = json!({ "type": "end" });
+            let _ = write.send(Message::Text(end.to_string())).await;
+            let _ = audio_done_tx.send(());
+        });
+
+        // 句子序号 -> 文本（同一序号的中间结果会被后续结果覆盖）
+        let mut sentences: BTreeMap<u32, String> = BTreeMap::new();
+        let mut last_text = String::new();
+        let mut audio_done = false;
+
+        loop {
+            let next = if audio_done {
+                match tokio::time::timeout(FINALIZE_TIMEOUT, read.next()).await {
+                    Ok(msg) => msg,
+                    Err(_) => {
+                        log::warn!("Tencent Cloud ASR finalize timeout");
+                        break;
+                    }
+                }
+            } else {
+                tokio::select! {
+                    msg = read.next() => msg,
+                    _ = &mut audio_done_rx => {
+                        audio_done = true;
+                        continue;
+                    }
+                }
+            };
+
+            let Some(msg) = next else {
+                log::info!("Tencent Cloud ASR connection closed");
+                break;
+            };
+
+            let text = match msg {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(e) => return Err(AsrError::Connection(e.to_string())),
+            };
+
+            let Ok(response) = serde_json::from_str::<Response>(&text) else {
+                continue;
+            };
+            if response.code != 0 {
+                return Err(AsrError::Transcription(format!(
+                    "{} ({})",
+                    response.message, response.code
+                )));
+            }
+
+            if let Some(result) = response.result {
+                sentences.insert(result.index, result.voice_text_str);
+            }
+
+            let full_text: String = sentences.values().map(String::as_str).collect();
+            if full_text != last_text && !full_text.is_empty() {
+                last_text = full_text.clone();
+                let result = AsrResult {
+                    text: full_text,
+                    is_final: false,
+                    ..Default::default()
+                };
+                if result_tx.send(result).await.is_err() {
+                    break;
+                }
+            }
+
+            if response.is_final == 1 {
+                break;
+            }
+        }
+
+        // 发送最终结果
+        let final_text: String = sentences.values().map(String::as_str).collect();
+        let final_text = final_text.trim().to_string();
+        if !final_text.is_empty() {
+            let _ = result_tx
+                .send(AsrResult {
+                    text: final_text,
+                    is_final: true,
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        log::info!("Tencent Cloud ASR session completed");
+        Ok(())
+    }
+}
@@ -0,0 +1,392 @@
+//! Vosk 本地语音识别 Provider
+//!
+//! 使用 Vosk (Kaldi) 进行离线流式识别，边录音边输出中间结果。
+
+use async_trait::async_trait;
+use directories::ProjectDirs;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
+use tokio::sync::mpsc;
+use vosk::{DecodingState, Model, Recognizer};
+
+use crate::asr::provider::{
+    AsrError, AsrProvider, AsrResult, DownloadProgress, ModelDownloadable, ModelInfo,
+    ProviderStatus,
+};
+
+/// 已加载的模型（加载较慢，跨会话复用）
+static LOADED_MODEL: LazyLock<Mutex<Option<(PathBuf, Arc<Model>)>>> =
+    LazyLock::new(|| Mutex::new(None));
+/// 下载取消标志（每次命令都会新建 Provider，需全局共享）
+static CANCEL_FLAG: LazyLock<Arc<AtomicBool>> = LazyLock::new(|| Arc::new(AtomicBool::new(false)));
+
+/// Vosk 模型
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VoskModel {
+    #[default]
+    SmallCn,
+    SmallEnUs,
+}
+
+impl VoskModel {
+    /// 所有可用的模型
+    pub fn all() -> Vec<Self> {
+        vec![Self::SmallCn, Self::SmallEnUs]
+    }
+
+    /// 模型目录名（与官方压缩包内的目录名一致）
+    pub fn dirname(&self) -> &str {
+        match self {
+            Self::SmallCn => "vosk-model-small-cn-0.22",
+            Self::SmallEnUs => "vosk-model-small-en-us-0.15",
+        }
+    }
+
+    /// 压缩包大小（字节）
+    pub fn size_bytes(&self) -> u64 {
+        match self {
+            Self::SmallCn => 42_000_000,
+            Self::SmallEnUs => 40_000_000,
+        }
+    }
+
+    /// 显示名称
+    pub fn display_name(&self) -> String {
+        match self {
+            Self::SmallCn => format!("中文 Small ({} MB)", self.size_bytes() / 1_000_000),
+            Self::SmallEnUs => format!("English Small ({} MB)", self.size_bytes() / 1_000_000),
+        }
+    }
+
+    /// 官方下载 URL
+    pub fn download_url(&self) -> String {
+        format!("https://alphacephei.com/vosk/models/{}.zip", self.dirname())
+    }
+
+    /// 压缩包的 SHA-256
+    pub fn sha256(&self) -> Option<&'static str> {
+        match self {
+            Self::SmallCn => None,
+            Self::SmallEnUs => None,
+        }
+    }
+
+    /// 识别结果中的词之间是否以空格分隔（中文模型输出的空格需要去掉）
+    fn uses_spaces(&self) -> bool {
+        !matches!(self, Self::SmallCn)
+    }
+
+    /// 从目录名解析模型
+    pub fn from_dirname(dirname: &str) -> Option<Self> {
+        Self::all().into_iter().find(|m| m.dirname() == dirname)
+    }
+}
+
+/// Vosk 配置
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct VoskConfig {
+    /// 模型
+    #[serde(default)]
+    pub model: VoskModel,
+    /// 自定义模型目录（可选）
+    #[serde(default)]
+    pub model_path: Option<PathBuf>,
+}
+
+/// 加载模型（同一路径只加载一次）
+fn load_model(path: &Path) -> Result<Arc<Model>, AsrError> {
+    let mut loaded = LOADED_MODEL.lock();
+    if let Some((ref loaded_path, ref model)) = *loaded {
+        if loaded_path == path {
+            return Ok(model.clone());
+        }
+    }
+
+    log::info!("Loading Vosk model: {:?}", path);
+    let model = Model::new(path.to_string_lossy())
+        .map(Arc::new)
+        .ok_or_else(|| AsrError::Transcription(format!("模型加载失败: {:?}", path)))?;
+    *loaded = Some((path.to_path_buf(), model.clone()));
+    Ok(model)
+}
+
+/// 已确定的句子 + 当前中间结果
+struct TranscriptState {
+    finals: Vec<String>,
+    partial: String,
+    uses_spaces: bool,
+}
+
+impl TranscriptState {
+    fn normalize(&self, text: &str) -> String {
+        if self.uses_spaces {
+            text.trim().to_string()
+        } else {
+            text.split_whitespace().collect()
+        }
+    }
+
+    fn push_final(&mut self, text: &str) {
+        let text = self.normalize(text);
+        if !text.is_empty() {
+            self.finals.push(text);
+        }
+        self.partial.clear();
+    }
+
+    fn set_partial(&mut self, text: &str) {
+        self.partial = self.normalize(text);
+    }
+
+    fn full_text(&self) -> String {
+        let separator = if self.uses_spaces { " " } else { "" };
+        self.finals
+            .iter()
+            .chain(std::iter::once(&self.partial))
+            .filter(|t| !t.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+}
+
+/// Vosk Provider
+pub struct VoskProvider {
+    config: VoskConfig,
+    models_dir: PathBuf,
+}
+
+impl VoskProvider {
+    pub fn new(config: VoskConfig) -> Self {
+        // 模型存储目录: ~/.config/speaky/models/vosk/
+        let models_dir = ProjectDirs::from("com", "speaky", "Speaky")
+            .map(|dirs| dirs.config_dir().join("models").join("vosk"))
+            .unwrap_or_else(|| PathBuf::from("./models/vosk"));
+
+        Self { config, models_dir }
+    }
+
+    /// 获取模型目录
+    fn model_path(&self) -> PathBuf {
+        self.config
+            .model_path
+            .clone()
+            .unwrap_or_else(|| self.models_dir.join(self.config.model.dirname()))
+    }
+
+    /// 检查模型目录是否完整（解压后包含 am/ 和 conf/）
+    fn is_model_dir_ready(path: &Path) -> bool {
+        path.join("am").is_dir() && path.join("conf").is_dir()
+    }
+}
+
+#[async_trait]
+impl AsrProvider for VoskProvider {
+    fn id(&self) -> &str {
+        "vosk"
+    }
+
+    fn display_name(&self) -> &str {
+        "Vosk 本地"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !Self::is_model_dir_ready(&self.model_path()) {
+            ProviderStatus::NeedsModelDownload {
+                model: self.config.model.dirname().to_string(),
+                size_mb: self.config.model.size_bytes() / 1_000_000,
+            }
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if !Self::is_model_dir_ready(&self.model_path()) {
+            return Err(AsrError::ModelNotFound(format!(
+                "需要先下载 {} 模型",
+                self.config.model.dirname()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        let model_path = self.model_path();
+        let uses_spaces = self.config.model.uses_spaces();
+
+        // Vosk 识别是同步调用，在阻塞线程中逐块送入音频
+        tokio::task::spawn_blocking(move || {
+            let model = load_model(&model_path)?;
+            let mut recognizer = Recognizer::new(&model, 16000.0)
+                .ok_or_else(|| AsrError::Transcription("创建识别器失败".into()))?;
+
+            let mut state = TranscriptState {
+                finals: Vec::new(),
+                partial: String::new(),
+                uses_spaces,
+            };
+            let mut last_text = String::new();
+
+            while let Some(chunk) = audio_rx.blocking_recv() {
+                let samples: Vec<i16> = chunk
+                    .chunks_exact(2)
+                    .map(|c| i16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+
+                match recognizer.accept_waveform(&samples) {
+                    Ok(DecodingState::Finalized) => {
+                        if let Some(result) = recognizer.result().single() {
+                            state.push_final(result.text);
+                        }
+                    }
+                    Ok(DecodingState::Running) => {
+                        state.set_partial(recognizer.partial_result().partial);
+                    }
+                    Ok(DecodingState::Failed) | Err(_) => {
+                        return Err(AsrError::Transcription("Vosk 解码失败".into()));
+                    }
+                }
+
+                let full_text = state.full_text();
+                if full_text != last_text && !full_text.is_empty() {
+                    last_text = full_text.clone();
+                    let result = AsrResult {
+                        text: full_text,
+                        is_final: false,
+                        ..Default::default()
+                    };
+                    if result_tx.blocking_send(result).is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+
+            // 输出剩余音频的识别结果
+            if let Some(result) = recognizer.final_result().single() {
+                state.push_final(result.text);
+            }
+            let final_text = state.full_text();
+            if !final_text.is_empty() {
+                let _ = result_tx.blocking_send(AsrResult {
+                    text: final_text,
+                    is_final: true,
+                    ..Default::default()
+                });
+            }
+            Ok(())
+        })
+        .await
+        .map_err(|e| AsrError::Transcription(format!("任务执行失败: {}", e)))??;
+
+        log::info!("Vosk session completed");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ModelDownloadable for VoskProvider {
+    fn available_models(&self) -> Vec<ModelInfo> {
+        VoskModel::all()
+            .into_iter()
+            .filter(|model| {
+                model.sha256().is_some()
+                    || Self::is_model_dir_ready(&self.models_dir.join(model.dirname()))
+            })
+            .map(|model| {
+                let dirname = model.dirname();
+                ModelInfo {
+                    id: dirname.to_string(),
+                    name: model.display_name(),
+                    size_bytes: model.size_bytes(),
+                    is_downloaded: Self::is_model_dir_ready(&self.models_dir.join(dirname)),
+                    is_selected: model == self.config.model,
+                }
+            })
+            .collect()
+    }
+
+    fn models_dir(&self) -> PathBuf {
+        self.models_dir.clone()
+    }
+
+    async fn download_model(
+        &self,
+        model_id: &str,
+        progress_tx: mpsc::Sender<DownloadProgress>,
+    ) -> Result<PathBuf, AsrError> {
+        let model = VoskModel::from_dirname(model_id)
+            .ok_or_else(|| AsrError::ModelNotFound(format!("未知模型: {}", model_id)))?;
+
+        let url = model.download_url();
+        let archive_path = self.models_dir.join(format!("{}.zip", model_id));
+        let temp_path = archive_path.with_extension("tmp");
+
+        // 创建目录
+        std::fs::create_dir_all(&self.models_dir)?;
+
+        // 重置取消标志
+        CANCEL_FLAG.store(false, Ordering::SeqCst);
+
+        // 使用模型管理器下载压缩包
+        crate::asr::model_manager::download_file(
+            &url,
+            &temp_path,
+            &archive_path,
+            model_id,
+            model.sha256(),
+            progress_tx,
+            CANCEL_FLAG.clone(),
+        )
+        .await?;
+
+        // 解压到模型目录（压缩包内包含同名的顶层目录）
+        let models_dir = self.models_dir.clone();
+        let extract_archive = archive_path.clone();
+        tokio::task::spawn_blocking(move || {
+            let file = std::fs::File::open(&extract_archive)?;
+            let mut archive = zip::ZipArchive::new(file)
+                .map_err(|e| AsrError::ModelDownload(format!("读取压缩包失败: {}", e)))?;
+            archive
+                .extract(&models_dir)
+                .map_err(|e| AsrError::ModelDownload(format!("解压失败: {}", e)))
+        })
+        .await
+        .map_err(|e| AsrError::ModelDownload(format!("任务执行失败: {}", e)))??;
+        let _ = std::fs::remove_file(&archive_path);
+
+        let dest_path = self.models_dir.join(model_id);
+        log::info!("Vosk 模型已解压: {:?}", dest_path);
+        Ok(dest_path)
+    }
+
+    async fn delete_model(&self, model_id: &str) -> Result<(), AsrError> {
+        let path = self.models_dir.join(model_id);
+        if path.exists() {
+            // 释放已加载的模型
+            let mut loaded = LOADED_MODEL.lock();
+            if loaded.as_ref().is_some_and(|(p, _)| *p == path) {
+                *loaded = None;
+            }
+            drop(loaded);
+
+            std::fs::remove_dir_all(&path)?;
+            log::info!("已删除模型: {:?}", path);
+        }
+        Ok(())
+    }
+
+    fn cancel_download(&self) {
+        CANCEL_FLAG.store(true, Ordering::SeqCst);
+    }
+}
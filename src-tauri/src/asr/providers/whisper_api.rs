@@ -5,9 +5,10 @@
 use async_trait::async_trait;
 use reqwest::multipart;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use tokio::sync::mpsc;
 
-use crate::asr::provider::{AsrError, AsrProvider, AsrResult, ProviderStatus};
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, AsrSegment, ProviderStatus};
 
 /// Whisper API 配置
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -24,6 +25,107 @@ pub struct WhisperApiConfig {
     /// 识别语言（可选）
     #[serde(default)]
     pub language: Option<String>,
+    /// 使用的服务预设 ID（None 表示自定义接口）
+    #[serde(default)]
+    pub preset: Option<String>,
+    /// 响应格式 ("verbose_json", "json", "text")，verbose_json 可获得分段时间戳和置信度
+    #[serde(default = "default_response_format")]
+    pub response_format: String,
+    /// 临时性失败（网络错误、429、5xx）的最大重试次数
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// 提示词（运行时由统一的热词设置填充）
+    #[serde(skip)]
+    pub prompt: Option<String>,
+}
+
+/// OpenAI 兼容转写服务预设
+#[derive(Clone, Debug, Serialize)]
+pub struct WhisperApiPreset {
+    /// 预设 ID
+    pub id: &'static str,
+    /// 显示名称
+    pub display_name: &'static str,
+    /// API Base URL
+    pub api_base: &'static str,
+    /// 默认模型名称
+    pub model: &'static str,
+    /// 单次上传文件大小上限（字节）
+    pub max_file_bytes: u64,
+    /// 是否支持 language 参数
+    pub supports_language: bool,
+    /// 是否支持 verbose_json 响应格式
+    pub supports_verbose_json: bool,
+    /// 单次请求超时（秒）
+    pub timeout_secs: u64,
+    /// 需要额外附带的表单参数
+    #[serde(skip)]
+    pub extra_params: &'static [(&'static str, &'static str)],
+}
+
+/// OpenAI 默认上传大小上限 (25 MB)
+const DEFAULT_MAX_FILE_BYTES: u64 = 25 * 1024 * 1024;
+/// 默认请求超时（秒）
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+/// Groq 转写通常在 1 秒内返回，超时设短一些以便尽快重试
+const GROQ_TIMEOUT_SECS: u64 = 30;
+
+/// 内置服务预设
+const PRESETS: &[WhisperApiPreset] = &[
+    WhisperApiPreset {
+        id: "openai",
+        display_name: "OpenAI",
+        api_base: "https://api.openai.com/v1",
+        model: "whisper-1",
+        max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+        supports_language: true,
+        supports_verbose_json: true,
+        timeout_secs: DEFAULT_TIMEOUT_SECS,
+        extra_params: &[],
+    },
+    WhisperApiPreset {
+        id: "groq",
+        display_name: "Groq (whisper-large-v3)",
+        api_base: "https://api.groq.com/openai/v1",
+        model: "whisper-large-v3",
+        max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+        supports_language: true,
+        supports_verbose_json: true,
+        timeout_secs: GROQ_TIMEOUT_SECS,
+        extra_params: &[("temperature", "0")],
+    },
+    WhisperApiPreset {
+        id: "groq_turbo",
+        display_name: "Groq (whisper-large-v3-turbo)",
+        api_base: "https://api.groq.com/openai/v1",
+        model: "whisper-large-v3-turbo",
+        max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+        supports_language: true,
+        supports_verbose_json: true,
+        timeout_secs: GROQ_TIMEOUT_SECS,
+        extra_params: &[("temperature", "0")],
+    },
+    WhisperApiPreset {
+        id: "siliconflow",
+        display_name: "SiliconFlow (SenseVoice)",
+        api_base: "https://api.siliconflow.cn/v1",
+        model: "FunAudioLLM/SenseVoiceSmall",
+        max_file_bytes: 50 * 1024 * 1024,
+        supports_language: false,
+        supports_verbose_json: false,
+        timeout_secs: DEFAULT_TIMEOUT_SECS,
+        extra_params: &[],
+    },
+];
+
+/// 获取所有内置预设
+pub fn whisper_api_presets() -> &'static [WhisperApiPreset] {
+    PRESETS
+}
+
+/// 根据 ID 查找预设
+pub fn find_whisper_api_preset(id: &str) -> Option<&'static WhisperApiPreset> {
+    PRESETS.iter().find(|p| p.id == id)
 }
 
 fn default_api_base() -> String {
@@ -34,6 +136,14 @@ fn default_model() -> String {
     "whisper-1".to_string()
 }
 
+fn default_response_format() -> String {
+    "verbose_json".to_string()
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
 impl Default for WhisperApiConfig {
     fn default() -> Self {
         Self {
@@ -41,6 +151,10 @@ impl Default for WhisperApiConfig {
             api_base: default_api_base(),
             model: default_model(),
             language: None,
+            preset: None,
+            response_format: default_response_format(),
+            max_retries: default_max_retries(),
+            prompt: None,
         }
     }
 }
@@ -49,6 +163,34 @@ impl WhisperApiConfig {
     pub fn is_configured(&self) -> bool {
         !self.api_key.is_empty()
     }
+
+    /// 应用预设：写入预设的 Base URL 和模型，并记录预设 ID
+    pub fn apply_preset(&mut self, preset: &WhisperApiPreset) {
+        self.api_base = preset.api_base.to_string();
+        self.model = preset.model.to_string();
+        self.preset = Some(preset.id.to_string());
+    }
+
+    /// 当前使用的预设（自定义接口返回 None）
+    fn active_preset(&self) -> Option<&'static WhisperApiPreset> {
+        self.preset.as_deref().and_then(find_whisper_api_preset)
+    }
+
+    /// 单次上传文件大小上限
+    pub fn max_file_bytes(&self) -> u64 {
+        self.active_preset()
+            .map(|p| p.max_file_bytes)
+            .unwrap_or(DEFAULT_MAX_FILE_BYTES)
+    }
+
+    /// 单次请求超时
+    fn request_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.active_preset()
+                .map(|p| p.timeout_secs)
+                .unwrap_or(DEFAULT_TIMEOUT_SECS),
+        )
+    }
 }
 
 /// Whisper API Provider
@@ -84,6 +226,16 @@ impl AsrProvider for WhisperApiProvider {
         }
     }
 
+    fn result_timeout(&self, audio_secs: f32) -> Duration {
+        // 超过上传上限的音频分段依次上传，每段都有完整的重试预算
+        let samples = (audio_secs.max(0.0) * SAMPLE_RATE as f32) as usize;
+        let uploads = samples
+            .div_ceil(max_samples_for(self.config.max_file_bytes()))
+            .max(1) as u32;
+        let backoff: Duration = (0..self.config.max_retries).map(retry_delay).sum();
+        (self.config.request_timeout() * (self.config.max_retries + 1) + backoff) * uploads
+    }
+
     fn validate(&self) -> Result<(), AsrError> {
         if self.config.api_key.is_empty() {
             return Err(AsrError::Configuration("API Key 不能为空".into()));
@@ -108,22 +260,271 @@ impl AsrProvider for WhisperApiProvider {
             return Ok(());
         }
 
-        // 转换为 WAV 格式（OpenAI API 需要）
-        let wav_data = pcm_to_wav(&audio_buffer, 16000, 1, 16);
+        let samples: Vec<i16> = audio_buffer
+            .chunks_exact(2)
+            .map(|c| i16::from_le_bytes([c[0], c[1]]))
+            .collect();
+
+        // 超过接口大小上限时，在静音处切分为多段分别上传
+        let max_samples = max_samples_for(self.config.max_file_bytes());
+        let chunks = split_at_silence(&samples, max_samples);
+        if chunks.len() > 1 {
+            log::info!(
+                "Audio exceeds upload limit, split into {} requests",
+                chunks.len()
+            );
+        }
 
+        let mut text = String::new();
+        let mut segments = Vec::new();
+        let mut language = None;
+        let mut offset_samples = 0usize;
+        let mut error = None;
+
+        for chunk in chunks {
+            // 转换为 WAV 格式（OpenAI API 需要）
+            let wav_data = pcm_to_wav(bytemuck::cast_slice(chunk), SAMPLE_RATE, 1, 16);
+            let transcription = match self.request_transcription(&wav_data).await {
+                Ok(transcription) => transcription,
+                Err(e) => {
+                    // 后续分段失败时保留已识别的文本，仍返回错误以便用缓存的音频重试
+                    if !text.is_empty() {
+                        log::warn!(
+                            "Whisper API chunk failed, keeping partial transcript: {}",
+                            e
+                        );
+                    }
+                    error = Some(e);
+                    break;
+                }
+            };
+
+            let offset_secs = offset_samples as f32 / SAMPLE_RATE as f32;
+            segments.extend(transcription.segments.into_iter().map(|mut s| {
+                s.start += offset_secs;
+                s.end += offset_secs;
+                s
+            }));
+            append_text(&mut text, transcription.text.trim());
+            language = language.or(transcription.language);
+            offset_samples += chunk.len();
+        }
+
+        if text.is_empty() {
+            if let Some(e) = error {
+                return Err(e);
+            }
+        }
+
+        let confidence = AsrSegment::overall_confidence(&segments);
+
+        let _ = result_tx
+            .send(AsrResult {
+                text,
+                is_final: true,
+                segments,
+                confidence,
+                language,
+                ..Default::default()
+            })
+            .await;
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 输入音频采样率
+const SAMPLE_RATE: u32 = 16000;
+/// 切分时预留的余量比例（multipart 开销等）
+const SIZE_SAFETY_RATIO: f64 = 0.95;
+/// 在每段末尾的该时长范围内寻找静音切分点（秒）
+const SPLIT_SEARCH_SECS: usize = 10;
+/// 静音检测帧长（100ms）
+const SILENCE_FRAME_SAMPLES: usize = 1600;
+
+/// 根据文件大小上限计算单段最多允许的样本数
+fn max_samples_for(max_file_bytes: u64) -> usize {
+    let usable = (max_file_bytes as f64 * SIZE_SAFETY_RATIO) as usize;
+    (usable.saturating_sub(44) / 2).max(SAMPLE_RATE as usize)
+}
+
+/// 将音频切分为不超过 `max_samples` 的多段，切分点尽量选在静音处
+fn split_at_silence(samples: &[i16], max_samples: usize) -> Vec<&[i16]> {
+    let mut chunks = Vec::new();
+    let mut rest = samples;
+
+    while rest.len() > max_samples {
+        let search_len = (SPLIT_SEARCH_SECS * SAMPLE_RATE as usize).min(max_samples / 2);
+        let search_start = max_samples - search_len;
+
+        // 在搜索窗口内找能量最低的帧，从该帧中点切开
+        let cut = rest[search_start..max_samples]
+            .chunks(SILENCE_FRAME_SAMPLES)
+            .enumerate()
+            .map(|(i, frame)| {
+                let energy: f64 = frame.iter().map(|&s| (s as f64) * (s as f64)).sum();
+                (i, energy / frame.len() as f64)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| search_start + i * SILENCE_FRAME_SAMPLES + SILENCE_FRAME_SAMPLES / 2)
+            .unwrap_or(max_samples)
+            .min(max_samples);
+
+        let (head, tail) = rest.split_at(cut);
+        chunks.push(head);
+        rest = tail;
+    }
+
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// 拼接分段文本：两侧都是拉丁字母/数字时插入空格，中日韩文本直接拼接
+fn append_text(text: &mut String, next: &str) {
+    if next.is_empty() {
+        return;
+    }
+    let needs_space = matches!(
+        (text.chars().last(), next.chars().next()),
+        (Some(a), Some(b)) if !a.is_whitespace() && a.is_ascii() && b.is_ascii_alphanumeric()
+    );
+    if needs_space {
+        text.push(' ');
+    }
+    text.push_str(next);
+}
+
+/// 首次重试前的等待时间
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// 重试等待时间上限
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(8);
+
+/// 第 `attempt` 次重试前的指数退避时间
+fn retry_delay(attempt: u32) -> Duration {
+    (RETRY_BASE_DELAY * 2u32.saturating_pow(attempt)).min(RETRY_MAX_DELAY)
+}
+
+/// 单次请求失败的原因
+enum RequestFailure {
+    /// 可重试（网络错误、超时、429、5xx），附带服务端建议的等待时间
+    Retryable(AsrError, Option<Duration>),
+    /// 不可重试（鉴权失败、参数错误等）
+    Fatal(AsrError),
+}
+
+/// 单次转写请求的结果
+struct Transcription {
+    text: String,
+    segments: Vec<AsrSegment>,
+    /// 自动检测出的语言代码
+    language: Option<String>,
+}
+
+/// verbose_json 响应中的分段
+#[derive(Deserialize)]
+struct VerboseSegment {
+    #[serde(default)]
+    start: f32,
+    #[serde(default)]
+    end: f32,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    avg_logprob: Option<f32>,
+}
+
+/// json / verbose_json 响应
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+    #[serde(default)]
+    segments: Vec<VerboseSegment>,
+    /// verbose_json 返回的检测语言（语言全称，如 "english"）
+    #[serde(default)]
+    language: Option<String>,
+}
+
+impl WhisperApiProvider {
+    /// 实际使用的响应格式（预设不支持 verbose_json 时回退为 json）
+    fn response_format(&self) -> &str {
+        let supports_verbose = self
+            .config
+            .active_preset()
+            .map(|p| p.supports_verbose_json)
+            .unwrap_or(true);
+        match self.config.response_format.as_str() {
+            "verbose_json" if !supports_verbose => "json",
+            format => format,
+        }
+    }
+
+    /// 上传一段 WAV 音频并解析转写结果，对网络错误、429 和 5xx 进行有限次退避重试
+    async fn request_transcription(&self, wav_data: &[u8]) -> Result<Transcription, AsrError> {
+        let mut attempt = 0;
+        loop {
+            match self.try_request_transcription(wav_data).await {
+                Ok(transcription) => return Ok(transcription),
+                Err(RequestFailure::Fatal(e)) => return Err(e),
+                Err(RequestFailure::Retryable(e, retry_after)) => {
+                    if attempt >= self.config.max_retries {
+                        return Err(e);
+                    }
+                    // 指数退避，服务端给出 Retry-After 时优先使用
+                    let backoff = retry_after
+                        .map(|delay| delay.min(RETRY_MAX_DELAY))
+                        .unwrap_or_else(|| retry_delay(attempt));
+                    attempt += 1;
+                    log::warn!(
+                        "Whisper API request failed ({}), retry {}/{} in {:?}",
+                        e,
+                        attempt,
+                        self.config.max_retries,
+                        backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                }
+            }
+        }
+    }
+
+    /// 发送一次转写请求
+    async fn try_request_transcription(
+        &self,
+        wav_data: &[u8],
+    ) -> Result<Transcription, RequestFailure> {
         // 构建 multipart 请求
-        let file_part = multipart::Part::bytes(wav_data)
+        let file_part = multipart::Part::bytes(wav_data.to_vec())
             .file_name("audio.wav")
             .mime_str("audio/wav")
-            .map_err(|e| AsrError::Transcription(e.to_string()))?;
+            .map_err(|e| RequestFailure::Fatal(AsrError::Transcription(e.to_string())))?;
 
+        let response_format = self.response_format().to_string();
         let mut form = multipart::Form::new()
             .part("file", file_part)
-            .text("model", self.config.model.clone());
+            .text("model", self.config.model.clone())
+            .text("response_format", response_format.clone());
+
+        let preset = self.config.active_preset();
 
-        // 添加语言参数（如果指定）
+        // 添加语言参数（如果指定且接口支持）
         if let Some(ref lang) = self.config.language {
-            form = form.text("language", lang.clone());
+            if preset.map(|p| p.supports_language).unwrap_or(true) {
+                form = form.text("language", lang.clone());
+            }
+        }
+
+        if let Some(ref prompt) = self.config.prompt {
+            form = form.text("prompt", prompt.clone());
+        }
+
+        // 预设要求的额外参数
+        for (key, value) in preset.map(|p| p.extra_params).unwrap_or_default() {
+            form = form.text(*key, *value);
         }
 
         let url = format!("{}/audio/transcriptions", self.config.api_base);
@@ -132,43 +533,108 @@ impl AsrProvider for WhisperApiProvider {
             .client
             .post(&url)
             .bearer_auth(&self.config.api_key)
+            .timeout(self.config.request_timeout())
             .multipart(form)
             .send()
             .await
-            .map_err(|e| AsrError::Connection(e.to_string()))?;
+            .map_err(|e| {
+                let error = AsrError::Connection(e.to_string());
+                // 超时和连接失败可重试，其他（如构建请求失败）直接返回
+                if e.is_timeout() || e.is_connect() || e.is_request() {
+                    RequestFailure::Retryable(error, None)
+                } else {
+                    RequestFailure::Fatal(error)
+                }
+            })?;
 
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
             let error_text = response.text().await.unwrap_or_default();
-            return Err(AsrError::Transcription(format!(
+            let error = AsrError::Transcription(format!(
                 "API 请求失败 ({}): {}",
                 status, error_text
-            )));
+            ));
+            return Err(
+                if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                    RequestFailure::Retryable(error, retry_after)
+                } else {
+                    RequestFailure::Fatal(error)
+                },
+            );
         }
 
-        #[derive(Deserialize)]
-        struct TranscriptionResponse {
-            text: String,
+        // text 格式直接返回纯文本
+        if response_format == "text" {
+            let text = response.text().await.map_err(|e| {
+                RequestFailure::Retryable(
+                    AsrError::Connection(format!("读取响应失败: {}", e)),
+                    None,
+                )
+            })?;
+            return Ok(Transcription {
+                text: text.trim().to_string(),
+                segments: Vec::new(),
+            });
         }
 
-        let result: TranscriptionResponse = response
-            .json()
-            .await
-            .map_err(|e| AsrError::Transcription(format!("解析响应失败: {}", e)))?;
-
-        let _ = result_tx
-            .send(AsrResult {
-                text: result.text,
-                is_final: true,
+        let result: TranscriptionResponse = response.json().await.map_err(|e| {
+            RequestFailure::Fatal(AsrError::Transcription(format!("解析响应失败: {}", e)))
+        })?;
+
+        let segments = result
+            .segments
+            .into_iter()
+            .map(|s| AsrSegment {
+                text: s.text.trim().to_string(),
+                start: s.start,
+                end: s.end,
+                // avg_logprob 为对数概率，取指数得到 0-1 的置信度
+                confidence: s.avg_logprob.map(|lp| lp.exp().clamp(0.0, 1.0)),
             })
-            .await;
+            .collect();
+
+        // 仅在未指定语言时上报检测结果
+        let language = match self.config.language {
+            Some(_) => None,
+            None => result.language.map(|name| language_code(&name).to_string()),
+        };
+
+        Ok(Transcription {
+            text: result.text,
+            segments,
+            language,
+        })
+    }
+}
 
-        Ok(())
+/// 将 verbose_json 返回的语言全称转换为语言代码，未知语言原样返回
+fn language_code(name: &str) -> &str {
+    match name {
+        "chinese" => "zh",
+        "english" => "en",
+        "japanese" => "ja",
+        "korean" => "ko",
+        "spanish" => "es",
+        "french" => "fr",
+        "german" => "de",
+        "russian" => "ru",
+        other => other,
     }
 }
 
 /// PCM 转 WAV 格式
-fn pcm_to_wav(pcm_data: &[u8], sample_rate: u32, channels: u16, bits_per_sample: u16) -> Vec<u8> {
+pub(super) fn pcm_to_wav(
+    pcm_data: &[u8],
+    sample_rate: u32,
+    channels: u16,
+    bits_per_sample: u16,
+) -> Vec<u8> {
     let data_size = pcm_data.len() as u32;
     let byte_rate = sample_rate * channels as u32 * bits_per_sample as u32 / 8;
     let block_align = channels * bits_per_sample / 8;
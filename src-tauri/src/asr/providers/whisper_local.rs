@@ -1,22 +1,45 @@
 //! Whisper 本地语音识别 Provider
 //!
 //! 使用 whisper.cpp 进行离线语音识别
+//!
+//! whisper.cpp 本身不支持流式识别。录音过程中每隔一段时间对滑动窗口内的音频重新推理，
+//! 输出中间结果；窗口过长时把前面已稳定的片段确认下来，并作为后续窗口的提示词，
+//! 保持上下文连贯。开启 VAD 分段后，Silero VAD 检测到停顿即识别并确认停顿前的音频。
 
 use async_trait::async_trait;
 use directories::ProjectDirs;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+#[cfg(feature = "sherpa")]
+use sherpa_rs::silero_vad::{SileroVad, SileroVadConfig};
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
 use crate::asr::provider::{
-    AsrError, AsrProvider, AsrResult, DownloadProgress, ModelDownloadable, ModelInfo,
-    ProviderStatus,
+    AsrError, AsrProvider, AsrResult, AsrSegment, DownloadProgress, ModelDownloadable, ModelInfo,
+    ProviderStatus, WordTiming,
 };
 
+/// Whisper 模型基准测试结果
+#[derive(Debug, Clone, Serialize)]
+pub struct WhisperBenchmark {
+    pub model_id: String,
+    /// 模型加载耗时
+    pub load_ms: u64,
+    /// 推理耗时
+    pub inference_ms: u64,
+    /// 测试音频时长
+    pub audio_ms: u64,
+    /// 实时率（推理耗时 / 音频时长），小于 1 表示比实时更快
+    pub realtime_factor: f32,
+}
+
 /// Whisper 模型大小
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -87,6 +110,18 @@ impl WhisperModelSize {
         )
     }
 
+    /// 模型文件的 SHA-256
+    pub fn sha256(&self) -> Option<&'static str> {
+        match self {
+            Self::Tiny => Some("be07e048e1e599ad46341c8d2a135645097a538221678b7acdd1b1919c6e1b21"),
+            Self::Base => Some("60ed5bc3dd14eea856493d334349b405782ddcaf0028d4b5df4088345fba2efe"),
+            Self::Small => Some("1be3a9b2063867b937e64e2ec7483364a79917e157fa98c5d94b5c1fffea987b"),
+            Self::Medium => Some("6c14d5adee5f86394037b4e4e8b59f1673b6cee10e3cf0b11bbdbee79c156208"),
+            Self::Large => None,
+            Self::LargeV3 => Some("64d182b440b98d5203c4f9bd541544d84c605196c4f7b845dfa11fb23594d1e2"),
+        }
+    }
+
     /// 从文件名解析模型大小
     pub fn from_filename(filename: &str) -> Option<Self> {
         match filename {
@@ -101,6 +136,77 @@ impl WhisperModelSize {
     }
 }
 
+/// 推理后端
+///
+/// whisper.cpp 的 GPU 后端在编译期选择，需要以对应的 feature（`cuda` / `metal` / `vulkan`）构建。
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WhisperGpuBackend {
+    #[default]
+    Cpu,
+    /// 使用当前构建中可用的 GPU 后端
+    Auto,
+    Cuda,
+    Metal,
+    Vulkan,
+}
+
+impl WhisperGpuBackend {
+    /// 当前构建支持的后端
+    pub fn available() -> Vec<Self> {
+        let mut backends = vec![Self::Cpu];
+        if cfg!(feature = "cuda") {
+            backends.push(Self::Cuda);
+        }
+        if cfg!(feature = "metal") {
+            backends.push(Self::Metal);
+        }
+        if cfg!(feature = "vulkan") {
+            backends.push(Self::Vulkan);
+        }
+        if backends.len() > 1 {
+            backends.insert(1, Self::Auto);
+        }
+        backends
+    }
+
+    /// 当前构建是否支持该后端
+    pub fn is_available(&self) -> bool {
+        match self {
+            Self::Cpu => true,
+            Self::Auto => Self::available().len() > 1,
+            Self::Cuda => cfg!(feature = "cuda"),
+            Self::Metal => cfg!(feature = "metal"),
+            Self::Vulkan => cfg!(feature = "vulkan"),
+        }
+    }
+
+    fn display_name(&self) -> &str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Auto => "GPU",
+            Self::Cuda => "CUDA",
+            Self::Metal => "Metal",
+            Self::Vulkan => "Vulkan",
+        }
+    }
+}
+
+/// ggml 模型文件头（小端存储的 0x67676d6c）
+const GGML_MAGIC: [u8; 4] = *b"lmgg";
+/// gguf 模型文件头
+const GGUF_MAGIC: [u8; 4] = *b"GGUF";
+
+/// Silero VAD 模型文件名（与 Whisper 模型放在同一目录）
+const VAD_MODEL_FILENAME: &str = "silero_vad.onnx";
+/// Silero VAD 模型下载地址
+const VAD_MODEL_URL: &str =
+    "https://github.com/k2-fsa/sherpa-onnx/releases/download/asr-models/silero_vad.onnx";
+/// Silero VAD 模型大小（字节）
+const VAD_MODEL_SIZE_BYTES: u64 = 1_800_000;
+/// Silero VAD 模型的 SHA-256
+const VAD_MODEL_SHA256: Option<&str> = None;
+
 /// Whisper 本地配置
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WhisperLocalConfig {
@@ -116,6 +222,22 @@ pub struct WhisperLocalConfig {
     /// 是否翻译为英语
     #[serde(default)]
     pub translate_to_english: bool,
+    /// 录音过程中输出中间结果的间隔（毫秒），0 表示只在录音结束后识别一次
+    #[serde(default = "default_partial_interval_ms")]
+    pub partial_interval_ms: u64,
+    /// 推理后端
+    #[serde(default)]
+    pub gpu_backend: WhisperGpuBackend,
+    /// 是否用 VAD 在停顿处切分音频，逐段识别并确认（需下载 Silero VAD 模型，且构建启用 sherpa feature）
+    #[serde(default)]
+    pub vad_segmentation: bool,
+    /// 初始提示词（运行时由统一的热词设置填充）
+    #[serde(skip)]
+    pub initial_prompt: Option<String>,
+}
+
+fn default_partial_interval_ms() -> u64 {
+    2000
 }
 
 fn default_language() -> String {
@@ -129,6 +251,10 @@ impl Default for WhisperLocalConfig {
             model_path: None,
             language: default_language(),
             translate_to_english: false,
+            partial_interval_ms: default_partial_interval_ms(),
+            gpu_backend: WhisperGpuBackend::default(),
+            vad_segmentation: false,
+            initial_prompt: None,
         }
     }
 }
@@ -174,6 +300,136 @@ impl WhisperLocalProvider {
         let path = self.models_dir.join(filename);
         path.exists() && std::fs::metadata(&path).map(|m| m.len() > 0).unwrap_or(false)
     }
+
+    /// 用户导入的模型文件（模型目录中不属于内置列表的 .bin / .gguf 文件）
+    fn imported_models(&self) -> Vec<(String, u64)> {
+        let Ok(entries) = std::fs::read_dir(&self.models_dir) else {
+            return Vec::new();
+        };
+        let mut models: Vec<(String, u64)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let filename = entry.file_name().to_string_lossy().into_owned();
+                let is_model = filename.ends_with(".bin") || filename.ends_with(".gguf");
+                if !is_model || WhisperModelSize::from_filename(&filename).is_some() {
+                    return None;
+                }
+                let size = entry.metadata().ok()?.len();
+                Some((filename, size))
+            })
+            .collect();
+        models.sort();
+        models
+    }
+
+    /// 是否是已导入的模型文件
+    pub fn is_imported_model(&self, filename: &str) -> bool {
+        self.imported_models().iter().any(|(name, _)| name == filename)
+    }
+
+    /// 用内置测试音频测量指定模型的加载耗时、推理耗时和实时率
+    pub async fn benchmark_model(&self, model_id: &str) -> Result<WhisperBenchmark, AsrError> {
+        if WhisperModelSize::from_filename(model_id).is_none() && !self.is_imported_model(model_id)
+        {
+            return Err(AsrError::ModelNotFound(format!("未知模型: {}", model_id)));
+        }
+        if !self.is_model_file_downloaded(model_id) {
+            return Err(AsrError::ModelNotFound(format!("模型未下载: {}", model_id)));
+        }
+        let (language, gpu_backend) = {
+            let config = self.config.read();
+            (config.language.clone(), config.gpu_backend)
+        };
+        let use_gpu = gpu_backend != WhisperGpuBackend::Cpu && gpu_backend.is_available();
+
+        let started = Instant::now();
+        let ctx = load_context(self.models_dir.join(model_id), use_gpu).await?;
+        let load_ms = started.elapsed().as_millis() as u64;
+
+        let audio = benchmark_sample();
+        let audio_ms = (audio.len() * 1000 / SAMPLE_RATE) as u64;
+        let options = DecodeOptions {
+            language,
+            translate: false,
+            prompt: None,
+        };
+        let started = Instant::now();
+        tokio::task::spawn_blocking(move || decode(&ctx, &audio, &options))
+            .await
+            .map_err(|e| AsrError::Transcription(format!("任务执行失败: {}", e)))??;
+        let inference_ms = started.elapsed().as_millis() as u64;
+
+        let benchmark = WhisperBenchmark {
+            model_id: model_id.to_string(),
+            load_ms,
+            inference_ms,
+            audio_ms,
+            realtime_factor: inference_ms as f32 / audio_ms as f32,
+        };
+        log::info!("Whisper benchmark: {:?}", benchmark);
+        Ok(benchmark)
+    }
+
+    /// 导入用户提供的 ggml / gguf 模型文件（复制到模型目录），返回模型 ID
+    pub fn import_model(&self, source: &Path) -> Result<String, AsrError> {
+        let mut magic = [0u8; 4];
+        std::fs::File::open(source)?.read_exact(&mut magic)?;
+        if magic != GGML_MAGIC && magic != GGUF_MAGIC {
+            return Err(AsrError::Configuration(
+                "不是有效的 ggml / gguf 模型文件".to_string(),
+            ));
+        }
+
+        let stem = source
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .ok_or_else(|| AsrError::Configuration("无效的文件路径".to_string()))?;
+        let extension = if magic == GGUF_MAGIC { "gguf" } else { "bin" };
+        let filename = format!("{}.{}", stem, extension);
+        if WhisperModelSize::from_filename(&filename).is_some() {
+            return Err(AsrError::Configuration(format!(
+                "{} 与内置模型重名，请先重命名",
+                filename
+            )));
+        }
+
+        let dest_path = self.models_dir.join(&filename);
+        if dest_path.exists() {
+            return Err(AsrError::Configuration(format!("已存在同名模型: {}", filename)));
+        }
+        std::fs::create_dir_all(&self.models_dir)?;
+        // 先复制到临时文件，避免中途失败留下不完整的模型
+        let temp_path = dest_path.with_extension("tmp");
+        std::fs::copy(source, &temp_path)?;
+        std::fs::rename(&temp_path, &dest_path)?;
+        log::info!("已导入模型: {:?} -> {:?}", source, dest_path);
+        Ok(filename)
+    }
+
+    /// 加载 Silero VAD，未开启或模型缺失时返回 None（退回滑动窗口）
+    #[cfg(feature = "sherpa")]
+    fn load_vad(&self) -> Option<SileroVad> {
+        if !self.config.read().vad_segmentation {
+            return None;
+        }
+        if !self.is_model_file_downloaded(VAD_MODEL_FILENAME) {
+            log::warn!("VAD segmentation enabled but {} not downloaded", VAD_MODEL_FILENAME);
+            return None;
+        }
+        let config = SileroVadConfig {
+            model: self.models_dir.join(VAD_MODEL_FILENAME).to_string_lossy().into_owned(),
+            min_silence_duration: VAD_MIN_SILENCE_SECS,
+            sample_rate: SAMPLE_RATE as u32,
+            ..Default::default()
+        };
+        match SileroVad::new(config, VAD_BUFFER_SECS) {
+            Ok(vad) => Some(vad),
+            Err(e) => {
+                log::warn!("Failed to load Silero VAD: {}", e);
+                None
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -187,12 +443,17 @@ impl AsrProvider for WhisperLocalProvider {
     }
 
     fn status(&self) -> ProviderStatus {
+        let config = self.config.read();
         if !self.is_model_downloaded() {
-            let config = self.config.read();
             ProviderStatus::NeedsModelDownload {
                 model: config.model_size.filename().to_string(),
                 size_mb: config.model_size.size_bytes() / 1_000_000,
             }
+        } else if !config.gpu_backend.is_available() {
+            ProviderStatus::Degraded(format!(
+                "当前构建未启用 {} 加速，将使用 CPU",
+                config.gpu_backend.display_name()
+            ))
         } else {
             ProviderStatus::Ready
         }
@@ -217,82 +478,140 @@ impl AsrProvider for WhisperLocalProvider {
         self.validate()?;
 
         let model_path = self.model_path();
-        let language = self.config.read().language.clone();
-        let translate = self.config.read().translate_to_english;
-
-        // Whisper 不支持真正的流式识别，需要累积音频后批量处理
-        let mut audio_buffer: Vec<i16> = Vec::new();
-
-        while let Some(chunk) = audio_rx.recv().await {
-            // PCM bytes -> i16 samples
-            let samples: Vec<i16> = chunk
-                .chunks_exact(2)
-                .map(|c| i16::from_le_bytes([c[0], c[1]]))
-                .collect();
-            audio_buffer.extend(samples);
-        }
-
-        if audio_buffer.is_empty() {
-            return Ok(());
+        let (language, translate, initial_prompt, partial_interval_ms, gpu_backend) = {
+            let config = self.config.read();
+            (
+                config.language.clone(),
+                config.translate_to_english,
+                config.initial_prompt.clone(),
+                config.partial_interval_ms,
+                config.gpu_backend,
+            )
+        };
+        let use_gpu = gpu_backend != WhisperGpuBackend::Cpu && gpu_backend.is_available();
+        if gpu_backend != WhisperGpuBackend::Cpu && !use_gpu {
+            log::warn!(
+                "{} backend not compiled in, falling back to CPU",
+                gpu_backend.display_name()
+            );
         }
 
-        // 转换为 f32 (whisper-rs 要求)
-        let audio_f32: Vec<f32> = audio_buffer
-            .iter()
-            .map(|&s| s as f32 / 32768.0)
-            .collect();
-
-        // 在阻塞线程中运行 Whisper
-        let result = tokio::task::spawn_blocking(move || {
-            // 加载模型
-            let params = WhisperContextParameters::default();
-            let ctx = WhisperContext::new_with_params(model_path.to_str().unwrap(), params)
-                .map_err(|e| AsrError::Transcription(format!("模型加载失败: {}", e)))?;
+        // 模型只加载一次，整个会话的各次推理共用
+        let ctx = Arc::new(load_context(model_path, use_gpu).await?);
 
-            let mut state = ctx
-                .create_state()
-                .map_err(|e| AsrError::Transcription(format!("创建状态失败: {}", e)))?;
+        #[cfg(feature = "sherpa")]
+        let mut vad = self.load_vad();
+        let mut window = SlidingWindow::default();
+        let streaming = partial_interval_ms > 0;
+        let mut ticker = tokio::time::interval(Duration::from_millis(partial_interval_ms.max(1)));
+        // 正在进行的推理，以及 VAD 分段推理时的分段长度（中间推理为 None）
+        let mut pending: Option<DecodeTask> = None;
+        let mut pending_segment: Option<usize> = None;
+        let mut last_partial = String::new();
 
-            // 配置识别参数
-            let mut full_params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
-
-            // 设置语言
-            if language != "auto" {
-                full_params.set_language(Some(&language));
-            }
-            full_params.set_translate(translate);
-            full_params.set_print_special(false);
-            full_params.set_print_progress(false);
-            full_params.set_print_realtime(false);
-            full_params.set_print_timestamps(false);
-
-            // 执行识别
-            state
-                .full(full_params, &audio_f32)
-                .map_err(|e| AsrError::Transcription(format!("识别失败: {}", e)))?;
-
-            // 收集所有片段
-            let num_segments = state.full_n_segments();
-
-            let mut full_text = String::new();
-            for i in 0..num_segments {
-                if let Some(segment) = state.get_segment(i) {
-                    if let Ok(text) = segment.to_str_lossy() {
-                        full_text.push_str(&text);
+        loop {
+            tokio::select! {
+                chunk = audio_rx.recv() => match chunk {
+                    Some(chunk) => {
+                        // PCM bytes -> f32 samples (whisper-rs 要求)
+                        let samples: Vec<f32> = chunk
+                            .chunks_exact(2)
+                            .map(|c| i16::from_le_bytes([c[0], c[1]]) as f32 / 32768.0)
+                            .collect();
+                        #[cfg(feature = "sherpa")]
+                        if let Some(ref mut vad) = vad {
+                            vad.accept_waveform(samples.clone());
+                            while !vad.is_empty() {
+                                let speech = vad.front();
+                                let end = speech.start as usize + speech.samples.len();
+                                window.pause_at = Some(end);
+                                vad.pop();
+                            }
+                        }
+                        window.audio.extend(samples);
+                    }
+                    None => break,
+                },
+                _ = ticker.tick(), if streaming && pending.is_none() && window.has_new_audio() => {
+                    let len = window.audio.len();
+                    let options = decode_options(&window, &language, translate, &initial_prompt);
+                    pending = Some(window.spawn_decode(&ctx, len, options));
+                }
+                decoded = async { pending.as_mut().unwrap().await }, if pending.is_some() => {
+                    pending = None;
+                    let segments = match decoded {
+                        Ok(segments) => segments?,
+                        Err(e) => {
+                            return Err(AsrError::Transcription(format!("任务执行失败: {}", e)))
+                        }
+                    };
+                    window.detect_language(&segments);
+                    // 先按本次推理时的窗口生成结果，再确认稳定片段
+                    let (text, words) = match pending_segment.take() {
+                        Some(len) => {
+                            window.commit_all(&segments, len);
+                            window.result(&[])
+                        }
+                        None => {
+                            let result = window.result(&segments);
+                            window.commit_stable(&segments);
+                            result
+                        }
+                    };
+                    if !text.is_empty() && text != last_partial {
+                        last_partial = text.clone();
+                        let partial = AsrResult {
+                            text,
+                            is_final: false,
+                            words,
+                            ..Default::default()
+                        };
+                        if result_tx.send(partial).await.is_err() {
+                            return Ok(());
+                        }
                     }
                 }
             }
 
-            Ok::<String, AsrError>(full_text.trim().to_string())
-        })
-        .await
-        .map_err(|e| AsrError::Transcription(format!("任务执行失败: {}", e)))??;
+            // VAD 检测到停顿：识别停顿前的音频并整体确认
+            if pending.is_none() {
+                if let Some(len) = window.segment_len() {
+                    let options = decode_options(&window, &language, translate, &initial_prompt);
+                    pending = Some(window.spawn_decode(&ctx, len, options));
+                    pending_segment = Some(len);
+                }
+            }
+        }
+
+        // 录音结束：丢弃尚未完成的中间推理，对剩余窗口做最终识别
+        if let Some(handle) = pending.take() {
+            let _ = handle.await;
+        }
+        if window.audio.is_empty() && window.committed_text.is_empty() {
+            return Ok(());
+        }
+        let segments = if window.audio.is_empty() {
+            Vec::new()
+        } else {
+            let options = decode_options(&window, &language, translate, &initial_prompt);
+            let audio = std::mem::take(&mut window.audio);
+            tokio::task::spawn_blocking(move || decode(&ctx, &audio, &options))
+                .await
+                .map_err(|e| AsrError::Transcription(format!("任务执行失败: {}", e)))??
+        };
 
         // 发送最终结果
+        window.detect_language(&segments);
+        let (text, words) = window.result(&segments);
+        let segments = window.segments(&segments);
         let _ = result_tx
             .send(AsrResult {
-                text: result,
+                text,
                 is_final: true,
+                segments,
+                words,
+                language: window.language.map(String::from),
+                ..Default::default()
             })
             .await;
 
@@ -300,13 +619,384 @@ impl AsrProvider for WhisperLocalProvider {
     }
 }
 
+/// 采样率
+const SAMPLE_RATE: usize = 16000;
+/// 基准测试音频时长（秒）
+const BENCHMARK_SAMPLE_SECS: usize = 10;
+/// 窗口超过该长度时确认前面的片段（whisper 单次最多处理 30 秒）
+const WINDOW_COMMIT_SAMPLES: usize = SAMPLE_RATE * 20;
+/// 新增音频不足该长度时不重新推理
+const MIN_NEW_SAMPLES: usize = SAMPLE_RATE / 2;
+/// 作为上下文提示的已确认文本长度（字符）
+const CONTEXT_PROMPT_CHARS: usize = 200;
+/// VAD 判定为停顿的最短静音时长（秒）
+#[cfg(feature = "sherpa")]
+const VAD_MIN_SILENCE_SECS: f32 = 0.5;
+/// VAD 内部缓冲的最长语音（秒）
+#[cfg(feature = "sherpa")]
+const VAD_BUFFER_SECS: f32 = 60.0;
+
+type DecodeTask = JoinHandle<Result<Vec<Segment>, AsrError>>;
+
+/// 单次推理参数
+struct DecodeOptions {
+    language: String,
+    translate: bool,
+    prompt: Option<String>,
+}
+
+/// 推理得到的片段，时间相对于窗口起点（秒）
+struct Segment {
+    text: String,
+    start: f32,
+    end: f32,
+    words: Vec<WordTiming>,
+    /// 自动检测出的语言（指定识别语言时为 None）
+    language: Option<&'static str>,
+}
+
+/// 滑动窗口状态：已确认的文本 + 尚未确认的音频
+#[derive(Default)]
+struct SlidingWindow {
+    /// 尚未确认的音频
+    audio: Vec<f32>,
+    /// 上次推理覆盖的样本数
+    decoded_len: usize,
+    /// 窗口起点在整段录音中的样本序号
+    start_sample: usize,
+    /// VAD 检测到的最近一次停顿在整段录音中的样本序号
+    pause_at: Option<usize>,
+    committed_text: String,
+    committed_words: Vec<WordTiming>,
+    committed_segments: Vec<AsrSegment>,
+    /// 最近一次推理检测出的语言
+    language: Option<&'static str>,
+}
+
+/// 按当前窗口构造推理参数
+fn decode_options(
+    window: &SlidingWindow,
+    language: &str,
+    translate: bool,
+    initial_prompt: &Option<String>,
+) -> DecodeOptions {
+    DecodeOptions {
+        language: language.to_string(),
+        translate,
+        prompt: window.prompt(initial_prompt.as_deref()),
+    }
+}
+
+impl SlidingWindow {
+    /// 在阻塞线程中识别窗口前 `len` 个样本
+    fn spawn_decode(
+        &mut self,
+        ctx: &Arc<WhisperContext>,
+        len: usize,
+        options: DecodeOptions,
+    ) -> DecodeTask {
+        let audio = self.audio[..len].to_vec();
+        let ctx = ctx.clone();
+        self.decoded_len = len;
+        tokio::task::spawn_blocking(move || decode(&ctx, &audio, &options))
+    }
+
+    /// 停顿前尚未确认的音频长度
+    fn segment_len(&self) -> Option<usize> {
+        self.pause_at
+            .map(|pause| pause.saturating_sub(self.start_sample).min(self.audio.len()))
+            .filter(|&len| len > 0)
+    }
+
+    fn offset(&self) -> f32 {
+        self.start_sample as f32 / SAMPLE_RATE as f32
+    }
+
+    fn has_new_audio(&self) -> bool {
+        self.audio.len() >= self.decoded_len + MIN_NEW_SAMPLES
+    }
+
+    /// 热词提示 + 已确认文本的末尾，引导新窗口延续上下文
+    fn prompt(&self, initial_prompt: Option<&str>) -> Option<String> {
+        let committed = self.committed_text.trim();
+        let skip = committed.chars().count().saturating_sub(CONTEXT_PROMPT_CHARS);
+        let context: String = committed.chars().skip(skip).collect();
+        match (initial_prompt, context.is_empty()) {
+            (Some(prompt), false) => Some(format!("{} {}", prompt, context)),
+            (Some(prompt), true) => Some(prompt.to_string()),
+            (None, false) => Some(context),
+            (None, true) => None,
+        }
+    }
+
+    /// 记录推理检测出的语言
+    fn detect_language(&mut self, segments: &[Segment]) {
+        if let Some(language) = segments.first().and_then(|s| s.language) {
+            self.language = Some(language);
+        }
+    }
+
+    /// 窗口过长时确认除最后一段以外的片段，并丢弃对应的音频
+    fn commit_stable(&mut self, segments: &[Segment]) {
+        if self.decoded_len < WINDOW_COMMIT_SAMPLES || segments.len() < 2 {
+            return;
+        }
+        let (stable, last) = segments.split_at(segments.len() - 1);
+        for segment in stable {
+            self.commit(segment);
+        }
+        let cut = ((last[0].start * SAMPLE_RATE as f32) as usize).min(self.audio.len());
+        self.advance(cut);
+    }
+
+    /// 确认 VAD 分段的全部识别结果，并丢弃这段音频
+    fn commit_all(&mut self, segments: &[Segment], len: usize) {
+        for segment in segments {
+            self.commit(segment);
+        }
+        self.advance(len);
+    }
+
+    fn commit(&mut self, segment: &Segment) {
+        self.committed_text.push_str(&segment.text);
+        self.committed_words
+            .extend(segment.words.iter().map(|w| self.shifted(w)));
+        if let Some(asr_segment) = self.asr_segment(segment) {
+            self.committed_segments.push(asr_segment);
+        }
+    }
+
+    /// 丢弃窗口开头 `len` 个已确认的样本
+    fn advance(&mut self, len: usize) {
+        let len = len.min(self.audio.len());
+        self.audio.drain(..len);
+        self.decoded_len = self.decoded_len.saturating_sub(len);
+        self.start_sample += len;
+        if self.pause_at.is_some_and(|pause| pause <= self.start_sample) {
+            self.pause_at = None;
+        }
+    }
+
+    /// 已确认部分加上当前窗口的识别结果
+    fn result(&self, segments: &[Segment]) -> (String, Vec<WordTiming>) {
+        let mut text = self.committed_text.clone();
+        let mut words = self.committed_words.clone();
+        for segment in segments {
+            text.push_str(&segment.text);
+            words.extend(segment.words.iter().map(|w| self.shifted(w)));
+        }
+        (text.trim().to_string(), words)
+    }
+
+    /// 已确认的分段加上当前窗口的分段（时间为整段录音的时间）
+    fn segments(&self, segments: &[Segment]) -> Vec<AsrSegment> {
+        let mut all = self.committed_segments.clone();
+        all.extend(segments.iter().filter_map(|s| self.asr_segment(s)));
+        all
+    }
+
+    /// 转换为整段录音时间的分段，空白片段返回 None
+    fn asr_segment(&self, segment: &Segment) -> Option<AsrSegment> {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        // 分段置信度取词置信度的平均值
+        let confidences: Vec<f32> = segment.words.iter().filter_map(|w| w.confidence).collect();
+        let confidence = (!confidences.is_empty())
+            .then(|| confidences.iter().sum::<f32>() / confidences.len() as f32);
+        Some(AsrSegment {
+            text: text.to_string(),
+            start: segment.start + self.offset(),
+            end: segment.end + self.offset(),
+            confidence,
+        })
+    }
+
+    /// 把窗口内的词时间换算为整段录音的时间
+    fn shifted(&self, word: &WordTiming) -> WordTiming {
+        WordTiming {
+            start: word.start + self.offset(),
+            end: word.end + self.offset(),
+            ..word.clone()
+        }
+    }
+}
+
+/// 内置的基准测试音频：合成的类语音信号（基频缓慢变化的谐波 + 音节状包络）
+///
+/// 只用于测量推理速度，不关心识别结果；每次生成的内容相同，便于对比不同模型。
+fn benchmark_sample() -> Vec<f32> {
+    use std::f32::consts::TAU;
+    (0..SAMPLE_RATE * BENCHMARK_SAMPLE_SECS)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let pitch = 140.0 + 40.0 * (t * 0.7 * TAU).sin();
+            // 每秒约 4 个音节
+            let envelope = (t * 2.0 * TAU).sin().abs();
+            let voice: f32 = (1..=6)
+                .map(|h| (t * pitch * h as f32 * TAU).sin() / h as f32)
+                .sum();
+            0.1 * envelope * voice
+        })
+        .collect()
+}
+
+/// 在阻塞线程中加载模型
+///
+/// 编译了 GPU 后端不代表运行时可用（缺少驱动、显存不足等），
+/// 启用 GPU 时先试着创建上下文和推理状态，失败则回退到 CPU。
+async fn load_context(model_path: PathBuf, use_gpu: bool) -> Result<WhisperContext, AsrError> {
+    tokio::task::spawn_blocking(move || {
+        let path = model_path.to_str().unwrap();
+        if use_gpu {
+            match create_context(path, true) {
+                Ok(ctx) => {
+                    log::info!("Whisper backend: GPU");
+                    return Ok(ctx);
+                }
+                Err(e) => log::warn!(
+                    "Whisper GPU backend unavailable, falling back to CPU: {}",
+                    e
+                ),
+            }
+        }
+        let ctx = create_context(path, false)?;
+        log::info!("Whisper backend: CPU");
+        Ok(ctx)
+    })
+    .await
+    .map_err(|e| AsrError::Transcription(format!("任务执行失败: {}", e)))?
+}
+
+/// 创建上下文并试建一次推理状态，确认所选后端可用
+fn create_context(path: &str, use_gpu: bool) -> Result<WhisperContext, AsrError> {
+    let mut params = WhisperContextParameters::default();
+    params.use_gpu(use_gpu);
+    let ctx = WhisperContext::new_with_params(path, params)
+        .map_err(|e| AsrError::Transcription(format!("模型加载失败: {}", e)))?;
+    ctx.create_state()
+        .map_err(|e| AsrError::Transcription(format!("创建状态失败: {}", e)))?;
+    Ok(ctx)
+}
+
+/// 对一段音频执行一次完整推理
+fn decode(
+    ctx: &WhisperContext,
+    audio: &[f32],
+    options: &DecodeOptions,
+) -> Result<Vec<Segment>, AsrError> {
+    let mut state = ctx
+        .create_state()
+        .map_err(|e| AsrError::Transcription(format!("创建状态失败: {}", e)))?;
+
+    // 配置识别参数
+    let mut full_params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+
+    // 设置语言
+    if options.language != "auto" {
+        full_params.set_language(Some(&options.language));
+    }
+    full_params.set_translate(options.translate);
+    // 热词和已确认文本作为初始提示，引导模型使用这些写法
+    if let Some(ref prompt) = options.prompt {
+        full_params.set_initial_prompt(prompt);
+    }
+    full_params.set_print_special(false);
+    full_params.set_print_progress(false);
+    full_params.set_print_realtime(false);
+    full_params.set_print_timestamps(false);
+    // 词级时间戳
+    full_params.set_token_timestamps(true);
+
+    // 执行识别
+    state
+        .full(full_params, audio)
+        .map_err(|e| AsrError::Transcription(format!("识别失败: {}", e)))?;
+
+    // 自动检测语言时读取检测结果
+    let language = if options.language == "auto" {
+        whisper_rs::get_lang_str(state.full_lang_id_from_state())
+    } else {
+        None
+    };
+
+    // 收集所有片段，时间戳单位为 10ms
+    let mut segments = Vec::new();
+    for i in 0..state.full_n_segments() {
+        let Some(segment) = state.get_segment(i) else {
+            continue;
+        };
+        let mut words = Vec::new();
+        for j in 0..segment.n_tokens() {
+            let Some(token) = segment.get_token(j) else {
+                continue;
+            };
+            let Ok(text) = token.to_str_lossy() else {
+                continue;
+            };
+            let data = token.token_data();
+            push_token(
+                &mut words,
+                &text,
+                data.t0 as f32 / 100.0,
+                data.t1 as f32 / 100.0,
+                data.p,
+            );
+        }
+        segments.push(Segment {
+            text: segment.to_str_lossy().map(|t| t.into_owned()).unwrap_or_default(),
+            start: segment.start_timestamp() as f32 / 100.0,
+            end: segment.end_timestamp() as f32 / 100.0,
+            words,
+            language,
+        });
+    }
+    Ok(segments)
+}
+
+/// 将 Whisper token 合并为词
+///
+/// token 是子词片段：以空格开头的 token 开始新词，否则接在上一个词后面；
+/// 中日韩等不以空格分词的文字每个 token 单独成词。特殊 token（如 `[_BEG_]`）被忽略。
+fn push_token(words: &mut Vec<WordTiming>, text: &str, start: f32, end: f32, probability: f32) {
+    if text.starts_with("[_") || text.starts_with("<|") || text.trim().is_empty() {
+        return;
+    }
+    let is_cjk = |c: char| !c.is_ascii();
+    let starts_word = text.starts_with(' ') || text.chars().next().is_some_and(is_cjk);
+    match words.last_mut() {
+        Some(last) if !starts_word && !last.text.chars().last().is_some_and(is_cjk) => {
+            last.text.push_str(text);
+            last.end = end;
+            last.confidence = last.confidence.map(|c| c.min(probability));
+        }
+        _ => words.push(WordTiming {
+            text: text.trim().to_string(),
+            start,
+            end,
+            confidence: Some(probability),
+        }),
+    }
+}
+
 #[async_trait]
 impl ModelDownloadable for WhisperLocalProvider {
     fn available_models(&self) -> Vec<ModelInfo> {
-        let current_model = self.config.read().model_size.clone();
+        let (current_model, custom_path, vad_segmentation) = {
+            let config = self.config.read();
+            (
+                config.model_size.clone(),
+                config.model_path.clone(),
+                config.vad_segmentation,
+            )
+        };
 
-        WhisperModelSize::all()
+        let mut models: Vec<ModelInfo> = WhisperModelSize::all()
             .into_iter()
+            .filter(|size| {
+                size.sha256().is_some() || self.is_model_file_downloaded(size.filename())
+            })
             .map(|size| {
                 let filename = size.filename();
                 ModelInfo {
@@ -314,10 +1004,31 @@ impl ModelDownloadable for WhisperLocalProvider {
                     name: size.display_name(),
                     size_bytes: size.size_bytes(),
                     is_downloaded: self.is_model_file_downloaded(filename),
-                    is_selected: size == current_model,
+                    is_selected: custom_path.is_none() && size == current_model,
                 }
             })
-            .collect()
+            .collect();
+        models.extend(self.imported_models().into_iter().map(|(filename, size)| {
+            let path = self.models_dir.join(&filename);
+            ModelInfo {
+                name: format!("{} ({} MB)", filename, size / 1_000_000),
+                is_selected: custom_path.as_deref() == Some(path.as_path()),
+                id: filename,
+                size_bytes: size,
+                is_downloaded: true,
+            }
+        }));
+        let vad_downloaded = self.is_model_file_downloaded(VAD_MODEL_FILENAME);
+        if VAD_MODEL_SHA256.is_some() || vad_downloaded {
+            models.push(ModelInfo {
+                id: VAD_MODEL_FILENAME.to_string(),
+                name: "Silero VAD (2 MB)".to_string(),
+                size_bytes: VAD_MODEL_SIZE_BYTES,
+                is_downloaded: vad_downloaded,
+                is_selected: vad_segmentation,
+            });
+        }
+        models
     }
 
     fn models_dir(&self) -> PathBuf {
@@ -329,10 +1040,13 @@ impl ModelDownloadable for WhisperLocalProvider {
         model_id: &str,
         progress_tx: mpsc::Sender<DownloadProgress>,
     ) -> Result<PathBuf, AsrError> {
-        let size = WhisperModelSize::from_filename(model_id)
-            .ok_or_else(|| AsrError::ModelNotFound(format!("未知模型: {}", model_id)))?;
-
-        let url = size.download_url();
+        let (url, sha256) = if model_id == VAD_MODEL_FILENAME {
+            (VAD_MODEL_URL.to_string(), VAD_MODEL_SHA256)
+        } else {
+            let model = WhisperModelSize::from_filename(model_id)
+                .ok_or_else(|| AsrError::ModelNotFound(format!("未知模型: {}", model_id)))?;
+            (model.download_url(), model.sha256())
+        };
         let dest_path = self.models_dir.join(model_id);
         let temp_path = dest_path.with_extension("tmp");
 
@@ -349,6 +1063,7 @@ impl ModelDownloadable for WhisperLocalProvider {
             &temp_path,
             &dest_path,
             model_id,
+            sha256,
             progress_tx,
             cancel_flag,
         )
@@ -0,0 +1,216 @@
+//! whisper.cpp server Provider
+//!
+//! 将录音上传到自建的 whisper.cpp `server` 推理接口（`POST /inference`），
+//! 便于局域网内多台设备共用一台 GPU 服务器。松开快捷键后一次性上传整段音频。
+
+use async_trait::async_trait;
+use reqwest::multipart;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::whisper_api::pcm_to_wav;
+use crate::asr::provider::{AsrError, AsrProvider, AsrResult, AsrSegment, ProviderStatus};
+
+/// 输入音频采样率
+const SAMPLE_RATE: u32 = 16000;
+
+/// whisper.cpp server 配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WhisperServerConfig {
+    /// 服务地址（如 http://192.168.1.10:8080）
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    /// 请求超时（秒）
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 识别语言（可选，为空时由服务端自动检测）
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+fn default_base_url() -> String {
+    "http://127.0.0.1:8080".to_string()
+}
+
+fn default_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for WhisperServerConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_base_url(),
+            timeout_secs: default_timeout_secs(),
+            language: None,
+        }
+    }
+}
+
+impl WhisperServerConfig {
+    pub fn is_configured(&self) -> bool {
+        self.base_url.starts_with("http://") || self.base_url.starts_with("https://")
+    }
+}
+
+/// verbose_json 响应中的分段
+#[derive(Deserialize)]
+struct ServerSegment {
+    #[serde(default)]
+    start: f32,
+    #[serde(default)]
+    end: f32,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    avg_logprob: Option<f32>,
+}
+
+/// 推理响应
+#[derive(Deserialize)]
+struct InferenceResponse {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    segments: Vec<ServerSegment>,
+    /// 服务端出错时返回
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// whisper.cpp server Provider
+pub struct WhisperServerProvider {
+    config: WhisperServerConfig,
+    client: reqwest::Client,
+}
+
+impl WhisperServerProvider {
+    pub fn new(config: WhisperServerConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl AsrProvider for WhisperServerProvider {
+    fn id(&self) -> &str {
+        "whisper_server"
+    }
+
+    fn display_name(&self) -> &str {
+        "whisper.cpp 服务器"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if !self.config.is_configured() {
+            ProviderStatus::NeedsConfiguration
+        } else {
+            ProviderStatus::Ready
+        }
+    }
+
+    fn result_timeout(&self, _audio_secs: f32) -> Duration {
+        Duration::from_secs(self.config.timeout_secs)
+    }
+
+    fn validate(&self) -> Result<(), AsrError> {
+        if !self.config.is_configured() {
+            return Err(AsrError::Configuration(
+                "服务地址必须以 http:// 或 https:// 开头".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn transcribe_stream(
+        &self,
+        mut audio_rx: mpsc::Receiver<Vec<u8>>,
+        result_tx: mpsc::Sender<AsrResult>,
+    ) -> Result<(), AsrError> {
+        self.validate()?;
+
+        // 累积所有音频数据
+        let mut audio_buffer = Vec::new();
+        while let Some(chunk) = audio_rx.recv().await {
+            audio_buffer.extend(chunk);
+        }
+
+        if audio_buffer.is_empty() {
+            return Ok(());
+        }
+
+        let wav_data = pcm_to_wav(&audio_buffer, SAMPLE_RATE, 1, 16);
+        let file_part = multipart::Part::bytes(wav_data)
+            .file_name("audio.wav")
+            .mime_str("audio/wav")
+            .map_err(|e| AsrError::Transcription(e.to_string()))?;
+
+        let mut form = multipart::Form::new()
+            .part("file", file_part)
+            .text("temperature", "0.0")
+            .text("response_format", "verbose_json");
+        if let Some(ref lang) = self.config.language {
+            form = form.text("language", lang.clone());
+        }
+
+        let url = format!("{}/inference", self.config.base_url.trim_end_matches('/'));
+        log::info!(
+            "Uploading {} bytes to whisper.cpp server {}",
+            audio_buffer.len(),
+            url
+        );
+
+        let response = self
+            .client
+            .post(&url)
+            .timeout(Duration::from_secs(self.config.timeout_secs))
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| AsrError::Connection(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AsrError::Transcription(format!(
+                "服务器请求失败 ({}): {}",
+                status, error_text
+            )));
+        }
+
+        let result: InferenceResponse = response
+            .json()
+            .await
+            .map_err(|e| AsrError::Transcription(format!("解析响应失败: {}", e)))?;
+        if let Some(error) = result.error {
+            return Err(AsrError::Transcription(error));
+        }
+
+        let segments: Vec<AsrSegment> = result
+            .segments
+            .into_iter()
+            .map(|s| AsrSegment {
+                text: s.text.trim().to_string(),
+                start: s.start,
+                end: s.end,
+                confidence: s.avg_logprob.map(|lp| lp.exp().clamp(0.0, 1.0)),
+            })
+            .collect();
+        let confidence = AsrSegment::overall_confidence(&segments);
+
+        let _ = result_tx
+            .send(AsrResult {
+                text: result.text.trim().to_string(),
+                is_final: true,
+                segments,
+                confidence,
+                ..Default::default()
+            })
+            .await;
+
+        log::info!("whisper.cpp server session completed");
+        Ok(())
+    }
+}
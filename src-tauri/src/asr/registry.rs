@@ -0,0 +1,255 @@
+//! ASR Provider 注册表
+//!
+//! 按 id 从应用配置构建 `Box<dyn AsrProvider>`，统一注入语言、热词和脏话过滤等全局设置。
+//! 新增 Provider 只需在这里注册，录音、基准测试和重试等流程无需改动。
+//!
+//! 识别语言可以按 Provider 单独覆盖，并在这里转换为各服务要求的格式
+//! （如 Azure、AWS 需要 "zh-CN" 这样带地区的代码）。
+
+use std::sync::LazyLock;
+
+use crate::asr::providers::{
+    region_locale, AlibabaNlsProvider, AwsTranscribeProvider, AzureSpeechProvider, BaiduProvider,
+    CustomWsProvider, DeepgramProvider, DoubaoProvider, IflytekProvider, MockProvider,
+    OpenAiRealtimeProvider, SonioxProvider, SpeachesProvider, TencentAsrProvider,
+    WhisperApiProvider, WhisperLocalProvider, WhisperServerProvider,
+};
+#[cfg(feature = "sherpa")]
+use crate::asr::providers::{FunAsrLocalProvider, MoonshineProvider, SenseVoiceProvider};
+#[cfg(feature = "vosk")]
+use crate::asr::providers::VoskProvider;
+use crate::asr::AsrProvider;
+use crate::state::AppConfig;
+
+/// 从应用配置构建 Provider
+type Factory = fn(&AppConfig) -> Box<dyn AsrProvider>;
+
+/// 注册的 Provider
+struct ProviderEntry {
+    id: &'static str,
+    /// 未就绪时提示用户的操作
+    setup_hint: &'static str,
+    factory: Factory,
+}
+
+static REGISTRY: LazyLock<ProviderRegistry> = LazyLock::new(ProviderRegistry::new);
+
+/// ASR Provider 注册表（按注册顺序在界面中展示）
+pub struct ProviderRegistry {
+    entries: Vec<ProviderEntry>,
+}
+
+impl ProviderRegistry {
+    /// 全局注册表
+    pub fn global() -> &'static ProviderRegistry {
+        &REGISTRY
+    }
+
+    fn new() -> Self {
+        let mut registry = Self {
+            entries: Vec::new(),
+        };
+
+        registry.register(
+            "doubao",
+            "请先配置豆包 App ID 和 Access Token",
+            |config| {
+                let mut doubao_config = config.asr.doubao.clone().unwrap_or_default();
+                doubao_config.hotwords = config.hotword_list();
+                Box::new(DoubaoProvider::new(doubao_config))
+            },
+        );
+        registry.register("whisper_local", "请先下载 Whisper 模型", |config| {
+            let mut whisper_config = config.asr.whisper_local.clone().unwrap_or_default();
+            whisper_config.language = config.language_for("whisper_local").to_string();
+            whisper_config.initial_prompt = config.hotword_prompt();
+            Box::new(WhisperLocalProvider::new(whisper_config))
+        });
+        registry.register("whisper_api", "请先配置 Whisper API Key", |config| {
+            let mut api_config = config.asr.whisper_api.clone().unwrap_or_default();
+            api_config.language = language(config, "whisper_api");
+            api_config.prompt = config.hotword_prompt();
+            Box::new(WhisperApiProvider::new(api_config))
+        });
+        registry.register("openai_realtime", "请先配置 OpenAI API Key", |config| {
+            let mut realtime_config = config.asr.openai_realtime.clone().unwrap_or_default();
+            realtime_config.language = language(config, "openai_realtime");
+            Box::new(OpenAiRealtimeProvider::new(realtime_config))
+        });
+        registry.register(
+            "azure",
+            "请先配置 Azure 订阅密钥和区域",
+            |config| {
+                let mut azure_config = config.asr.azure.clone().unwrap_or_default();
+                azure_config.language = region_language(config, "azure");
+                azure_config.profanity_filter = config.profanity_filter.provider_mode();
+                Box::new(AzureSpeechProvider::new(azure_config))
+            },
+        );
+        registry.register(
+            "aws_transcribe",
+            "请先配置 AWS 区域和访问凭证",
+            |config| {
+                let mut aws_config = config.asr.aws_transcribe.clone().unwrap_or_default();
+                aws_config.language = region_language(config, "aws_transcribe");
+                Box::new(AwsTranscribeProvider::new(aws_config))
+            },
+        );
+        registry.register("deepgram", "请先配置 Deepgram API Key", |config| {
+            let mut deepgram_config = config.asr.deepgram.clone().unwrap_or_default();
+            deepgram_config.language = language(config, "deepgram");
+            deepgram_config.profanity_filter = config.profanity_filter.provider_mode();
+            Box::new(DeepgramProvider::new(deepgram_config))
+        });
+        #[cfg(feature = "vosk")]
+        registry.register("vosk", "请先下载 Vosk 模型", |config| {
+            Box::new(VoskProvider::new(
+                config.asr.vosk.clone().unwrap_or_default(),
+            ))
+        });
+        #[cfg(feature = "sherpa")]
+        registry.register("funasr_local", "请先下载 Paraformer 模型", |config| {
+            Box::new(FunAsrLocalProvider::new(
+                config.asr.funasr_local.clone().unwrap_or_default(),
+            ))
+        });
+        #[cfg(feature = "sherpa")]
+        registry.register("sense_voice", "请先下载 SenseVoice 模型", |config| {
+            let mut sense_voice_config = config.asr.sense_voice.clone().unwrap_or_default();
+            sense_voice_config.language = language(config, "sense_voice");
+            Box::new(SenseVoiceProvider::new(sense_voice_config))
+        });
+        #[cfg(feature = "sherpa")]
+        registry.register("moonshine", "请先下载 Moonshine 模型", |config| {
+            Box::new(MoonshineProvider::new(
+                config.asr.moonshine.clone().unwrap_or_default(),
+            ))
+        });
+        registry.register(
+            "iflytek",
+            "请先配置讯飞 APPID、APIKey 和 APISecret",
+            |config| {
+                let mut iflytek_config = config.asr.iflytek.clone().unwrap_or_default();
+                iflytek_config.language = language(config, "iflytek");
+                Box::new(IflytekProvider::new(iflytek_config))
+            },
+        );
+        registry.register(
+            "tencent",
+            "请先配置腾讯云 AppID、SecretId 和 SecretKey",
+            |config| {
+                let mut tencent_config = config.asr.tencent.clone().unwrap_or_default();
+                tencent_config.language = language(config, "tencent");
+                tencent_config.profanity_filter = config.profanity_filter.provider_mode();
+                Box::new(TencentAsrProvider::new(tencent_config))
+            },
+        );
+        // 识别语言由 appkey 绑定的模型决定
+        registry.register(
+            "alibaba",
+            "请先配置阿里云 Appkey 和 Token",
+            |config| {
+                Box::new(AlibabaNlsProvider::new(
+                    config.asr.alibaba.clone().unwrap_or_default(),
+                ))
+            },
+        );
+        registry.register(
+            "custom_ws",
+            "请先配置自定义 WebSocket 连接地址",
+            |config| {
+                let mut custom_ws_config = config.asr.custom_ws.clone().unwrap_or_default();
+                custom_ws_config.language = language(config, "custom_ws");
+                Box::new(CustomWsProvider::new(custom_ws_config))
+            },
+        );
+        registry.register(
+            "whisper_server",
+            "请先配置 whisper.cpp 服务器地址",
+            |config| {
+                let mut whisper_server_config =
+                    config.asr.whisper_server.clone().unwrap_or_default();
+                whisper_server_config.language = language(config, "whisper_server");
+                Box::new(WhisperServerProvider::new(whisper_server_config))
+            },
+        );
+        registry.register(
+            "speaches",
+            "请先配置 speaches 服务地址和模型",
+            |config| {
+                let mut speaches_config = config.asr.speaches.clone().unwrap_or_default();
+                speaches_config.language = language(config, "speaches");
+                Box::new(SpeachesProvider::new(speaches_config))
+            },
+        );
+        registry.register("soniox", "请先配置 Soniox API Key", |config| {
+            let mut soniox_config = config.asr.soniox.clone().unwrap_or_default();
+            soniox_config.language = language(config, "soniox");
+            Box::new(SonioxProvider::new(soniox_config))
+        });
+        registry.register(
+            "baidu",
+            "请先配置百度语音 API Key 和 Secret Key",
+            |config| {
+                let mut baidu_config = config.asr.baidu.clone().unwrap_or_default();
+                baidu_config.language = language(config, "baidu");
+                Box::new(BaiduProvider::new(baidu_config))
+            },
+        );
+        registry.register("mock", "演示模式不可用", |config| {
+            Box::new(MockProvider::new(
+                config.asr.mock.clone().unwrap_or_default(),
+            ))
+        });
+
+        registry
+    }
+
+    fn register(&mut self, id: &'static str, setup_hint: &'static str, factory: Factory) {
+        self.entries.push(ProviderEntry {
+            id,
+            setup_hint,
+            factory,
+        });
+    }
+
+    /// 所有已注册的 Provider id
+    pub fn ids(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|entry| entry.id)
+    }
+
+    /// 按当前配置构建指定 Provider，未知 id 返回 None
+    pub fn build(&self, config: &AppConfig, id: &str) -> Option<Box<dyn AsrProvider>> {
+        self.entry(id).map(|entry| (entry.factory)(config))
+    }
+
+    /// 按当前配置构建所有 Provider
+    pub fn build_all<'a>(
+        &'a self,
+        config: &'a AppConfig,
+    ) -> impl Iterator<Item = Box<dyn AsrProvider>> + 'a {
+        self.entries
+            .iter()
+            .map(move |entry| (entry.factory)(config))
+    }
+
+    /// Provider 未就绪时给用户的提示
+    pub fn setup_hint(&self, id: &str) -> Option<&'static str> {
+        self.entry(id).map(|entry| entry.setup_hint)
+    }
+
+    fn entry(&self, id: &str) -> Option<&ProviderEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+}
+
+/// Provider 的识别语言，"auto" 表示交给 Provider 自动检测
+fn language(config: &AppConfig, provider_id: &str) -> Option<String> {
+    let language = config.language_for(provider_id);
+    (language != "auto").then(|| language.to_string())
+}
+
+/// 带地区的识别语言（如 "zh" -> "zh-CN"），用于要求完整 locale 的云服务
+fn region_language(config: &AppConfig, provider_id: &str) -> Option<String> {
+    language(config, provider_id).map(|language| region_locale(&language).to_string())
+}
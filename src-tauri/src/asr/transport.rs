@@ -0,0 +1,485 @@
+//! Seed 协议的帧传输层
+//!
+//! `AsrClient` 只关心二进制帧的收发，具体由 [`Transport`] 实现：
+//! - [`WebSocketTransport`]：实际的 WebSocket 连接
+//! - [`RecordingTransport`]：包装其他传输层，把双向帧写入文件，便于排查协议问题
+//! - [`ReplayTransport`]：从录制文件回放服务端帧，无需网络和凭据即可复现一次会话
+//!
+//! 录制文件格式：每帧为 `方向(1 字节) + 长度(4 字节大端) + 帧内容`，
+//! 方向 0 表示客户端发出，1 表示服务端返回。
+
+use async_trait::async_trait;
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{http::Request, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+type WsStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+const DIRECTION_CLIENT: u8 = 0;
+const DIRECTION_SERVER: u8 = 1;
+
+/// 帧发送端
+#[async_trait]
+pub trait FrameSink: Send {
+    async fn send(&mut self, frame: Vec<u8>) -> Result<(), TransportError>;
+
+    async fn close(&mut self) -> Result<(), TransportError>;
+}
+
+/// 帧接收端，连接关闭时返回 None
+#[async_trait]
+pub trait FrameSource: Send {
+    async fn next(&mut self) -> Option<Result<Vec<u8>, TransportError>>;
+}
+
+/// 已建立的连接（发送端与接收端可分别移入不同任务）
+pub struct Connection {
+    pub sink: Box<dyn FrameSink>,
+    pub source: Box<dyn FrameSource>,
+}
+
+/// 传输层：根据握手请求建立连接
+#[async_trait]
+pub trait Transport: Send + Sync {
+    async fn connect(&self, request: Request<()>) -> Result<Connection, TransportError>;
+}
+
+/// WebSocket 传输层
+pub struct WebSocketTransport;
+
+struct WebSocketSink(SplitSink<WsStream, Message>);
+
+struct WebSocketSource(SplitStream<WsStream>);
+
+#[async_trait]
+impl Transport for WebSocketTransport {
+    async fn connect(&self, request: Request<()>) -> Result<Connection, TransportError> {
+        let (ws_stream, _response) = connect_async(request).await?;
+        let (write, read) = ws_stream.split();
+        Ok(Connection {
+            sink: Box::new(WebSocketSink(write)),
+            source: Box::new(WebSocketSource(read)),
+        })
+    }
+}
+
+#[async_trait]
+impl FrameSink for WebSocketSink {
+    async fn send(&mut self, frame: Vec<u8>) -> Result<(), TransportError> {
+        self.0.send(Message::Binary(frame)).await?;
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<(), TransportError> {
+        self.0.close().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FrameSource for WebSocketSource {
+    async fn next(&mut self) -> Option<Result<Vec<u8>, TransportError>> {
+        loop {
+            match self.0.next().await? {
+                Ok(Message::Binary(data)) => return Some(Ok(data)),
+                Ok(Message::Close(_)) => {
+                    log::info!("WebSocket connection closed");
+                    return None;
+                }
+                Ok(_) => continue,
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+    }
+}
+
+/// 录制传输层：透传所有帧，同时写入录制文件
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    path: PathBuf,
+}
+
+impl RecordingTransport {
+    pub fn new(inner: Arc<dyn Transport>, path: PathBuf) -> Self {
+        Self { inner, path }
+    }
+}
+
+type Recorder = Arc<Mutex<File>>;
+
+fn record(recorder: &Recorder, direction: u8, frame: &[u8]) {
+    let mut file = recorder.lock();
+    let result = file
+        .write_all(&[direction])
+        .and_then(|_| file.write_all(&(frame.len() as u32).to_be_bytes()))
+        .and_then(|_| file.write_all(frame));
+    if let Err(e) = result {
+        log::warn!("Failed to record ASR frame: {}", e);
+    }
+}
+
+struct RecordingSink {
+    inner: Box<dyn FrameSink>,
+    recorder: Recorder,
+}
+
+struct RecordingSource {
+    inner: Box<dyn FrameSource>,
+    recorder: Recorder,
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn connect(&self, request: Request<()>) -> Result<Connection, TransportError> {
+        let connection = self.inner.connect(request).await?;
+        let recorder = Arc::new(Mutex::new(File::create(&self.path)?));
+        log::info!("Recording ASR frames to {:?}", self.path);
+        Ok(Connection {
+            sink: Box::new(RecordingSink {
+                inner: connection.sink,
+                recorder: recorder.clone(),
+            }),
+            source: Box::new(RecordingSource {
+                inner: connection.source,
+                recorder,
+            }),
+        })
+    }
+}
+
+#[async_trait]
+impl FrameSink for RecordingSink {
+    async fn send(&mut self, frame: Vec<u8>) -> Result<(), TransportError> {
+        record(&self.recorder, DIRECTION_CLIENT, &frame);
+        self.inner.send(frame).await
+    }
+
+    async fn close(&mut self) -> Result<(), TransportError> {
+        self.inner.close().await
+    }
+}
+
+#[async_trait]
+impl FrameSource for RecordingSource {
+    async fn next(&mut self) -> Option<Result<Vec<u8>, TransportError>> {
+        let frame = self.inner.next().await;
+        if let Some(Ok(ref data)) = frame {
+            record(&self.recorder, DIRECTION_SERVER, data);
+        }
+        frame
+    }
+}
+
+/// 回放传输层：忽略客户端发出的帧，按顺序返回录制的服务端帧
+///
+/// 客户端发出与录制时相同的最后一帧（结束标记）后才关闭连接，
+/// 避免服务端帧提前放完被当作断线重连。
+pub struct ReplayTransport {
+    path: PathBuf,
+}
+
+impl ReplayTransport {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+/// 解析录制文件，返回 (方向, 帧) 列表
+fn read_recording(data: &[u8]) -> Result<Vec<(u8, Vec<u8>)>, TransportError> {
+    let mut frames = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let header = data
+            .get(pos..pos + 5)
+            .ok_or("Truncated frame header in recording")?;
+        let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        let frame = data
+            .get(pos + 5..pos + 5 + len)
+            .ok_or("Truncated frame in recording")?;
+        frames.push((header[0], frame.to_vec()));
+        pos += 5 + len;
+    }
+    Ok(frames)
+}
+
+struct ReplaySink {
+    /// 录制时客户端发出的最后一帧
+    last_client_frame: Option<Vec<u8>>,
+    finished: watch::Sender<bool>,
+}
+
+struct ReplaySource {
+    frames: VecDeque<Vec<u8>>,
+    finished: watch::Receiver<bool>,
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn connect(&self, _request: Request<()>) -> Result<Connection, TransportError> {
+        let data = fs::read(&self.path)?;
+        let (client_frames, server_frames): (Vec<_>, Vec<_>) = read_recording(&data)?
+            .into_iter()
+            .partition(|(direction, _)| *direction == DIRECTION_CLIENT);
+        let frames: VecDeque<Vec<u8>> = server_frames.into_iter().map(|(_, frame)| frame).collect();
+        log::info!(
+            "Replaying {} ASR frames from {:?}",
+            frames.len(),
+            self.path
+        );
+        let last_client_frame = client_frames.into_iter().last().map(|(_, frame)| frame);
+        let (finished_tx, finished_rx) = watch::channel(last_client_frame.is_none());
+        Ok(Connection {
+            sink: Box::new(ReplaySink {
+                last_client_frame,
+                finished: finished_tx,
+            }),
+            source: Box::new(ReplaySource {
+                frames,
+                finished: finished_rx,
+            }),
+        })
+    }
+}
+
+#[async_trait]
+impl FrameSink for ReplaySink {
+    async fn send(&mut self, frame: Vec<u8>) -> Result<(), TransportError> {
+        if self.last_client_frame.as_ref() == Some(&frame) {
+            self.finished.send_replace(true);
+        }
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<(), TransportError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FrameSource for ReplaySource {
+    async fn next(&mut self) -> Option<Result<Vec<u8>, TransportError>> {
+        if let Some(frame) = self.frames.pop_front() {
+            return Some(Ok(frame));
+        }
+        // 发送端已释放时 wait_for 返回错误，同样视为连接关闭
+        let _ = self.finished.wait_for(|finished| *finished).await;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asr::client::{AsrClient, AsrResult};
+    use flate2::read::GzDecoder;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Read;
+    use tokio::sync::mpsc;
+
+    /// 结束标记帧的第二个字节（仅音频消息 + 最后一包标志）
+    const FINISH_FLAGS: u8 = 0x22;
+
+    /// 内存中的模拟服务端：收到结束标记后依次返回响应帧，然后关闭连接
+    struct MockTransport {
+        responses: Vec<Vec<u8>>,
+        received: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    struct MockSink {
+        received: Arc<Mutex<Vec<Vec<u8>>>>,
+        /// 发往接收端的通道，发完响应后释放
+        server: Option<mpsc::UnboundedSender<Vec<u8>>>,
+        responses: Vec<Vec<u8>>,
+    }
+
+    struct MockSource(mpsc::UnboundedReceiver<Vec<u8>>);
+
+    impl MockTransport {
+        fn new(responses: Vec<Vec<u8>>) -> Self {
+            Self {
+                responses,
+                received: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Transport for MockTransport {
+        async fn connect(&self, _request: Request<()>) -> Result<Connection, TransportError> {
+            let (tx, rx) = mpsc::unbounded_channel();
+            Ok(Connection {
+                sink: Box::new(MockSink {
+                    received: self.received.clone(),
+                    server: Some(tx),
+                    responses: self.responses.clone(),
+                }),
+                source: Box::new(MockSource(rx)),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl FrameSink for MockSink {
+        async fn send(&mut self, frame: Vec<u8>) -> Result<(), TransportError> {
+            let finished = frame.get(1) == Some(&FINISH_FLAGS);
+            self.received.lock().push(frame);
+            if finished {
+                // 发送者在此处释放，接收端随后返回 None
+                if let Some(tx) = self.server.take() {
+                    for response in self.responses.drain(..) {
+                        tx.send(response)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<(), TransportError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl FrameSource for MockSource {
+        async fn next(&mut self) -> Option<Result<Vec<u8>, TransportError>> {
+            self.0.recv().await.map(Ok)
+        }
+    }
+
+    /// 构造服务端的识别结果帧（消息类型 0x09，带序号）
+    fn server_response(text: &str, gzip: bool) -> Vec<u8> {
+        let json = serde_json::json!({ "result": { "text": text } }).to_string();
+        let payload = if gzip {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            std::io::Write::write_all(&mut encoder, json.as_bytes()).unwrap();
+            encoder.finish().unwrap()
+        } else {
+            json.into_bytes()
+        };
+        let mut frame = vec![0x11, 0x91, 0x10 | gzip as u8, 0x00];
+        frame.extend_from_slice(&1u32.to_be_bytes());
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        frame
+    }
+
+    fn responses() -> Vec<Vec<u8>> {
+        vec![
+            server_response("你好", false),
+            server_response("你好世界", true),
+        ]
+    }
+
+    fn audio_chunks() -> Vec<Vec<u8>> {
+        vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]]
+    }
+
+    /// 通过指定传输层完成一次会话，返回收到的识别结果
+    async fn stream(transport: Arc<dyn Transport>) -> Vec<AsrResult> {
+        let client = AsrClient::new("app".to_string(), "token".to_string(), String::new())
+            .with_transport(transport);
+        let (audio_tx, audio_rx) = mpsc::channel(16);
+        let (result_tx, mut result_rx) = mpsc::channel(16);
+        for chunk in audio_chunks() {
+            audio_tx.send(chunk).await.unwrap();
+        }
+        drop(audio_tx);
+
+        client
+            .connect_and_stream(audio_rx, result_tx)
+            .await
+            .unwrap();
+
+        let mut results = Vec::new();
+        while let Some(result) = result_rx.recv().await {
+            results.push(result);
+        }
+        results
+    }
+
+    fn texts(results: &[AsrResult]) -> Vec<&str> {
+        results.iter().map(|result| result.text.as_str()).collect()
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("speaky-{}-{}.bin", name, uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn client_streams_through_transport() {
+        let transport = Arc::new(MockTransport::new(responses()));
+        let results = stream(transport.clone()).await;
+        assert_eq!(texts(&results), ["你好", "你好世界"]);
+
+        let received = transport.received.lock().clone();
+        assert_eq!(received.len(), 4);
+
+        // 初始化消息：完整客户端请求，gzip 压缩的 JSON 配置
+        let init = &received[0];
+        assert_eq!(init[1] >> 4, 0x01);
+        assert_eq!(init[2], 0x11);
+        let len = u32::from_be_bytes([init[4], init[5], init[6], init[7]]) as usize;
+        assert_eq!(init.len(), 8 + len);
+        let mut json = String::new();
+        GzDecoder::new(&init[8..])
+            .read_to_string(&mut json)
+            .unwrap();
+        let config: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(config["audio"]["rate"], 16000);
+        assert_eq!(config["request"]["model_name"], "bigmodel");
+
+        // 音频消息原样携带数据，最后是结束标记
+        for (frame, chunk) in received[1..3].iter().zip(audio_chunks()) {
+            assert_eq!(frame[1], 0x20);
+            assert_eq!(frame[8..], chunk[..]);
+        }
+        assert_eq!(received[3][1], FINISH_FLAGS);
+    }
+
+    #[tokio::test]
+    async fn recording_replays_the_same_session() {
+        let path = temp_path("recording");
+        let mock = Arc::new(MockTransport::new(responses()));
+        let recording = Arc::new(RecordingTransport::new(mock.clone(), path.clone()));
+        let recorded = stream(recording).await;
+
+        let frames = read_recording(&fs::read(&path).unwrap()).unwrap();
+        let client_frames: Vec<_> = frames
+            .iter()
+            .filter(|(direction, _)| *direction == DIRECTION_CLIENT)
+            .map(|(_, frame)| frame.clone())
+            .collect();
+        let server_frames: Vec<_> = frames
+            .iter()
+            .filter(|(direction, _)| *direction == DIRECTION_SERVER)
+            .map(|(_, frame)| frame.clone())
+            .collect();
+        assert_eq!(client_frames, *mock.received.lock());
+        assert_eq!(server_frames, responses());
+
+        let replayed = stream(Arc::new(ReplayTransport::new(path.clone()))).await;
+        let _ = fs::remove_file(&path);
+        assert_eq!(texts(&replayed), texts(&recorded));
+    }
+
+    #[test]
+    fn truncated_recording_is_rejected() {
+        assert!(read_recording(&[DIRECTION_SERVER, 0, 0, 0, 4, 1, 2]).is_err());
+        assert!(read_recording(&[DIRECTION_CLIENT, 0, 0]).is_err());
+        assert!(read_recording(&[]).unwrap().is_empty());
+    }
+}
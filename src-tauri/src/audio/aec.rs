@@ -0,0 +1,331 @@
+//! 回声消除（AEC）
+//!
+//! 使用 NLMS 自适应滤波器，以系统输出（loopback / monitor）作为参考信号，
+//! 从麦克风信号中减去扬声器泄漏的声音，避免会议音频或提示音被识别进听写内容。
+//!
+//! 参考流与麦克风流由不同设备采集，两者之间存在不确定的延迟（缓冲、声学路径）。
+//! 滤波前先用包络互相关估计该延迟并对齐参考信号，滤波器只需覆盖对齐后的回声拖尾。
+//! 处理开销较大，由采集线程之外的处理线程调用，不在 cpal 的实时回调中运行。
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// 自适应滤波器长度（16kHz 下约 64ms，对齐延迟后的回声拖尾）
+const FILTER_LEN: usize = 1024;
+/// NLMS 步长
+const STEP_SIZE: f32 = 0.3;
+/// 防止除零的正则项
+const REGULARIZATION: f32 = 1e-3;
+/// Geigel 双讲检测阈值（近端幅度超过参考峰值的该比例时视为双讲）
+const DOUBLE_TALK_THRESHOLD: f32 = 0.5;
+/// 检测到双讲后冻结自适应的样本数（约 30ms）
+const DOUBLE_TALK_HANGOVER: u32 = 480;
+/// 参考缓冲区最多保留的样本数（1 秒），防止两路时钟漂移导致无限增长
+const MAX_REFERENCE_SAMPLES: usize = 16000;
+
+/// 可估计的最大延迟（500ms）
+const MAX_DELAY: usize = 8000;
+/// 包络的块长度（16kHz 下每块 1ms）
+const ENVELOPE_BLOCK: usize = 16;
+/// 互相关窗口长度（包络点数，约 1 秒）
+const ENVELOPE_WINDOW: usize = 1000;
+/// 最大延迟对应的包络点数
+const MAX_LAG: usize = MAX_DELAY / ENVELOPE_BLOCK;
+/// 每隔多少包络点重新估计一次延迟（约 1 秒）
+const ESTIMATE_INTERVAL: usize = 1000;
+/// 互相关系数低于该值时认为没有可靠的回声，不更新延迟
+const MIN_CORRELATION: f32 = 0.4;
+/// 对齐时给滤波器留出的提前量，估计略有偏差时回声仍落在滤波器窗口内
+const DELAY_MARGIN: usize = FILTER_LEN / 8;
+/// 参考历史长度（延迟线 + 滤波器窗口）
+const HISTORY_LEN: usize = MAX_DELAY + FILTER_LEN;
+
+/// 参考信号缓冲区（由参考流写入，麦克风流读取）
+pub type ReferenceBuffer = Arc<Mutex<VecDeque<i16>>>;
+
+/// 创建空的参考信号缓冲区
+pub fn reference_buffer() -> ReferenceBuffer {
+    Arc::new(Mutex::new(VecDeque::with_capacity(MAX_REFERENCE_SAMPLES)))
+}
+
+/// 写入参考信号，超出上限时丢弃最旧的样本
+pub fn push_reference(buffer: &ReferenceBuffer, samples: &[i16]) {
+    let mut buffer = buffer.lock();
+    buffer.extend(samples.iter().copied());
+    let overflow = buffer.len().saturating_sub(MAX_REFERENCE_SAMPLES);
+    if overflow > 0 {
+        buffer.drain(..overflow);
+    }
+}
+
+/// 取出最多 `len` 个参考样本（不足时返回较短的结果）
+pub fn take_reference(buffer: &ReferenceBuffer, len: usize) -> Vec<i16> {
+    let mut buffer = buffer.lock();
+    let available = buffer.len().min(len);
+    buffer.drain(..available).collect()
+}
+
+/// 参考信号相对麦克风信号的延迟估计
+///
+/// 两路信号各自按块取平均幅度得到包络，在包络上做归一化互相关，
+/// 比在原始样本上搜索 8000 个延迟的开销小两个数量级。
+struct DelayEstimator {
+    mic_env: VecDeque<f32>,
+    ref_env: VecDeque<f32>,
+    mic_acc: f32,
+    ref_acc: f32,
+    block_pos: usize,
+    since_estimate: usize,
+}
+
+impl DelayEstimator {
+    fn new() -> Self {
+        Self {
+            mic_env: VecDeque::with_capacity(ENVELOPE_WINDOW),
+            ref_env: VecDeque::with_capacity(ENVELOPE_WINDOW + MAX_LAG),
+            mic_acc: 0.0,
+            ref_acc: 0.0,
+            block_pos: 0,
+            since_estimate: 0,
+        }
+    }
+
+    /// 写入一对样本，估计出新的延迟（样本数）时返回
+    fn push(&mut self, mic: f32, reference: f32) -> Option<usize> {
+        self.mic_acc += mic.abs();
+        self.ref_acc += reference.abs();
+        self.block_pos += 1;
+        if self.block_pos < ENVELOPE_BLOCK {
+            return None;
+        }
+
+        if self.mic_env.len() == ENVELOPE_WINDOW {
+            self.mic_env.pop_front();
+        }
+        if self.ref_env.len() == ENVELOPE_WINDOW + MAX_LAG {
+            self.ref_env.pop_front();
+        }
+        self.mic_env.push_back(self.mic_acc);
+        self.ref_env.push_back(self.ref_acc);
+        self.mic_acc = 0.0;
+        self.ref_acc = 0.0;
+        self.block_pos = 0;
+
+        self.since_estimate += 1;
+        if self.since_estimate < ESTIMATE_INTERVAL || self.ref_env.len() < ENVELOPE_WINDOW + MAX_LAG
+        {
+            return None;
+        }
+        self.since_estimate = 0;
+        self.estimate()
+    }
+
+    /// 在 0..=MAX_LAG 范围内找互相关系数最大的延迟
+    fn estimate(&self) -> Option<usize> {
+        let mic: Vec<f32> = self.mic_env.iter().copied().collect();
+        let reference: Vec<f32> = self.ref_env.iter().copied().collect();
+        let (mic, mic_norm) = centered(&mic);
+        if mic_norm == 0.0 {
+            return None;
+        }
+
+        // mic_env[i] 与 ref_env 中早 lag 个包络点的值对应
+        let (lag, correlation) = (0..=MAX_LAG)
+            .filter_map(|lag| {
+                let start = MAX_LAG - lag;
+                let (window, norm) = centered(&reference[start..start + ENVELOPE_WINDOW]);
+                if norm == 0.0 {
+                    return None;
+                }
+                let dot: f32 = mic.iter().zip(&window).map(|(a, b)| a * b).sum();
+                Some((lag, dot / (mic_norm * norm)))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+        (correlation >= MIN_CORRELATION).then_some(lag * ENVELOPE_BLOCK)
+    }
+}
+
+/// 去均值并返回 L2 范数
+fn centered(values: &[f32]) -> (Vec<f32>, f32) {
+    let mean = values.iter().sum::<f32>() / values.len() as f32;
+    let centered: Vec<f32> = values.iter().map(|v| v - mean).collect();
+    let norm = centered.iter().map(|v| v * v).sum::<f32>().sqrt();
+    (centered, norm)
+}
+
+/// NLMS 回声消除器
+pub struct EchoCanceller {
+    weights: Vec<f32>,
+    /// 双倍长度的环形历史，使 `history[pos..pos + HISTORY_LEN]` 始终连续（最新样本在前）
+    history: Vec<f32>,
+    pos: usize,
+    /// 参考信号相对麦克风的延迟（样本数），滤波器窗口从该位置开始
+    delay: usize,
+    estimator: DelayEstimator,
+    /// 参考信号在滤波器窗口内的能量
+    power: f32,
+    /// 参考信号的衰减峰值（用于双讲检测）
+    reference_peak: f32,
+    hangover: u32,
+}
+
+impl EchoCanceller {
+    pub fn new() -> Self {
+        Self {
+            weights: vec![0.0; FILTER_LEN],
+            history: vec![0.0; HISTORY_LEN * 2],
+            pos: 0,
+            delay: 0,
+            estimator: DelayEstimator::new(),
+            power: 0.0,
+            reference_peak: 0.0,
+            hangover: 0,
+        }
+    }
+
+    /// 从参考缓冲区取出与麦克风等长的样本并处理（不足部分以静音补齐）
+    pub fn process_with_buffer(&mut self, mic: &mut [i16], buffer: &ReferenceBuffer) {
+        let reference = take_reference(buffer, mic.len());
+        self.process(mic, &reference);
+    }
+
+    /// 原地消除麦克风信号中的回声
+    ///
+    /// `reference` 比 `mic` 短时，缺失部分视为静音。
+    pub fn process(&mut self, mic: &mut [i16], reference: &[i16]) {
+        for (i, sample) in mic.iter_mut().enumerate() {
+            let input = reference.get(i).copied().unwrap_or(0) as f32 / 32768.0;
+            let d = *sample as f32 / 32768.0;
+
+            if let Some(delay) = self.estimator.push(d, input) {
+                self.set_delay(delay.saturating_sub(DELAY_MARGIN));
+            }
+
+            // 写入新参考样本，滤波器窗口为延迟之后的 FILTER_LEN 个样本，增量维护窗口能量
+            self.pos = (self.pos + HISTORY_LEN - 1) % HISTORY_LEN;
+            self.history[self.pos] = input;
+            self.history[self.pos + HISTORY_LEN] = input;
+            let start = self.pos + self.delay;
+            let x = self.history[start];
+            let leaving = self.history[start + FILTER_LEN];
+            self.power = (self.power + x * x - leaving * leaving).max(0.0);
+
+            let window = &self.history[start..start + FILTER_LEN];
+            let estimate: f32 = self
+                .weights
+                .iter()
+                .zip(window)
+                .map(|(w, x)| w * x)
+                .sum();
+            let error = d - estimate;
+
+            // Geigel 双讲检测：近端说话时冻结自适应，避免滤波器发散
+            self.reference_peak = x.abs().max(self.reference_peak * 0.9995);
+            if d.abs() > DOUBLE_TALK_THRESHOLD * self.reference_peak {
+                self.hangover = DOUBLE_TALK_HANGOVER;
+            } else if self.hangover > 0 {
+                self.hangover -= 1;
+            }
+
+            if self.hangover == 0 && self.power > 0.0 {
+                let step = STEP_SIZE * error / (self.power + REGULARIZATION);
+                for (w, x) in self.weights.iter_mut().zip(window) {
+                    *w += step * x;
+                }
+            }
+
+            *sample = (error * 32768.0).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        }
+    }
+
+    /// 切换对齐延迟；变化超过一个包络块时旧权重已不对应新的窗口，重新收敛
+    fn set_delay(&mut self, delay: usize) {
+        if delay.abs_diff(self.delay) <= ENVELOPE_BLOCK {
+            return;
+        }
+        log::debug!("Echo reference delay: {} ms", delay * 1000 / 16000);
+        self.delay = delay;
+        self.weights.fill(0.0);
+        // 按新延迟重新计算当前窗口的能量，之后继续增量维护
+        let start = self.pos + delay;
+        self.power = self.history[start..start + FILTER_LEN]
+            .iter()
+            .map(|x| x * x)
+            .sum();
+    }
+}
+
+impl Default for EchoCanceller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 伪随机噪声（线性同余），幅度在 ±amplitude 内
+    fn noise(len: usize, amplitude: f32, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 8) as f32 / (1 << 24) as f32 * 2.0 - 1.0
+            })
+            .map(|v| v * amplitude)
+            .collect()
+    }
+
+    /// 按 50ms 随机开关调制噪声，让包络有不重复的起伏
+    fn bursts(len: usize, seed: u32) -> Vec<i16> {
+        let gates = noise(len / 800 + 1, 1.0, seed + 100);
+        noise(len, 8000.0, seed)
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| if gates[i / 800] > 0.0 { v as i16 } else { 0 })
+            .collect()
+    }
+
+    fn energy(samples: &[i16]) -> f64 {
+        samples.iter().map(|&s| (s as f64).powi(2)).sum()
+    }
+
+    #[test]
+    fn estimates_reference_delay() {
+        let delay = 3200; // 200ms
+        let reference = bursts(16000 * 4, 1);
+        let mut estimator = DelayEstimator::new();
+        let mut estimated = None;
+        for i in 0..reference.len() {
+            let mic = if i >= delay { reference[i - delay] } else { 0 };
+            if let Some(d) = estimator.push(mic as f32, reference[i] as f32) {
+                estimated = Some(d);
+            }
+        }
+        let estimated = estimated.expect("delay should be estimated");
+        assert!(estimated.abs_diff(delay) <= ENVELOPE_BLOCK, "{}", estimated);
+    }
+
+    #[test]
+    fn cancels_delayed_echo() {
+        // 250ms 超过滤波器长度，必须对齐后才能消除；回声衰减 12dB
+        let delay = 4000;
+        let reference = bursts(16000 * 8, 2);
+        let mic: Vec<i16> = (0..reference.len())
+            .map(|i| if i >= delay { reference[i - delay] / 4 } else { 0 })
+            .collect();
+
+        let mut canceller = EchoCanceller::new();
+        let mut output = mic.clone();
+        for (out, reference) in output.chunks_mut(160).zip(reference.chunks(160)) {
+            canceller.process(out, reference);
+        }
+
+        let tail = mic.len() - 16000;
+        let residual = energy(&output[tail..]) / energy(&mic[tail..]);
+        assert!(residual < 0.01, "residual echo ratio {}", residual);
+    }
+}
@@ -1,17 +1,56 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use serde::Serialize;
+use cpal::SampleFormat;
+use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 
+use super::aec::{self, EchoCanceller, ReferenceBuffer};
+use super::denoise::NoiseSuppressor;
+use super::preroll::{self, PrerollBuffer};
+use super::dsp::{self, HighPassFilter, InputConverter, StartTrim};
+
+/// ASR 要求的采样率（16kHz 单声道 16-bit PCM）
+pub(super) const TARGET_SAMPLE_RATE: u32 = 16000;
+
 /// 音频设备信息
 #[derive(Debug, Clone, Serialize)]
 pub struct AudioDevice {
+    /// 稳定的设备 ID（不随系统语言和重新插拔变化），空字符串表示系统默认设备
+    pub id: String,
     pub name: String,
     pub is_default: bool,
 }
 
+/// 设备的稳定 ID；后端不提供 ID 时以设备名称作为指纹
+fn device_id(device: &cpal::Device) -> Option<String> {
+    match device.id() {
+        Ok(id) => Some(id.to_string()),
+        Err(_) => device
+            .description()
+            .ok()
+            .map(|desc| format!("name:{}", desc.name())),
+    }
+}
+
+/// 按设备 ID 查找输入设备，兼容旧配置中保存的设备名称
+pub(super) fn find_input_device(host: &cpal::Host, selector: &str) -> Option<cpal::Device> {
+    let devices: Vec<cpal::Device> = host.input_devices().ok()?.collect();
+    let by_id = devices
+        .iter()
+        .position(|d| device_id(d).as_deref() == Some(selector));
+    let index = by_id.or_else(|| {
+        devices.iter().position(|d| {
+            d.description()
+                .ok()
+                .map(|desc| desc.name() == selector)
+                .unwrap_or(false)
+        })
+    })?;
+    devices.into_iter().nth(index)
+}
+
 /// 获取所有可用的输入设备列表
 pub fn list_audio_devices() -> Vec<AudioDevice> {
     let host = cpal::default_host();
@@ -23,16 +62,17 @@ pub fn list_audio_devices() -> Vec<AudioDevice> {
 
     // 添加 "系统默认" 选项
     devices.push(AudioDevice {
+        id: String::new(),
         name: String::new(),
         is_default: true,
     });
 
     if let Ok(input_devices) = host.input_devices() {
         for device in input_devices {
-            if let Ok(desc) = device.description() {
+            if let (Ok(desc), Some(id)) = (device.description(), device_id(&device)) {
                 let name = desc.name().to_string();
                 let is_default = default_device_name.as_ref() == Some(&name);
-                devices.push(AudioDevice { name, is_default });
+                devices.push(AudioDevice { id, name, is_default });
             }
         }
     }
@@ -40,13 +80,93 @@ pub fn list_audio_devices() -> Vec<AudioDevice> {
     devices
 }
 
+/// 双音源（麦克风 + 系统音频）混音配置，用于采访、会议转写
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DualSourceConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 系统音频设备（空字符串表示自动选择系统输出回环）
+    #[serde(default)]
+    pub system_device: String,
+    /// 麦克风混音增益
+    #[serde(default = "default_mix_gain")]
+    pub mic_gain: f32,
+    /// 系统音频混音增益
+    #[serde(default = "default_mix_gain")]
+    pub system_gain: f32,
+}
+
+fn default_mix_gain() -> f32 {
+    1.0
+}
+
+impl Default for DualSourceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            system_device: String::new(),
+            mic_gain: default_mix_gain(),
+            system_gain: default_mix_gain(),
+        }
+    }
+}
+
+/// 实际使用的输入设备
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveInputDevice {
+    pub id: String,
+    pub name: String,
+    /// 所选设备未找到，已回退到系统默认设备
+    pub fallback: bool,
+}
+
+/// 采集开始时通知实际使用的输入设备
+type DeviceListener = Arc<dyn Fn(&ActiveInputDevice) + Send + Sync>;
+
+/// 采集参数，由控制器的 `with_*` 方法设置，开始录音时整体交给采集线程
+#[derive(Clone)]
+struct CaptureOptions {
+    device_name: String,
+    /// 回声消除参考设备（None 表示不启用回声消除，空字符串表示自动选择）
+    echo_reference: Option<String>,
+    /// 麦克风软件增益倍数
+    input_gain: f32,
+    /// 高通滤波截止频率（Hz），None 表示不滤波
+    high_pass_hz: Option<u32>,
+    /// 是否启用 RNNoise 降噪
+    noise_suppression: bool,
+    /// 与系统音频混音（None 表示只录麦克风）
+    dual_source: Option<DualSourceConfig>,
+    /// 录音开头丢弃的时长和淡入时长（毫秒）
+    start_trim: (u32, u32),
+    /// 开始录音前预缓冲的音频，拼接到录音开头
+    preroll: Option<PrerollBuffer>,
+    device_listener: Option<DeviceListener>,
+}
+
+impl Default for CaptureOptions {
+    fn default() -> Self {
+        Self {
+            device_name: String::new(),
+            echo_reference: None,
+            input_gain: 1.0,
+            high_pass_hz: None,
+            noise_suppression: false,
+            dual_source: None,
+            start_trim: (0, 0),
+            preroll: None,
+            device_listener: None,
+        }
+    }
+}
+
 /// 音频采集控制器
 /// 使用独立线程管理 cpal::Stream，避免跨线程发送问题
 pub struct AudioCaptureController {
     is_recording: Arc<AtomicBool>,
     stop_signal: Arc<AtomicBool>,
     thread_handle: Option<JoinHandle<()>>,
-    device_name: String,
+    options: CaptureOptions,
 }
 
 impl AudioCaptureController {
@@ -55,20 +175,72 @@ impl AudioCaptureController {
             is_recording: Arc::new(AtomicBool::new(false)),
             stop_signal: Arc::new(AtomicBool::new(false)),
             thread_handle: None,
-            device_name: String::new(),
+            options: CaptureOptions::default(),
         }
     }
 
     /// 创建一个指定设备的控制器
     pub fn with_device(device_name: String) -> Self {
         Self {
-            is_recording: Arc::new(AtomicBool::new(false)),
-            stop_signal: Arc::new(AtomicBool::new(false)),
-            thread_handle: None,
-            device_name,
+            options: CaptureOptions {
+                device_name,
+                ..Default::default()
+            },
+            ..Self::new()
         }
     }
 
+    /// 启用回声消除，使用指定设备（空字符串表示自动选择系统输出回环）作为参考信号
+    pub fn with_echo_reference(mut self, reference_device: String) -> Self {
+        self.options.echo_reference = Some(reference_device);
+        self
+    }
+
+    /// 对麦克风信号施加软件增益（带削波保护），用于放大音量过低的麦克风
+    pub fn with_input_gain(mut self, gain: f32) -> Self {
+        self.options.input_gain = gain;
+        self
+    }
+
+    /// 启用高通滤波，去除截止频率以下的低频噪声（桌面敲击、空调声）
+    pub fn with_high_pass(mut self, cutoff_hz: u32) -> Self {
+        self.options.high_pass_hz = Some(cutoff_hz);
+        self
+    }
+
+    /// 启用 RNNoise 降噪，抑制风扇声等背景噪声
+    pub fn with_noise_suppression(mut self) -> Self {
+        self.options.noise_suppression = true;
+        self
+    }
+
+    /// 丢弃录音开头的 `skip_ms` 毫秒并在之后 `fade_ms` 毫秒内淡入，去除按键声
+    pub fn with_start_trim(mut self, skip_ms: u32, fade_ms: u32) -> Self {
+        self.options.start_trim = (skip_ms, fade_ms);
+        self
+    }
+
+    /// 把预缓冲的音频拼接到录音开头，避免采集流启动期间丢失第一个字
+    pub fn with_preroll(mut self, buffer: PrerollBuffer) -> Self {
+        self.options.preroll = Some(buffer);
+        self
+    }
+
+    /// 采集开始时回调实际使用的输入设备（所选设备不可用时为回退的默认设备）
+    pub fn with_device_listener(
+        mut self,
+        listener: impl Fn(&ActiveInputDevice) + Send + Sync + 'static,
+    ) -> Self {
+        self.options.device_listener = Some(Arc::new(listener));
+        self
+    }
+
+    /// 同时采集系统音频并与麦克风混音
+    pub fn with_dual_source(mut self, dual_source: DualSourceConfig) -> Self {
+        self.options.dual_source = Some(dual_source);
+        self
+    }
+
     pub fn start_recording(&mut self, audio_sender: Sender<Vec<i16>>) -> Result<(), String> {
         if self.is_recording.load(Ordering::SeqCst) {
             return Err("Already recording".to_string());
@@ -76,7 +248,7 @@ impl AudioCaptureController {
 
         let is_recording = self.is_recording.clone();
         let stop_signal = self.stop_signal.clone();
-        let device_name = self.device_name.clone();
+        let options = self.options.clone();
 
         // 重置停止信号
         stop_signal.store(false, Ordering::SeqCst);
@@ -84,7 +256,7 @@ impl AudioCaptureController {
 
         // 在独立线程中运行音频采集
         let handle = thread::spawn(move || {
-            if let Err(e) = run_audio_capture(audio_sender, stop_signal.clone(), device_name) {
+            if let Err(e) = run_audio_capture(audio_sender, stop_signal.clone(), options) {
                 log::error!("Audio capture error: {}", e);
             }
             is_recording.store(false, Ordering::SeqCst);
@@ -102,53 +274,329 @@ impl Default for AudioCaptureController {
     }
 }
 
+/// 查找系统音频回环设备（回声消除参考 / 双音源混音）
+///
+/// 指定名称时在输入/输出设备中按名称查找；否则 Windows 使用默认输出设备（WASAPI loopback），
+/// 其他平台查找 PulseAudio/PipeWire 的 monitor 输入设备。
+fn find_reference_device(host: &cpal::Host, name: &str) -> Option<cpal::Device> {
+    let matches_name = |d: &cpal::Device| {
+        d.description()
+            .ok()
+            .map(|desc| desc.name() == name)
+            .unwrap_or(false)
+    };
+
+    if !name.is_empty() {
+        return host
+            .input_devices()
+            .ok()
+            .and_then(|mut devices| devices.find(|d| matches_name(d)))
+            .or_else(|| {
+                host.output_devices()
+                    .ok()
+                    .and_then(|mut devices| devices.find(|d| matches_name(d)))
+            });
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        host.default_output_device()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        host.input_devices().ok().and_then(|mut devices| {
+            devices.find(|d| {
+                d.description()
+                    .ok()
+                    .map(|desc| desc.name().to_lowercase().contains("monitor"))
+                    .unwrap_or(false)
+            })
+        })
+    }
+}
+
+/// 启动系统音频回环流，样本同时写入所有缓冲区；失败时返回 None（继续只录麦克风）
+fn start_loopback_stream(
+    host: &cpal::Host,
+    loopback_device: &str,
+    buffers: Vec<ReferenceBuffer>,
+) -> Option<cpal::Stream> {
+    let Some(device) = find_reference_device(host, loopback_device) else {
+        log::warn!("System audio loopback device not found");
+        return None;
+    };
+
+    let name = device.description().map(|d| d.name().to_string()).unwrap_or_default();
+    let stream = negotiate_config(&device)
+        .and_then(|supported| {
+            build_converted_stream(
+                &device,
+                &supported,
+                move |data| {
+                    for buffer in &buffers {
+                        aec::push_reference(buffer, &data);
+                    }
+                },
+                "Loopback stream error",
+            )
+        })
+        .and_then(|stream| stream.play().map(|_| stream).map_err(|e| e.to_string()));
+
+    match stream {
+        Ok(stream) => {
+            log::info!("System audio loopback opened: {}", name);
+            Some(stream)
+        }
+        Err(e) => {
+            log::warn!("Failed to open loopback stream '{}': {}", name, e);
+            None
+        }
+    }
+}
+
+/// 选择设备的采集配置：优先使用 16kHz（声道越少越好），设备不支持时使用其默认配置，
+/// 采集后再混为单声道并重采样
+pub(super) fn negotiate_config(device: &cpal::Device) -> Result<cpal::SupportedStreamConfig, String> {
+    let ranges: Vec<_> = device
+        .supported_input_configs()
+        .map(|ranges| ranges.collect())
+        .unwrap_or_default();
+    let native = ranges
+        .into_iter()
+        .filter(|range| {
+            matches!(
+                range.sample_format(),
+                SampleFormat::I16
+                    | SampleFormat::F32
+                    | SampleFormat::U16
+                    | SampleFormat::I32
+                    | SampleFormat::F64
+            )
+        })
+        .filter(|range| {
+            range.min_sample_rate() <= TARGET_SAMPLE_RATE
+                && TARGET_SAMPLE_RATE <= range.max_sample_rate()
+        })
+        .min_by_key(|range| (range.channels(), range.sample_format() != SampleFormat::I16));
+    if let Some(range) = native {
+        return Ok(range.with_sample_rate(TARGET_SAMPLE_RATE));
+    }
+
+    // 回环设备在 Windows 上是输出设备，没有输入配置
+    device
+        .default_input_config()
+        .or_else(|_| device.default_output_config())
+        .map_err(|e| format!("Failed to get device config: {}", e))
+}
+
+/// 按设备原生格式建立输入流，样本统一转换为 16kHz 单声道 i16 后交给回调
+///
+/// 部分驱动只提供 f32、u16 或 i32 格式，直接以 i16 建流会失败，需要先转换样本格式。
+/// 多声道设备（如只提供立体声或多路输入的声卡）在转换时混为单声道。
+pub(super) fn build_converted_stream(
+    device: &cpal::Device,
+    supported: &cpal::SupportedStreamConfig,
+    mut on_samples: impl FnMut(Vec<i16>) + Send + 'static,
+    error_label: &'static str,
+) -> Result<cpal::Stream, String> {
+    let config = supported.config();
+    log::info!(
+        "Input stream: {} channel(s), {} Hz, {:?}",
+        config.channels,
+        config.sample_rate,
+        supported.sample_format()
+    );
+    let mut converter =
+        InputConverter::new(config.channels, config.sample_rate, TARGET_SAMPLE_RATE);
+    let on_data = move |samples: &[i16]| on_samples(converter.process(samples));
+    let on_error = move |err| log::error!("{}: {}", error_label, err);
+    let stream = match supported.sample_format() {
+        SampleFormat::I16 => build_typed_stream(device, &config, |s: i16| s, on_data, on_error),
+        SampleFormat::F32 => build_typed_stream(device, &config, f32_to_i16, on_data, on_error),
+        SampleFormat::U16 => build_typed_stream(device, &config, u16_to_i16, on_data, on_error),
+        SampleFormat::I32 => build_typed_stream(device, &config, i32_to_i16, on_data, on_error),
+        SampleFormat::F64 => build_typed_stream(device, &config, f64_to_i16, on_data, on_error),
+        other => return Err(format!("Unsupported sample format: {:?}", other)),
+    };
+    stream.map_err(|e| format!("Failed to build input stream: {}", e))
+}
+
+/// 以设备的样本类型建流，逐块转换为 i16
+fn build_typed_stream<T: cpal::SizedSample>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    convert: fn(T) -> i16,
+    mut on_data: impl FnMut(&[i16]) + Send + 'static,
+    on_error: impl FnMut(cpal::StreamError) + Send + 'static,
+) -> Result<cpal::Stream, cpal::BuildStreamError> {
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            let samples: Vec<i16> = data.iter().map(|&s| convert(s)).collect();
+            on_data(&samples);
+        },
+        on_error,
+        None,
+    )
+}
+
+fn f32_to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
+
+fn f64_to_i16(sample: f64) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f64) as i16
+}
+
+/// 取 i32 的高 16 位
+fn i32_to_i16(sample: i32) -> i16 {
+    (sample >> 16) as i16
+}
+
+/// u16 以 32768 为零点
+fn u16_to_i16(sample: u16) -> i16 {
+    (sample as i32 - 32768) as i16
+}
+
+/// 把系统音频按增益混入麦克风样本（系统音频不足部分视为静音）
+fn mix_into(mic: &mut [i16], system: &[i16], mic_gain: f32, system_gain: f32) {
+    for (i, sample) in mic.iter_mut().enumerate() {
+        let system = system.get(i).copied().unwrap_or(0) as f32;
+        let mixed = *sample as f32 * mic_gain + system * system_gain;
+        *sample = mixed.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    }
+}
+
 /// 在当前线程运行音频采集
 fn run_audio_capture(
     audio_sender: Sender<Vec<i16>>,
     stop_signal: Arc<AtomicBool>,
-    device_name: String,
+    options: CaptureOptions,
 ) -> Result<(), String> {
+    let CaptureOptions {
+        device_name,
+        echo_reference,
+        input_gain,
+        high_pass_hz,
+        noise_suppression,
+        dual_source,
+        start_trim,
+        mut preroll,
+        device_listener,
+    } = options;
     let host = cpal::default_host();
 
-    // 根据设备名称选择设备
-    let device = if device_name.is_empty() {
-        host.default_input_device()
-            .ok_or("No input device available")?
+    // 根据设备 ID 选择设备，所选设备被拔出时回退到系统默认设备
+    let selected = if device_name.is_empty() {
+        None
     } else {
-        host.input_devices()
-            .map_err(|e| format!("Failed to enumerate devices: {}", e))?
-            .find(|d| d.description().ok().map(|desc| desc.name() == device_name).unwrap_or(false))
-            .ok_or_else(|| format!("Device '{}' not found", device_name))?
+        find_input_device(&host, &device_name)
+    };
+    let fallback = !device_name.is_empty() && selected.is_none();
+    if fallback {
+        log::warn!("Input device '{}' not found, falling back to default input", device_name);
+    }
+    let device = match selected {
+        Some(device) => device,
+        None => host.default_input_device().ok_or("No input device available")?,
     };
 
     let device_name_str = device.description().map(|d| d.name().to_string()).unwrap_or_default();
     log::info!("Using input device: {}", device_name_str);
+    if let Some(listener) = device_listener {
+        listener(&ActiveInputDevice {
+            id: if fallback { String::new() } else { device_name.clone() },
+            name: device_name_str.clone(),
+            fallback,
+        });
+    }
 
-    // 豆包 ASR 要求: 16kHz, 单声道, 16-bit PCM
-    let config = cpal::StreamConfig {
-        channels: 1,
-        sample_rate: 16000,
-        buffer_size: cpal::BufferSize::Default,
-    };
+    // ASR 要求 16kHz 单声道 16-bit PCM；设备不支持时按原生配置采集再转换
+    let supported = negotiate_config(&device)?;
+    log::info!(
+        "Input config: {} Hz, {} channel(s), {:?}",
+        supported.sample_rate(),
+        supported.channels(),
+        supported.sample_format()
+    );
 
     let stop = stop_signal.clone();
 
-    // 使用预分配缓冲区的发送策略，减少每帧的内存分配
-    let stream = device
-        .build_input_stream(
-            &config,
-            move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                if !stop.load(Ordering::Relaxed) {
-                    // 预分配恰好大小的 Vec，避免过度分配
-                    let mut buffer = Vec::with_capacity(data.len());
-                    buffer.extend_from_slice(data);
-                    let _ = audio_sender.send(buffer);
-                }
-            },
-            |err| log::error!("Audio stream error: {}", err),
-            None,
-        )
-        .map_err(|e| format!("Failed to build input stream: {}", e))?;
+    // 系统音频回环：用作回声消除参考和/或双音源混音，流需要在整个录音期间保持存活
+    let reference_buffer = aec::reference_buffer();
+    let system_buffer = aec::reference_buffer();
+    let mut loopback_buffers = Vec::new();
+    if echo_reference.is_some() {
+        loopback_buffers.push(reference_buffer.clone());
+    }
+    if dual_source.is_some() {
+        loopback_buffers.push(system_buffer.clone());
+    }
+    // 双音源优先使用其指定的设备
+    let loopback_device = dual_source
+        .as_ref()
+        .map(|d| d.system_device.clone())
+        .or_else(|| echo_reference.clone());
+    let loopback_stream = loopback_device.as_deref().and_then(|loopback_device| {
+        start_loopback_stream(&host, loopback_device, loopback_buffers)
+    });
+    let mut echo_canceller = loopback_stream
+        .as_ref()
+        .filter(|_| echo_reference.is_some())
+        .map(|_| EchoCanceller::new());
+    let mixer = loopback_stream.as_ref().and(dual_source);
+    let mut high_pass =
+        high_pass_hz.map(|cutoff| HighPassFilter::new(TARGET_SAMPLE_RATE, cutoff as f32));
+    let mut denoiser = noise_suppression.then(|| NoiseSuppressor::new(TARGET_SAMPLE_RATE));
+    let mut trim = StartTrim::new(TARGET_SAMPLE_RATE, start_trim.0, start_trim.1);
+
+    // 回声消除、降噪较耗时，实时回调只转发样本，由处理线程完成后续处理。
+    // 转换后的样本块直接作为发送缓冲区，减少每帧的内存分配
+    let (raw_tx, raw_rx) = std::sync::mpsc::channel::<Vec<i16>>();
+    let processor = thread::spawn(move || {
+        for mut buffer in raw_rx {
+            if let Some(canceller) = echo_canceller.as_mut() {
+                canceller.process_with_buffer(&mut buffer, &reference_buffer);
+            }
+            // 裁剪只作用于本次打开麦克风后的样本，预缓冲的音频不受影响
+            trim.process(&mut buffer);
+            // 第一块数据到达时拼接预缓冲的音频（回声消除的参考信号不覆盖这段音频）
+            if let Some(preroll) = preroll.take() {
+                let mut samples = preroll::take(&preroll);
+                log::debug!("Prepending {} pre-roll samples", samples.len());
+                samples.append(&mut buffer);
+                buffer = samples;
+            }
+            if buffer.is_empty() {
+                continue;
+            }
+            dsp::apply_gain(&mut buffer, input_gain);
+            if let Some(filter) = high_pass.as_mut() {
+                filter.process(&mut buffer);
+            }
+            // 降噪只作用于麦克风信号，需在混入系统音频之前进行
+            if let Some(denoiser) = denoiser.as_mut() {
+                buffer = denoiser.process(&buffer);
+            }
+            if let Some(ref mix) = mixer {
+                let system = aec::take_reference(&system_buffer, buffer.len());
+                mix_into(&mut buffer, &system, mix.mic_gain, mix.system_gain);
+            }
+            let _ = audio_sender.send(buffer);
+        }
+    });
+
+    let stream = build_converted_stream(
+        &device,
+        &supported,
+        move |buffer| {
+            if !stop.load(Ordering::Relaxed) {
+                let _ = raw_tx.send(buffer);
+            }
+        },
+        "Audio stream error",
+    )?;
 
     stream
         .play()
@@ -159,5 +607,8 @@ fn run_audio_capture(
         thread::sleep(std::time::Duration::from_millis(50));
     }
 
+    // 关闭流后发送端随回调一起释放，等待处理线程处理完剩余样本
+    drop(stream);
+    let _ = processor.join();
     Ok(())
 }
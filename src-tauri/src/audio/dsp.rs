@@ -0,0 +1,356 @@
+//! 音频信号处理工具
+//!
+//! 在采集线程与 ASR 通道之间对 16-bit PCM 样本做轻量处理。
+
+/// 对样本施加增益（带削波保护）
+///
+/// 增益为 1.0 时直接返回，不做任何处理。
+pub fn apply_gain(samples: &mut [i16], gain: f32) {
+    if (gain - 1.0).abs() < f32::EPSILON {
+        return;
+    }
+
+    for sample in samples.iter_mut() {
+        let amplified = *sample as f32 * gain;
+        // 饱和截断，避免整数溢出产生爆音
+        *sample = amplified.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    }
+}
+
+/// 分贝转换为增益倍数
+pub fn db_to_gain(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// 降采样前低通滤波的截止频率（相对目标采样率，16kHz 时约 7.2kHz）
+const ANTI_ALIAS_CUTOFF_RATIO: f32 = 0.45;
+/// 四阶巴特沃斯低通拆成两个二阶节时各节的 Q 值
+const BUTTERWORTH_Q4: [f32; 2] = [0.541_196_1, 1.306_563];
+
+/// 二阶低通滤波节（RBJ Audio EQ Cookbook）
+struct LowPassSection {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl LowPassSection {
+    fn new(sample_rate: u32, cutoff_hz: f32, q: f32) -> Self {
+        let omega = 2.0 * std::f32::consts::PI * cutoff_hz / sample_rate as f32;
+        let alpha = omega.sin() / (2.0 * q);
+        let cos = omega.cos();
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: (1.0 - cos) / 2.0 / a0,
+            b1: (1.0 - cos) / a0,
+            b2: (1.0 - cos) / 2.0 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// 线性插值重采样器（跨块保持相位连续）
+///
+/// 降采样时先经过四阶巴特沃斯低通，滤掉目标采样率无法表示的高频，避免混叠到语音频段。
+pub struct LinearResampler {
+    /// 每个输出样本在输入上前进的步长 (from_rate / to_rate)
+    step: f64,
+    /// 下一个输出样本在虚拟序列 `[prev, input...]` 中的位置
+    pos: f64,
+    /// 上一块的最后一个样本（已滤波）
+    prev: f32,
+    /// 抗混叠低通滤波器（升采样时为空）
+    anti_alias: Vec<LowPassSection>,
+}
+
+impl LinearResampler {
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        let anti_alias = if to_rate < from_rate {
+            let cutoff = to_rate as f32 * ANTI_ALIAS_CUTOFF_RATIO;
+            BUTTERWORTH_Q4
+                .iter()
+                .map(|&q| LowPassSection::new(from_rate, cutoff, q))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Self {
+            step: from_rate as f64 / to_rate as f64,
+            pos: 1.0,
+            prev: 0.0,
+            anti_alias,
+        }
+    }
+
+    /// 重采样一块样本
+    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        let input: Vec<f32> = input
+            .iter()
+            .map(|&sample| {
+                self.anti_alias
+                    .iter_mut()
+                    .fold(sample as f32, |x, section| section.process(x))
+            })
+            .collect();
+        let len = input.len() as f64;
+        let mut output = Vec::with_capacity((len / self.step) as usize + 1);
+
+        while self.pos < len {
+            let index = self.pos.floor() as usize;
+            let frac = (self.pos - index as f64) as f32;
+            let a = if index == 0 {
+                self.prev
+            } else {
+                input[index - 1]
+            };
+            let b = input[index];
+            let y = a + (b - a) * frac;
+            output.push(y.clamp(i16::MIN as f32, i16::MAX as f32) as i16);
+            self.pos += self.step;
+        }
+
+        self.pos -= len;
+        if let Some(&last) = input.last() {
+            self.prev = last;
+        }
+        output
+    }
+}
+
+/// 能量低于最强声道该比例的声道视为未接入，不参与混音
+const ACTIVE_CHANNEL_RATIO: f32 = 0.01;
+/// 声道能量的平滑系数
+const CHANNEL_ENERGY_SMOOTHING: f32 = 0.9;
+
+/// 把设备原生的多声道、任意采样率样本转换为单声道目标采样率
+pub struct InputConverter {
+    channels: usize,
+    /// 上一块末尾不足一帧的样本（回调数据不一定按帧对齐）
+    remainder: Vec<i16>,
+    /// 各声道的平滑能量，多输入声卡上未接麦克风的声道不参与平均，避免音量被拉低
+    channel_energy: Vec<f32>,
+    /// 采样率与目标一致时为 None
+    resampler: Option<LinearResampler>,
+}
+
+impl InputConverter {
+    pub fn new(channels: u16, from_rate: u32, to_rate: u32) -> Self {
+        let channels = channels.max(1) as usize;
+        Self {
+            channels,
+            remainder: Vec::new(),
+            channel_energy: vec![0.0; channels],
+            resampler: (from_rate != to_rate).then(|| LinearResampler::new(from_rate, to_rate)),
+        }
+    }
+
+    /// 转换一块交错排列的样本（有信号的声道取平均混为单声道）
+    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        let mono = if self.channels == 1 {
+            input.to_vec()
+        } else {
+            self.downmix(input)
+        };
+        match self.resampler.as_mut() {
+            Some(resampler) => resampler.process(&mono),
+            None => mono,
+        }
+    }
+
+    fn downmix(&mut self, input: &[i16]) -> Vec<i16> {
+        let channels = self.channels;
+        self.remainder.extend_from_slice(input);
+        let frames = self.remainder.len() / channels;
+        if frames == 0 {
+            return Vec::new();
+        }
+        let data = &self.remainder[..frames * channels];
+
+        // 更新各声道能量，挑出有信号的声道
+        for (channel, energy) in self.channel_energy.iter_mut().enumerate() {
+            let sum: f32 = data
+                .iter()
+                .skip(channel)
+                .step_by(channels)
+                .map(|&s| (s as f32) * (s as f32))
+                .sum();
+            *energy = *energy * CHANNEL_ENERGY_SMOOTHING
+                + sum / frames as f32 * (1.0 - CHANNEL_ENERGY_SMOOTHING);
+        }
+        let loudest = self.channel_energy.iter().cloned().fold(0.0, f32::max);
+        let active: Vec<bool> = self
+            .channel_energy
+            .iter()
+            .map(|&energy| loudest == 0.0 || energy >= loudest * ACTIVE_CHANNEL_RATIO)
+            .collect();
+        let active_count = active.iter().filter(|&&a| a).count().max(1) as i32;
+
+        let mono = data
+            .chunks_exact(channels)
+            .map(|frame| {
+                let sum: i32 = frame
+                    .iter()
+                    .zip(&active)
+                    .filter(|(_, &a)| a)
+                    .map(|(&s, _)| s as i32)
+                    .sum();
+                (sum / active_count) as i16
+            })
+            .collect();
+        self.remainder.drain(..frames * channels);
+        mono
+    }
+}
+
+/// 二阶巴特沃斯高通滤波器，去除桌面敲击、空调等低频隆隆声
+pub struct HighPassFilter {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    /// 前两个输入 / 输出样本
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl HighPassFilter {
+    pub fn new(sample_rate: u32, cutoff_hz: f32) -> Self {
+        // RBJ Audio EQ Cookbook，Q = 1/√2
+        let omega = 2.0 * std::f32::consts::PI * cutoff_hz / sample_rate as f32;
+        let alpha = omega.sin() / std::f32::consts::SQRT_2;
+        let cos = omega.cos();
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: (1.0 + cos) / 2.0 / a0,
+            b1: -(1.0 + cos) / a0,
+            b2: (1.0 + cos) / 2.0 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    /// 原地滤波一块样本（跨块保持滤波器状态）
+    pub fn process(&mut self, samples: &mut [i16]) {
+        for sample in samples.iter_mut() {
+            let x = *sample as f32;
+            let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+                - self.a1 * self.y1
+                - self.a2 * self.y2;
+            self.x2 = self.x1;
+            self.x1 = x;
+            self.y2 = self.y1;
+            self.y1 = y;
+            *sample = y.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        }
+    }
+}
+
+/// 录音开头的静音裁剪与淡入（去除按下快捷键时的按键声）
+pub struct StartTrim {
+    /// 还需要丢弃的样本数
+    skip_remaining: usize,
+    /// 淡入长度（样本数）
+    fade_len: usize,
+    /// 已淡入的样本数
+    fade_pos: usize,
+}
+
+impl StartTrim {
+    pub fn new(sample_rate: u32, skip_ms: u32, fade_ms: u32) -> Self {
+        let samples = |ms: u32| (sample_rate as u64 * ms as u64 / 1000) as usize;
+        Self {
+            skip_remaining: samples(skip_ms),
+            fade_len: samples(fade_ms),
+            fade_pos: 0,
+        }
+    }
+
+    /// 处理一块样本，开头部分被丢弃时返回的样本数会变少
+    pub fn process(&mut self, samples: &mut Vec<i16>) {
+        if self.skip_remaining > 0 {
+            let skip = self.skip_remaining.min(samples.len());
+            samples.drain(..skip);
+            self.skip_remaining -= skip;
+        }
+
+        for sample in samples.iter_mut() {
+            if self.fade_pos >= self.fade_len {
+                break;
+            }
+            let gain = self.fade_pos as f32 / self.fade_len as f32;
+            *sample = (*sample as f32 * gain) as i16;
+            self.fade_pos += 1;
+        }
+    }
+}
+
+/// 基于能量的静音检测（用于静音自动停止录音）
+///
+/// 检测到语音之后，连续静音超过设定时长即判定说话结束；
+/// 尚未开口时不计时，避免刚开始录音就被停止。
+pub struct SilenceDetector {
+    /// 静音判定阈值（RMS，i16 量程）
+    threshold: f32,
+    /// 触发停止所需的连续静音样本数
+    limit: usize,
+    silent_samples: usize,
+    heard_speech: bool,
+}
+
+impl SilenceDetector {
+    pub fn new(sample_rate: u32, threshold_db: f32, silence_secs: f32) -> Self {
+        Self {
+            threshold: i16::MAX as f32 * 10f32.powf(threshold_db / 20.0),
+            limit: (sample_rate as f32 * silence_secs.max(0.0)) as usize,
+            silent_samples: 0,
+            heard_speech: false,
+        }
+    }
+
+    /// 送入一块样本，静音时长达到上限时返回 true
+    pub fn push(&mut self, samples: &[i16]) -> bool {
+        if samples.is_empty() {
+            return false;
+        }
+
+        let energy: f64 = samples.iter().map(|&s| (s as f64) * (s as f64)).sum();
+        let rms = (energy / samples.len() as f64).sqrt() as f32;
+        if rms >= self.threshold {
+            self.heard_speech = true;
+            self.silent_samples = 0;
+        } else if self.heard_speech {
+            self.silent_samples += samples.len();
+        }
+
+        self.heard_speech && self.silent_samples >= self.limit
+    }
+}
@@ -0,0 +1,193 @@
+//! 录音期间系统输出音量闪避（Ducking）
+//!
+//! 开始录音时按比例降低系统输出音量，停止录音时恢复原始音量。
+
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+/// 闪避前的原始音量（0.0 - 1.0），None 表示当前未处于闪避状态
+static ORIGINAL_VOLUME: Mutex<Option<f32>> = Mutex::new(None);
+/// 当前是否期望处于闪避状态（用于处理开始/停止过快时的竞争）
+static DUCK_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// 降低系统输出音量（在后台线程执行，不阻塞调用方）
+///
+/// `percent` 为降低的百分比（0-100），例如 70 表示音量降至原来的 30%。
+pub fn duck(percent: u8) {
+    DUCK_REQUESTED.store(true, Ordering::SeqCst);
+    thread::spawn(move || {
+        let mut original = ORIGINAL_VOLUME.lock();
+        // 已经恢复或已经处于闪避状态时跳过，避免覆盖原始音量
+        if !DUCK_REQUESTED.load(Ordering::SeqCst) || original.is_some() {
+            return;
+        }
+
+        let current = match platform::get_volume() {
+            Ok(v) => v,
+            Err(e) => {
+                log::warn!("Failed to read output volume: {}", e);
+                return;
+            }
+        };
+
+        let factor = 1.0 - (percent.min(100) as f32 / 100.0);
+        let target = (current * factor).clamp(0.0, 1.0);
+
+        match platform::set_volume(target) {
+            Ok(()) => {
+                *original = Some(current);
+                log::info!(
+                    "Output volume ducked: {:.0}% -> {:.0}%",
+                    current * 100.0,
+                    target * 100.0
+                );
+            }
+            Err(e) => log::warn!("Failed to duck output volume: {}", e),
+        }
+    });
+}
+
+/// 恢复闪避前的系统输出音量（在后台线程执行）
+pub fn restore() {
+    if !DUCK_REQUESTED.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    thread::spawn(|| {
+        let Some(volume) = ORIGINAL_VOLUME.lock().take() else {
+            return;
+        };
+
+        match platform::set_volume(volume) {
+            Ok(()) => log::info!("Output volume restored to {:.0}%", volume * 100.0),
+            Err(e) => log::warn!("Failed to restore output volume: {}", e),
+        }
+    });
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::process::Command;
+
+    /// 通过 pactl 读取默认输出设备音量（PulseAudio / PipeWire）
+    pub fn get_volume() -> Result<f32, String> {
+        let output = Command::new("pactl")
+            .args(["get-sink-volume", "@DEFAULT_SINK@"])
+            .output()
+            .map_err(|e| format!("Failed to run pactl: {}", e))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        // 输出示例: "Volume: front-left: 32768 /  50% / -18.06 dB,   front-right: ..."
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        stdout
+            .split('/')
+            .find_map(|part| part.trim().strip_suffix('%')?.trim().parse::<f32>().ok())
+            .map(|percent| percent / 100.0)
+            .ok_or_else(|| format!("Unexpected pactl output: {}", stdout.trim()))
+    }
+
+    pub fn set_volume(volume: f32) -> Result<(), String> {
+        let percent = format!("{}%", (volume * 100.0).round() as u32);
+        let status = Command::new("pactl")
+            .args(["set-sink-volume", "@DEFAULT_SINK@", &percent])
+            .status()
+            .map_err(|e| format!("Failed to run pactl: {}", e))?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("pactl exited with {}", status))
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::process::Command;
+
+    /// 通过 osascript 读取系统输出音量（0-100）
+    pub fn get_volume() -> Result<f32, String> {
+        let output = Command::new("osascript")
+            .args(["-e", "output volume of (get volume settings)"])
+            .output()
+            .map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse::<f32>()
+            .map(|v| v / 100.0)
+            .map_err(|e| format!("Unexpected osascript output: {}", e))
+    }
+
+    pub fn set_volume(volume: f32) -> Result<(), String> {
+        let script = format!("set volume output volume {}", (volume * 100.0).round() as u32);
+        let status = Command::new("osascript")
+            .args(["-e", &script])
+            .status()
+            .map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("osascript exited with {}", status))
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Win32::Media::Audio::Endpoints::IAudioEndpointVolume;
+    use windows::Win32::Media::Audio::{
+        eMultimedia, eRender, IMMDeviceEnumerator, MMDeviceEnumerator,
+    };
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED,
+    };
+
+    /// 获取默认输出设备的音量控制接口
+    fn endpoint_volume() -> Result<IAudioEndpointVolume, String> {
+        unsafe {
+            // 重复初始化会返回 S_FALSE，忽略即可
+            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+            let enumerator: IMMDeviceEnumerator =
+                CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)
+                    .map_err(|e| format!("Failed to create device enumerator: {}", e))?;
+            let device = enumerator
+                .GetDefaultAudioEndpoint(eRender, eMultimedia)
+                .map_err(|e| format!("Failed to get default output device: {}", e))?;
+            device
+                .Activate::<IAudioEndpointVolume>(CLSCTX_ALL, None)
+                .map_err(|e| format!("Failed to activate endpoint volume: {}", e))
+        }
+    }
+
+    pub fn get_volume() -> Result<f32, String> {
+        let volume = endpoint_volume()?;
+        unsafe { volume.GetMasterVolumeLevelScalar() }
+            .map_err(|e| format!("Failed to get master volume: {}", e))
+    }
+
+    pub fn set_volume(level: f32) -> Result<(), String> {
+        let volume = endpoint_volume()?;
+        unsafe { volume.SetMasterVolumeLevelScalar(level, std::ptr::null()) }
+            .map_err(|e| format!("Failed to set master volume: {}", e))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub fn get_volume() -> Result<f32, String> {
+        Err("Output ducking is not supported on this platform".to_string())
+    }
+
+    pub fn set_volume(_volume: f32) -> Result<(), String> {
+        Err("Output ducking is not supported on this platform".to_string())
+    }
+}
@@ -0,0 +1,133 @@
+//! 输入电平诊断
+//!
+//! 录音过程中统计峰值、平均电平和削波比例，持续削波或音量过低时发出警告，
+//! 帮助用户理解识别结果为何出现乱码。
+
+use serde::{Deserialize, Serialize};
+
+/// 统计窗口（16kHz 下 1 秒）
+const WINDOW_SAMPLES: usize = 16000;
+/// 视为削波的幅度
+const CLIP_LEVEL: u16 = 32000;
+/// 单个窗口内削波样本超过该比例视为削波窗口
+const CLIP_RATIO: f32 = 0.01;
+/// 连续多少个削波窗口视为持续削波
+const CLIP_WINDOWS: u32 = 2;
+/// 录音满多少个窗口后判断音量是否过低
+const QUIET_WINDOWS: u32 = 3;
+/// 整段峰值低于该电平（dBFS）视为音量过低
+const QUIET_PEAK_DBFS: f32 = -40.0;
+
+/// 输入电平警告
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioWarning {
+    /// 持续削波（麦克风增益过高或离得太近）
+    Clipping,
+    /// 输入接近静音（麦克风静音、选错设备或增益过低）
+    TooQuiet,
+}
+
+/// 一次录音的电平统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioStats {
+    /// 峰值电平（dBFS）
+    pub peak_dbfs: f32,
+    /// 平均电平（RMS，dBFS）
+    pub rms_dbfs: f32,
+    /// 削波样本占比 (0.0 - 1.0)
+    pub clipped_ratio: f32,
+    /// 本次录音发出的警告
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<AudioWarning>,
+}
+
+/// 电平监测器
+#[derive(Default)]
+pub struct LevelMonitor {
+    samples: u64,
+    clipped: u64,
+    sum_squares: f64,
+    peak: u16,
+    window_len: usize,
+    window_clipped: usize,
+    clipped_windows: u32,
+    windows: u32,
+    warnings: Vec<AudioWarning>,
+}
+
+impl LevelMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一批样本，新出现的警告（每种只报告一次）通过返回值给出
+    pub fn push(&mut self, samples: &[i16]) -> Option<AudioWarning> {
+        let mut warning = None;
+        for &sample in samples {
+            let level = sample.unsigned_abs();
+            self.samples += 1;
+            self.sum_squares += (sample as f64) * (sample as f64);
+            self.peak = self.peak.max(level);
+            if level >= CLIP_LEVEL {
+                self.clipped += 1;
+                self.window_clipped += 1;
+            }
+
+            self.window_len += 1;
+            if self.window_len == WINDOW_SAMPLES {
+                warning = self.finish_window().or(warning);
+            }
+        }
+        warning
+    }
+
+    fn finish_window(&mut self) -> Option<AudioWarning> {
+        let clipping = self.window_clipped as f32 / self.window_len as f32 > CLIP_RATIO;
+        self.clipped_windows = if clipping {
+            self.clipped_windows + 1
+        } else {
+            0
+        };
+        self.windows += 1;
+        self.window_len = 0;
+        self.window_clipped = 0;
+
+        if self.clipped_windows >= CLIP_WINDOWS {
+            return self.warn(AudioWarning::Clipping);
+        }
+        if self.windows == QUIET_WINDOWS && to_dbfs(self.peak as f64) < QUIET_PEAK_DBFS {
+            return self.warn(AudioWarning::TooQuiet);
+        }
+        None
+    }
+
+    fn warn(&mut self, warning: AudioWarning) -> Option<AudioWarning> {
+        if self.warnings.contains(&warning) {
+            return None;
+        }
+        self.warnings.push(warning);
+        Some(warning)
+    }
+
+    /// 整段录音的统计（没有样本时为 None）
+    pub fn stats(&self) -> Option<AudioStats> {
+        if self.samples == 0 {
+            return None;
+        }
+        Some(AudioStats {
+            peak_dbfs: to_dbfs(self.peak as f64),
+            rms_dbfs: to_dbfs((self.sum_squares / self.samples as f64).sqrt()),
+            clipped_ratio: self.clipped as f32 / self.samples as f32,
+            warnings: self.warnings.clone(),
+        })
+    }
+}
+
+/// 幅度转换为 dBFS（静音时为 -96）
+fn to_dbfs(level: f64) -> f32 {
+    if level < 1.0 {
+        return -96.0;
+    }
+    (20.0 * (level / i16::MAX as f64).log10()) as f32
+}
@@ -1 +1,10 @@
+pub mod aec;
 pub mod capture;
+pub mod denoise;
+pub mod dsp;
+pub mod ducking;
+pub mod level;
+pub mod preroll;
+pub mod recordings;
+pub mod wav;
+pub mod waveform;
@@ -0,0 +1,123 @@
+//! 录音预缓冲（pre-roll）
+//!
+//! 在后台保持一个轻量的麦克风输入流，只保留最近 1~2 秒的音频。开始录音时把这段音频
+//! 拼接到录音开头，避免采集流启动期间第一个字被截掉。默认关闭（需要麦克风常开）。
+
+use cpal::traits::{HostTrait, StreamTrait};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use super::capture::{
+    build_converted_stream, find_input_device, negotiate_config, TARGET_SAMPLE_RATE,
+};
+
+/// 预缓冲的音频（16kHz 单声道，最新样本在末尾）
+pub type PrerollBuffer = Arc<Mutex<VecDeque<i16>>>;
+
+/// 后台预缓冲采集
+pub struct PrerollMonitor {
+    buffer: PrerollBuffer,
+    stop_signal: Arc<AtomicBool>,
+    thread_handle: Option<JoinHandle<()>>,
+    device: String,
+    duration_ms: u32,
+}
+
+impl PrerollMonitor {
+    /// 在指定设备（空字符串表示系统默认设备）上开始预缓冲最近 `duration_ms` 毫秒的音频
+    pub fn start(device: String, duration_ms: u32) -> Self {
+        let capacity = (TARGET_SAMPLE_RATE as usize * duration_ms as usize) / 1000;
+        let buffer: PrerollBuffer = Arc::new(Mutex::new(VecDeque::with_capacity(capacity)));
+        let stop_signal = Arc::new(AtomicBool::new(false));
+
+        let thread_buffer = buffer.clone();
+        let thread_stop = stop_signal.clone();
+        let thread_device = device.clone();
+        let handle = thread::spawn(move || {
+            if let Err(e) = run_preroll(thread_buffer, thread_stop, &thread_device, capacity) {
+                log::warn!("Pre-roll capture unavailable: {}", e);
+            }
+        });
+
+        log::info!("Pre-roll capture started ({} ms)", duration_ms);
+        Self {
+            buffer,
+            stop_signal,
+            thread_handle: Some(handle),
+            device,
+            duration_ms,
+        }
+    }
+
+    /// 是否与给定设置一致（不一致时需要重新启动）
+    pub fn matches(&self, device: &str, duration_ms: u32) -> bool {
+        self.device == device && self.duration_ms == duration_ms
+    }
+
+    /// 预缓冲区（由录音流在开始时取出）
+    pub fn buffer(&self) -> PrerollBuffer {
+        self.buffer.clone()
+    }
+}
+
+impl Drop for PrerollMonitor {
+    fn drop(&mut self) {
+        self.stop_signal.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+        log::info!("Pre-roll capture stopped");
+    }
+}
+
+/// 取出并清空预缓冲的音频
+pub fn take(buffer: &PrerollBuffer) -> Vec<i16> {
+    buffer.lock().drain(..).collect()
+}
+
+fn run_preroll(
+    buffer: PrerollBuffer,
+    stop_signal: Arc<AtomicBool>,
+    device_name: &str,
+    capacity: usize,
+) -> Result<(), String> {
+    let host = cpal::default_host();
+    let device = if device_name.is_empty() {
+        None
+    } else {
+        find_input_device(&host, device_name)
+    };
+    let device = match device {
+        Some(device) => device,
+        None => host
+            .default_input_device()
+            .ok_or("No input device available")?,
+    };
+
+    let supported = negotiate_config(&device)?;
+    let stream = build_converted_stream(
+        &device,
+        &supported,
+        move |samples| {
+            let mut buffer = buffer.lock();
+            buffer.extend(samples);
+            let overflow = buffer.len().saturating_sub(capacity);
+            if overflow > 0 {
+                buffer.drain(..overflow);
+            }
+        },
+        "Pre-roll stream error",
+    )?;
+    stream
+        .play()
+        .map_err(|e| format!("Failed to play stream: {}", e))?;
+
+    while !stop_signal.load(Ordering::SeqCst) {
+        thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! 录音存档
+//!
+//! 开启 `save_recordings` 后，每次录音的 PCM 以带时间戳的 WAV 文件保存在数据目录的
+//! `recordings` 下，便于重新识别或核对实际采集到的声音。按数量和天数自动清理旧文件。
+
+use chrono::Local;
+use directories::ProjectDirs;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use super::wav;
+
+/// 录音存档目录
+pub fn recordings_dir() -> Option<PathBuf> {
+    ProjectDirs::from("com", "speaky", "Speaky").map(|dirs| dirs.data_dir().join("recordings"))
+}
+
+/// 保存一次录音，返回文件路径
+///
+/// `max_files` 和 `max_days` 为 0 时表示不限制。
+pub fn save(samples: &[i16], max_files: usize, max_days: u32) -> Result<PathBuf, String> {
+    let dir = recordings_dir().ok_or("Failed to get recordings dir")?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create recordings dir: {}", e))?;
+
+    let file_name = format!("{}.wav", Local::now().format("%Y-%m-%d_%H-%M-%S%.3f"));
+    let path = dir.join(file_name);
+    wav::write_wav(&path, samples)?;
+    log::info!("Recording saved to {:?}", path);
+
+    prune(&dir, max_files, max_days);
+    Ok(path)
+}
+
+/// 删除超出数量上限或保存天数的旧录音
+fn prune(dir: &std::path::Path, max_files: usize, max_days: u32) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    // 文件名即时间戳，按名称排序即按时间排序
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wav"))
+        .collect();
+    files.sort();
+    files.reverse();
+
+    let max_age = Duration::from_secs(max_days as u64 * 24 * 60 * 60);
+    let now = SystemTime::now();
+    for (index, path) in files.iter().enumerate() {
+        let over_count = max_files > 0 && index >= max_files;
+        let expired = max_days > 0
+            && fs::metadata(path)
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age > max_age);
+        if over_count || expired {
+            if let Err(e) = fs::remove_file(path) {
+                log::warn!("Failed to remove old recording {:?}: {}", path, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! WAV 文件读写
+//!
+//! 读取 16bit PCM WAV 文件并转换为 ASR 使用的 16kHz 单声道格式，
+//! 以及把录音样本写成 16kHz 单声道 WAV。
+
+use std::fs;
+use std::path::Path;
+
+use super::dsp::LinearResampler;
+
+/// ASR 统一输入采样率
+const TARGET_SAMPLE_RATE: u32 = 16000;
+
+/// 读取 WAV 文件，返回 16kHz 单声道 PCM 样本
+pub fn read_wav(path: &Path) -> Result<Vec<i16>, String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    parse_wav(&data)
+}
+
+/// 把 16kHz 单声道 PCM 样本写入 WAV 文件
+pub fn write_wav(path: &Path, samples: &[i16]) -> Result<(), String> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut data = Vec::with_capacity(44 + data_len as usize);
+    data.extend_from_slice(b"RIFF");
+    data.extend_from_slice(&(36 + data_len).to_le_bytes());
+    data.extend_from_slice(b"WAVE");
+    data.extend_from_slice(b"fmt ");
+    data.extend_from_slice(&16u32.to_le_bytes());
+    data.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    data.extend_from_slice(&1u16.to_le_bytes()); // 单声道
+    data.extend_from_slice(&TARGET_SAMPLE_RATE.to_le_bytes());
+    data.extend_from_slice(&(TARGET_SAMPLE_RATE * 2).to_le_bytes()); // 字节率
+    data.extend_from_slice(&2u16.to_le_bytes()); // 块对齐
+    data.extend_from_slice(&16u16.to_le_bytes());
+    data.extend_from_slice(b"data");
+    data.extend_from_slice(&data_len.to_le_bytes());
+    data.extend_from_slice(bytemuck::cast_slice(samples));
+
+    fs::write(path, data).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+fn parse_wav(data: &[u8]) -> Result<Vec<i16>, String> {
+    if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+        return Err("Not a WAV file".to_string());
+    }
+
+    let mut format: Option<(u16, u16, u32, u16)> = None;
+    let mut pcm: Option<&[u8]> = None;
+
+    // 遍历 RIFF 子块
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let id = &data[pos..pos + 4];
+        let size = u32::from_le_bytes([data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]])
+            as usize;
+        let body_start = pos + 8;
+        let body_end = body_start.saturating_add(size).min(data.len());
+        let body = &data[body_start..body_end];
+
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                let audio_format = u16::from_le_bytes([body[0], body[1]]);
+                let channels = u16::from_le_bytes([body[2], body[3]]);
+                let sample_rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+                let bits_per_sample = u16::from_le_bytes([body[14], body[15]]);
+                format = Some((audio_format, channels, sample_rate, bits_per_sample));
+            }
+            b"data" => pcm = Some(body),
+            _ => {}
+        }
+
+        // 子块按偶数字节对齐
+        pos = body_start.saturating_add(size + (size & 1));
+    }
+
+    let (audio_format, channels, sample_rate, bits_per_sample) =
+        format.ok_or("WAV file has no fmt chunk")?;
+    let pcm = pcm.ok_or("WAV file has no data chunk")?;
+
+    // 1 = PCM, 0xFFFE = WAVE_FORMAT_EXTENSIBLE
+    if !matches!(audio_format, 1 | 0xFFFE) || bits_per_sample != 16 {
+        return Err(format!(
+            "Unsupported WAV format (format {}, {} bit), only 16-bit PCM is supported",
+            audio_format, bits_per_sample
+        ));
+    }
+    if channels == 0 || sample_rate == 0 {
+        return Err("Invalid WAV header".to_string());
+    }
+
+    // 多声道取平均
+    let channels = channels as usize;
+    let mono: Vec<i16> = pcm
+        .chunks_exact(2 * channels)
+        .map(|frame| {
+            let sum: i32 = frame
+                .chunks_exact(2)
+                .map(|s| i16::from_le_bytes([s[0], s[1]]) as i32)
+                .sum();
+            (sum / channels as i32) as i16
+        })
+        .collect();
+
+    if sample_rate == TARGET_SAMPLE_RATE {
+        Ok(mono)
+    } else {
+        Ok(LinearResampler::new(sample_rate, TARGET_SAMPLE_RATE).process(&mono))
+    }
+}
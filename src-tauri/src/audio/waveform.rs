@@ -0,0 +1,60 @@
+//! 录音波形包络
+//!
+//! 录音过程中按固定窗口记录峰值，结束后降采样为固定点数，供历史记录绘制迷你波形。
+
+/// 峰值窗口大小（16kHz 下 10ms）
+const WINDOW_SAMPLES: usize = 160;
+/// 导出的波形点数
+pub const WAVEFORM_POINTS: usize = 64;
+
+/// 波形记录器
+#[derive(Default)]
+pub struct WaveformRecorder {
+    /// 每个窗口的峰值
+    peaks: Vec<u16>,
+    current_peak: u16,
+    current_len: usize,
+}
+
+impl WaveformRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一批 PCM 样本
+    pub fn push(&mut self, samples: &[i16]) {
+        for sample in samples {
+            self.current_peak = self.current_peak.max(sample.unsigned_abs());
+            self.current_len += 1;
+            if self.current_len == WINDOW_SAMPLES {
+                self.peaks.push(self.current_peak);
+                self.current_peak = 0;
+                self.current_len = 0;
+            }
+        }
+    }
+
+    /// 导出降采样后的幅度包络（0-255，按整段录音的峰值归一化）
+    ///
+    /// 录音窗口数少于 `points` 时按实际窗口数返回。
+    pub fn envelope(&self, points: usize) -> Vec<u8> {
+        let mut peaks = self.peaks.clone();
+        if self.current_len > 0 {
+            peaks.push(self.current_peak);
+        }
+        if peaks.is_empty() || points == 0 {
+            return Vec::new();
+        }
+
+        let max = peaks.iter().copied().max().unwrap_or(0).max(1) as u32;
+        let count = points.min(peaks.len());
+        (0..count)
+            .map(|i| {
+                let start = i * peaks.len() / count;
+                let end = ((i + 1) * peaks.len() / count).max(start + 1);
+                let peak = peaks[start..end].iter().copied().max().unwrap_or(0) as u32;
+                (peak * 255 / max) as u8
+            })
+            .collect()
+    }
+}
@@ -1,15 +1,46 @@
-use crate::asr::client::AsrClient;
-use crate::asr::provider::{AsrResult, DownloadProgress, ModelInfo, ProviderInfo};
-use crate::asr::providers::{DoubaoProvider, WhisperApiProvider, WhisperLocalProvider, WhisperModelSize};
+use crate::asr::benchmark::{self, BenchmarkResult};
+use crate::asr::codec::AudioEncoder;
+use crate::asr::dual::{self, TranscriptCandidate};
+use crate::asr::metrics::{self, ProviderStats, SessionMetrics};
+use crate::asr::provider::{AsrError, AsrResult, DownloadProgress, ModelInfo, ProviderInfo};
+use crate::asr::providers::{
+    find_whisper_api_preset, whisper_api_presets, DoubaoConfig, DoubaoProvider, WhisperApiPreset,
+    WhisperBenchmark, WhisperGpuBackend, WhisperLocalProvider, WhisperModelSize,
+};
+#[cfg(feature = "sherpa")]
+use crate::asr::providers::{
+    FunAsrLocalProvider, FunAsrModel, MoonshineModel, MoonshineProvider, SenseVoiceModel,
+    SenseVoiceProvider, SherpaModel,
+};
+#[cfg(feature = "vosk")]
+use crate::asr::providers::{VoskModel, VoskProvider};
+use crate::asr::registry::ProviderRegistry;
 use crate::asr::{AsrProvider, ModelDownloadable};
 use crate::audio::capture::{list_audio_devices, AudioCaptureController, AudioDevice};
+use crate::audio::level::LevelMonitor;
+use crate::audio::preroll::PrerollMonitor;
+use crate::audio::wav;
+use crate::audio::waveform::{WaveformRecorder, WAVEFORM_POINTS};
+use crate::audio::{dsp, ducking, recordings};
 use crate::history::{History, HistoryEntry};
-use crate::input::keyboard::KeyboardSimulator;
-use crate::postprocess::{self, LlmProvider};
-use crate::state::{AppConfig, AppState, AsrConfig, RecordingState};
+use crate::input::active_app::{self, FocusedWindow};
+use crate::input::clipboard::ClipboardSnapshot;
+use crate::input::diagnostics::{self, KeyboardCheck};
+use crate::input::double_tap::{self, TapModifier};
+use crate::input::key_events;
+use crate::input::mouse_trigger::{self, MouseTrigger};
+use crate::input::keyboard::{KeyboardBackend, KeyboardOptions, KeyboardSimulator, TrailingKey};
+use crate::input::primary_selection;
+use crate::input::shortcut_capture;
+use crate::plugins::script::{self, OutputTarget};
+use crate::plugins::{self, PluginInfo, Stage};
+use crate::postprocess::{self, LlmProvider, PostProcessMode};
+use crate::state::{AppConfig, AppState, AsrConfig, AutoStopConfig, FocusLock, RecordingState, ShortcutMode};
+use crate::text;
 use auto_launch::AutoLaunchBuilder;
 use parking_lot::Mutex;
-use std::sync::atomic::{AtomicBool, Ordering};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, LazyLock};
 use std::time::Instant;
 use tauri::{command, AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize};
@@ -32,19 +63,80 @@ static ASR_COMPLETE_RX: LazyLock<Arc<Mutex<Option<tokio::sync::oneshot::Receiver
 // 全局键盘模拟器（复用）
 static KEYBOARD: LazyLock<Arc<Mutex<Option<KeyboardSimulator>>>> =
     LazyLock::new(|| Arc::new(Mutex::new(None)));
+// 键盘输入设置（随配置同步）
+static KEYBOARD_OPTIONS: LazyLock<Arc<Mutex<KeyboardOptions>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(KeyboardOptions::default())));
 // 键盘输入命令通道
 static KEYBOARD_TX: LazyLock<Arc<Mutex<Option<std::sync::mpsc::Sender<KeyboardCommand>>>>> =
     LazyLock::new(|| Arc::new(Mutex::new(None)));
+// 上一次录音的音频（用于 Provider 基准测试）
+static LAST_SESSION_AUDIO: LazyLock<Arc<Mutex<Vec<i16>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(Vec::new())));
+// 本次录音 Provider 返回的错误（识别失败时可用缓存的音频重试）
+static SESSION_ERROR: LazyLock<Arc<Mutex<Option<String>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+// 停止录音的时间（用于计算解码耗时）
+static AUDIO_END: LazyLock<Arc<Mutex<Option<Instant>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+// 本次录音是否为切换模式（切换快捷键模式或启用静音自动停止时，快捷键按一下开始、再按一下停止）
+static TOGGLE_SESSION: LazyLock<Arc<AtomicBool>> =
+    LazyLock::new(|| Arc::new(AtomicBool::new(false)));
+// 本次录音是否已取消（每次录音新建，避免影响下一次录音）
+static CANCEL_SIGNAL: LazyLock<Arc<Mutex<Arc<AtomicBool>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(Arc::new(AtomicBool::new(false)))));
+// 本次录音的 Provider（停止时按其重试预算等待结果）
+static SESSION_PROVIDER: LazyLock<Arc<Mutex<Option<SessionProvider>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+// 本次录音是否同时送入第二个 Provider（停止时需等待其结果）
+static DUAL_SESSION: LazyLock<Arc<AtomicBool>> =
+    LazyLock::new(|| Arc::new(AtomicBool::new(false)));
+// 开始录音时的焦点窗口（开启 focus_lock 时记录）
+static RECORDING_WINDOW: LazyLock<Arc<Mutex<Option<FocusedWindow>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+// 录音预缓冲（开启 preroll_ms 时麦克风常开）
+static PREROLL: LazyLock<Arc<Mutex<Option<PrerollMonitor>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+// 最近一次快捷键冲突（启动时前端尚未加载，需主动查询）
+static SHORTCUT_CONFLICT: LazyLock<Arc<Mutex<Option<ShortcutConflict>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+/// 最多保留的录音样本数（16kHz 下 10 分钟）
+const MAX_SESSION_SAMPLES: usize = 16000 * 600;
+/// 键盘自检时输入的测试文本（含中文，用于发现丢字问题）
+const KEYBOARD_TEST_MARKER: &str = "Speaky 键盘测试 123";
+/// 自动粘贴后等待多久再恢复剪贴板（给目标程序读取剪贴板的时间）
+const CLIPBOARD_RESTORE_DELAY: std::time::Duration = std::time::Duration::from_millis(300);
+/// 录制快捷键时等待按键的最长时间
+const SHORTCUT_CAPTURE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// 对比模式下主 Provider 结束后等待第二个 Provider 的最长时间
+const SECONDARY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// 录音中的 Provider 及开始时间
+struct SessionProvider {
+    provider: Arc<dyn AsrProvider>,
+    started: Instant,
+}
 
 /// 获取或创建键盘模拟器
 fn get_keyboard() -> Result<parking_lot::MutexGuard<'static, Option<KeyboardSimulator>>, String> {
+    let options = *KEYBOARD_OPTIONS.lock();
     let mut guard = KEYBOARD.lock();
-    if guard.is_none() {
-        *guard = Some(KeyboardSimulator::new()?);
+    // 设置变更后重新创建
+    if !guard.as_ref().is_some_and(|keyboard| keyboard.options() == options) {
+        *guard = Some(KeyboardSimulator::new(options)?);
     }
     Ok(guard)
 }
 
+/// 按配置切换键盘输入设置（下次输入时生效）
+pub fn sync_keyboard_options(config: &AppConfig) {
+    *KEYBOARD_OPTIONS.lock() = KeyboardOptions {
+        backend: config.keyboard_backend,
+        send_input_delay_ms: config.send_input_delay_ms,
+        typing: config.typing,
+        realtime_replace: config.realtime_replace,
+    };
+}
+
 /// 发送键盘命令（非阻塞）
 fn send_keyboard_command(cmd: KeyboardCommand) {
     let tx = KEYBOARD_TX.lock();
@@ -95,9 +187,15 @@ fn ensure_keyboard_thread() {
     }
 }
 
+/// 开始录音，`auto_stop` 可覆盖本次录音的静音自动停止设置
 #[command]
-pub async fn start_recording(app: AppHandle) -> Result<(), String> {
-    handle_start_recording(&app).await
+pub async fn start_recording(
+    app: AppHandle,
+    auto_stop: Option<AutoStopConfig>,
+    mode: Option<PostProcessMode>,
+    provider: Option<String>,
+) -> Result<(), String> {
+    handle_start_recording(&app, auto_stop, SessionOverride { mode, provider }).await
 }
 
 #[command]
@@ -105,6 +203,11 @@ pub async fn stop_recording(app: AppHandle) -> Result<String, String> {
     handle_stop_recording(&app).await
 }
 
+#[command]
+pub async fn cancel_recording(app: AppHandle) -> Result<(), String> {
+    handle_cancel_recording(&app)
+}
+
 #[command]
 pub fn get_state(app: AppHandle) -> Result<String, String> {
     let state = app.state::<AppState>();
@@ -127,6 +230,19 @@ pub fn update_config(app: AppHandle, config: AppConfig) -> Result<(), String> {
     if old_config.shortcut != config.shortcut {
         update_shortcut(&app, &old_config.shortcut, &config.shortcut)?;
     }
+    if old_config.pause_shortcut != config.pause_shortcut {
+        replace_shortcut(&app, &old_config.pause_shortcut, &config.pause_shortcut)?;
+    }
+    if old_config.reprocess_shortcut != config.reprocess_shortcut {
+        replace_shortcut(&app, &old_config.reprocess_shortcut, &config.reprocess_shortcut)?;
+    }
+    if old_config.shortcut != config.shortcut
+        || old_config.pause_shortcut != config.pause_shortcut
+        || old_config.postprocess.shortcuts != config.postprocess.shortcuts
+        || old_config.asr.shortcuts != config.asr.shortcuts
+    {
+        update_session_shortcuts(&app, &old_config, &config);
+    }
 
     // 如果开机启动变更，更新自启动设置
     if old_config.auto_start != config.auto_start {
@@ -136,6 +252,8 @@ pub fn update_config(app: AppHandle, config: AppConfig) -> Result<(), String> {
         update_auto_launch(config.auto_start, config.silent_start)?;
     }
 
+    sync_preroll(&config);
+    sync_keyboard_options(&config);
     state.update_config(config)
 }
 
@@ -145,6 +263,88 @@ pub fn get_transcript(app: AppHandle) -> Result<String, String> {
     Ok(state.get_transcript())
 }
 
+/// 把 LLM 改写前的原始识别结果复制到剪贴板
+#[command]
+pub fn copy_raw_transcript(app: AppHandle) -> Result<String, String> {
+    let state = app.state::<AppState>();
+    let raw = state.get_raw_transcript();
+    if raw.is_empty() {
+        return Err("没有可用的原始识别结果".to_string());
+    }
+    app.clipboard()
+        .write_text(&raw)
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    Ok(raw)
+}
+
+/// 对最近一条历史记录重新执行后处理（LLM 超时只得到原文时使用）
+///
+/// 结果替换该条历史记录并复制到剪贴板；`retype` 未指定时按 `reprocess_retype` 决定是否输入到当前窗口。
+#[command]
+pub async fn reprocess_last_transcript(
+    app: AppHandle,
+    retype: Option<bool>,
+) -> Result<String, String> {
+    handle_reprocess_last(&app, retype).await
+}
+
+pub async fn handle_reprocess_last(app: &AppHandle, retype: Option<bool>) -> Result<String, String> {
+    let state = app.state::<AppState>();
+    let config = state.get_config();
+    if !config.postprocess.enabled {
+        return Err("后处理未启用".to_string());
+    }
+
+    let mut history = History::load();
+    let entry = history
+        .entries
+        .first_mut()
+        .ok_or_else(|| "没有可处理的历史记录".to_string())?;
+
+    // 从原始识别文本重新走完整流程，避免在已处理过的文本上再叠加一次后处理
+    let mode = state.get_session_mode();
+    log::info!("Reprocessing last transcript ({:?})", mode);
+    let formatted = match &entry.raw_text {
+        Some(raw_text) => format_transcript(&config, raw_text),
+        // 旧记录没有原始文本，只能基于已处理的文本
+        None => entry.text.clone(),
+    };
+    let mut postprocess_config = config.postprocess.clone();
+    postprocess_config.mode = mode;
+    let processed = postprocess::process_text(&formatted, &postprocess_config).await?;
+    let processed = finish_transcript(&config, &processed);
+    state.set_raw_transcript(formatted);
+
+    if processed != entry.text {
+        entry.text = processed.clone();
+        if let Err(e) = history.save() {
+            log::error!("Failed to save history: {}", e);
+        }
+    }
+    state.set_transcript(processed.clone());
+
+    app.clipboard()
+        .write_text(&processed)
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    let _ = app.emit("transcript-update", &processed);
+
+    if retype.unwrap_or(config.reprocess_retype) {
+        let text = processed.clone();
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut guard = get_keyboard()?;
+            match guard.as_mut() {
+                Some(keyboard) => keyboard.type_text(&text),
+                None => Err("Keyboard simulator unavailable".to_string()),
+            }
+        })
+        .await
+        .map_err(|e| format!("Keyboard task failed: {}", e))??;
+    }
+
+    log::info!("Reprocessed last transcript: {}", processed);
+    Ok(processed)
+}
+
 #[command]
 pub async fn test_llm_connection(provider: LlmProvider) -> Result<String, String> {
     postprocess::test_connection(&provider).await
@@ -160,6 +360,13 @@ pub fn get_history() -> Vec<HistoryEntry> {
     History::load().entries
 }
 
+/// 按 Provider 汇总历史记录中的识别性能指标
+#[command]
+pub fn get_asr_stats() -> Vec<ProviderStats> {
+    let history = History::load();
+    metrics::summarize(history.entries.iter().filter_map(|e| e.metrics.as_ref()))
+}
+
 #[command]
 pub fn delete_history_entry(id: String) -> Result<(), String> {
     let mut history = History::load();
@@ -225,11 +432,33 @@ pub fn save_config_file_content(content: String, app: AppHandle) -> Result<(), S
     let config: AppConfig =
         toml::from_str(&content).map_err(|e| format!("Invalid TOML format: {}", e))?;
 
+    // 快捷键变更时重新注册（失败则不写入文件）
+    let state = app.state::<AppState>();
+    let old_config = state.get_config();
+    if old_config.shortcut != config.shortcut {
+        update_shortcut(&app, &old_config.shortcut, &config.shortcut)?;
+    }
+    if old_config.pause_shortcut != config.pause_shortcut {
+        replace_shortcut(&app, &old_config.pause_shortcut, &config.pause_shortcut)?;
+    }
+    if old_config.reprocess_shortcut != config.reprocess_shortcut {
+        replace_shortcut(&app, &old_config.reprocess_shortcut, &config.reprocess_shortcut)?;
+    }
+    if old_config.shortcut != config.shortcut
+        || old_config.pause_shortcut != config.pause_shortcut
+        || old_config.postprocess.shortcuts != config.postprocess.shortcuts
+        || old_config.asr.shortcuts != config.asr.shortcuts
+    {
+        update_session_shortcuts(&app, &old_config, &config);
+    }
+
     // 写入文件
     fs::write(&path, &content).map_err(|e| format!("Failed to write config file: {}", e))?;
 
-    // 更新内存中的配置
-    let state = app.state::<AppState>();
+    // 更新内存中的配置，并同步运行时状态
+    crate::logging::set_logging_enabled(config.enable_logging);
+    sync_preroll(&config);
+    sync_keyboard_options(&config);
     *state.config.write() = config;
 
     log::info!("Config file saved and reloaded");
@@ -302,39 +531,205 @@ pub fn update_asr_config(app: AppHandle, asr_config: AsrConfig) -> Result<(), St
     state.update_config(config)
 }
 
+/// 使用给定的豆包凭据测试连接（无需保存配置即可验证）
+#[command]
+pub async fn test_doubao_connection(config: DoubaoConfig) -> Result<(), String> {
+    DoubaoProvider::new(config.clone())
+        .validate()
+        .map_err(|e| e.to_string())?;
+
+    config
+        .client()
+        .test_connection()
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// 列出所有可用的 ASR Provider
 #[command]
 pub fn list_asr_providers(app: AppHandle) -> Vec<ProviderInfo> {
     let state = app.state::<AppState>();
     let config = state.get_config();
-    let mut providers = Vec::new();
+    ProviderRegistry::global()
+        .build_all(&config)
+        .map(|provider| provider.info())
+        .collect()
+}
 
-    // 豆包
-    if let Some(ref doubao_config) = config.asr.doubao {
-        let provider = DoubaoProvider::new(doubao_config.clone());
-        providers.push(provider.info());
-    } else {
-        // 即使没配置也显示
-        let provider = DoubaoProvider::new(Default::default());
-        providers.push(provider.info());
+/// 用同一段音频对比所有已就绪的 Provider
+///
+/// 未指定 `sample_path` 时使用上一次录音的音频。
+#[command]
+pub async fn benchmark_providers(
+    app: AppHandle,
+    sample_path: Option<String>,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let samples = match sample_path {
+        Some(path) => wav::read_wav(std::path::Path::new(&path))?,
+        None => LAST_SESSION_AUDIO.lock().clone(),
+    };
+    if samples.is_empty() {
+        return Err("没有可用于测试的音频".to_string());
     }
 
-    // Whisper 本地
-    let whisper_local = WhisperLocalProvider::new(
-        config.asr.whisper_local.clone().unwrap_or_default(),
-    );
-    providers.push(whisper_local.info());
+    let config = app.state::<AppState>().get_config();
+    let mut results = Vec::new();
+    // 逐个运行，避免并发请求互相影响耗时
+    // 演示模式不参与对比
+    let registry = ProviderRegistry::global();
+    for provider in registry.build_all(&config).filter(|p| p.id() != "mock") {
+        if !provider.is_ready() {
+            continue;
+        }
+        log::info!("Benchmarking ASR provider: {}", provider.id());
+        results.push(benchmark::run(provider.as_ref(), &samples).await);
+    }
+
+    if results.is_empty() {
+        return Err("没有已配置的 ASR Provider".to_string());
+    }
+    Ok(results)
+}
+
+/// 用上一次录音缓存的音频重新识别（用于 Provider 中途出错后的重试）
+///
+/// 未指定 `provider_id` 时使用当前 Provider。结果写入历史并复制到剪贴板，
+/// 不做键盘输入，因为此时焦点窗口可能已经改变。
+#[command]
+pub async fn retry_last_session(
+    app: AppHandle,
+    provider_id: Option<String>,
+) -> Result<String, String> {
+    let samples = LAST_SESSION_AUDIO.lock().clone();
+    if samples.is_empty() {
+        return Err("没有可重试的录音".to_string());
+    }
+
+    log::info!("Retrying last session ({:.1}s)", samples.len() as f32 / 16000.0);
+    let transcript = transcribe_to_history(&app, &samples, provider_id, None).await?;
+    *SESSION_ERROR.lock() = None;
+    Ok(transcript)
+}
+
+/// 重新识别保存的录音文件（见 `save_recordings`），结果写入历史并复制到剪贴板
+#[command]
+pub async fn transcribe_recording(
+    app: AppHandle,
+    path: String,
+    provider_id: Option<String>,
+) -> Result<String, String> {
+    let samples = wav::read_wav(std::path::Path::new(&path))?;
+    if samples.is_empty() {
+        return Err("录音文件为空".to_string());
+    }
+
+    log::info!("Re-transcribing recording {}", path);
+    transcribe_to_history(&app, &samples, provider_id, Some(path)).await
+}
+
+/// 用指定 Provider（默认当前 Provider）识别一段录音，结果写入历史并复制到剪贴板
+async fn transcribe_to_history(
+    app: &AppHandle,
+    samples: &[i16],
+    provider_id: Option<String>,
+    recording: Option<String>,
+) -> Result<String, String> {
+    let state = app.state::<AppState>();
+    let config = state.get_config();
+    let provider_id = provider_id.unwrap_or_else(|| config.asr.active_provider.clone());
+    let provider = ProviderRegistry::global()
+        .build(&config, &provider_id)
+        .ok_or_else(|| "未知的 ASR Provider".to_string())?;
+    if !provider.is_ready() {
+        return Err(format!("{} 尚未就绪", provider.display_name()));
+    }
+
+    log::info!("Transcribing with {}", provider_id);
+    let (outcome, last) = benchmark::transcribe_samples(provider.as_ref(), samples).await;
+    let result = match (outcome, last) {
+        (_, Some(result)) if !result.text.is_empty() => result,
+        (Err(e), _) => return Err(e.to_string()),
+        _ => return Err("未识别到内容".to_string()),
+    };
+
+    let (formatted, transcript) =
+        process_transcript(&config, &result.text, state.get_session_mode(), true).await;
 
-    // Whisper API
-    if let Some(ref api_config) = config.asr.whisper_api {
-        let provider = WhisperApiProvider::new(api_config.clone());
-        providers.push(provider.info());
+    state.set_transcript(transcript.clone());
+    state.set_raw_transcript(formatted);
+    state.set_confidence(result.confidence);
+    let mut history = History::load();
+    if let Some(entry) = history.add_entry(transcript.clone()) {
+        entry.raw_text = Some(result.text);
+        entry.confidence = result.confidence;
+        entry.low_confidence = config.is_low_confidence(result.confidence);
+        entry.segments = result.segments;
+        entry.language = result.language;
+        entry.recording = recording;
+    }
+    if let Err(e) = history.save() {
+        log::error!("Failed to save history: {}", e);
+    }
+    // 不经过 handle_stop_recording 的输出流程，复制前在此套用输出模板
+    let output = config
+        .postprocess
+        .templates
+        .apply(&state.get_session_mode(), &transcript);
+    if let Err(e) = app.clipboard().write_text(&output) {
+        log::error!("Failed to copy to clipboard: {}", e);
+    }
+    let _ = app.emit("transcript-update", &transcript);
+    Ok(transcript)
+}
+
+/// 识别结果的文本处理流程（录音结束与重新识别共用）
+///
+/// 依次为口述指令、英文规整、PostAsr 插件、LLM 后处理（`llm` 为 false 时跳过）、
+/// PostLlm 插件和脏话过滤；输出模板在输出时套用。
+/// 返回 (LLM 后处理前的文本, 最终文本)。
+async fn process_transcript(
+    config: &AppConfig,
+    asr_text: &str,
+    mode: PostProcessMode,
+    llm: bool,
+) -> (String, String) {
+    let formatted = format_transcript(config, asr_text);
+
+    let processed = if config.postprocess.enabled && llm {
+        let mut postprocess_config = config.postprocess.clone();
+        postprocess_config.mode = mode;
+        match postprocess::process_text(&formatted, &postprocess_config).await {
+            Ok(text) => text,
+            Err(e) => {
+                log::error!("Postprocess failed: {}", e);
+                formatted.clone()
+            }
+        }
     } else {
-        let provider = WhisperApiProvider::new(Default::default());
-        providers.push(provider.info());
+        formatted.clone()
+    };
+
+    let processed = finish_transcript(config, &processed);
+    (formatted, processed)
+}
+
+/// LLM 后处理之前的步骤：口述指令、英文规整、PostAsr 插件
+fn format_transcript(config: &AppConfig, asr_text: &str) -> String {
+    // 口述的标点、换行指令
+    let mut formatted = config.spoken_commands.apply(asr_text);
+
+    // 英文大小写和空格规整（LLM 关闭时同样生效）
+    if config.english_formatting {
+        formatted = text::english::format_english(&formatted);
     }
+    plugins::apply(Stage::PostAsr, &formatted, &config.enabled_plugins)
+}
 
-    providers
+/// LLM 后处理之后的步骤：PostLlm 插件、脏话过滤
+fn finish_transcript(config: &AppConfig, text: &str) -> String {
+    let processed = plugins::apply(Stage::PostLlm, text, &config.enabled_plugins);
+    // 脏话过滤放在最后，避免 LLM 或插件重新引入
+    config.profanity_filter.apply(&processed)
 }
 
 /// 获取 Whisper 模型列表
@@ -348,6 +743,18 @@ pub fn get_whisper_models(app: AppHandle) -> Vec<ModelInfo> {
     provider.available_models()
 }
 
+/// 转换模型下载错误；校验失败时额外发送 model-checksum-mismatch 事件
+fn download_error(app: &AppHandle, model_id: &str, error: AsrError) -> String {
+    let message = error.to_string();
+    if matches!(error, AsrError::ChecksumMismatch(_)) {
+        let _ = app.emit(
+            "model-checksum-mismatch",
+            serde_json::json!({ "model_id": model_id, "message": message }),
+        );
+    }
+    message
+}
+
 /// 下载 Whisper 模型
 #[command]
 pub async fn download_whisper_model(app: AppHandle, model_id: String) -> Result<(), String> {
@@ -371,7 +778,7 @@ pub async fn download_whisper_model(app: AppHandle, model_id: String) -> Result<
     provider
         .download_model(&model_id, progress_tx)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| download_error(&app, &model_id, e))?;
 
     // 发送完成事件
     let _ = app.emit("model-download-complete", &model_id);
@@ -404,20 +811,615 @@ pub fn cancel_whisper_download(app: AppHandle) {
     provider.cancel_download();
 }
 
-/// 设置当前使用的 Whisper 模型
-#[command]
-pub fn set_whisper_model(app: AppHandle, model_id: String) -> Result<(), String> {
-    let model_size = WhisperModelSize::from_filename(&model_id)
-        .ok_or_else(|| format!("未知模型: {}", model_id))?;
+/// 导入本地 Whisper 模型文件（ggml / gguf），供离线环境使用
+#[command]
+pub fn import_whisper_model(app: AppHandle, path: String) -> Result<ModelInfo, String> {
+    let state = app.state::<AppState>();
+    let config = state.get_config();
+    let provider = WhisperLocalProvider::new(
+        config.asr.whisper_local.clone().unwrap_or_default(),
+    );
+
+    let model_id = provider
+        .import_model(std::path::Path::new(&path))
+        .map_err(|e| e.to_string())?;
+    provider
+        .available_models()
+        .into_iter()
+        .find(|model| model.id == model_id)
+        .ok_or_else(|| format!("导入后未找到模型: {}", model_id))
+}
+
+/// 设置当前使用的 Whisper 模型
+#[command]
+pub fn set_whisper_model(app: AppHandle, model_id: String) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut config = state.get_config();
+    let mut whisper_config = config.asr.whisper_local.unwrap_or_default();
+
+    // 内置模型按大小选择，导入的模型通过自定义路径指定
+    if let Some(model_size) = WhisperModelSize::from_filename(&model_id) {
+        whisper_config.model_size = model_size;
+        whisper_config.model_path = None;
+    } else {
+        let provider = WhisperLocalProvider::new(whisper_config.clone());
+        if !provider.is_imported_model(&model_id) {
+            return Err(format!("未知模型: {}", model_id));
+        }
+        whisper_config.model_path = Some(provider.models_dir().join(&model_id));
+    }
+    config.asr.whisper_local = Some(whisper_config);
+
+    state.update_config(config)
+}
+
+/// 用内置测试音频测试 Whisper 模型的速度，帮助用户选择硬件能承受的模型
+#[command]
+pub async fn benchmark_whisper_model(
+    app: AppHandle,
+    model_id: String,
+) -> Result<WhisperBenchmark, String> {
+    let config = app.state::<AppState>().get_config();
+    let provider = WhisperLocalProvider::new(config.asr.whisper_local.clone().unwrap_or_default());
+    provider
+        .benchmark_model(&model_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 获取当前构建支持的 Whisper 推理后端
+#[command]
+pub fn get_whisper_gpu_backends() -> Vec<WhisperGpuBackend> {
+    WhisperGpuBackend::available()
+}
+
+/// 获取 Vosk 模型列表
+#[cfg(feature = "vosk")]
+#[command]
+pub fn get_vosk_models(app: AppHandle) -> Vec<ModelInfo> {
+    let state = app.state::<AppState>();
+    let config = state.get_config();
+    let provider = VoskProvider::new(config.asr.vosk.clone().unwrap_or_default());
+    provider.available_models()
+}
+
+/// 下载 Vosk 模型
+#[cfg(feature = "vosk")]
+#[command]
+pub async fn download_vosk_model(app: AppHandle, model_id: String) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let config = state.get_config();
+    let provider = VoskProvider::new(config.asr.vosk.clone().unwrap_or_default());
+
+    let (progress_tx, mut progress_rx) = mpsc::channel::<DownloadProgress>(32);
+
+    // 转发进度到前端
+    let app_clone = app.clone();
+    tokio::spawn(async move {
+        while let Some(progress) = progress_rx.recv().await {
+            let _ = app_clone.emit("model-download-progress", &progress);
+        }
+    });
+
+    // 执行下载并解压
+    provider
+        .download_model(&model_id, progress_tx)
+        .await
+        .map_err(|e| download_error(&app, &model_id, e))?;
+
+    // 发送完成事件
+    let _ = app.emit("model-download-complete", &model_id);
+    Ok(())
+}
+
+/// 删除 Vosk 模型
+#[cfg(feature = "vosk")]
+#[command]
+pub async fn delete_vosk_model(app: AppHandle, model_id: String) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let config = state.get_config();
+    let provider = VoskProvider::new(config.asr.vosk.clone().unwrap_or_default());
+
+    provider
+        .delete_model(&model_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 取消 Vosk 模型下载
+#[cfg(feature = "vosk")]
+#[command]
+pub fn cancel_vosk_download(app: AppHandle) {
+    let state = app.state::<AppState>();
+    let config = state.get_config();
+    let provider = VoskProvider::new(config.asr.vosk.clone().unwrap_or_default());
+    provider.cancel_download();
+}
+
+/// 设置当前使用的 Vosk 模型
+#[cfg(feature = "vosk")]
+#[command]
+pub fn set_vosk_model(app: AppHandle, model_id: String) -> Result<(), String> {
+    let model = VoskModel::from_dirname(&model_id)
+        .ok_or_else(|| format!("未知模型: {}", model_id))?;
+
+    let state = app.state::<AppState>();
+    let mut config = state.get_config();
+
+    let mut vosk_config = config.asr.vosk.unwrap_or_default();
+    vosk_config.model = model;
+    config.asr.vosk = Some(vosk_config);
+
+    state.update_config(config)
+}
+
+/// 按 Provider ID 构建支持模型下载的本地 Provider
+#[cfg(feature = "sherpa")]
+fn local_model_provider(
+    config: &AppConfig,
+    provider_id: &str,
+) -> Result<Box<dyn ModelDownloadable>, String> {
+    match provider_id {
+        "funasr_local" => Ok(Box::new(FunAsrLocalProvider::new(
+            config.asr.funasr_local.clone().unwrap_or_default(),
+        ))),
+        "sense_voice" => Ok(Box::new(SenseVoiceProvider::new(
+            config.asr.sense_voice.clone().unwrap_or_default(),
+        ))),
+        "moonshine" => Ok(Box::new(MoonshineProvider::new(
+            config.asr.moonshine.clone().unwrap_or_default(),
+        ))),
+        _ => Err(format!("Provider 不支持模型下载: {}", provider_id)),
+    }
+}
+
+/// 获取本地 Provider 的模型列表
+#[cfg(feature = "sherpa")]
+#[command]
+pub fn get_local_models(app: AppHandle, provider_id: String) -> Result<Vec<ModelInfo>, String> {
+    let config = app.state::<AppState>().get_config();
+    Ok(local_model_provider(&config, &provider_id)?.available_models())
+}
+
+/// 下载本地 Provider 的模型
+#[cfg(feature = "sherpa")]
+#[command]
+pub async fn download_local_model(
+    app: AppHandle,
+    provider_id: String,
+    model_id: String,
+) -> Result<(), String> {
+    let config = app.state::<AppState>().get_config();
+    let provider = local_model_provider(&config, &provider_id)?;
+
+    let (progress_tx, mut progress_rx) = mpsc::channel::<DownloadProgress>(32);
+
+    // 转发进度到前端
+    let app_clone = app.clone();
+    tokio::spawn(async move {
+        while let Some(progress) = progress_rx.recv().await {
+            let _ = app_clone.emit("model-download-progress", &progress);
+        }
+    });
+
+    provider
+        .download_model(&model_id, progress_tx)
+        .await
+        .map_err(|e| download_error(&app, &model_id, e))?;
+
+    // 发送完成事件
+    let _ = app.emit("model-download-complete", &model_id);
+    Ok(())
+}
+
+/// 删除本地 Provider 的模型
+#[cfg(feature = "sherpa")]
+#[command]
+pub async fn delete_local_model(
+    app: AppHandle,
+    provider_id: String,
+    model_id: String,
+) -> Result<(), String> {
+    let config = app.state::<AppState>().get_config();
+    local_model_provider(&config, &provider_id)?
+        .delete_model(&model_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 取消本地 Provider 的模型下载
+#[cfg(feature = "sherpa")]
+#[command]
+pub fn cancel_local_download(app: AppHandle, provider_id: String) -> Result<(), String> {
+    let config = app.state::<AppState>().get_config();
+    local_model_provider(&config, &provider_id)?.cancel_download();
+    Ok(())
+}
+
+/// 设置本地 Provider 当前使用的模型
+#[cfg(feature = "sherpa")]
+#[command]
+pub fn set_local_model(
+    app: AppHandle,
+    provider_id: String,
+    model_id: String,
+) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut config = state.get_config();
+
+    match provider_id.as_str() {
+        "funasr_local" => {
+            let model = FunAsrModel::from_id(&model_id)
+                .ok_or_else(|| format!("未知模型: {}", model_id))?;
+            let mut funasr_config = config.asr.funasr_local.unwrap_or_default();
+            funasr_config.model = model;
+            config.asr.funasr_local = Some(funasr_config);
+        }
+        "sense_voice" => {
+            let model = SenseVoiceModel::from_id(&model_id)
+                .ok_or_else(|| format!("未知模型: {}", model_id))?;
+            let mut sense_voice_config = config.asr.sense_voice.unwrap_or_default();
+            sense_voice_config.model = model;
+            config.asr.sense_voice = Some(sense_voice_config);
+        }
+        "moonshine" => {
+            let model = MoonshineModel::from_id(&model_id)
+                .ok_or_else(|| format!("未知模型: {}", model_id))?;
+            let mut moonshine_config = config.asr.moonshine.unwrap_or_default();
+            moonshine_config.model = model;
+            config.asr.moonshine = Some(moonshine_config);
+        }
+        _ => return Err(format!("Provider 不支持模型下载: {}", provider_id)),
+    }
+
+    state.update_config(config)
+}
+
+/// 列出已安装的文本变换插件
+#[command]
+pub fn list_plugins(app: AppHandle) -> Vec<PluginInfo> {
+    let state = app.state::<AppState>();
+    plugins::list(&state.get_config().enabled_plugins)
+}
+
+/// 启用或禁用插件
+#[command]
+pub fn set_plugin_enabled(app: AppHandle, name: String, enabled: bool) -> Result<(), String> {
+    if !plugins::exists(&name) {
+        return Err(format!("未知插件: {}", name));
+    }
+
+    let state = app.state::<AppState>();
+    let mut config = state.get_config();
+    config.enabled_plugins.retain(|n| n != &name);
+    if enabled {
+        config.enabled_plugins.push(name);
+    }
+    plugins::clear_cache();
+
+    state.update_config(config)
+}
+
+/// 列出 Whisper API 服务预设
+#[command]
+pub fn list_whisper_api_presets() -> Vec<WhisperApiPreset> {
+    whisper_api_presets().to_vec()
+}
+
+/// 应用 Whisper API 服务预设（保留已填写的 API Key）
+#[command]
+pub fn apply_whisper_api_preset(app: AppHandle, preset_id: String) -> Result<(), String> {
+    let preset = find_whisper_api_preset(&preset_id)
+        .ok_or_else(|| format!("未知预设: {}", preset_id))?;
+
+    let state = app.state::<AppState>();
+    let mut config = state.get_config();
+
+    let mut api_config = config.asr.whisper_api.unwrap_or_default();
+    api_config.apply_preset(preset);
+    config.asr.whisper_api = Some(api_config);
+
+    state.update_config(config)
+}
+
+/// 启用或暂停语音输入（暂停时注销录音快捷键并拒绝新的录音）
+#[command]
+pub fn set_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    set_app_enabled(&app, enabled)
+}
+
+/// 获取是否已启用
+#[command]
+pub fn is_enabled(app: AppHandle) -> bool {
+    !app.state::<AppState>().is_paused()
+}
+
+/// 键盘输出自检结果
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyboardTestReport {
+    pub backend: KeyboardBackend,
+    pub checks: Vec<KeyboardCheck>,
+    /// 输入的测试文本（未请求输入时为 None）
+    pub marker: Option<String>,
+    /// 输入测试文本时的错误
+    pub error: Option<String>,
+}
+
+/// 键盘输出自检：检查当前后端依赖的环境，`type_marker` 为 true 时向焦点输入框输入测试文本
+#[command]
+pub async fn test_keyboard_output(
+    app: AppHandle,
+    type_marker: bool,
+) -> Result<KeyboardTestReport, String> {
+    let config = app.state::<AppState>().get_config();
+    sync_keyboard_options(&config);
+    let backend = config.keyboard_backend;
+    let checks = diagnostics::run_checks(backend);
+
+    let mut report = KeyboardTestReport {
+        backend,
+        checks,
+        marker: None,
+        error: None,
+    };
+    if type_marker {
+        let marker = KEYBOARD_TEST_MARKER.to_string();
+        let text = marker.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let mut guard = get_keyboard()?;
+            match guard.as_mut() {
+                Some(keyboard) => keyboard.type_text(&text),
+                None => Err("Keyboard simulator unavailable".to_string()),
+            }
+        })
+        .await
+        .map_err(|e| format!("Keyboard task failed: {}", e))?;
+        if let Err(e) = result {
+            log::warn!("Keyboard test failed: {}", e);
+            report.error = Some(e);
+        }
+        report.marker = Some(marker);
+    }
+    Ok(report)
+}
+
+/// 切换启用/暂停状态（托盘、快捷键、定时任务共用）
+pub fn set_app_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if state.is_paused() != enabled {
+        return Ok(());
+    }
+
+    let shortcut = current_shortcut(app);
+    let config = state.get_config();
+    if enabled {
+        if let Some(shortcut) = shortcut {
+            app.global_shortcut()
+                .register(shortcut)
+                .map_err(|e| format!("Failed to register shortcut: {}", e))?;
+        }
+        register_session_shortcuts(app, &config);
+    } else {
+        // 正在录音时不中断，只是不再接受新的录音
+        if let Some(shortcut) = shortcut {
+            let _ = app.global_shortcut().unregister(shortcut);
+        }
+        unregister_session_shortcuts(app, &config);
+    }
+
+    state.set_paused(!enabled);
+    crate::update_tray_state(app, enabled);
+    let _ = app.emit("enabled-changed", enabled);
+    log::info!("Speaky {}", if enabled { "enabled" } else { "paused" });
+    Ok(())
+}
+
+/// 注册暂停/恢复、重新后处理等附加快捷键（替换旧的）
+pub fn replace_shortcut(app: &AppHandle, old_shortcut: &str, new_shortcut: &str) -> Result<(), String> {
+    let global_shortcut = app.global_shortcut();
+
+    if !new_shortcut.is_empty() {
+        register_shortcut(app, new_shortcut)?;
+    }
+    if !old_shortcut.is_empty() {
+        if let Ok(old) = parse_shortcut(old_shortcut) {
+            let _ = global_shortcut.unregister(old);
+        }
+    }
+    Ok(())
+}
+
+/// 获取配置中的重新后处理快捷键（未配置或无效时为 None）
+pub fn reprocess_shortcut(app: &AppHandle) -> Option<Shortcut> {
+    let config = app.state::<AppState>().get_config();
+    if config.reprocess_shortcut.is_empty() {
+        return None;
+    }
+    parse_shortcut(&config.reprocess_shortcut).ok()
+}
+
+/// 获取配置中的暂停/恢复快捷键（未配置或无效时为 None）
+pub fn pause_shortcut(app: &AppHandle) -> Option<Shortcut> {
+    let config = app.state::<AppState>().get_config();
+    if config.pause_shortcut.is_empty() {
+        return None;
+    }
+    parse_shortcut(&config.pause_shortcut).ok()
+}
+
+/// 快捷键对本次录音的覆盖设置（录音快捷键不覆盖任何设置）
+#[derive(Clone, Debug, Default)]
+pub struct SessionOverride {
+    /// 后处理模式，None 时按前台应用确定
+    pub mode: Option<PostProcessMode>,
+    /// ASR Provider ID，None 时使用 `asr.active_provider`
+    pub provider: Option<String>,
+}
+
+/// 配置中有效的模式、Provider 快捷键（与录音、暂停快捷键重复的忽略）
+fn session_shortcuts(config: &AppConfig) -> Vec<(Shortcut, SessionOverride)> {
+    let modes = config.postprocess.shortcuts.entries().map(|(mode, shortcut)| {
+        let session = SessionOverride {
+            mode: Some(mode),
+            ..Default::default()
+        };
+        (shortcut, session)
+    });
+    let providers = config
+        .asr
+        .shortcuts
+        .iter()
+        .filter(|binding| !binding.shortcut.is_empty() && !binding.provider.is_empty())
+        .map(|binding| {
+            let session = SessionOverride {
+                provider: Some(binding.provider.clone()),
+                ..Default::default()
+            };
+            (binding.shortcut.as_str(), session)
+        });
+
+    let main = main_shortcut(config);
+    let pause = parse_shortcut(&config.pause_shortcut).ok();
+    let mut shortcuts: Vec<(Shortcut, SessionOverride)> = Vec::new();
+    for (shortcut_str, session) in modes.chain(providers) {
+        match parse_shortcut(shortcut_str) {
+            // 同一快捷键只取第一个绑定
+            Ok(shortcut)
+                if Some(shortcut) != main
+                    && Some(shortcut) != pause
+                    && !shortcuts.iter().any(|(s, _)| *s == shortcut) =>
+            {
+                shortcuts.push((shortcut, session));
+            }
+            Ok(_) => log::warn!("Shortcut '{}' is already in use, ignored", shortcut_str),
+            Err(e) => log::warn!("Invalid shortcut '{}': {}", shortcut_str, e),
+        }
+    }
+    shortcuts
+}
+
+/// 按下的快捷键对应的覆盖设置（不是模式或 Provider 快捷键时为 None）
+pub fn session_override_for(app: &AppHandle, hotkey: &Shortcut) -> Option<SessionOverride> {
+    let config = app.state::<AppState>().get_config();
+    session_shortcuts(&config)
+        .into_iter()
+        .find(|(shortcut, _)| shortcut == hotkey)
+        .map(|(_, session)| session)
+}
+
+/// 注册模式、Provider 快捷键（单个失败时跳过，不影响其他快捷键）
+pub fn register_session_shortcuts(app: &AppHandle, config: &AppConfig) {
+    for (shortcut, session) in session_shortcuts(config) {
+        match app.global_shortcut().register(shortcut) {
+            Ok(()) => log::info!("Session shortcut registered: {:?}", session),
+            Err(e) => log::warn!("Failed to register session shortcut {:?}: {}", session, e),
+        }
+    }
+}
+
+fn unregister_session_shortcuts(app: &AppHandle, config: &AppConfig) {
+    for (shortcut, _) in session_shortcuts(config) {
+        let _ = app.global_shortcut().unregister(shortcut);
+    }
+}
+
+/// 模式、Provider 快捷键变更时重新注册（暂停状态下恢复时再注册）
+fn update_session_shortcuts(app: &AppHandle, old_config: &AppConfig, config: &AppConfig) {
+    let main = main_shortcut(config);
+    let pause = parse_shortcut(&config.pause_shortcut).ok();
+    for (shortcut, _) in session_shortcuts(old_config) {
+        // 旧的快捷键已改作录音或暂停快捷键时保留注册
+        if Some(shortcut) != main && Some(shortcut) != pause {
+            let _ = app.global_shortcut().unregister(shortcut);
+        }
+    }
+    if !app.state::<AppState>().is_paused() {
+        register_session_shortcuts(app, config);
+    }
+}
+
+/// 获取配置中的取消录音快捷键（未配置、无效或与录音快捷键相同时为 None）
+pub fn cancel_shortcut(app: &AppHandle) -> Option<Shortcut> {
+    let config = app.state::<AppState>().get_config();
+    if config.cancel_shortcut.is_empty() {
+        return None;
+    }
+    parse_shortcut(&config.cancel_shortcut)
+        .ok()
+        .filter(|shortcut| Some(*shortcut) != current_shortcut(app))
+}
+
+/// 录音期间注册取消快捷键（Esc 等按键不能常驻注册，否则其他应用收不到）
+fn register_cancel_shortcut(app: &AppHandle) {
+    if let Some(shortcut) = cancel_shortcut(app) {
+        if let Err(e) = app.global_shortcut().register(shortcut) {
+            log::warn!("Failed to register cancel shortcut: {}", e);
+        }
+    }
+}
+
+/// 录音结束后注销取消快捷键
+fn unregister_cancel_shortcut(app: &AppHandle) {
+    if let Some(shortcut) = cancel_shortcut(app) {
+        let _ = app.global_shortcut().unregister(shortcut);
+    }
+}
+
+/// 默认快捷键 (Alt+Space)
+pub fn default_shortcut() -> Shortcut {
+    Shortcut::new(Some(Modifiers::ALT), Code::Space)
+}
+
+/// 获取当前配置中的录音快捷键（双击修饰键、鼠标侧键时为 None）
+pub fn current_shortcut(app: &AppHandle) -> Option<Shortcut> {
+    main_shortcut(&app.state::<AppState>().get_config())
+}
+
+/// 配置中的录音快捷键（解析失败时使用默认快捷键，双击修饰键、鼠标侧键时为 None）
+fn main_shortcut(config: &AppConfig) -> Option<Shortcut> {
+    if is_hook_shortcut(&config.shortcut) {
+        return None;
+    }
+    Some(parse_shortcut(&config.shortcut).unwrap_or_else(|_| default_shortcut()))
+}
+
+/// 解析双击修饰键触发方式，如 "DoubleTap+Ctrl"（不是双击写法时为 None）
+pub fn parse_double_tap(shortcut_str: &str) -> Option<TapModifier> {
+    let (prefix, modifier) = shortcut_str.split_once('+')?;
+    if !prefix.trim().eq_ignore_ascii_case("doubletap") {
+        return None;
+    }
+    TapModifier::parse(modifier)
+}
 
-    let state = app.state::<AppState>();
-    let mut config = state.get_config();
+/// 是否为通过全局输入监听识别的录音快捷键（双击修饰键、鼠标侧键），这类快捷键不能注册为全局快捷键
+///
+/// 当前平台收不到侧键事件时，侧键写法按无效快捷键处理。
+pub fn is_hook_shortcut(shortcut_str: &str) -> bool {
+    parse_double_tap(shortcut_str).is_some()
+        || (mouse_trigger::SUPPORTED && MouseTrigger::parse(shortcut_str).is_some())
+}
 
-    let mut whisper_config = config.asr.whisper_local.unwrap_or_default();
-    whisper_config.model_size = model_size;
-    config.asr.whisper_local = Some(whisper_config);
+/// 检查当前平台能否使用该鼠标侧键快捷键（非侧键写法直接通过）
+pub fn check_mouse_shortcut(shortcut_str: &str) -> Result<(), String> {
+    if !mouse_trigger::SUPPORTED && MouseTrigger::parse(shortcut_str).is_some() {
+        return Err("当前系统不支持鼠标侧键快捷键，请使用键盘快捷键或双击修饰键".to_string());
+    }
+    Ok(())
+}
 
-    state.update_config(config)
+/// 按录音快捷键开启或关闭双击修饰键、鼠标侧键监听
+pub fn sync_hook_shortcut(app: &AppHandle, shortcut_str: &str) {
+    let modifier = parse_double_tap(shortcut_str);
+    let mouse = MouseTrigger::parse(shortcut_str);
+    if modifier.is_some() || mouse.is_some() {
+        let app = app.clone();
+        key_events::start_triggers(move |pressed| {
+            crate::handle_record_shortcut(&app, SessionOverride::default(), pressed);
+        });
+    }
+    double_tap::set_modifier(modifier);
+    mouse_trigger::set_trigger(mouse);
 }
 
 /// 解析快捷键字符串为 Shortcut
@@ -521,28 +1523,216 @@ pub fn parse_shortcut(shortcut_str: &str) -> Result<Shortcut, String> {
 
 /// 更新全局快捷键
 fn update_shortcut(app: &AppHandle, old_shortcut: &str, new_shortcut: &str) -> Result<(), String> {
+    check_mouse_shortcut(new_shortcut)?;
+
     let global_shortcut = app.global_shortcut();
+    let paused = app.state::<AppState>().is_paused();
+
+    // 双击修饰键、鼠标侧键由全局输入监听识别，不注册全局快捷键
+    if is_hook_shortcut(new_shortcut) {
+        sync_hook_shortcut(app, new_shortcut);
+        if !paused {
+            if let Ok(old) = parse_shortcut(old_shortcut) {
+                let _ = global_shortcut.unregister(old);
+            }
+        }
+        log::info!("Shortcut updated from {} to {}", old_shortcut, new_shortcut);
+        return Ok(());
+    }
 
     // 解析新快捷键
     let new = parse_shortcut(new_shortcut)?;
+    sync_hook_shortcut(app, new_shortcut);
 
-    // 先尝试注册新快捷键（检查是否被占用）
-    if let Err(e) = global_shortcut.register(new.clone()) {
-        return Err(format!(
-            "Shortcut '{}' is already in use or invalid: {}",
-            new_shortcut, e
-        ));
+    // 暂停状态下快捷键未注册，恢复时会注册新的快捷键
+    if paused {
+        return Ok(());
     }
 
+    // 先尝试注册新快捷键（检查是否被占用）
+    register_shortcut(app, new_shortcut)?;
+
     // 注册成功后，注销旧快捷键
     if let Ok(old) = parse_shortcut(old_shortcut) {
-        let _ = global_shortcut.unregister(old);
+        if old != new {
+            let _ = global_shortcut.unregister(old);
+        }
     }
 
     log::info!("Shortcut updated from {} to {}", old_shortcut, new_shortcut);
     Ok(())
 }
 
+/// 快捷键冲突信息
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortcutConflict {
+    /// 冲突的快捷键
+    pub shortcut: String,
+    /// 冲突原因
+    pub reason: String,
+    /// 按推荐程度排序的可用替代快捷键
+    pub suggestions: Vec<String>,
+}
+
+impl std::fmt::Display for ShortcutConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Shortcut '{}' {}", self.shortcut, self.reason)?;
+        if !self.suggestions.is_empty() {
+            write!(f, ". Try: {}", self.suggestions.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// 系统保留的快捷键（可以注册成功，但按下时被系统拦截）
+#[cfg(target_os = "macos")]
+const RESERVED_SHORTCUTS: &[(&str, &str)] = &[
+    ("Cmd+Space", "Spotlight"),
+    ("Ctrl+Space", "input source switching"),
+    ("Cmd+Tab", "app switcher"),
+    ("Cmd+Q", "quit application"),
+    ("Cmd+Shift+3", "screenshot"),
+    ("Cmd+Shift+4", "screenshot"),
+    ("Cmd+Shift+5", "screenshot"),
+];
+#[cfg(target_os = "windows")]
+const RESERVED_SHORTCUTS: &[(&str, &str)] = &[
+    ("Win+L", "lock screen"),
+    ("Win+D", "show desktop"),
+    ("Win+Space", "input language switching"),
+    ("Alt+Tab", "task switcher"),
+    ("Alt+F4", "close window"),
+    ("Ctrl+Alt+Delete", "security screen"),
+    ("Win+Shift+S", "screenshot"),
+];
+#[cfg(target_os = "linux")]
+const RESERVED_SHORTCUTS: &[(&str, &str)] = &[
+    ("Super+Space", "input source switching"),
+    ("Alt+Tab", "window switcher"),
+    ("Alt+F4", "close window"),
+    ("Ctrl+Alt+Delete", "log out"),
+    ("Super+L", "lock screen"),
+    ("Ctrl+Alt+T", "terminal"),
+];
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+const RESERVED_SHORTCUTS: &[(&str, &str)] = &[];
+
+/// 推荐替代快捷键时尝试的修饰键组合（按推荐程度排序）
+const ALTERNATIVE_MODIFIERS: &[&str] = &["Alt", "Ctrl+Alt", "Ctrl+Shift", "Alt+Shift", "Ctrl+Alt+Shift"];
+/// 与原按键无关的常用备选快捷键
+const FALLBACK_SHORTCUTS: &[&str] = &[
+    "Alt+Space",
+    "Ctrl+Alt+Space",
+    "Ctrl+Shift+Space",
+    "Alt+Shift+R",
+    "Ctrl+Alt+R",
+    "F8",
+    "F9",
+];
+/// 最多推荐的替代快捷键数量
+const MAX_SUGGESTIONS: usize = 5;
+
+/// 检查快捷键是否为系统保留，返回占用它的系统功能
+fn reserved_by(shortcut: &Shortcut) -> Option<&'static str> {
+    RESERVED_SHORTCUTS
+        .iter()
+        .find(|(s, _)| parse_shortcut(s).ok().as_ref() == Some(shortcut))
+        .map(|(_, owner)| *owner)
+}
+
+/// 注册快捷键，失败或为系统保留时记录冲突并附带可用的替代快捷键
+pub fn register_shortcut(app: &AppHandle, shortcut_str: &str) -> Result<(), String> {
+    let shortcut = parse_shortcut(shortcut_str)?;
+
+    let reason = if let Some(owner) = reserved_by(&shortcut) {
+        format!("is reserved by the system ({})", owner)
+    } else {
+        match app.global_shortcut().register(shortcut) {
+            Ok(()) => {
+                let mut conflict = SHORTCUT_CONFLICT.lock();
+                if conflict.as_ref().is_some_and(|c| c.shortcut == shortcut_str) {
+                    *conflict = None;
+                }
+                return Ok(());
+            }
+            Err(e) => format!("is already in use or invalid: {}", e),
+        }
+    };
+
+    let conflict = ShortcutConflict {
+        shortcut: shortcut_str.to_string(),
+        reason,
+        suggestions: suggest_alternatives(app, shortcut_str),
+    };
+    log::warn!("{}", conflict);
+    let _ = app.emit("shortcut-conflict", &conflict);
+    let message = conflict.to_string();
+    *SHORTCUT_CONFLICT.lock() = Some(conflict);
+    Err(message)
+}
+
+/// 生成可用的替代快捷键
+///
+/// 优先保留原按键、只更换修饰键，其次是常用备选。每个候选都通过实际注册再注销来确认可用。
+fn suggest_alternatives(app: &AppHandle, shortcut_str: &str) -> Vec<String> {
+    let key = shortcut_str.rsplit('+').next().unwrap_or_default().trim();
+    let candidates = ALTERNATIVE_MODIFIERS
+        .iter()
+        .map(|modifiers| format!("{}+{}", modifiers, key))
+        .chain(FALLBACK_SHORTCUTS.iter().map(|s| s.to_string()));
+
+    let config = app.state::<AppState>().get_config();
+    let mut taken: Vec<Shortcut> = [
+        shortcut_str,
+        &config.shortcut,
+        &config.pause_shortcut,
+        &config.reprocess_shortcut,
+    ]
+    .iter()
+        .filter_map(|s| parse_shortcut(s).ok())
+        .chain(session_shortcuts(&config).into_iter().map(|(shortcut, _)| shortcut))
+        .collect();
+
+    let global_shortcut = app.global_shortcut();
+    let mut suggestions = Vec::new();
+    for candidate in candidates {
+        if suggestions.len() >= MAX_SUGGESTIONS {
+            break;
+        }
+        let Ok(shortcut) = parse_shortcut(&candidate) else {
+            continue;
+        };
+        if taken.contains(&shortcut) || reserved_by(&shortcut).is_some() {
+            continue;
+        }
+        taken.push(shortcut);
+
+        if global_shortcut.register(shortcut).is_ok() {
+            let _ = global_shortcut.unregister(shortcut);
+            suggestions.push(candidate);
+        }
+    }
+    suggestions
+}
+
+/// 录制下一次按下的组合键，返回 `parse_shortcut` 可解析的规范写法（单独按 Esc 取消）
+#[command]
+pub async fn capture_shortcut() -> Result<String, String> {
+    shortcut_capture::capture(SHORTCUT_CAPTURE_TIMEOUT).await
+}
+
+/// 获取最近一次未解决的快捷键冲突
+#[command]
+pub fn get_shortcut_conflict() -> Option<ShortcutConflict> {
+    SHORTCUT_CONFLICT.lock().clone()
+}
+
+/// 为指定快捷键推荐可用的替代快捷键
+#[command]
+pub fn suggest_shortcuts(app: AppHandle, shortcut: String) -> Vec<String> {
+    suggest_alternatives(&app, &shortcut)
+}
+
 /// 更新开机启动设置
 fn update_auto_launch(enable: bool, silent: bool) -> Result<(), String> {
     let app_name = "Speaky";
@@ -581,11 +1771,96 @@ fn update_auto_launch(enable: bool, silent: bool) -> Result<(), String> {
     Ok(())
 }
 
+/// 检查焦点是否仍在开始录音时的窗口，返回是否可以继续键盘输出
+fn ensure_output_focus(app: &AppHandle, focus_lock: FocusLock) -> bool {
+    let Some(window) = RECORDING_WINDOW.lock().take() else {
+        return true;
+    };
+    if active_app::focused_window().as_ref() == Some(&window) {
+        return true;
+    }
+    if focus_lock == FocusLock::Refocus {
+        match active_app::activate(&window) {
+            Ok(()) => {
+                log::info!("Refocused recording window {:?}", window);
+                return true;
+            }
+            Err(e) => log::warn!("Failed to refocus recording window: {}", e),
+        }
+    }
+    log::warn!("Focus changed during recording, skipping keyboard output");
+    let _ = app.emit("output-suppressed", "focus_changed");
+    false
+}
+
+/// 按配置启动、重启或关闭录音预缓冲
+pub fn sync_preroll(config: &AppConfig) {
+    let mut preroll = PREROLL.lock();
+    if config.preroll_ms == 0 {
+        *preroll = None;
+    } else if !preroll
+        .as_ref()
+        .is_some_and(|monitor| monitor.matches(&config.audio_device, config.preroll_ms))
+    {
+        // 先停止旧的采集流再打开新的
+        *preroll = None;
+        *preroll = Some(PrerollMonitor::start(
+            config.audio_device.clone(),
+            config.preroll_ms,
+        ));
+    }
+}
+
 /// 检查是否为静默启动模式
 pub fn is_silent_mode() -> bool {
     std::env::args().any(|arg| arg == "--silent")
 }
 
+/// 检查是否需要把识别结果输出到 stdout
+pub fn is_print_mode() -> bool {
+    std::env::args().any(|arg| arg == "--print")
+}
+
+/// 检查是否为单次模式（启动即录音，结束后退出）
+pub fn is_once_mode() -> bool {
+    std::env::args().any(|arg| arg == "--once")
+}
+
+/// 本次录音是否为切换模式（快捷键释放时不停止录音）
+pub fn is_toggle_session() -> bool {
+    TOGGLE_SESSION.load(Ordering::SeqCst)
+}
+
+/// 记录本次录音是否为切换模式
+///
+/// 快捷键按下时需同步调用：录音在异步任务中启动，快速松开时释放事件可能先于任务到达。
+pub fn set_toggle_session(config: &AppConfig, auto_stop: Option<&AutoStopConfig>) {
+    let auto_stop_enabled = auto_stop.unwrap_or(&config.auto_stop).enabled;
+    let toggle = auto_stop_enabled || config.shortcut_mode == ShortcutMode::Toggle;
+    TOGGLE_SESSION.store(toggle, Ordering::SeqCst);
+}
+
+/// 命令行 `--auto-stop=<秒>` 指定的静音自动停止时长
+pub fn auto_stop_arg() -> Option<f32> {
+    std::env::args().find_map(|arg| {
+        arg.strip_prefix("--auto-stop=").and_then(|secs| secs.parse().ok())
+    })
+}
+
+/// 单次模式的退出码
+pub mod exit_code {
+    /// 识别成功
+    pub const SUCCESS: i32 = 0;
+    /// 没有识别到文本
+    pub const EMPTY: i32 = 1;
+    /// 启动录音失败
+    pub const START_FAILED: i32 = 2;
+    /// 结果因置信度过低或包含敏感内容被拦截
+    pub const HELD_BACK: i32 = 3;
+    /// 录音被取消
+    pub const CANCELLED: i32 = 4;
+}
+
 /// 显示指示器窗口（屏幕底部居中）
 fn show_indicator(app: &AppHandle) {
     if let Some(indicator) = app.get_webview_window("indicator") {
@@ -610,6 +1885,28 @@ fn show_indicator(app: &AppHandle) {
     }
 }
 
+/// 在预览窗口中显示识别结果（屏幕底部居中，位于指示器上方）
+fn show_review(app: &AppHandle, text: &str) {
+    let Some(review) = app.get_webview_window("review") else {
+        return;
+    };
+    if let Ok(Some(monitor)) = review.primary_monitor() {
+        let screen_size = monitor.size();
+        let scale_factor = review.scale_factor().unwrap_or(1.0);
+
+        let window_width = (420.0 * scale_factor) as u32;
+        let window_height = (200.0 * scale_factor) as u32;
+        let _ = review.set_size(PhysicalSize::new(window_width, window_height));
+
+        let x = (screen_size.width as i32 - window_width as i32) / 2;
+        let y = screen_size.height as i32 - window_height as i32 - (140.0 * scale_factor) as i32;
+        let _ = review.set_position(PhysicalPosition::new(x, y));
+    }
+    let _ = app.emit_to("review", "review-transcript", text);
+    let _ = review.show();
+    let _ = review.set_focus();
+}
+
 /// 隐藏指示器窗口
 fn hide_indicator(app: &AppHandle) {
     if let Some(indicator) = app.get_webview_window("indicator") {
@@ -617,13 +1914,21 @@ fn hide_indicator(app: &AppHandle) {
     }
 }
 
-pub async fn handle_start_recording(app: &AppHandle) -> Result<(), String> {
+pub async fn handle_start_recording(
+    app: &AppHandle,
+    auto_stop: Option<AutoStopConfig>,
+    session: SessionOverride,
+) -> Result<(), String> {
     let state = app.state::<AppState>();
 
     if state.get_recording_state() == RecordingState::Recording {
         return Err("Already recording".to_string());
     }
 
+    if state.is_paused() {
+        return Err("语音输入已暂停".to_string());
+    }
+
     let config = state.get_config();
 
     // 显示指示器窗口（如果启用）- 在配置检查前显示，以便测试 UI
@@ -631,50 +1936,74 @@ pub async fn handle_start_recording(app: &AppHandle) -> Result<(), String> {
         show_indicator(app);
     }
 
-    // 根据 active_provider 选择 ASR Provider 并验证配置
-    let provider_error: Option<&str> = match config.asr.active_provider.as_str() {
-        "doubao" => {
-            match &config.asr.doubao {
-                Some(cfg) if cfg.is_configured() => None,
-                _ => Some("请先配置豆包 App ID 和 Access Token"),
-            }
-        }
-        "whisper_local" => {
-            let whisper_config = config.asr.whisper_local.clone().unwrap_or_default();
-            let provider = WhisperLocalProvider::new(whisper_config);
-            if provider.is_ready() { None } else { Some("请先下载 Whisper 模型") }
-        }
-        "whisper_api" => {
-            match &config.asr.whisper_api {
-                Some(cfg) if cfg.is_configured() => None,
-                _ => Some("请先配置 Whisper API Key"),
-            }
+    // 根据 active_provider（或快捷键绑定的 Provider）构建 ASR Provider 并验证配置
+    let provider_id = session
+        .provider
+        .unwrap_or_else(|| config.asr.active_provider.clone());
+    let registry = ProviderRegistry::global();
+    let provider = match registry.build(&config, &provider_id) {
+        Some(provider) if provider.is_ready() => Ok(provider),
+        Some(provider) => Err(registry.setup_hint(provider.id()).unwrap_or_default()),
+        None => Err("未知的 ASR Provider"),
+    };
+    let provider = match provider {
+        Ok(provider) => provider,
+        Err(error_msg) => {
+            // 发送未配置事件
+            let _ = app.emit("indicator-not-configured", ());
+            // 延迟隐藏指示器
+            let app_clone = app.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                hide_indicator(&app_clone);
+            });
+            return Err(error_msg.to_string());
         }
-        _ => Some("未知的 ASR Provider"),
     };
-
-    if let Some(error_msg) = provider_error {
-        // 发送未配置事件
-        let _ = app.emit("indicator-not-configured", ());
-        // 延迟隐藏指示器
-        let app_clone = app.clone();
-        tokio::spawn(async move {
-            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-            hide_indicator(&app_clone);
-        });
-        return Err(error_msg.to_string());
-    }
+    // 按 Provider 声明的格式编码上传音频
+    let mut encoder = AudioEncoder::new(provider.audio_codec())?;
 
     state.set_recording_state(RecordingState::Recording);
     state.clear_transcript();
 
+    set_toggle_session(&config, auto_stop.as_ref());
+    let auto_stop = auto_stop.unwrap_or_else(|| config.auto_stop.clone());
+
+    // 按模式快捷键启动时使用对应模式，否则按前台应用确定本次会话的后处理模式
+    let session_mode = match session.mode {
+        Some(mode) => {
+            log::info!("Session mode: {:?} (shortcut)", mode);
+            mode
+        }
+        None => {
+            let app_name = if config.postprocess.mode_pinned {
+                None
+            } else {
+                active_app::active_app_name()
+            };
+            let mode = config.postprocess.resolve_mode(app_name.as_deref());
+            log::info!("Session mode: {:?} (app: {:?})", mode, app_name);
+            mode
+        }
+    };
+    state.set_session_mode(session_mode);
+
+    // 记录焦点窗口，结束时检查是否仍在同一窗口
+    *RECORDING_WINDOW.lock() = if config.focus_lock == FocusLock::Off {
+        None
+    } else {
+        active_app::focused_window()
+    };
+
     // 如果启用实时输入，确保键盘线程已启动
     if config.realtime_input {
         ensure_keyboard_thread();
     }
     STOP_SIGNAL.store(false, Ordering::SeqCst);
+    let cancelled = Arc::new(AtomicBool::new(false));
+    *CANCEL_SIGNAL.lock() = cancelled.clone();
 
-    app.emit("recording-started", ())
+    app.emit("recording-started", serde_json::json!({ "toggle": is_toggle_session() }))
         .map_err(|e| e.to_string())?;
 
     // 创建通道
@@ -689,94 +2018,182 @@ pub async fn handle_start_recording(app: &AppHandle) -> Result<(), String> {
 
     // 启动音频采集
     let (pcm_tx, pcm_rx) = std::sync::mpsc::channel();
-    let mut capture = AudioCaptureController::with_device(config.audio_device.clone());
+    let device_app = app.clone();
+    let mut capture = AudioCaptureController::with_device(config.audio_device.clone())
+        .with_start_trim(config.start_trim_ms, config.start_fade_ms)
+        .with_input_gain(config.input_gain_for(&config.audio_device))
+        .with_device_listener(move |device| {
+            let _ = device_app.emit("audio-device-active", device);
+        });
+    if config.echo_cancellation {
+        capture = capture.with_echo_reference(config.echo_reference_device.clone());
+    }
+    if config.high_pass_hz > 0 {
+        capture = capture.with_high_pass(config.high_pass_hz);
+    }
+    if config.noise_suppression {
+        capture = capture.with_noise_suppression();
+    }
+    if let Some(buffer) = PREROLL.lock().as_ref().map(PrerollMonitor::buffer) {
+        capture = capture.with_preroll(buffer);
+    }
+    if config.dual_source.enabled {
+        capture = capture.with_dual_source(config.dual_source.clone());
+    }
     capture.start_recording(pcm_tx)?;
 
-    // 音频转发线程 - 使用 bytemuck 零拷贝
+    // 采集启动成功后再注册取消快捷键、降低系统输出音量，启动失败时无需恢复
+    register_cancel_shortcut(app);
+    if config.ducking_enabled {
+        ducking::duck(config.ducking_percent);
+    }
+
+    LAST_SESSION_AUDIO.lock().clear();
+    *SESSION_ERROR.lock() = None;
+
+    // 录音波形（会话结束后发送给前端）
+    let waveform = Arc::new(Mutex::new(WaveformRecorder::new()));
+    // 输入电平诊断（削波 / 音量过低）
+    let levels = Arc::new(Mutex::new(LevelMonitor::new()));
+
+    // 对比模式：同一份音频同时送入第二个 Provider
+    let secondary = config
+        .asr
+        .secondary_provider
+        .as_deref()
+        .filter(|id| *id != provider_id)
+        .and_then(|id| ProviderRegistry::global().build(&config, id))
+        .filter(|provider| provider.is_ready())
+        .and_then(|provider| match AudioEncoder::new(provider.audio_codec()) {
+            Ok(encoder) => Some((provider, encoder)),
+            Err(e) => {
+                log::error!("Secondary ASR ({}) unavailable: {}", provider.id(), e);
+                None
+            }
+        });
+    let (mut secondary_audio, secondary_session) = match secondary {
+        Some((provider, encoder)) => {
+            let (tx, rx) = mpsc::channel::<Vec<u8>>(100);
+            let (result_tx, mut result_rx) = mpsc::channel::<AsrResult>(10);
+            let secondary_id = provider.id().to_string();
+            log::info!("Dual mode: also transcribing with {}", secondary_id);
+            let id = secondary_id.clone();
+            tokio::spawn(async move {
+                if let Err(e) = provider.transcribe_stream(rx, result_tx).await {
+                    log::error!("Secondary ASR ({}) error: {}", id, e);
+                }
+            });
+            // 只保留最后一个结果
+            let collector = tokio::spawn(async move {
+                let mut last = None;
+                while let Some(result) = result_rx.recv().await {
+                    last = Some(result);
+                }
+                last
+            });
+            (Some((tx, encoder)), Some((secondary_id, collector)))
+        }
+        None => (None, None),
+    };
+    DUAL_SESSION.store(secondary_session.is_some(), Ordering::SeqCst);
+
+    // 会话指标
+    let session_start = Instant::now();
+    let provider: Arc<dyn AsrProvider> = Arc::from(provider);
+    *SESSION_PROVIDER.lock() = Some(SessionProvider {
+        provider: provider.clone(),
+        started: session_start,
+    });
+    let bytes_uploaded = Arc::new(AtomicU64::new(0));
+    *AUDIO_END.lock() = None;
+
+    // 音频转发线程 - 按各 Provider 的编码格式发送
     let audio_tx_clone = audio_tx.clone();
     let stop_signal = STOP_SIGNAL.clone();
+    let waveform_clone = waveform.clone();
+    let levels_clone = levels.clone();
+    let bytes_uploaded_clone = bytes_uploaded.clone();
+    let mut silence = auto_stop.enabled.then(|| {
+        dsp::SilenceDetector::new(16000, auto_stop.threshold_db, auto_stop.silence_secs)
+    });
+    let thread_app = app.clone();
     std::thread::spawn(move || {
+        let mut audio_truncated = false;
         while let Ok(samples) = pcm_rx.recv() {
             if stop_signal.load(Ordering::SeqCst) {
                 break;
             }
-            // 零拷贝转换: &[i16] -> &[u8]
-            let bytes: &[u8] = bytemuck::cast_slice(&samples);
-            if audio_tx_clone.blocking_send(bytes.to_vec()).is_err() {
-                break;
-            }
-        }
-        drop(capture);
-    });
-
-    // 根据 active_provider 启动对应的 ASR
-    match config.asr.active_provider.as_str() {
-        "doubao" => {
-            // 使用原有的豆包 ASR 客户端（性能更好的流式实现）
-            let doubao_config = config.asr.doubao.clone().unwrap_or_default();
-            let asr_client = AsrClient::new(
-                doubao_config.app_id,
-                doubao_config.access_token,
-                doubao_config.secret_key,
-            );
-
-            // 创建内部结果通道，转换格式
-            let (internal_tx, mut internal_rx) = mpsc::channel::<crate::asr::client::AsrResult>(32);
-
-            // 启动格式转换任务
-            let result_tx_clone = result_tx.clone();
-            tokio::spawn(async move {
-                while let Some(internal_result) = internal_rx.recv().await {
-                    let result = AsrResult {
-                        text: internal_result.text,
-                        is_final: !internal_result.is_prefetch,
-                    };
-                    if result_tx_clone.send(result).await.is_err() {
-                        break;
+            if silence.as_mut().is_some_and(|detector| detector.push(&samples)) {
+                silence = None;
+                log::info!("Silence detected, stopping recording");
+                let app = thread_app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let _ = app.emit("recording-auto-stopped", ());
+                    if let Err(e) = handle_stop_recording(&app).await {
+                        log::error!("Failed to auto-stop recording: {}", e);
                     }
+                    crate::SHORTCUT_PROCESSING.store(false, Ordering::SeqCst);
+                });
+            }
+            waveform_clone.lock().push(&samples);
+            if let Some(warning) = levels_clone.lock().push(&samples) {
+                log::warn!("Audio input warning: {:?}", warning);
+                let _ = thread_app.emit("audio-warning", warning);
+            }
+            {
+                let mut session_audio = LAST_SESSION_AUDIO.lock();
+                if session_audio.len() + samples.len() <= MAX_SESSION_SAMPLES {
+                    session_audio.extend_from_slice(&samples);
+                } else if !audio_truncated {
+                    // 超出部分不再缓存：重试与保存的录音只包含前 10 分钟
+                    audio_truncated = true;
+                    log::warn!(
+                        "Session audio exceeds {} minutes, later audio won't be kept for retry or saving",
+                        MAX_SESSION_SAMPLES / 16000 / 60
+                    );
+                    let _ = thread_app.emit("session-audio-truncated", MAX_SESSION_SAMPLES / 16000);
                 }
-            });
-
-            tokio::spawn(async move {
-                if let Err(e) = asr_client.connect_and_stream(audio_rx, internal_tx).await {
-                    log::error!("ASR session error: {}", e);
+            }
+            let data = encoder.encode(&samples);
+            if !data.is_empty() && audio_tx_clone.blocking_send(data).is_err() {
+                break;
+            }
+            if let Some((tx, encoder)) = secondary_audio.as_mut() {
+                let data = encoder.encode(&samples);
+                if !data.is_empty() {
+                    let _ = tx.blocking_send(data);
                 }
-            });
+            }
+            // 按 PCM 字节数统计，用于换算录音时长
+            bytes_uploaded_clone.fetch_add((samples.len() * 2) as u64, Ordering::Relaxed);
         }
-        "whisper_local" => {
-            let mut whisper_config = config.asr.whisper_local.clone().unwrap_or_default();
-            // 使用统一的语言设置
-            whisper_config.language = config.asr_language.clone();
-            let provider = WhisperLocalProvider::new(whisper_config);
-            tokio::spawn(async move {
-                if let Err(e) = provider.transcribe_stream(audio_rx, result_tx).await {
-                    log::error!("Whisper local ASR error: {}", e);
-                }
-            });
+        // 发送编码器中剩余的数据（Opus 的最后一帧和流结束标记）
+        let tail = encoder.finish();
+        if !tail.is_empty() {
+            let _ = audio_tx_clone.blocking_send(tail);
         }
-        "whisper_api" => {
-            let mut api_config = config.asr.whisper_api.clone().unwrap_or_default();
-            // 使用统一的语言设置
-            if config.asr_language != "auto" {
-                api_config.language = Some(config.asr_language.clone());
-            } else {
-                api_config.language = None;
+        if let Some((tx, encoder)) = secondary_audio.as_mut() {
+            let tail = encoder.finish();
+            if !tail.is_empty() {
+                let _ = tx.blocking_send(tail);
             }
-            let provider = WhisperApiProvider::new(api_config);
-            tokio::spawn(async move {
-                if let Err(e) = provider.transcribe_stream(audio_rx, result_tx).await {
-                    log::error!("Whisper API ASR error: {}", e);
-                }
-            });
         }
-        _ => {
-            return Err("未知的 ASR Provider".to_string());
+        drop(capture);
+    });
+
+    // 启动 ASR
+    // 识别任务持有结果通道直到记录完错误，保证结果处理任务结束时能读到 SESSION_ERROR
+    tokio::spawn(async move {
+        if let Err(e) = provider.transcribe_stream(audio_rx, result_tx.clone()).await {
+            log::error!("ASR session error ({}): {}", provider.id(), e);
+            *SESSION_ERROR.lock() = Some(e.to_string());
         }
-    }
+    });
 
     // 处理识别结果 - 带节流和 prefetch 检测
     let app_clone = app.clone();
-    let realtime_input = config.auto_type && config.realtime_input;
+    // 预览模式下不做任何键盘输出
+    let realtime_input = config.auto_type && config.realtime_input && !config.review_output;
 
     // 如果启用实时输入，重置键盘状态
     if realtime_input {
@@ -787,15 +2204,35 @@ pub async fn handle_start_recording(app: &AppHandle) -> Result<(), String> {
         }
     }
 
+    let profanity_filter = config.profanity_filter.clone();
+    let spoken_commands = config.spoken_commands.clone();
+    let auto_language = config.language_for(&provider_id) == "auto";
     tokio::spawn(async move {
         let mut final_text = String::new();
+        let mut final_confidence = None;
+        let mut detected_language = None;
+        let mut final_segments = Vec::new();
+        let mut first_partial_ms = None;
         let mut last_emit = Instant::now();
         const THROTTLE_MS: u128 = 100;
 
         while let Some(result) = result_rx.recv().await {
+            if cancelled.load(Ordering::SeqCst) {
+                break;
+            }
             // 直接移动 result.text，避免多次 clone
             let text = result.text;
             let is_final = result.is_final;
+            final_confidence = result.confidence;
+            if result.language.is_some() {
+                detected_language = result.language;
+            }
+            if !result.segments.is_empty() {
+                final_segments = result.segments;
+            }
+            if first_partial_ms.is_none() && !text.is_empty() {
+                first_partial_ms = Some(session_start.elapsed().as_millis() as u64);
+            }
 
             // 更新 state
             let state = app_clone.state::<AppState>();
@@ -807,7 +2244,8 @@ pub async fn handle_start_recording(app: &AppHandle) -> Result<(), String> {
 
                 // 实时输入到当前焦点窗口（使用专用线程通道，避免频繁创建线程）
                 if realtime_input && !text.is_empty() {
-                    send_keyboard_command(KeyboardCommand::UpdateText(text.clone()));
+                    let typed = profanity_filter.apply(&spoken_commands.apply(&text));
+                    send_keyboard_command(KeyboardCommand::UpdateText(typed));
                 }
 
                 last_emit = Instant::now();
@@ -822,31 +2260,124 @@ pub async fn handle_start_recording(app: &AppHandle) -> Result<(), String> {
             }
         }
 
+        // 已取消：丢弃结果，不保存录音和历史记录
+        if cancelled.load(Ordering::SeqCst) {
+            log::info!("Recording cancelled, transcript discarded");
+            return;
+        }
+
+        // 自动检测语言时通知前端
+        if !auto_language {
+            detected_language = None;
+        }
+        if let Some(ref language) = detected_language {
+            log::info!("Detected language: {}", language);
+            let _ = app_clone.emit("language-detected", language);
+        }
+
+        // 发送整段录音的波形包络
+        let envelope = waveform.lock().envelope(WAVEFORM_POINTS);
+        let _ = app_clone.emit("recording-waveform", &envelope);
+        let audio_stats = levels.lock().stats();
+
+        // 发送会话指标
+        let decode_ms = AUDIO_END
+            .lock()
+            .map(|end| end.elapsed().as_millis() as u64)
+            .unwrap_or_default();
+        let session_metrics = SessionMetrics::new(
+            provider_id.clone(),
+            bytes_uploaded.load(Ordering::Relaxed),
+            decode_ms,
+            first_partial_ms,
+        );
+        log::info!("Session metrics: {:?}", session_metrics);
+        let _ = app_clone.emit("session-metrics", &session_metrics);
+
+        // 对比模式：等待第二个 Provider 的结果并选出最终文本
+        let mut candidates = Vec::new();
+        if let Some((secondary_id, collector)) = secondary_session {
+            match tokio::time::timeout(SECONDARY_TIMEOUT, collector).await {
+                Ok(Ok(Some(result))) => {
+                    let mut primary =
+                        TranscriptCandidate::new(provider_id, final_text.clone(), final_confidence);
+                    let mut secondary =
+                        TranscriptCandidate::new(secondary_id, result.text, result.confidence);
+                    let strategy = app_clone.state::<AppState>().get_config().asr.dual_selection;
+                    let use_secondary = dual::prefer_secondary(&primary, &secondary, strategy);
+                    if use_secondary {
+                        final_text = secondary.text.clone();
+                        final_confidence = secondary.confidence;
+                        final_segments = result.segments;
+                    }
+                    primary.selected = !use_secondary;
+                    secondary.selected = use_secondary;
+                    candidates = vec![primary, secondary];
+                    let _ = app_clone.emit("dual-transcripts", &candidates);
+                }
+                Ok(_) => log::warn!("Secondary ASR ({}) returned no result", secondary_id),
+                Err(_) => log::warn!("Secondary ASR ({}) timed out", secondary_id),
+            }
+        }
+
+        // 保存本次录音（识别失败或结果为空时同样保存，便于核对）
+        let recording = {
+            let config = app_clone.state::<AppState>().get_config();
+            let samples = LAST_SESSION_AUDIO.lock().clone();
+            if config.save_recordings && !samples.is_empty() {
+                match recordings::save(
+                    &samples,
+                    config.max_recordings,
+                    config.recording_retention_days,
+                ) {
+                    Ok(path) => Some(path.to_string_lossy().into_owned()),
+                    Err(e) => {
+                        log::error!("Failed to save recording: {}", e);
+                        None
+                    }
+                }
+            } else {
+                None
+            }
+        };
+
         // 使用最终结果
         if !final_text.is_empty() {
             let state = app_clone.state::<AppState>();
             let config = state.get_config();
 
             // 后处理（仅非实时输入模式）
-            let processed_result = if config.postprocess.enabled && !realtime_input {
-                match postprocess::process_text(&final_text, &config.postprocess).await {
-                    Ok(text) => text,
-                    Err(e) => {
-                        log::error!("Postprocess failed: {}", e);
-                        final_text.clone()
-                    }
-                }
-            } else {
-                final_text.clone()
-            };
+            let (formatted, processed_result) = process_transcript(
+                &config,
+                &final_text,
+                state.get_session_mode(),
+                !realtime_input,
+            )
+            .await;
+            let asr_text = std::mem::replace(&mut final_text, formatted);
 
             log::info!("ASR completed: {} -> {}", final_text, processed_result);
             state.set_transcript(processed_result.clone());
+            state.set_raw_transcript(final_text.clone());
+            state.set_confidence(final_confidence);
 
             // 保存到历史记录
             {
                 let mut history = crate::history::History::load();
-                history.add_entry(processed_result.clone());
+                if let Some(entry) = history.add_entry(processed_result.clone()) {
+                    entry.raw_text = Some(asr_text);
+                    entry.confidence = final_confidence;
+                    entry.low_confidence = config.is_low_confidence(final_confidence);
+                    if config.history_waveform {
+                        entry.waveform = envelope;
+                    }
+                    entry.metrics = Some(session_metrics);
+                    entry.candidates = candidates;
+                    entry.language = detected_language;
+                    entry.segments = final_segments;
+                    entry.recording = recording;
+                    entry.audio_stats = audio_stats;
+                }
                 if let Err(e) = history.save() {
                     log::error!("Failed to save history: {}", e);
                 }
@@ -855,13 +2386,24 @@ pub async fn handle_start_recording(app: &AppHandle) -> Result<(), String> {
             // 发送最终结果事件
             let _ = app_clone.emit("transcript-update", &processed_result);
 
-            // 实时输入模式下，完成时再次更新确保最终文本正确
+            // 实时输入模式下，完成时再次更新确保最终文本正确（使用过滤后的文本）
             if realtime_input {
-                send_keyboard_command(KeyboardCommand::UpdateText(final_text.clone()));
+                send_keyboard_command(KeyboardCommand::UpdateText(processed_result.clone()));
                 send_keyboard_command(KeyboardCommand::Finish);
             }
         }
 
+        // Provider 出错：保留本次录音，前端可调用 retry_last_session 重新识别
+        if let Some(error) = SESSION_ERROR.lock().clone() {
+            let audio_secs = LAST_SESSION_AUDIO.lock().len() as f32 / 16000.0;
+            if audio_secs > 0.0 {
+                let _ = app_clone.emit(
+                    "asr-session-failed",
+                    serde_json::json!({ "error": error, "audio_secs": audio_secs }),
+                );
+            }
+        }
+
         // 通知完成
         let _ = complete_tx.send(());
     });
@@ -870,6 +2412,48 @@ pub async fn handle_start_recording(app: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// 取消录音：丢弃本次识别结果，不复制、不输入、不写入历史记录
+pub fn handle_cancel_recording(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+
+    if state.get_recording_state() != RecordingState::Recording {
+        return Err("Not recording".to_string());
+    }
+
+    CANCEL_SIGNAL.lock().store(true, Ordering::SeqCst);
+    STOP_SIGNAL.store(true, Ordering::SeqCst);
+    unregister_cancel_shortcut(app);
+
+    // 恢复系统输出音量
+    ducking::restore();
+
+    // 关闭音频通道，识别任务自行结束
+    *AUDIO_TX.lock() = None;
+    ASR_COMPLETE_RX.lock().take();
+    SESSION_PROVIDER.lock().take();
+
+    // 实时输入模式下删除已经输入的文字
+    let config = state.get_config();
+    if config.auto_type && config.realtime_input && !config.review_output {
+        send_keyboard_command(KeyboardCommand::UpdateText(String::new()));
+        send_keyboard_command(KeyboardCommand::Finish);
+    }
+
+    state.clear_transcript();
+    state.set_recording_state(RecordingState::Idle);
+    hide_indicator(app);
+
+    app.emit("recording-cancelled", ())
+        .map_err(|e| e.to_string())?;
+
+    log::info!("Recording cancelled");
+
+    if is_once_mode() {
+        app.exit(exit_code::CANCELLED);
+    }
+    Ok(())
+}
+
 pub async fn handle_stop_recording(app: &AppHandle) -> Result<String, String> {
     let state = app.state::<AppState>();
 
@@ -879,6 +2463,11 @@ pub async fn handle_stop_recording(app: &AppHandle) -> Result<String, String> {
 
     state.set_recording_state(RecordingState::Processing);
     STOP_SIGNAL.store(true, Ordering::SeqCst);
+    *AUDIO_END.lock() = Some(Instant::now());
+    unregister_cancel_shortcut(app);
+
+    // 恢复系统输出音量
+    ducking::restore();
 
     // 关闭音频通道
     {
@@ -886,29 +2475,158 @@ pub async fn handle_stop_recording(app: &AppHandle) -> Result<String, String> {
         *tx = None;
     }
 
-    // 等待 ASR 完成（最多 2 秒）
+    // 等待 ASR 完成（对比模式下还需等待第二个 Provider 的结果，再输出选中的文本）
+    // 录音结束后才上传的 Provider 可能还要重试，按其声明的预算等待
     let complete_rx = ASR_COMPLETE_RX.lock().take();
-    if let Some(rx) = complete_rx {
-        let _ = tokio::time::timeout(tokio::time::Duration::from_millis(2000), rx).await;
+    let result_timeout = SESSION_PROVIDER
+        .lock()
+        .take()
+        .map(|session| {
+            let audio_secs = session.started.elapsed().as_secs_f32();
+            session.provider.result_timeout(audio_secs)
+        });
+    if let (Some(rx), Some(mut timeout)) = (complete_rx, result_timeout) {
+        if DUAL_SESSION.load(Ordering::SeqCst) {
+            timeout += SECONDARY_TIMEOUT;
+        }
+        let _ = tokio::time::timeout(timeout, rx).await;
     }
 
-    let transcript = state.get_transcript();
+    let mut transcript = state.get_transcript();
     let config = state.get_config();
 
-    if !transcript.is_empty() {
+    // 输出脚本：可改写文本并选择本次的输出方式
+    let mut target = OutputTarget::Default;
+    if !config.output_script.is_empty() && !transcript.is_empty() {
+        let app_name = active_app::active_app_name();
+        match script::run(
+            std::path::Path::new(&config.output_script),
+            &transcript,
+            app_name.as_deref(),
+            &config,
+        ) {
+            Ok(outcome) => {
+                if outcome.text != transcript {
+                    transcript = outcome.text;
+                    state.set_transcript(transcript.clone());
+                }
+                target = outcome.target;
+            }
+            Err(e) => log::error!("{}", e),
+        }
+    }
+
+    // 输出方式：(复制到剪贴板, 键盘输入)
+    let (copy, type_out) = match target {
+        // 预览模式：结果只显示在预览窗口，由用户决定是否复制
+        OutputTarget::Default if config.review_output => (false, false),
+        OutputTarget::Default => (config.auto_copy, config.auto_type),
+        OutputTarget::Type => (false, true),
+        OutputTarget::Paste => (true, true),
+        OutputTarget::Clipboard => (true, false),
+        OutputTarget::None => (false, false),
+    };
+
+    // 需要用户确认的结果（置信度过低 / 含敏感内容）：不自动输入，仅复制到剪贴板并提醒
+    let confidence = state.get_confidence();
+    let held_back = if transcript.is_empty() {
+        false
+    } else if config.is_low_confidence(confidence) {
+        log::warn!(
+            "Low confidence transcript ({:?} < {}), skipping auto type",
+            confidence,
+            config.min_confidence
+        );
+        let _ = app.emit(
+            "low-confidence",
+            serde_json::json!({ "text": transcript, "confidence": confidence }),
+        );
+        true
+    } else if let Some(kind) = config.sensitive_filter.detect(&transcript) {
+        log::warn!("Sensitive content detected ({}), skipping auto type", kind);
+        let _ = app.emit(
+            "sensitive-content",
+            serde_json::json!({ "text": transcript, "kind": kind }),
+        );
+        true
+    } else {
+        false
+    };
+
+    // 录音期间切换了窗口：重新激活原窗口或放弃键盘输出
+    let type_out = type_out
+        && (held_back
+            || transcript.is_empty()
+            || config.realtime_input
+            || ensure_output_focus(app, config.focus_lock));
+
+    if held_back {
+        if let Err(e) = app.clipboard().write_text(&transcript) {
+            log::error!("Failed to copy to clipboard: {}", e);
+        }
+    } else if !transcript.is_empty() {
+        // 套用当前模式的输出模板
+        let output = config
+            .postprocess
+            .templates
+            .apply(&state.get_session_mode(), &transcript);
+
+        if target == OutputTarget::Default && config.review_output {
+            show_review(app, &output);
+        }
+
+        // 输出到终端时不能带换行
+        let output = if type_out && config.terminal_safe.enabled {
+            let app_name = active_app::active_app_name();
+            config.terminal_safe.apply(&output, app_name.as_deref())
+        } else {
+            output
+        };
+
+        // 粘贴会覆盖剪贴板，先保存原有内容
+        let pasting = copy && type_out && !config.realtime_input;
+        let snapshot = (pasting && config.restore_clipboard)
+            .then(|| ClipboardSnapshot::capture(app));
+
         // 复制到剪贴板
-        if config.auto_copy {
-            if let Err(e) = app.clipboard().write_text(&transcript) {
+        if copy {
+            if let Err(e) = app.clipboard().write_text(&output) {
                 log::error!("Failed to copy to clipboard: {}", e);
             } else {
                 log::info!("Text copied to clipboard");
             }
         }
 
+        // 写入主选区，供鼠标中键粘贴
+        if config.copy_to_primary {
+            if let Err(e) = primary_selection::write_text(&output) {
+                log::warn!("Failed to write primary selection: {}", e);
+            }
+        }
+
+        // 保留 LLM 改写前的原文（开启时覆盖上面写入的主选区）
+        let raw = state.get_raw_transcript();
+        if config.keep_raw_transcript && !raw.is_empty() && raw != transcript {
+            if let Err(e) = primary_selection::write_text(&raw) {
+                log::debug!("Raw transcript not written to primary selection: {}", e);
+            }
+            let _ = app.emit("raw-transcript-available", &raw);
+        }
+
         // 实时输入模式下跳过最后的粘贴/输入（已经实时输入了）
         if !config.realtime_input {
             // 键盘输入（在独立线程中执行以避免影响 X11 状态）
-            if config.auto_type && config.auto_copy {
+            // 当前模式开启自动发送且置信度达标时按回车
+            let trailing_key = if config
+                .postprocess
+                .auto_send
+                .should_send(&state.get_session_mode(), confidence)
+            {
+                TrailingKey::Enter
+            } else {
+                config.trailing_key
+            };
+            if type_out && copy {
                 let result = tokio::task::spawn_blocking(move || match get_keyboard() {
                     Ok(mut guard) => {
                         if let Some(keyboard) = guard.as_mut() {
@@ -916,6 +2634,9 @@ pub async fn handle_stop_recording(app: &AppHandle) -> Result<String, String> {
                                 log::error!("Failed to paste text: {}", e);
                             } else {
                                 log::info!("Text pasted successfully");
+                                if let Err(e) = keyboard.press_trailing_key(trailing_key) {
+                                    log::error!("{}", e);
+                                }
                             }
                         }
                     }
@@ -927,8 +2648,22 @@ pub async fn handle_stop_recording(app: &AppHandle) -> Result<String, String> {
                 if let Err(e) = result {
                     log::error!("Keyboard task failed: {}", e);
                 }
-            } else if config.auto_type {
-                let transcript_clone = transcript.clone();
+
+                // 稍后恢复粘贴前的剪贴板内容
+                if let Some(snapshot) = snapshot {
+                    let app = app.clone();
+                    let pasted = output.clone();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(CLIPBOARD_RESTORE_DELAY).await;
+                        if let Err(e) = snapshot.restore(&app, &pasted) {
+                            log::warn!("{}", e);
+                        } else {
+                            log::info!("Clipboard restored");
+                        }
+                    });
+                }
+            } else if type_out {
+                let transcript_clone = output.clone();
                 let result = tokio::task::spawn_blocking(move || match get_keyboard() {
                     Ok(mut guard) => {
                         if let Some(keyboard) = guard.as_mut() {
@@ -936,6 +2671,9 @@ pub async fn handle_stop_recording(app: &AppHandle) -> Result<String, String> {
                                 log::error!("Failed to type text: {}", e);
                             } else {
                                 log::info!("Text typed successfully");
+                                if let Err(e) = keyboard.press_trailing_key(trailing_key) {
+                                    log::error!("{}", e);
+                                }
                             }
                         }
                     }
@@ -960,5 +2698,24 @@ pub async fn handle_stop_recording(app: &AppHandle) -> Result<String, String> {
         .map_err(|e| e.to_string())?;
 
     log::info!("Recording stopped, transcript: {}", transcript);
+
+    // 命令行调用：输出到 stdout，单次模式下以结果对应的退出码结束
+    if is_print_mode() && !held_back && !transcript.is_empty() {
+        use std::io::Write;
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{}", transcript);
+        let _ = stdout.flush();
+    }
+    if is_once_mode() {
+        let code = if held_back {
+            exit_code::HELD_BACK
+        } else if transcript.is_empty() {
+            exit_code::EMPTY
+        } else {
+            exit_code::SUCCESS
+        };
+        app.exit(code);
+    }
+
     Ok(transcript)
 }
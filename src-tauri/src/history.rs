@@ -2,6 +2,11 @@ use chrono::{DateTime, Local};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use std::fs;
+
+use crate::asr::dual::TranscriptCandidate;
+use crate::asr::metrics::SessionMetrics;
+use crate::asr::provider::AsrSegment;
+use crate::audio::level::AudioStats;
 use std::path::PathBuf;
 
 /// 历史记录条目
@@ -10,6 +15,36 @@ pub struct HistoryEntry {
     pub id: String,
     pub text: String,
     pub timestamp: DateTime<Local>,
+    /// 未经任何处理的识别文本，重新后处理时以此为输入（旧记录为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_text: Option<String>,
+    /// 识别置信度 (0.0 - 1.0)，Provider 不支持时为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    /// 置信度低于阈值，未自动输入，需要用户确认
+    #[serde(default)]
+    pub low_confidence: bool,
+    /// 录音幅度包络 (0-255)，用于绘制迷你波形
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waveform: Vec<u8>,
+    /// 识别性能指标
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<SessionMetrics>,
+    /// 对比模式下各 Provider 的识别结果
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<TranscriptCandidate>,
+    /// 带时间戳的识别分段（原始识别文本，长录音时用于分段展示）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<AsrSegment>,
+    /// 自动检测出的识别语言
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// 保存的录音文件路径（开启 save_recordings 时）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recording: Option<String>,
+    /// 输入电平统计（峰值、削波比例及警告）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_stats: Option<AudioStats>,
 }
 
 /// 历史记录管理器
@@ -61,17 +96,27 @@ impl History {
         Ok(())
     }
 
-    /// 添加一条历史记录
-    pub fn add_entry(&mut self, text: String) {
+    /// 添加一条历史记录，返回新条目以便调用方补充元数据
+    pub fn add_entry(&mut self, text: String) -> Option<&mut HistoryEntry> {
         // 跳过空白文本
         if text.trim().is_empty() {
-            return;
+            return None;
         }
 
         let entry = HistoryEntry {
             id: uuid::Uuid::new_v4().to_string(),
             text,
             timestamp: Local::now(),
+            raw_text: None,
+            confidence: None,
+            low_confidence: false,
+            waveform: Vec::new(),
+            metrics: None,
+            candidates: Vec::new(),
+            segments: Vec::new(),
+            language: None,
+            recording: None,
+            audio_stats: None,
         };
         self.entries.insert(0, entry);
 
@@ -79,6 +124,7 @@ impl History {
         if self.entries.len() > MAX_HISTORY_ENTRIES {
             self.entries.truncate(MAX_HISTORY_ENTRIES);
         }
+        self.entries.first_mut()
     }
 
     /// 删除一条历史记录
@@ -0,0 +1,176 @@
+//! macOS Accessibility API 文本写入
+//!
+//! 通过 AXUIElement 直接替换焦点输入框的选中文本，不经过按键模拟，
+//! 因此不受输入法干扰，在 Electron 等应用中也不会丢字。需要授予辅助功能权限。
+//! 其他平台以及不支持 `AXSelectedText` 的控件返回错误，由调用方回退到按键模拟。
+
+/// 在光标处插入文本（有选中内容时替换选中内容）
+pub fn insert_text(text: &str) -> Result<(), String> {
+    platform::insert_text(text)
+}
+
+/// 是否已授予辅助功能权限（非 macOS 返回 None）
+pub fn is_trusted() -> Option<bool> {
+    platform::is_trusted()
+}
+
+/// 删除光标前 `utf16_len` 个 UTF-16 码元
+pub fn delete_backward(utf16_len: usize) -> Result<(), String> {
+    platform::delete_backward(utf16_len)
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use core_foundation::base::{CFRelease, CFTypeRef, TCFType};
+    use core_foundation::string::{CFString, CFStringRef};
+    use std::ffi::c_void;
+
+    type AXUIElementRef = *const c_void;
+    type AXError = i32;
+
+    const AX_ERROR_SUCCESS: AXError = 0;
+    const AX_VALUE_CF_RANGE_TYPE: u32 = 4;
+
+    #[repr(C)]
+    struct CFRange {
+        location: isize,
+        length: isize,
+    }
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+        fn AXUIElementCreateSystemWide() -> AXUIElementRef;
+        fn AXUIElementCopyAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: *mut CFTypeRef,
+        ) -> AXError;
+        fn AXUIElementSetAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: CFTypeRef,
+        ) -> AXError;
+        fn AXValueCreate(value_type: u32, value: *const c_void) -> CFTypeRef;
+        fn AXValueGetValue(value: CFTypeRef, value_type: u32, out: *mut c_void) -> bool;
+    }
+
+    /// 持有所有权的 CoreFoundation 对象，离开作用域时释放
+    struct Owned(CFTypeRef);
+
+    impl Drop for Owned {
+        fn drop(&mut self) {
+            unsafe { CFRelease(self.0) }
+        }
+    }
+
+    pub fn is_trusted() -> Option<bool> {
+        Some(unsafe { AXIsProcessTrusted() })
+    }
+
+    pub fn insert_text(text: &str) -> Result<(), String> {
+        let element = focused_element()?;
+        set_selected_text(&element, text)
+    }
+
+    pub fn delete_backward(utf16_len: usize) -> Result<(), String> {
+        let element = focused_element()?;
+        let range = selected_range(&element)?;
+        let start = (range.location - utf16_len as isize).max(0);
+        let expanded = CFRange {
+            location: start,
+            length: range.location - start + range.length,
+        };
+        set_selected_range(&element, &expanded)?;
+        set_selected_text(&element, "")
+    }
+
+    /// 获取拥有键盘焦点的 UI 元素
+    fn focused_element() -> Result<Owned, String> {
+        unsafe {
+            if !AXIsProcessTrusted() {
+                return Err("Accessibility permission not granted".to_string());
+            }
+            let system = Owned(AXUIElementCreateSystemWide());
+            let attribute = CFString::from_static_string("AXFocusedUIElement");
+            let mut value: CFTypeRef = std::ptr::null();
+            let err =
+                AXUIElementCopyAttributeValue(system.0, attribute.as_concrete_TypeRef(), &mut value);
+            if err != AX_ERROR_SUCCESS || value.is_null() {
+                return Err(format!("No focused element (AXError {})", err));
+            }
+            Ok(Owned(value))
+        }
+    }
+
+    fn set_selected_text(element: &Owned, text: &str) -> Result<(), String> {
+        let attribute = CFString::from_static_string("AXSelectedText");
+        let value = CFString::new(text);
+        let err = unsafe {
+            AXUIElementSetAttributeValue(
+                element.0,
+                attribute.as_concrete_TypeRef(),
+                value.as_CFTypeRef(),
+            )
+        };
+        if err != AX_ERROR_SUCCESS {
+            return Err(format!("Failed to set selected text (AXError {})", err));
+        }
+        Ok(())
+    }
+
+    fn selected_range(element: &Owned) -> Result<CFRange, String> {
+        let attribute = CFString::from_static_string("AXSelectedTextRange");
+        let mut range = CFRange {
+            location: 0,
+            length: 0,
+        };
+        unsafe {
+            let mut value: CFTypeRef = std::ptr::null();
+            let err =
+                AXUIElementCopyAttributeValue(element.0, attribute.as_concrete_TypeRef(), &mut value);
+            if err != AX_ERROR_SUCCESS || value.is_null() {
+                return Err(format!("Failed to get selected range (AXError {})", err));
+            }
+            let value = Owned(value);
+            let range_ptr: *mut CFRange = &mut range;
+            if !AXValueGetValue(value.0, AX_VALUE_CF_RANGE_TYPE, range_ptr.cast()) {
+                return Err("Selected range is not a CFRange".to_string());
+            }
+        }
+        Ok(range)
+    }
+
+    fn set_selected_range(element: &Owned, range: &CFRange) -> Result<(), String> {
+        let attribute = CFString::from_static_string("AXSelectedTextRange");
+        unsafe {
+            let range_ptr: *const CFRange = range;
+            let value = AXValueCreate(AX_VALUE_CF_RANGE_TYPE, range_ptr.cast());
+            if value.is_null() {
+                return Err("Failed to create range value".to_string());
+            }
+            let value = Owned(value);
+            let err =
+                AXUIElementSetAttributeValue(element.0, attribute.as_concrete_TypeRef(), value.0);
+            if err != AX_ERROR_SUCCESS {
+                return Err(format!("Failed to set selected range (AXError {})", err));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    pub fn is_trusted() -> Option<bool> {
+        None
+    }
+
+    pub fn insert_text(_text: &str) -> Result<(), String> {
+        Err("Accessibility text insertion is only available on macOS".to_string())
+    }
+
+    pub fn delete_backward(_utf16_len: usize) -> Result<(), String> {
+        Err("Accessibility text insertion is only available on macOS".to_string())
+    }
+}
@@ -0,0 +1,200 @@
+//! 当前前台应用识别
+//!
+//! 返回拥有焦点窗口的应用名称，用于按应用定制输出。获取失败时返回 None。
+//! 另外提供焦点窗口标识和重新激活，用于把输出锁定到开始录音时的窗口。
+
+/// 获取前台应用名称
+pub fn active_app_name() -> Option<String> {
+    match platform::active_app_name() {
+        Ok(name) if !name.is_empty() => Some(name),
+        Ok(_) => None,
+        Err(e) => {
+            log::debug!("Failed to get active app: {}", e);
+            None
+        }
+    }
+}
+
+/// 焦点窗口标识（X11 窗口 ID / macOS 应用名 / Windows 窗口句柄）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FocusedWindow(String);
+
+/// 获取当前焦点窗口（Wayland 等无法获取时返回 None）
+pub fn focused_window() -> Option<FocusedWindow> {
+    match platform::focused_window() {
+        Ok(id) if !id.is_empty() => Some(FocusedWindow(id)),
+        Ok(_) => None,
+        Err(e) => {
+            log::debug!("Failed to get focused window: {}", e);
+            None
+        }
+    }
+}
+
+/// 重新激活窗口
+pub fn activate(window: &FocusedWindow) -> Result<(), String> {
+    platform::activate(&window.0)
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::fs;
+    use std::process::Command;
+
+    pub fn focused_window() -> Result<String, String> {
+        xdotool(&["getactivewindow"])
+    }
+
+    pub fn activate(id: &str) -> Result<(), String> {
+        xdotool(&["windowactivate", "--sync", id]).map(|_| ())
+    }
+
+    fn xdotool(args: &[&str]) -> Result<String, String> {
+        let output = Command::new("xdotool")
+            .args(args)
+            .output()
+            .map_err(|e| format!("Failed to run xdotool: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// 通过 xdotool 获取焦点窗口的进程，再读取进程名（仅 X11）
+    pub fn active_app_name() -> Result<String, String> {
+        let output = Command::new("xdotool")
+            .args(["getactivewindow", "getwindowpid"])
+            .output()
+            .map_err(|e| format!("Failed to run xdotool: {}", e))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        let pid = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        fs::read_to_string(format!("/proc/{}/comm", pid))
+            .map(|name| name.trim().to_string())
+            .map_err(|e| format!("Failed to read process name: {}", e))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::process::Command;
+
+    /// macOS 以前台应用为单位
+    pub fn focused_window() -> Result<String, String> {
+        active_app_name()
+    }
+
+    pub fn activate(app_name: &str) -> Result<(), String> {
+        let script = format!(
+            "tell application \"{}\" to activate",
+            app_name.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        let output = Command::new("osascript")
+            .args(["-e", &script])
+            .output()
+            .map_err(|e| format!("Failed to run osascript: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        Ok(())
+    }
+
+    pub fn active_app_name() -> Result<String, String> {
+        let output = Command::new("osascript")
+            .args([
+                "-e",
+                "tell application \"System Events\" to get name of first application process whose frontmost is true",
+            ])
+            .output()
+            .map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::path::Path;
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId, SetForegroundWindow,
+    };
+
+    pub fn focused_window() -> Result<String, String> {
+        let hwnd = unsafe { GetForegroundWindow() };
+        if hwnd.0.is_null() {
+            return Err("No foreground window".to_string());
+        }
+        Ok((hwnd.0 as isize).to_string())
+    }
+
+    pub fn activate(id: &str) -> Result<(), String> {
+        let handle: isize = id
+            .parse()
+            .map_err(|_| format!("Invalid window handle: {}", id))?;
+        let ok = unsafe { SetForegroundWindow(HWND(handle as *mut std::ffi::c_void)) };
+        if !ok.as_bool() {
+            return Err("SetForegroundWindow refused to switch focus".to_string());
+        }
+        Ok(())
+    }
+
+    /// 返回前台窗口所属进程的可执行文件名（不含扩展名）
+    pub fn active_app_name() -> Result<String, String> {
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            let mut pid = 0u32;
+            GetWindowThreadProcessId(hwnd, Some(&mut pid));
+            if pid == 0 {
+                return Err("No foreground window".to_string());
+            }
+
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
+                .map_err(|e| format!("Failed to open process: {}", e))?;
+            let mut buffer = [0u16; 260];
+            let mut len = buffer.len() as u32;
+            let result = QueryFullProcessImageNameW(
+                process,
+                PROCESS_NAME_WIN32,
+                PWSTR(buffer.as_mut_ptr()),
+                &mut len,
+            );
+            let _ = CloseHandle(process);
+            result.map_err(|e| format!("Failed to query process image: {}", e))?;
+
+            let path = String::from_utf16_lossy(&buffer[..len as usize]);
+            Ok(Path::new(&path)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or(path))
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub fn active_app_name() -> Result<String, String> {
+        Err("Active app detection is not supported on this platform".to_string())
+    }
+
+    pub fn focused_window() -> Result<String, String> {
+        Err("Focused window detection is not supported on this platform".to_string())
+    }
+
+    pub fn activate(_id: &str) -> Result<(), String> {
+        Err("Window activation is not supported on this platform".to_string())
+    }
+}
@@ -0,0 +1,47 @@
+//! 剪贴板快照
+//!
+//! 自动粘贴需要借用剪贴板，开启 `restore_clipboard` 后先保存用户原有的内容
+//! （文本或图片），粘贴完成后再写回。
+
+use tauri::image::Image;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// 粘贴前的剪贴板内容
+pub enum ClipboardSnapshot {
+    Text(String),
+    Image(Image<'static>),
+    /// 剪贴板为空或内容格式无法读取
+    Empty,
+}
+
+impl ClipboardSnapshot {
+    /// 读取当前剪贴板内容
+    pub fn capture(app: &AppHandle) -> Self {
+        let clipboard = app.clipboard();
+        if let Ok(text) = clipboard.read_text() {
+            return Self::Text(text);
+        }
+        match clipboard.read_image() {
+            Ok(image) => Self::Image(image.to_owned()),
+            Err(_) => Self::Empty,
+        }
+    }
+
+    /// 写回保存的内容
+    ///
+    /// 剪贴板已不再是 `pasted`（用户在此期间复制了其他内容）时不做任何操作。
+    pub fn restore(&self, app: &AppHandle, pasted: &str) -> Result<(), String> {
+        let clipboard = app.clipboard();
+        if clipboard.read_text().ok().as_deref() != Some(pasted) {
+            log::debug!("Clipboard changed after paste, not restoring");
+            return Ok(());
+        }
+        match self {
+            Self::Text(text) => clipboard.write_text(text.clone()),
+            Self::Image(image) => clipboard.write_image(image),
+            Self::Empty => clipboard.clear(),
+        }
+        .map_err(|e| format!("Failed to restore clipboard: {}", e))
+    }
+}
@@ -0,0 +1,141 @@
+//! 键盘输出自检
+//!
+//! 检查当前键盘后端依赖的环境（显示服务器类型、ydotool/uinput、辅助功能权限等），
+//! 帮助用户在“什么都没输入”时定位原因，而不必翻日志。
+
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use super::accessibility;
+use super::keyboard::KeyboardBackend;
+
+/// 单项检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyboardCheck {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl KeyboardCheck {
+    fn new(name: &str, ok: bool, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            ok,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// 按后端执行环境检查
+pub fn run_checks(backend: KeyboardBackend) -> Vec<KeyboardCheck> {
+    let mut checks = vec![session_check()];
+
+    match backend {
+        KeyboardBackend::Ydotool => {
+            checks.push(program_check("ydotool", &["help"]));
+            checks.push(ydotool_socket_check());
+            checks.push(uinput_check());
+        }
+        KeyboardBackend::Accessibility => checks.push(accessibility_check()),
+        KeyboardBackend::Enigo | KeyboardBackend::SendInput => {
+            if is_wayland() {
+                checks.push(KeyboardCheck::new(
+                    "Wayland",
+                    false,
+                    "Simulated input is often blocked on Wayland; try the ydotool backend",
+                ));
+            }
+            if cfg!(target_os = "macos") {
+                checks.push(accessibility_check());
+            }
+        }
+    }
+
+    if cfg!(target_os = "linux") {
+        // 焦点窗口识别和主选区依赖的外部工具
+        checks.push(program_check("xdotool", &["version"]));
+        if is_wayland() {
+            checks.push(program_check("wl-copy", &["--version"]));
+        } else {
+            checks.push(program_check("xclip", &["-version"]));
+        }
+    }
+
+    checks
+}
+
+fn is_wayland() -> bool {
+    cfg!(target_os = "linux") && std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/// 显示服务器 / 平台
+fn session_check() -> KeyboardCheck {
+    let detail = if cfg!(target_os = "macos") {
+        "macOS".to_string()
+    } else if cfg!(target_os = "windows") {
+        "Windows".to_string()
+    } else if is_wayland() {
+        "Wayland".to_string()
+    } else if std::env::var_os("DISPLAY").is_some() {
+        "X11".to_string()
+    } else {
+        return KeyboardCheck::new("Session", false, "No display server detected");
+    };
+    KeyboardCheck::new("Session", true, detail)
+}
+
+/// 外部程序是否可以运行
+fn program_check(program: &str, args: &[&str]) -> KeyboardCheck {
+    let result = Command::new(program)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match result {
+        Ok(_) => KeyboardCheck::new(program, true, "Installed"),
+        Err(e) => KeyboardCheck::new(program, false, format!("Not available: {}", e)),
+    }
+}
+
+/// ydotoold 的套接字（YDOTOOL_SOCKET 优先，否则按 ydotool 的默认位置查找）
+fn ydotool_socket_check() -> KeyboardCheck {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if let Some(path) = std::env::var_os("YDOTOOL_SOCKET") {
+        candidates.push(path.into());
+    } else {
+        if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+            candidates.push(PathBuf::from(dir).join(".ydotool_socket"));
+        }
+        candidates.push(PathBuf::from("/tmp/.ydotool_socket"));
+    }
+    match candidates.iter().find(|path| path.exists()) {
+        Some(path) => KeyboardCheck::new("ydotoold", true, path.display().to_string()),
+        None => KeyboardCheck::new("ydotoold", false, "Socket not found; is ydotoold running?"),
+    }
+}
+
+/// /dev/uinput 是否可写（ydotoold 需要）
+fn uinput_check() -> KeyboardCheck {
+    match std::fs::OpenOptions::new().write(true).open("/dev/uinput") {
+        Ok(_) => KeyboardCheck::new("uinput", true, "/dev/uinput is writable"),
+        Err(e) => KeyboardCheck::new(
+            "uinput",
+            false,
+            format!("/dev/uinput not writable ({}); ydotoold may need root or the input group", e),
+        ),
+    }
+}
+
+fn accessibility_check() -> KeyboardCheck {
+    match accessibility::is_trusted() {
+        Some(true) => KeyboardCheck::new("Accessibility", true, "Permission granted"),
+        Some(false) => KeyboardCheck::new(
+            "Accessibility",
+            false,
+            "Grant Speaky access in System Settings > Privacy & Security > Accessibility",
+        ),
+        None => KeyboardCheck::new("Accessibility", false, "Only available on macOS"),
+    }
+}
@@ -0,0 +1,136 @@
+//! 双击修饰键触发录音
+//!
+//! 单独的修饰键不能注册为全局快捷键，这里通过全局按键监听（见 `key_events`）自行识别。
+//! 第二次按下视为快捷键按下，松开视为快捷键释放，与普通快捷键的按住说话、切换模式一致。
+//! Linux 仅支持 X11，macOS 需要辅助功能权限。
+
+use parking_lot::Mutex;
+use rdev::{EventType, Key};
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+
+/// 两次按下的最大间隔
+pub(super) const DOUBLE_TAP_WINDOW: Duration = Duration::from_millis(400);
+
+/// 可双击触发的修饰键（不区分左右）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TapModifier {
+    Ctrl,
+    Alt,
+    Shift,
+    Super,
+}
+
+impl TapModifier {
+    /// 解析修饰键名称（与 `parse_shortcut` 的修饰键写法一致）
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "ctrl" | "control" => Some(Self::Ctrl),
+            "alt" | "option" => Some(Self::Alt),
+            "shift" => Some(Self::Shift),
+            "super" | "meta" | "cmd" | "command" | "win" => Some(Self::Super),
+            _ => None,
+        }
+    }
+
+    /// 与 `parse_shortcut` 一致的修饰键名称
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ctrl => "Ctrl",
+            Self::Alt => "Alt",
+            Self::Shift => "Shift",
+            Self::Super => "Super",
+        }
+    }
+
+    /// 按键对应的修饰键
+    pub(super) fn from_key(key: Key) -> Option<Self> {
+        [Self::Ctrl, Self::Alt, Self::Shift, Self::Super]
+            .into_iter()
+            .find(|modifier| modifier.matches(key))
+    }
+
+    pub(super) fn matches(self, key: Key) -> bool {
+        match self {
+            Self::Ctrl => matches!(key, Key::ControlLeft | Key::ControlRight),
+            Self::Alt => matches!(key, Key::Alt | Key::AltGr),
+            Self::Shift => matches!(key, Key::ShiftLeft | Key::ShiftRight),
+            Self::Super => matches!(key, Key::MetaLeft | Key::MetaRight),
+        }
+    }
+}
+
+/// 双击识别状态
+#[derive(Default)]
+struct Detector {
+    modifier: Option<TapModifier>,
+    /// 修饰键是否按住（过滤系统重复发送的按下事件）
+    held: bool,
+    /// 按住期间是否按了其他键（组合键不算单击）
+    combined: bool,
+    /// 上一次单击松开的时间
+    last_tap: Option<Instant>,
+    /// 第二次按下已触发，等待松开
+    triggered: bool,
+}
+
+impl Detector {
+    /// 处理按键事件，返回 Some(true) 表示触发按下，Some(false) 表示触发释放
+    fn handle(&mut self, event: EventType) -> Option<bool> {
+        let modifier = self.modifier?;
+        match event {
+            EventType::KeyPress(key) if modifier.matches(key) => {
+                if self.held {
+                    return None;
+                }
+                self.held = true;
+                self.combined = false;
+                if self
+                    .last_tap
+                    .take()
+                    .is_some_and(|tap| tap.elapsed() <= DOUBLE_TAP_WINDOW)
+                {
+                    self.triggered = true;
+                    return Some(true);
+                }
+                None
+            }
+            EventType::KeyRelease(key) if modifier.matches(key) => {
+                self.held = false;
+                if self.triggered {
+                    self.triggered = false;
+                    return Some(false);
+                }
+                if !self.combined {
+                    self.last_tap = Some(Instant::now());
+                }
+                None
+            }
+            EventType::KeyPress(_) => {
+                self.combined = true;
+                self.last_tap = None;
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+static DETECTOR: LazyLock<Arc<Mutex<Detector>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(Detector::default())));
+
+/// 设置双击触发的修饰键，None 表示关闭
+pub fn set_modifier(modifier: Option<TapModifier>) {
+    let mut detector = DETECTOR.lock();
+    if detector.modifier != modifier {
+        *detector = Detector {
+            modifier,
+            ..Default::default()
+        };
+    }
+}
+
+/// 处理一个全局按键事件（由 `key_events` 调用）
+pub(super) fn handle_event(event: EventType) -> Option<bool> {
+    DETECTOR.lock().handle(event)
+}
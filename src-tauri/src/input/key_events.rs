@@ -0,0 +1,59 @@
+//! 全局输入监听
+//!
+//! rdev 的监听线程启动后无法停止，整个进程只启动一次，
+//! 事件分发给双击修饰键、鼠标侧键识别和快捷键录制。
+
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Once};
+
+use super::{double_tap, mouse_trigger, shortcut_capture};
+
+type TriggerHandler = Box<dyn Fn(bool) + Send>;
+
+static LISTENER: Once = Once::new();
+// 监听启动失败（Wayland、缺少辅助功能权限等）
+static FAILED: AtomicBool = AtomicBool::new(false);
+// 双击修饰键、鼠标侧键触发录音时的回调
+static ON_TRIGGER: LazyLock<Arc<Mutex<Option<TriggerHandler>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+
+/// 启动全局输入监听（只启动一次）
+pub fn start() {
+    LISTENER.call_once(|| {
+        std::thread::spawn(|| {
+            log::info!("Global input listener started");
+            let result = rdev::listen(|event| {
+                // 录制快捷键期间不触发录音
+                if shortcut_capture::is_capturing() {
+                    shortcut_capture::handle_event(event.event_type);
+                    return;
+                }
+                let triggered = double_tap::handle_event(event.event_type)
+                    .or_else(|| mouse_trigger::handle_event(event.event_type));
+                if let Some(pressed) = triggered {
+                    if let Some(on_trigger) = ON_TRIGGER.lock().as_ref() {
+                        on_trigger(pressed);
+                    }
+                }
+            });
+            if let Err(e) = result {
+                log::error!("Global input listener failed: {:?}", e);
+                FAILED.store(true, Ordering::SeqCst);
+            }
+        });
+    });
+}
+
+/// 设置触发回调并启动监听
+///
+/// `on_trigger(true)` 对应快捷键按下，`on_trigger(false)` 对应释放。
+pub fn start_triggers(on_trigger: impl Fn(bool) + Send + 'static) {
+    *ON_TRIGGER.lock() = Some(Box::new(on_trigger));
+    start();
+}
+
+/// 监听是否已失败
+pub fn failed() -> bool {
+    FAILED.load(Ordering::SeqCst)
+}
@@ -1,72 +1,193 @@
 use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use serde::{Deserialize, Serialize};
+use std::process::Command;
 use std::thread;
 use std::time::Duration;
 
+use super::{accessibility, send_input};
+
+/// 键盘输入后端
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyboardBackend {
+    /// enigo（X11 / Windows / macOS）
+    #[default]
+    Enigo,
+    /// ydotool（通过 ydotoold 的 uinput 设备注入，适用于限制虚拟输入的 Wayland 合成器）
+    Ydotool,
+    /// macOS Accessibility API（直接写入焦点输入框，不经过输入法；不支持的控件回退到 enigo）
+    Accessibility,
+    /// Windows SendInput（以 KEYEVENTF_UNICODE 直接发送字符，适用于会丢失中文的应用）
+    SendInput,
+}
+
+/// 实时输入时替换上一次文本的方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RealtimeReplace {
+    /// 逐字退格
+    #[default]
+    Backspace,
+    /// Shift+← 选中上一次输入的文本后一次删除（退格会触发自动补全或括号删除的编辑器）
+    Select,
+}
+
+/// 输入结果后追加的按键
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingKey {
+    #[default]
+    None,
+    Space,
+    /// 回车（聊天软件中直接发送消息）
+    Enter,
+}
+
+/// 输入节奏（远程桌面等较慢的环境下放慢速度可避免丢字）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypingConfig {
+    /// 每秒输入的字符数，0 表示不限制（enigo 和 ydotool 后端）
+    #[serde(default)]
+    pub chars_per_second: u32,
+    /// 连续按键（退格、粘贴快捷键）之间的间隔（毫秒）
+    #[serde(default)]
+    pub key_delay_ms: u32,
+    /// 粘贴前等待剪贴板就绪的时间（毫秒）
+    #[serde(default = "default_paste_settle_ms")]
+    pub paste_settle_ms: u32,
+    /// 输入前等待焦点切换完成的时间（毫秒）
+    #[serde(default = "default_focus_settle_ms")]
+    pub focus_settle_ms: u32,
+}
+
+fn default_paste_settle_ms() -> u32 {
+    50
+}
+
+fn default_focus_settle_ms() -> u32 {
+    100
+}
+
+impl Default for TypingConfig {
+    fn default() -> Self {
+        Self {
+            chars_per_second: 0,
+            key_delay_ms: 0,
+            paste_settle_ms: default_paste_settle_ms(),
+            focus_settle_ms: default_focus_settle_ms(),
+        }
+    }
+}
+
+impl TypingConfig {
+    /// 按输入速度计算的字符间隔（不限速时为 None）
+    fn char_interval(&self) -> Option<Duration> {
+        (self.chars_per_second > 0)
+            .then(|| Duration::from_micros(1_000_000 / self.chars_per_second as u64))
+    }
+}
+
+/// 粘贴快捷键各按键之间的最小间隔
+const MIN_PASTE_KEY_GAP_MS: u32 = 10;
+
+/// 键盘输入设置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyboardOptions {
+    pub backend: KeyboardBackend,
+    /// SendInput 每批字符之间的间隔（毫秒）
+    pub send_input_delay_ms: u32,
+    pub typing: TypingConfig,
+    pub realtime_replace: RealtimeReplace,
+}
+
+enum Injector {
+    /// enigo 输入失败时回退到 SendInput（仅 Windows）
+    Enigo(Enigo),
+    Ydotool,
+    /// 粘贴快捷键和回退输入仍使用 enigo
+    Accessibility(Enigo),
+    /// 删除和粘贴仍使用 enigo
+    SendInput(Enigo),
+}
+
 pub struct KeyboardSimulator {
-    enigo: Enigo,
-    /// 跟踪已输入的字符数（用于实时更新）
-    last_input_len: usize,
+    injector: Injector,
+    options: KeyboardOptions,
+    /// 跟踪已输入的文本（用于实时更新）
+    last_input: String,
 }
 
 impl KeyboardSimulator {
-    pub fn new() -> Result<Self, String> {
-        let enigo = Enigo::new(&Settings::default())
-            .map_err(|e| format!("Failed to create Enigo: {}", e))?;
+    pub fn new(options: KeyboardOptions) -> Result<Self, String> {
+        let injector = match options.backend {
+            KeyboardBackend::Enigo => Injector::Enigo(new_enigo()?),
+            KeyboardBackend::Ydotool => Injector::Ydotool,
+            KeyboardBackend::Accessibility => Injector::Accessibility(new_enigo()?),
+            KeyboardBackend::SendInput => Injector::SendInput(new_enigo()?),
+        };
         Ok(Self {
-            enigo,
-            last_input_len: 0,
+            injector,
+            options,
+            last_input: String::new(),
         })
     }
 
+    /// 当前使用的设置
+    pub fn options(&self) -> KeyboardOptions {
+        self.options
+    }
+
     /// 重置输入状态（开始新的录音会话时调用）
     pub fn reset_input_state(&mut self) {
-        self.last_input_len = 0;
+        self.last_input.clear();
     }
 
     /// 实时更新文本（删除旧文本，输入新文本）
     pub fn update_text(&mut self, new_text: &str) -> Result<(), String> {
-        let new_len = new_text.chars().count();
-
         // 删除之前输入的字符
-        if self.last_input_len > 0 {
-            for _ in 0..self.last_input_len {
-                self.enigo
-                    .key(Key::Backspace, Direction::Click)
-                    .map_err(|e| format!("Failed to press backspace: {}", e))?;
-            }
+        if !self.last_input.is_empty() {
+            self.delete_last_input()?;
             thread::sleep(Duration::from_millis(5));
         }
 
         // 输入新文本
         if !new_text.is_empty() {
-            self.enigo
-                .text(new_text)
-                .map_err(|e| format!("Failed to type text: {}", e))?;
+            self.text(new_text)?;
         }
 
-        self.last_input_len = new_len;
+        self.last_input = new_text.to_string();
         Ok(())
     }
 
     /// 完成实时输入（重置状态，不做任何操作）
     pub fn finish_realtime_input(&mut self) {
-        self.last_input_len = 0;
+        self.last_input.clear();
     }
 
     /// 模拟键盘输入文本
     pub fn type_text(&mut self, text: &str) -> Result<(), String> {
         // 等待一小段时间确保焦点切换完成
-        thread::sleep(Duration::from_millis(100));
+        sleep_ms(self.options.typing.focus_settle_ms);
 
-        self.enigo
-            .text(text)
-            .map_err(|e| format!("Failed to type text: {}", e))
+        self.text(text)
     }
 
     /// 模拟粘贴操作（跨平台：macOS 使用 Cmd+V，其他平台使用 Ctrl+V）
     pub fn paste(&mut self) -> Result<(), String> {
         // 短暂等待确保剪贴板内容可用
-        thread::sleep(Duration::from_millis(50));
+        sleep_ms(self.options.typing.paste_settle_ms);
+        let key_gap = self.options.typing.key_delay_ms.max(MIN_PASTE_KEY_GAP_MS);
+
+        let enigo = match &mut self.injector {
+            Injector::Enigo(enigo)
+            | Injector::Accessibility(enigo)
+            | Injector::SendInput(enigo) => enigo,
+            Injector::Ydotool => {
+                ydotool::paste(key_gap)?;
+                thread::sleep(Duration::from_millis(30));
+                return Ok(());
+            }
+        };
 
         // macOS 使用 Command 键，其他平台使用 Control 键
         #[cfg(target_os = "macos")]
@@ -75,21 +196,21 @@ impl KeyboardSimulator {
         let modifier_key = Key::Control;
 
         // 按下修饰键
-        self.enigo
+        enigo
             .key(modifier_key, Direction::Press)
             .map_err(|e| format!("Failed to press modifier: {}", e))?;
 
-        thread::sleep(Duration::from_millis(10));
+        sleep_ms(key_gap);
 
         // 按下 V
-        self.enigo
+        enigo
             .key(Key::Unicode('v'), Direction::Click)
             .map_err(|e| format!("Failed to press V: {}", e))?;
 
-        thread::sleep(Duration::from_millis(10));
+        sleep_ms(key_gap);
 
         // 释放修饰键
-        self.enigo
+        enigo
             .key(modifier_key, Direction::Release)
             .map_err(|e| format!("Failed to release modifier: {}", e))?;
 
@@ -98,10 +219,233 @@ impl KeyboardSimulator {
 
         Ok(())
     }
+
+    /// 输入结果后按下追加键
+    pub fn press_trailing_key(&mut self, key: TrailingKey) -> Result<(), String> {
+        let (enigo_key, code) = match key {
+            TrailingKey::None => return Ok(()),
+            TrailingKey::Space => (Key::Space, ydotool::KEY_SPACE),
+            TrailingKey::Enter => (Key::Return, ydotool::KEY_ENTER),
+        };
+        sleep_ms(self.options.typing.key_delay_ms);
+        match &mut self.injector {
+            Injector::Enigo(enigo)
+            | Injector::Accessibility(enigo)
+            | Injector::SendInput(enigo) => enigo
+                .key(enigo_key, Direction::Click)
+                .map_err(|e| format!("Failed to press {:?}: {}", key, e)),
+            Injector::Ydotool => ydotool::press(code),
+        }
+    }
+
+    fn text(&mut self, text: &str) -> Result<(), String> {
+        let options = self.options;
+        let interval = options.typing.char_interval();
+        match &mut self.injector {
+            Injector::Enigo(enigo) => match enigo_text(enigo, text, interval) {
+                Ok(()) => Ok(()),
+                Err(e) => {
+                    // Windows 上回退到 SendInput，其他平台直接报告 enigo 的错误
+                    if send_input::type_text(text, options.send_input_delay_ms).is_ok() {
+                        log::warn!("enigo failed ({}), text typed via SendInput", e);
+                        Ok(())
+                    } else {
+                        Err(format!("Failed to type text: {}", e))
+                    }
+                }
+            },
+            Injector::SendInput(_) => send_input::type_text(text, options.send_input_delay_ms),
+            Injector::Ydotool => ydotool::type_text(text, interval),
+            Injector::Accessibility(enigo) => match accessibility::insert_text(text) {
+                Ok(()) => Ok(()),
+                Err(e) => {
+                    log::debug!("Accessibility insert failed, falling back to enigo: {}", e);
+                    enigo_text(enigo, text, interval)
+                }
+            },
+        }
+    }
+
+    /// 删除实时输入时上一次输入的文本
+    fn delete_last_input(&mut self) -> Result<(), String> {
+        let count = self.last_input.chars().count();
+        let key_delay = self.options.typing.key_delay_ms;
+        let select = self.options.realtime_replace == RealtimeReplace::Select;
+        match &mut self.injector {
+            Injector::Enigo(enigo) | Injector::SendInput(enigo) if select => {
+                select_backward(enigo, count, key_delay)?;
+                press_backspace(enigo, 1, 0)
+            }
+            Injector::Enigo(enigo) | Injector::SendInput(enigo) => {
+                press_backspace(enigo, count, key_delay)
+            }
+            Injector::Ydotool if select => {
+                ydotool::select_backward(count, key_delay)?;
+                ydotool::backspace(1, 0)
+            }
+            Injector::Ydotool => ydotool::backspace(count, key_delay),
+            // Accessibility API 本身就是选中后替换
+            Injector::Accessibility(enigo) => {
+                // Accessibility API 的文本范围以 UTF-16 码元计
+                let units = self.last_input.encode_utf16().count();
+                match accessibility::delete_backward(units) {
+                    Ok(()) => Ok(()),
+                    Err(e) => {
+                        log::debug!("Accessibility delete failed, falling back to enigo: {}", e);
+                        press_backspace(enigo, count, key_delay)
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn new_enigo() -> Result<Enigo, String> {
+    Enigo::new(&Settings::default()).map_err(|e| format!("Failed to create Enigo: {}", e))
+}
+
+fn sleep_ms(ms: u32) {
+    if ms > 0 {
+        thread::sleep(Duration::from_millis(ms as u64));
+    }
+}
+
+/// 用 enigo 输入文本，限速时逐字输入
+fn enigo_text(enigo: &mut Enigo, text: &str, interval: Option<Duration>) -> Result<(), String> {
+    let Some(interval) = interval else {
+        return enigo
+            .text(text)
+            .map_err(|e| format!("Failed to type text: {}", e));
+    };
+    let mut buf = [0u8; 4];
+    for (index, ch) in text.chars().enumerate() {
+        if index > 0 {
+            thread::sleep(interval);
+        }
+        enigo
+            .text(ch.encode_utf8(&mut buf))
+            .map_err(|e| format!("Failed to type text: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 按住 Shift 向左选中 `count` 个字符
+fn select_backward(enigo: &mut Enigo, count: usize, key_delay_ms: u32) -> Result<(), String> {
+    enigo
+        .key(Key::Shift, Direction::Press)
+        .map_err(|e| format!("Failed to press shift: {}", e))?;
+    let mut result = Ok(());
+    for index in 0..count {
+        if index > 0 {
+            sleep_ms(key_delay_ms);
+        }
+        if let Err(e) = enigo.key(Key::LeftArrow, Direction::Click) {
+            result = Err(format!("Failed to press left arrow: {}", e));
+            break;
+        }
+    }
+    // 无论是否出错都要松开 Shift，避免卡键
+    enigo
+        .key(Key::Shift, Direction::Release)
+        .map_err(|e| format!("Failed to release shift: {}", e))?;
+    result
+}
+
+fn press_backspace(enigo: &mut Enigo, count: usize, key_delay_ms: u32) -> Result<(), String> {
+    for index in 0..count {
+        if index > 0 {
+            sleep_ms(key_delay_ms);
+        }
+        enigo
+            .key(Key::Backspace, Direction::Click)
+            .map_err(|e| format!("Failed to press backspace: {}", e))?;
+    }
+    Ok(())
 }
 
 impl Default for KeyboardSimulator {
     fn default() -> Self {
-        Self::new().expect("Failed to create keyboard simulator")
+        Self::new(KeyboardOptions::default()).expect("Failed to create keyboard simulator")
+    }
+}
+
+/// 调用 ydotool 命令行（需要 ydotoold 正在运行，`YDOTOOL_SOCKET` 环境变量原样传递）
+///
+/// ydotool 按键盘布局逐键输入，只能输入布局上存在的字符；中文等文本应开启自动复制走粘贴。
+mod ydotool {
+    use super::Command;
+    use std::time::Duration;
+
+    /// Linux input-event-codes 中的键码
+    const KEY_BACKSPACE: u16 = 14;
+    pub const KEY_ENTER: u16 = 28;
+    const KEY_LEFTCTRL: u16 = 29;
+    const KEY_LEFTSHIFT: u16 = 42;
+    const KEY_V: u16 = 47;
+    pub const KEY_SPACE: u16 = 57;
+    const KEY_LEFT: u16 = 105;
+
+    pub fn type_text(text: &str, interval: Option<Duration>) -> Result<(), String> {
+        let delay = interval.map_or(0, |interval| interval.as_millis()).to_string();
+        run(&["type", "--key-delay", &delay, "--", text])
+    }
+
+    pub fn backspace(count: usize, key_delay_ms: u32) -> Result<(), String> {
+        let press = format!("{}:1", KEY_BACKSPACE);
+        let release = format!("{}:0", KEY_BACKSPACE);
+        let delay = key_delay_ms.to_string();
+        let mut args = vec!["key", "--key-delay", delay.as_str()];
+        for _ in 0..count {
+            args.push(&press);
+            args.push(&release);
+        }
+        run(&args)
+    }
+
+    pub fn press(code: u16) -> Result<(), String> {
+        let press = format!("{}:1", code);
+        let release = format!("{}:0", code);
+        run(&["key", &press, &release])
+    }
+
+    pub fn select_backward(count: usize, key_delay_ms: u32) -> Result<(), String> {
+        let mut keys = vec![format!("{}:1", KEY_LEFTSHIFT)];
+        for _ in 0..count {
+            keys.push(format!("{}:1", KEY_LEFT));
+            keys.push(format!("{}:0", KEY_LEFT));
+        }
+        keys.push(format!("{}:0", KEY_LEFTSHIFT));
+        let delay = key_delay_ms.to_string();
+        let mut args = vec!["key", "--key-delay", delay.as_str()];
+        args.extend(keys.iter().map(String::as_str));
+        run(&args)
+    }
+
+    pub fn paste(key_delay_ms: u32) -> Result<(), String> {
+        let keys = [
+            format!("{}:1", KEY_LEFTCTRL),
+            format!("{}:1", KEY_V),
+            format!("{}:0", KEY_V),
+            format!("{}:0", KEY_LEFTCTRL),
+        ];
+        let delay = key_delay_ms.to_string();
+        let mut args = vec!["key", "--key-delay", delay.as_str()];
+        args.extend(keys.iter().map(String::as_str));
+        run(&args)
+    }
+
+    fn run(args: &[&str]) -> Result<(), String> {
+        let output = Command::new("ydotool")
+            .args(args)
+            .output()
+            .map_err(|e| format!("Failed to run ydotool: {}", e))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "ydotool failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
     }
 }
@@ -1 +1,11 @@
+pub mod accessibility;
+pub mod active_app;
+pub mod clipboard;
+pub mod diagnostics;
+pub mod double_tap;
+pub mod key_events;
 pub mod keyboard;
+pub mod mouse_trigger;
+pub mod primary_selection;
+pub mod send_input;
+pub mod shortcut_capture;
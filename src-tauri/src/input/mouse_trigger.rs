@@ -0,0 +1,144 @@
+//! 鼠标侧键触发录音
+//!
+//! 支持 "Mouse4"（后退键）、"Mouse5"（前进键）及修饰键组合（如 "Ctrl+Mouse4"），
+//! 按下侧键视为快捷键按下，松开视为释放。全局监听不会拦截点击，浏览器等程序仍会收到侧键。
+//! macOS 上 rdev 的监听只订阅左右键事件，收不到侧键，因此不支持侧键触发。
+
+use parking_lot::Mutex;
+use rdev::{Button, EventType};
+use std::sync::{Arc, LazyLock};
+
+use super::double_tap::TapModifier;
+
+/// 鼠标侧键
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SideButton {
+    /// 后退键 (Mouse4)
+    Back,
+    /// 前进键 (Mouse5)
+    Forward,
+}
+
+/// 当前平台能否收到侧键事件
+pub const SUPPORTED: bool = cfg!(not(target_os = "macos"));
+
+// 侧键在 rdev 中的按钮编号：X11 为 8/9，Windows 为 XBUTTON1/2
+#[cfg(windows)]
+const SIDE_BUTTON_CODES: (u8, u8) = (1, 2);
+#[cfg(not(windows))]
+const SIDE_BUTTON_CODES: (u8, u8) = (8, 9);
+
+impl SideButton {
+    /// 与 `parse_shortcut` 风格一致的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Back => "Mouse4",
+            Self::Forward => "Mouse5",
+        }
+    }
+
+    /// rdev 按钮对应的侧键
+    pub(super) fn from_button(button: Button) -> Option<Self> {
+        match button {
+            Button::Unknown(code) if code == SIDE_BUTTON_CODES.0 => Some(Self::Back),
+            Button::Unknown(code) if code == SIDE_BUTTON_CODES.1 => Some(Self::Forward),
+            _ => None,
+        }
+    }
+}
+
+/// 鼠标触发方式：修饰键 + 侧键
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MouseTrigger {
+    pub modifiers: Vec<TapModifier>,
+    pub button: SideButton,
+}
+
+impl MouseTrigger {
+    /// 解析 "Mouse4"、"Ctrl+Shift+Mouse5" 等写法（不是鼠标触发时为 None）
+    pub fn parse(shortcut_str: &str) -> Option<Self> {
+        let mut modifiers = Vec::new();
+        let mut button = None;
+        for part in shortcut_str.split('+').map(str::trim) {
+            match part.to_lowercase().as_str() {
+                "mouse4" | "mouseback" => button = Some(SideButton::Back),
+                "mouse5" | "mouseforward" => button = Some(SideButton::Forward),
+                name => modifiers.push(TapModifier::parse(name)?),
+            }
+        }
+        Some(Self {
+            modifiers,
+            button: button?,
+        })
+    }
+}
+
+/// 侧键识别状态
+#[derive(Default)]
+struct Detector {
+    trigger: Option<MouseTrigger>,
+    /// 当前按住的修饰键
+    held: Vec<TapModifier>,
+    /// 侧键按下已触发，等待松开
+    triggered: bool,
+}
+
+impl Detector {
+    /// 处理输入事件，返回 Some(true) 表示触发按下，Some(false) 表示触发释放
+    fn handle(&mut self, event: EventType) -> Option<bool> {
+        let trigger = self.trigger.as_ref()?;
+        match event {
+            EventType::KeyPress(key) => {
+                let modifier = TapModifier::from_key(key)?;
+                if !self.held.contains(&modifier) {
+                    self.held.push(modifier);
+                }
+                None
+            }
+            EventType::KeyRelease(key) => {
+                let modifier = TapModifier::from_key(key)?;
+                self.held.retain(|held| *held != modifier);
+                None
+            }
+            EventType::ButtonPress(button) => {
+                let pressed = SideButton::from_button(button)?;
+                // 修饰键需要完全一致，避免 Ctrl+Mouse4 与 Mouse4 同时触发
+                let modifiers_match = self.held.len() == trigger.modifiers.len()
+                    && trigger.modifiers.iter().all(|m| self.held.contains(m));
+                if pressed == trigger.button && modifiers_match && !self.triggered {
+                    self.triggered = true;
+                    return Some(true);
+                }
+                None
+            }
+            EventType::ButtonRelease(button) => {
+                let released = SideButton::from_button(button)?;
+                if released == trigger.button && self.triggered {
+                    self.triggered = false;
+                    return Some(false);
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+static DETECTOR: LazyLock<Arc<Mutex<Detector>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(Detector::default())));
+
+/// 设置触发录音的鼠标侧键，None 表示关闭
+pub fn set_trigger(trigger: Option<MouseTrigger>) {
+    let mut detector = DETECTOR.lock();
+    if detector.trigger != trigger {
+        *detector = Detector {
+            trigger,
+            ..Default::default()
+        };
+    }
+}
+
+/// 处理一个全局输入事件（由 `key_events` 调用）
+pub(super) fn handle_event(event: EventType) -> Option<bool> {
+    DETECTOR.lock().handle(event)
+}
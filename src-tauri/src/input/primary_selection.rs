@@ -0,0 +1,52 @@
+//! 主选区（PRIMARY selection）写入
+//!
+//! Linux 桌面除了剪贴板外还有主选区，可通过鼠标中键粘贴。
+//! 其他平台没有主选区，调用时返回错误。
+
+/// 写入主选区
+pub fn write_text(text: &str) -> Result<(), String> {
+    platform::write_text(text)
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    /// Wayland 使用 wl-copy，X11 使用 xclip
+    pub fn write_text(text: &str) -> Result<(), String> {
+        let (program, args): (&str, &[&str]) = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            ("wl-copy", &["--primary"])
+        } else {
+            ("xclip", &["-selection", "primary"])
+        };
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+        child
+            .stdin
+            .take()
+            .ok_or("Failed to open stdin")?
+            .write_all(text.as_bytes())
+            .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+
+        // xclip / wl-copy 会转入后台提供选区内容，在独立线程中回收进程
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    pub fn write_text(_text: &str) -> Result<(), String> {
+        Err("Primary selection is only available on Linux".to_string())
+    }
+}
@@ -0,0 +1,81 @@
+//! Windows SendInput Unicode 输入
+//!
+//! 部分 Windows 应用会丢失 enigo 输入的中日韩字符。这里直接以 `KEYEVENTF_UNICODE`
+//! 发送 UTF-16 码元，并按块分批发送、块间停顿，避免目标程序的消息队列来不及处理。
+
+/// 每批发送的字符数
+const CHUNK_CHARS: usize = 16;
+
+/// 输入文本，每批之间等待 `chunk_delay_ms` 毫秒
+pub fn type_text(text: &str, chunk_delay_ms: u32) -> Result<(), String> {
+    let chars: Vec<char> = text.chars().collect();
+    for (index, chunk) in chars.chunks(CHUNK_CHARS).enumerate() {
+        if index > 0 && chunk_delay_ms > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(chunk_delay_ms as u64));
+        }
+        platform::send_chars(chunk)?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS,
+        KEYEVENTF_KEYUP, KEYEVENTF_UNICODE, VIRTUAL_KEY, VK_RETURN,
+    };
+
+    pub fn send_chars(chars: &[char]) -> Result<(), String> {
+        let mut inputs = Vec::with_capacity(chars.len() * 4);
+        for &ch in chars {
+            if ch == '\n' {
+                // 换行以回车键发送，Unicode 方式的 '\n' 在很多控件中无效
+                inputs.push(key_input(VK_RETURN, 0, KEYBD_EVENT_FLAGS(0)));
+                inputs.push(key_input(VK_RETURN, 0, KEYEVENTF_KEYUP));
+                continue;
+            }
+            let mut units = [0u16; 2];
+            for &unit in ch.encode_utf16(&mut units).iter() {
+                inputs.push(key_input(VIRTUAL_KEY(0), unit, KEYEVENTF_UNICODE));
+                inputs.push(key_input(
+                    VIRTUAL_KEY(0),
+                    unit,
+                    KEYEVENTF_UNICODE | KEYEVENTF_KEYUP,
+                ));
+            }
+        }
+
+        let sent = unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+        if sent as usize != inputs.len() {
+            return Err(format!(
+                "SendInput sent {} of {} events: {}",
+                sent,
+                inputs.len(),
+                windows::core::Error::from_win32()
+            ));
+        }
+        Ok(())
+    }
+
+    fn key_input(vk: VIRTUAL_KEY, scan: u16, flags: KEYBD_EVENT_FLAGS) -> INPUT {
+        INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: vk,
+                    wScan: scan,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    pub fn send_chars(_chars: &[char]) -> Result<(), String> {
+        Err("SendInput is only available on Windows".to_string())
+    }
+}
@@ -0,0 +1,213 @@
+//! 录制快捷键
+//!
+//! 通过全局按键监听获取下一次按下的组合键，返回 `parse_shortcut` 可解析的规范写法（如 "Ctrl+Shift+F9"），
+//! 设置界面不再依赖用户手动输入。同一修饰键快速按两次时返回双击写法（如 "DoubleTap+Ctrl"），
+//! 按下鼠标侧键时返回侧键写法（如 "Ctrl+Mouse4"），单独按 Esc 取消录制。
+
+use parking_lot::Mutex;
+use rdev::{EventType, Key};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+use super::double_tap::{TapModifier, DOUBLE_TAP_WINDOW};
+use super::key_events;
+use super::mouse_trigger::SideButton;
+
+/// 修饰键的规范顺序
+const MODIFIER_ORDER: [TapModifier; 4] = [
+    TapModifier::Ctrl,
+    TapModifier::Alt,
+    TapModifier::Shift,
+    TapModifier::Super,
+];
+
+/// 一次录制的状态
+struct Capture {
+    id: u64,
+    /// 当前按住的修饰键
+    held: Vec<TapModifier>,
+    /// 本轮按住修饰键期间是否按了其他键
+    combined: bool,
+    /// 上一次单独按下并松开的修饰键
+    last_tap: Option<(TapModifier, Instant)>,
+    /// 录制结果，None 表示取消
+    tx: Option<oneshot::Sender<Option<String>>>,
+}
+
+impl Capture {
+    /// 当前按住的修饰键加上指定按键，按规范顺序拼接
+    fn chord(&self, key: &str) -> String {
+        let mut parts: Vec<&str> = MODIFIER_ORDER
+            .iter()
+            .filter(|modifier| self.held.contains(modifier))
+            .map(|modifier| modifier.name())
+            .collect();
+        parts.push(key);
+        parts.join("+")
+    }
+
+    /// 处理按键事件，录制完成时返回 Some(结果)
+    fn handle(&mut self, event: EventType) -> Option<Option<String>> {
+        match event {
+            EventType::KeyPress(key) => match TapModifier::from_key(key) {
+                Some(modifier) => {
+                    if self.held.contains(&modifier) {
+                        return None;
+                    }
+                    if self.held.is_empty() {
+                        self.combined = false;
+                        let double_tap = self.last_tap.take().is_some_and(|(last, at)| {
+                            last == modifier && at.elapsed() <= DOUBLE_TAP_WINDOW
+                        });
+                        if double_tap {
+                            return Some(Some(format!("DoubleTap+{}", modifier.name())));
+                        }
+                    }
+                    self.held.push(modifier);
+                    None
+                }
+                None => {
+                    self.combined = true;
+                    self.last_tap = None;
+                    if key == Key::Escape && self.held.is_empty() {
+                        return Some(None);
+                    }
+                    let Some(name) = key_name(key) else {
+                        log::debug!("Unsupported key for shortcut: {:?}", key);
+                        return None;
+                    };
+                    Some(Some(self.chord(&name)))
+                }
+            },
+            EventType::ButtonPress(button) => {
+                let button = SideButton::from_button(button)?;
+                Some(Some(self.chord(button.name())))
+            }
+            EventType::KeyRelease(key) => {
+                let modifier = TapModifier::from_key(key)?;
+                self.held.retain(|held| *held != modifier);
+                if self.held.is_empty() && !self.combined {
+                    self.last_tap = Some((modifier, Instant::now()));
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+static CAPTURE: LazyLock<Arc<Mutex<Option<Capture>>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(None)));
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// 是否正在录制（录制期间忽略已注册的快捷键）
+pub fn is_capturing() -> bool {
+    CAPTURE.lock().is_some()
+}
+
+/// 等待下一次按下的组合键
+///
+/// 新的录制会取消尚未完成的录制。
+pub async fn capture(timeout: Duration) -> Result<String, String> {
+    key_events::start();
+    if key_events::failed() {
+        return Err("无法监听全局按键（Wayland 不支持，macOS 需要辅助功能权限）".to_string());
+    }
+
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    let (tx, rx) = oneshot::channel();
+    *CAPTURE.lock() = Some(Capture {
+        id,
+        held: Vec::new(),
+        combined: false,
+        last_tap: None,
+        tx: Some(tx),
+    });
+
+    let result = tokio::time::timeout(timeout, rx).await;
+    {
+        let mut capture = CAPTURE.lock();
+        if capture.as_ref().is_some_and(|capture| capture.id == id) {
+            *capture = None;
+        }
+    }
+
+    match result {
+        Ok(Ok(Some(shortcut))) => {
+            log::info!("Captured shortcut: {}", shortcut);
+            Ok(shortcut)
+        }
+        Ok(_) => Err("已取消".to_string()),
+        Err(_) if key_events::failed() => {
+            Err("无法监听全局按键（Wayland 不支持，macOS 需要辅助功能权限）".to_string())
+        }
+        Err(_) => Err("等待按键超时".to_string()),
+    }
+}
+
+/// 处理一个全局按键事件（由 `key_events` 调用）
+pub(super) fn handle_event(event: EventType) {
+    let mut guard = CAPTURE.lock();
+    let Some(capture) = guard.as_mut() else {
+        return;
+    };
+    if let Some(result) = capture.handle(event) {
+        if let Some(tx) = capture.tx.take() {
+            let _ = tx.send(result);
+        }
+        *guard = None;
+    }
+}
+
+/// 按键对应的 `parse_shortcut` 名称（不支持的按键返回 None）
+fn key_name(key: Key) -> Option<String> {
+    let name = match key {
+        Key::Space => "Space",
+        Key::Return | Key::KpReturn => "Enter",
+        Key::Tab => "Tab",
+        Key::Escape => "Escape",
+        Key::Backspace => "Backspace",
+        Key::Delete => "Delete",
+        Key::UpArrow => "Up",
+        Key::DownArrow => "Down",
+        Key::LeftArrow => "Left",
+        Key::RightArrow => "Right",
+        Key::Home => "Home",
+        Key::End => "End",
+        Key::PageUp => "PageUp",
+        Key::PageDown => "PageDown",
+        Key::F1 => "F1",
+        Key::F2 => "F2",
+        Key::F3 => "F3",
+        Key::F4 => "F4",
+        Key::F5 => "F5",
+        Key::F6 => "F6",
+        Key::F7 => "F7",
+        Key::F8 => "F8",
+        Key::F9 => "F9",
+        Key::F10 => "F10",
+        Key::F11 => "F11",
+        Key::F12 => "F12",
+        Key::Num0 => "0",
+        Key::Num1 => "1",
+        Key::Num2 => "2",
+        Key::Num3 => "3",
+        Key::Num4 => "4",
+        Key::Num5 => "5",
+        Key::Num6 => "6",
+        Key::Num7 => "7",
+        Key::Num8 => "8",
+        Key::Num9 => "9",
+        // 字母键：rdev 的 KeyA..KeyZ 调试名称去掉 "Key" 前缀
+        key => {
+            let debug = format!("{:?}", key);
+            return debug
+                .strip_prefix("Key")
+                .filter(|letter| letter.len() == 1)
+                .map(str::to_string);
+        }
+    };
+    Some(name.to_string())
+}
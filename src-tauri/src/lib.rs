@@ -1,11 +1,12 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tauri::{
-    menu::{MenuBuilder, MenuItemBuilder},
+    image::Image,
+    menu::{MenuBuilder, MenuItem, MenuItemBuilder},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager,
+    Emitter, Manager, Wry,
 };
-use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+use tauri_plugin_global_shortcut::ShortcutState;
 
 mod asr;
 mod audio;
@@ -13,13 +14,27 @@ mod commands;
 mod history;
 mod input;
 mod logging;
+mod plugins;
 mod postprocess;
 mod state;
+mod text;
 
 pub use state::AppState;
 
 static SHORTCUT_PROCESSING: std::sync::LazyLock<Arc<AtomicBool>> =
     std::sync::LazyLock::new(|| Arc::new(AtomicBool::new(false)));
+// 开始录音后快捷键是否已松开（切换模式下忽略按住时系统重复发送的按下事件）
+static SHORTCUT_RELEASED: std::sync::LazyLock<Arc<AtomicBool>> =
+    std::sync::LazyLock::new(|| Arc::new(AtomicBool::new(true)));
+
+const TRAY_ID: &str = "main";
+const TRAY_TOOLTIP: &str = "Audio Input - Alt+Space 开始录音";
+const TRAY_TOOLTIP_PAUSED: &str = "Audio Input - 已暂停";
+
+/// 托盘菜单中需要动态更新的菜单项
+struct TrayMenu {
+    pause: MenuItem<Wry>,
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -29,63 +44,67 @@ pub fn run() {
     // 初始化日志系统（使用配置中的设置）
     logging::init_logger(config.enable_logging);
 
-    let shortcut = commands::parse_shortcut(&config.shortcut)
-        .unwrap_or_else(|_| Shortcut::new(Some(Modifiers::ALT), Code::Space));
-
-    // 检查是否为静默启动
-    let silent_mode = commands::is_silent_mode();
+    // 检查是否为静默启动（单次模式同样不显示主窗口）
+    let once_mode = commands::is_once_mode();
+    let silent_mode = commands::is_silent_mode() || once_mode;
 
     tauri::Builder::default()
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(
             tauri_plugin_global_shortcut::Builder::new()
                 .with_handler(move |app, hotkey, event| {
-                    if hotkey == &shortcut {
-                        let processing = SHORTCUT_PROCESSING.clone();
-                        let app_clone = app.clone();
-
-                        match event.state() {
-                            ShortcutState::Pressed => {
-                                // 使用 compare_exchange 确保只有一个线程能启动录音
-                                if processing
-                                    .compare_exchange(
-                                        false,
-                                        true,
-                                        Ordering::SeqCst,
-                                        Ordering::SeqCst,
-                                    )
-                                    .is_err()
-                                {
-                                    return; // 已经在处理中
-                                }
-                                log::info!("Shortcut pressed - starting recording");
-                                tauri::async_runtime::spawn(async move {
-                                    if let Err(e) =
-                                        commands::handle_start_recording(&app_clone).await
-                                    {
-                                        log::error!("Failed to start recording: {}", e);
-                                        // 如果启动失败，重置状态
-                                        SHORTCUT_PROCESSING.store(false, Ordering::SeqCst);
-                                    }
-                                });
+                    // 设置界面正在录制快捷键
+                    if input::shortcut_capture::is_capturing() {
+                        return;
+                    }
+
+                    // 暂停/恢复快捷键
+                    if commands::pause_shortcut(app).as_ref() == Some(hotkey) {
+                        if matches!(event.state(), ShortcutState::Pressed) {
+                            let enabled = app.state::<AppState>().is_paused();
+                            if let Err(e) = commands::set_app_enabled(app, enabled) {
+                                log::error!("Failed to toggle enabled state: {}", e);
                             }
-                            ShortcutState::Released => {
-                                // 只有在录音中才处理释放事件
-                                if !processing.load(Ordering::SeqCst) {
-                                    return;
+                        }
+                        return;
+                    }
+
+                    // 对最近一条记录重新执行后处理
+                    if commands::reprocess_shortcut(app).as_ref() == Some(hotkey) {
+                        if matches!(event.state(), ShortcutState::Pressed) {
+                            let app = app.clone();
+                            tauri::async_runtime::spawn(async move {
+                                if let Err(e) = commands::handle_reprocess_last(&app, None).await {
+                                    log::error!("Failed to reprocess transcript: {}", e);
+                                    let _ = app.emit("error", e);
                                 }
-                                log::info!("Shortcut released - stopping recording");
-                                tauri::async_runtime::spawn(async move {
-                                    if let Err(e) =
-                                        commands::handle_stop_recording(&app_clone).await
-                                    {
-                                        log::error!("Failed to stop recording: {}", e);
-                                    }
-                                    SHORTCUT_PROCESSING.store(false, Ordering::SeqCst);
-                                });
+                            });
+                        }
+                        return;
+                    }
+
+                    // 取消录音快捷键（仅录音期间注册）
+                    if commands::cancel_shortcut(app).as_ref() == Some(hotkey) {
+                        if matches!(event.state(), ShortcutState::Pressed) {
+                            match commands::handle_cancel_recording(app) {
+                                Ok(()) => SHORTCUT_PROCESSING.store(false, Ordering::SeqCst),
+                                Err(e) => log::warn!("Failed to cancel recording: {}", e),
                             }
                         }
+                        return;
                     }
+
+                    // 每次触发时从 AppState 读取当前快捷键，配置变更后无需重启
+                    // 模式、Provider 快捷键启动的录音改用对应的处理模式或 Provider
+                    let session = if commands::current_shortcut(app).as_ref() == Some(hotkey) {
+                        commands::SessionOverride::default()
+                    } else if let Some(session) = commands::session_override_for(app, hotkey) {
+                        session
+                    } else {
+                        return;
+                    };
+                    let pressed = matches!(event.state(), ShortcutState::Pressed);
+                    handle_record_shortcut(app, session, pressed);
                 })
                 .build(),
         )
@@ -94,20 +113,74 @@ pub fn run() {
             // 设置系统托盘
             setup_tray(app)?;
 
-            let config = app.state::<AppState>().get_config();
-            let shortcut = commands::parse_shortcut(&config.shortcut)
-                .unwrap_or_else(|_| Shortcut::new(Some(Modifiers::ALT), Code::Space));
-            app.global_shortcut().register(shortcut)?;
-            log::info!("Global shortcut {} registered", config.shortcut);
+            // 旧配置按名称保存麦克风，迁移为稳定的设备 ID
+            let mut config = app.state::<AppState>().get_config();
+            if config.migrate_audio_device_ids(&audio::capture::list_audio_devices()) {
+                if let Err(e) = app.state::<AppState>().update_config(config.clone()) {
+                    log::warn!("Failed to save migrated audio device: {}", e);
+                }
+            }
+            commands::sync_preroll(&config);
+            commands::sync_keyboard_options(&config);
+            // 配置无效时回退到默认快捷键（与 current_shortcut 一致）
+            let shortcut_str = if commands::parse_shortcut(&config.shortcut).is_ok() {
+                config.shortcut.as_str()
+            } else {
+                "Alt+Space"
+            };
+            // 注册失败时不退出，显示窗口让用户从推荐中选择
+            let shortcut_conflict = if commands::is_hook_shortcut(&config.shortcut) {
+                // 双击修饰键、鼠标侧键由全局输入监听识别，不注册全局快捷键
+                commands::sync_hook_shortcut(app.handle(), &config.shortcut);
+                log::info!("Input hook shortcut {} enabled", config.shortcut);
+                false
+            } else if let Err(e) = commands::check_mouse_shortcut(&config.shortcut) {
+                // 当前平台收不到侧键事件：仍注册默认快捷键，并显示窗口提示用户改用其他快捷键
+                log::error!("{}", e);
+                if let Err(e) = commands::register_shortcut(app.handle(), shortcut_str) {
+                    log::error!("Failed to register global shortcut: {}", e);
+                }
+                true
+            } else {
+                match commands::register_shortcut(app.handle(), shortcut_str) {
+                    Ok(()) => {
+                        log::info!("Global shortcut {} registered", shortcut_str);
+                        false
+                    }
+                    Err(e) => {
+                        log::error!("Failed to register global shortcut: {}", e);
+                        true
+                    }
+                }
+            };
+            if !config.pause_shortcut.is_empty() {
+                if let Err(e) = commands::register_shortcut(app.handle(), &config.pause_shortcut) {
+                    log::warn!("Failed to register pause shortcut: {}", e);
+                }
+            }
+            if !config.reprocess_shortcut.is_empty() {
+                if let Err(e) = commands::register_shortcut(app.handle(), &config.reprocess_shortcut) {
+                    log::warn!("Failed to register reprocess shortcut: {}", e);
+                }
+            }
+            commands::register_session_shortcuts(app.handle(), &config);
 
-            // 如果不是静默模式，显示窗口
-            if !silent_mode {
+            // 如果不是静默模式（或快捷键冲突需要用户处理），显示窗口
+            if !silent_mode || (shortcut_conflict && !once_mode) {
                 if let Some(window) = app.get_webview_window("main") {
                     let _ = window.show();
                     let _ = window.set_focus();
                 }
             }
 
+            // 单次模式：立即开始录音，按回车或快捷键结束
+            if once_mode {
+                start_once_session(app.handle().clone());
+            }
+
+            // 免打扰时段调度
+            start_quiet_hours_scheduler(app.handle().clone());
+
             // 预热 LLM 连接（后台异步执行）
             let postprocess_config = config.postprocess.clone();
             tauri::async_runtime::spawn(async move {
@@ -120,13 +193,23 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::start_recording,
             commands::stop_recording,
+            commands::cancel_recording,
             commands::get_state,
             commands::get_config,
             commands::update_config,
             commands::get_transcript,
+            commands::copy_raw_transcript,
+            commands::reprocess_last_transcript,
+            commands::set_enabled,
+            commands::is_enabled,
+            commands::test_keyboard_output,
+            commands::get_shortcut_conflict,
+            commands::suggest_shortcuts,
+            commands::capture_shortcut,
             commands::test_llm_connection,
             commands::get_audio_devices,
             commands::get_history,
+            commands::get_asr_stats,
             commands::delete_history_entry,
             commands::clear_history,
             commands::get_config_file_path,
@@ -139,35 +222,213 @@ pub fn run() {
             // ASR Provider 相关命令
             commands::get_asr_config,
             commands::update_asr_config,
+            commands::test_doubao_connection,
+            commands::benchmark_providers,
+            commands::retry_last_session,
+            commands::transcribe_recording,
+            commands::list_plugins,
+            commands::set_plugin_enabled,
             commands::list_asr_providers,
             commands::get_whisper_models,
+            commands::get_whisper_gpu_backends,
+            commands::benchmark_whisper_model,
+            commands::import_whisper_model,
             commands::download_whisper_model,
             commands::delete_whisper_model,
             commands::cancel_whisper_download,
             commands::set_whisper_model,
+            #[cfg(feature = "vosk")]
+            commands::get_vosk_models,
+            #[cfg(feature = "vosk")]
+            commands::download_vosk_model,
+            #[cfg(feature = "vosk")]
+            commands::delete_vosk_model,
+            #[cfg(feature = "vosk")]
+            commands::cancel_vosk_download,
+            #[cfg(feature = "vosk")]
+            commands::set_vosk_model,
+            #[cfg(feature = "sherpa")]
+            commands::get_local_models,
+            #[cfg(feature = "sherpa")]
+            commands::download_local_model,
+            #[cfg(feature = "sherpa")]
+            commands::delete_local_model,
+            #[cfg(feature = "sherpa")]
+            commands::cancel_local_download,
+            #[cfg(feature = "sherpa")]
+            commands::set_local_model,
+            commands::list_whisper_api_presets,
+            commands::apply_whisper_api_preset,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
+/// 免打扰时段调度：进入时段时自动暂停，离开时恢复
+///
+/// 只在进入/离开时段时切换，时段内用户手动恢复后不会被再次暂停。
+fn start_quiet_hours_scheduler(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+        let mut was_quiet = false;
+        // 是否由调度器暂停（用户手动暂停的不自动恢复）
+        let mut paused_by_scheduler = false;
+
+        loop {
+            interval.tick().await;
+
+            let state = app.state::<AppState>();
+            let quiet = state.get_config().in_quiet_hours(chrono::Local::now());
+            if quiet == was_quiet {
+                continue;
+            }
+            was_quiet = quiet;
+
+            if quiet && !state.is_paused() {
+                log::info!("Entering quiet hours");
+                match commands::set_app_enabled(&app, false) {
+                    Ok(()) => paused_by_scheduler = true,
+                    Err(e) => log::error!("Failed to pause for quiet hours: {}", e),
+                }
+            } else if !quiet && paused_by_scheduler {
+                log::info!("Leaving quiet hours");
+                paused_by_scheduler = false;
+                if state.is_paused() {
+                    if let Err(e) = commands::set_app_enabled(&app, true) {
+                        log::error!("Failed to resume after quiet hours: {}", e);
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// 处理录音快捷键的按下、释放（全局快捷键与双击修饰键共用）
+pub(crate) fn handle_record_shortcut(
+    app: &tauri::AppHandle,
+    session: commands::SessionOverride,
+    pressed: bool,
+) {
+    let processing = SHORTCUT_PROCESSING.clone();
+    let app_clone = app.clone();
+
+    if pressed {
+        // 使用 compare_exchange 确保只有一个线程能启动录音
+        if processing
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            // 切换模式下松开后再按一次快捷键停止
+            if commands::is_toggle_session() && SHORTCUT_RELEASED.swap(false, Ordering::SeqCst) {
+                log::info!("Shortcut pressed - stopping recording");
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = commands::handle_stop_recording(&app_clone).await {
+                        log::error!("Failed to stop recording: {}", e);
+                    }
+                    SHORTCUT_PROCESSING.store(false, Ordering::SeqCst);
+                });
+            }
+            return; // 已经在处理中
+        }
+        log::info!("Shortcut pressed - starting recording");
+        SHORTCUT_RELEASED.store(false, Ordering::SeqCst);
+        // 在启动任务前确定切换模式，避免快速松开时释放事件读到上一次的值
+        let config = app.state::<AppState>().get_config();
+        commands::set_toggle_session(&config, None);
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = commands::handle_start_recording(&app_clone, None, session).await {
+                log::error!("Failed to start recording: {}", e);
+                // 如果启动失败，重置状态
+                SHORTCUT_PROCESSING.store(false, Ordering::SeqCst);
+            }
+        });
+    } else {
+        // 只有在录音中才处理释放事件
+        if !processing.load(Ordering::SeqCst) {
+            return;
+        }
+        // 切换模式下松开快捷键不结束录音
+        if commands::is_toggle_session() {
+            SHORTCUT_RELEASED.store(true, Ordering::SeqCst);
+            return;
+        }
+        log::info!("Shortcut released - stopping recording");
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = commands::handle_stop_recording(&app_clone).await {
+                log::error!("Failed to stop recording: {}", e);
+            }
+            SHORTCUT_PROCESSING.store(false, Ordering::SeqCst);
+        });
+    }
+}
+
+/// 单次模式：启动后立即录音，stdin 收到回车（或 EOF）时停止
+///
+/// 指定 `--auto-stop=<秒>` 时说完后静音达到该时长也会自动停止。此时 stdin 的 EOF 不视为停止，
+/// 否则从 `</dev/null`、cron 或没有 stdin 的启动器运行时会立即结束录音。
+fn start_once_session(app: tauri::AppHandle) {
+    // 标记为处理中，使快捷键的释放事件也能结束录音
+    SHORTCUT_PROCESSING.store(true, Ordering::SeqCst);
+
+    tauri::async_runtime::spawn(async move {
+        let auto_stop_arg = commands::auto_stop_arg();
+        let auto_stop = auto_stop_arg.map(|silence_secs| state::AutoStopConfig {
+            enabled: true,
+            silence_secs,
+            ..app.state::<AppState>().get_config().auto_stop
+        });
+        if let Err(e) = commands::handle_start_recording(&app, auto_stop, Default::default()).await {
+            eprintln!("Failed to start recording: {}", e);
+            app.exit(commands::exit_code::START_FAILED);
+            return;
+        }
+
+        std::thread::spawn(move || {
+            let mut line = String::new();
+            let eof = !matches!(std::io::stdin().read_line(&mut line), Ok(n) if n > 0);
+            if eof && auto_stop_arg.is_some() {
+                log::info!("stdin closed, waiting for auto-stop or the shortcut");
+                return;
+            }
+            if !SHORTCUT_PROCESSING.load(Ordering::SeqCst) {
+                return;
+            }
+            tauri::async_runtime::block_on(async {
+                if let Err(e) = commands::handle_stop_recording(&app).await {
+                    log::error!("Failed to stop recording: {}", e);
+                }
+                SHORTCUT_PROCESSING.store(false, Ordering::SeqCst);
+            });
+        });
+    });
+}
+
 fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let show = MenuItemBuilder::with_id("show", "显示窗口").build(app)?;
     let settings = MenuItemBuilder::with_id("settings", "设置").build(app)?;
+    let pause = MenuItemBuilder::with_id("pause", "暂停").build(app)?;
     let quit = MenuItemBuilder::with_id("quit", "退出").build(app)?;
 
     let menu = MenuBuilder::new(app)
-        .items(&[&show, &settings, &quit])
+        .items(&[&show, &settings, &pause, &quit])
         .build()?;
+    app.manage(TrayMenu { pause });
 
-    TrayIconBuilder::new()
+    TrayIconBuilder::with_id(TRAY_ID)
         .icon(app.default_window_icon().unwrap().clone())
         .menu(&menu)
-        .tooltip("Audio Input - Alt+Space 开始录音")
+        .tooltip(TRAY_TOOLTIP)
         .on_menu_event(|app, event| match event.id().as_ref() {
             "quit" => {
                 log::info!("Quit requested");
                 app.exit(0);
             }
+            "pause" => {
+                let enabled = app.state::<AppState>().is_paused();
+                if let Err(e) = commands::set_app_enabled(app, enabled) {
+                    log::error!("Failed to toggle enabled state: {}", e);
+                }
+            }
             "show" | "settings" => {
                 if let Some(window) = app.get_webview_window("main") {
                     let _ = window.show();
@@ -195,3 +456,36 @@ fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     log::info!("System tray initialized");
     Ok(())
 }
+
+/// 根据启用状态更新托盘图标、提示和菜单文字
+pub(crate) fn update_tray_state(app: &tauri::AppHandle, enabled: bool) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Some(icon) = app.default_window_icon() {
+            let icon = if enabled {
+                icon.clone()
+            } else {
+                paused_icon(icon)
+            };
+            let _ = tray.set_icon(Some(icon));
+        }
+        let tooltip = if enabled { TRAY_TOOLTIP } else { TRAY_TOOLTIP_PAUSED };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+
+    if let Some(menu) = app.try_state::<TrayMenu>() {
+        let _ = menu.pause.set_text(if enabled { "暂停" } else { "恢复" });
+    }
+}
+
+/// 生成暂停状态的托盘图标（灰度、半透明）
+fn paused_icon(icon: &Image<'_>) -> Image<'static> {
+    let rgba = icon
+        .rgba()
+        .chunks_exact(4)
+        .flat_map(|p| {
+            let gray = ((p[0] as u32 * 30 + p[1] as u32 * 59 + p[2] as u32 * 11) / 100) as u8;
+            [gray, gray, gray, p[3] / 2]
+        })
+        .collect();
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
@@ -0,0 +1,187 @@
+//! 文本变换插件
+//!
+//! 插件位于数据目录的 `plugins/<名称>/` 下，包含 `plugin.toml` 清单和 WASM 模块。
+//! 启用的插件按名称顺序在 ASR 结果之后（post_asr）和 LLM 后处理之后（post_llm）执行。
+//! WASM 运行时需要启用 `wasm-plugins` feature，否则插件只列出、不执行。
+
+pub mod script;
+#[cfg(feature = "wasm-plugins")]
+mod wasm;
+
+use directories::ProjectDirs;
+#[cfg(feature = "wasm-plugins")]
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "wasm-plugins")]
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+#[cfg(feature = "wasm-plugins")]
+use std::sync::LazyLock;
+#[cfg(feature = "wasm-plugins")]
+use wasmtime::Module;
+
+/// 插件清单文件名
+const MANIFEST_FILE: &str = "plugin.toml";
+
+/// 已编译模块缓存（插件名 -> 模块）
+#[cfg(feature = "wasm-plugins")]
+static MODULES: LazyLock<Mutex<HashMap<String, Module>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 插件执行阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    /// ASR 识别完成后
+    PostAsr,
+    /// LLM 后处理完成后
+    PostLlm,
+}
+
+#[cfg(feature = "wasm-plugins")]
+impl Stage {
+    /// 传给插件 `transform` 的阶段编号
+    fn code(self) -> i32 {
+        match self {
+            Stage::PostAsr => 0,
+            Stage::PostLlm => 1,
+        }
+    }
+}
+
+/// 插件清单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    /// WASM 模块文件名（相对插件目录）
+    #[serde(default = "default_module")]
+    pub module: String,
+    /// 挂载的阶段
+    pub stages: Vec<Stage>,
+}
+
+fn default_module() -> String {
+    "plugin.wasm".to_string()
+}
+
+/// 前端展示用的插件信息
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginInfo {
+    #[serde(flatten)]
+    pub manifest: PluginManifest,
+    pub enabled: bool,
+}
+
+struct Plugin {
+    manifest: PluginManifest,
+    #[cfg_attr(not(feature = "wasm-plugins"), allow(dead_code))]
+    dir: PathBuf,
+}
+
+/// 插件根目录
+fn plugins_dir() -> Option<PathBuf> {
+    ProjectDirs::from("com", "speaky", "Speaky").map(|dirs| dirs.data_dir().join("plugins"))
+}
+
+/// 扫描插件目录，跳过清单无效的插件
+fn discover() -> Vec<Plugin> {
+    let Some(dir) = plugins_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut plugins: Vec<Plugin> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let content = fs::read_to_string(dir.join(MANIFEST_FILE)).ok()?;
+            match toml::from_str::<PluginManifest>(&content) {
+                Ok(manifest) => Some(Plugin { manifest, dir }),
+                Err(e) => {
+                    log::warn!("Invalid plugin manifest in {:?}: {}", dir, e);
+                    None
+                }
+            }
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.manifest.name.cmp(&b.manifest.name));
+    plugins
+}
+
+/// 列出所有已安装的插件
+pub fn list(enabled: &[String]) -> Vec<PluginInfo> {
+    discover()
+        .into_iter()
+        .map(|plugin| PluginInfo {
+            enabled: enabled.contains(&plugin.manifest.name),
+            manifest: plugin.manifest,
+        })
+        .collect()
+}
+
+/// 检查插件是否存在
+pub fn exists(name: &str) -> bool {
+    discover().iter().any(|p| p.manifest.name == name)
+}
+
+/// 清除已编译模块缓存（插件更新或启用状态变化后调用）
+pub fn clear_cache() {
+    #[cfg(feature = "wasm-plugins")]
+    MODULES.lock().clear();
+}
+
+#[cfg(feature = "wasm-plugins")]
+fn module_for(plugin: &Plugin) -> Result<Module, String> {
+    let mut modules = MODULES.lock();
+    if let Some(module) = modules.get(&plugin.manifest.name) {
+        return Ok(module.clone());
+    }
+    let module = wasm::compile(&plugin.dir.join(&plugin.manifest.module))?;
+    modules.insert(plugin.manifest.name.clone(), module.clone());
+    Ok(module)
+}
+
+/// 依次执行启用且挂载在该阶段的插件
+///
+/// 单个插件失败时记录日志并跳过，不影响后续插件和原始文本。
+#[cfg(feature = "wasm-plugins")]
+pub fn apply(stage: Stage, text: &str, enabled: &[String]) -> String {
+    if enabled.is_empty() {
+        return text.to_string();
+    }
+
+    let mut result = text.to_string();
+    for plugin in discover() {
+        if !enabled.contains(&plugin.manifest.name) || !plugin.manifest.stages.contains(&stage) {
+            continue;
+        }
+
+        match module_for(&plugin).and_then(|m| wasm::transform(&m, &result, stage.code())) {
+            Ok(transformed) => {
+                log::debug!("Plugin {} applied at {:?}", plugin.manifest.name, stage);
+                result = transformed;
+            }
+            Err(e) => log::error!("Plugin {} failed: {}", plugin.manifest.name, e),
+        }
+    }
+    result
+}
+
+/// 未启用 wasm-plugins feature 时原样返回文本
+#[cfg(not(feature = "wasm-plugins"))]
+pub fn apply(_stage: Stage, text: &str, enabled: &[String]) -> String {
+    if !enabled.is_empty() {
+        log::warn!(
+            "WASM plugins are not enabled in this build, skipping {} plugin(s)",
+            enabled.len()
+        );
+    }
+    text.to_string()
+}
@@ -0,0 +1,135 @@
+//! Rhai 输出脚本
+//!
+//! 比 WASM 插件更轻量的定制方式：每次录音结束时执行一次用户脚本。脚本可读取
+//! `text`（识别结果）、`app`（前台应用名称）和 `config`（与输出相关的部分设置，
+//! 不含 Provider 配置和密钥），通过重新赋值 `text` 改写文本，赋值 `target` 选择本次的输出方式：
+//!
+//! ```rhai
+//! if app == "Terminal" { target = "clipboard"; }
+//! text = text.replace("句号", "。");
+//! ```
+
+#[cfg(feature = "scripting")]
+use rhai::module_resolvers::DummyModuleResolver;
+#[cfg(feature = "scripting")]
+use rhai::{Engine, Scope};
+#[cfg(feature = "scripting")]
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::state::AppConfig;
+
+/// 脚本最多执行的操作数，防止死循环卡住输出
+#[cfg(feature = "scripting")]
+const MAX_OPERATIONS: u64 = 1_000_000;
+/// 脚本中字符串的最大长度
+#[cfg(feature = "scripting")]
+const MAX_STRING_SIZE: usize = 1024 * 1024;
+
+/// 本次会话的输出方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+pub enum OutputTarget {
+    /// 按配置（auto_copy / auto_type）输出
+    #[default]
+    Default,
+    /// 直接键盘输入
+    Type,
+    /// 复制后粘贴
+    Paste,
+    /// 仅复制到剪贴板
+    Clipboard,
+    /// 不输出
+    None,
+}
+
+#[cfg(feature = "scripting")]
+impl OutputTarget {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "" | "default" => Ok(Self::Default),
+            "type" => Ok(Self::Type),
+            "paste" => Ok(Self::Paste),
+            "clipboard" => Ok(Self::Clipboard),
+            "none" => Ok(Self::None),
+            other => Err(format!("Unknown output target: {}", other)),
+        }
+    }
+}
+
+/// 脚本执行结果
+#[derive(Debug, Clone)]
+pub struct ScriptOutcome {
+    pub text: String,
+    pub target: OutputTarget,
+}
+
+/// 执行输出脚本
+#[cfg(feature = "scripting")]
+pub fn run(
+    path: &Path,
+    text: &str,
+    app: Option<&str>,
+    config: &AppConfig,
+) -> Result<ScriptOutcome, String> {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    // 禁止 import 其他脚本文件
+    engine.set_module_resolver(DummyModuleResolver::new());
+
+    let config = rhai::serde::to_dynamic(script_config(config))
+        .map_err(|e| format!("Failed to expose config to script: {}", e))?;
+
+    let mut scope = Scope::new();
+    scope.push("text", text.to_string());
+    scope.push("app", app.unwrap_or_default().to_string());
+    scope.push("target", String::new());
+    scope.push_constant_dynamic("config", config);
+
+    engine
+        .run_file_with_scope(&mut scope, path.to_path_buf())
+        .map_err(|e| format!("Output script failed: {}", e))?;
+
+    let text = scope
+        .get_value::<String>("text")
+        .ok_or("Output script must keep `text` a string")?;
+    let target = scope.get_value::<String>("target").unwrap_or_default();
+
+    Ok(ScriptOutcome {
+        text,
+        target: OutputTarget::parse(&target)?,
+    })
+}
+
+/// 未启用 scripting feature 时无法执行输出脚本
+#[cfg(not(feature = "scripting"))]
+pub fn run(
+    _path: &Path,
+    _text: &str,
+    _app: Option<&str>,
+    _config: &AppConfig,
+) -> Result<ScriptOutcome, String> {
+    Err("Output scripts are not enabled in this build".to_string())
+}
+
+/// 暴露给脚本的配置（白名单），Provider 配置中的密钥、鉴权请求头等一律不可见
+#[cfg(feature = "scripting")]
+fn script_config(config: &AppConfig) -> Value {
+    json!({
+        "asr": {
+            "active_provider": config.asr.active_provider,
+            "secondary_provider": config.asr.secondary_provider,
+        },
+        "asr_language": config.asr_language,
+        "provider_languages": config.provider_languages,
+        "hotwords": config.hotwords,
+        "auto_type": config.auto_type,
+        "auto_copy": config.auto_copy,
+        "realtime_input": config.realtime_input,
+        "review_output": config.review_output,
+        "restore_clipboard": config.restore_clipboard,
+        "english_formatting": config.english_formatting,
+        "enabled_plugins": config.enabled_plugins,
+    })
+}
@@ -0,0 +1,82 @@
+//! WASM 插件运行时
+//!
+//! 插件模块不导入任何宿主函数（无文件、网络访问），需要导出：
+//! - `memory`：线性内存
+//! - `alloc(len: i32) -> i32`：分配输入缓冲区
+//! - `transform(ptr: i32, len: i32, stage: i32) -> i64`：返回 `(out_ptr << 32) | out_len`
+//!
+//! 每次调用使用独立的 Store，并限制指令数（fuel）和内存大小。
+
+use std::path::Path;
+use std::sync::LazyLock;
+use wasmtime::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// 单次调用最多消耗的 fuel（约等于 WASM 指令数）
+const FUEL_LIMIT: u64 = 100_000_000;
+/// 插件线性内存上限
+const MAX_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+/// 插件输出文本上限
+const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+static ENGINE: LazyLock<Engine> = LazyLock::new(|| {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    Engine::new(&config).expect("Failed to create WASM engine")
+});
+
+/// 编译 WASM 模块
+pub fn compile(path: &Path) -> Result<Module, String> {
+    Module::from_file(&ENGINE, path).map_err(|e| format!("Failed to compile {:?}: {}", path, e))
+}
+
+/// 在沙箱中调用插件的 `transform`
+pub fn transform(module: &Module, text: &str, stage: i32) -> Result<String, String> {
+    let limits = StoreLimitsBuilder::new()
+        .memory_size(MAX_MEMORY_BYTES)
+        .instances(1)
+        .build();
+    let mut store: Store<StoreLimits> = Store::new(&ENGINE, limits);
+    store.limiter(|limits| limits);
+    store
+        .set_fuel(FUEL_LIMIT)
+        .map_err(|e| format!("Failed to set fuel: {}", e))?;
+
+    let linker = Linker::new(&ENGINE);
+    let instance = linker
+        .instantiate(&mut store, module)
+        .map_err(|e| format!("Failed to instantiate plugin: {}", e))?;
+
+    let memory = instance
+        .get_memory(&mut store, "memory")
+        .ok_or("Plugin does not export memory")?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&mut store, "alloc")
+        .map_err(|e| format!("Plugin does not export alloc: {}", e))?;
+    let transform = instance
+        .get_typed_func::<(i32, i32, i32), i64>(&mut store, "transform")
+        .map_err(|e| format!("Plugin does not export transform: {}", e))?;
+
+    let input = text.as_bytes();
+    let input_len = i32::try_from(input.len()).map_err(|_| "Input text too long")?;
+    let input_ptr = alloc
+        .call(&mut store, input_len)
+        .map_err(|e| format!("Plugin alloc failed: {}", e))?;
+    memory
+        .write(&mut store, input_ptr as u32 as usize, input)
+        .map_err(|e| format!("Failed to write plugin input: {}", e))?;
+
+    let packed = transform
+        .call(&mut store, (input_ptr, input_len, stage))
+        .map_err(|e| format!("Plugin transform failed: {}", e))?;
+    let output_ptr = (packed as u64 >> 32) as usize;
+    let output_len = (packed as u64 & 0xffff_ffff) as usize;
+    if output_len > MAX_OUTPUT_BYTES {
+        return Err(format!("Plugin output too large: {} bytes", output_len));
+    }
+
+    let mut output = vec![0u8; output_len];
+    memory
+        .read(&store, output_ptr, &mut output)
+        .map_err(|e| format!("Failed to read plugin output: {}", e))?;
+    String::from_utf8(output).map_err(|e| format!("Plugin output is not UTF-8: {}", e))
+}
@@ -24,6 +24,154 @@ pub enum PostProcessMode {
     Meeting, // 会议记录
 }
 
+/// 按前台应用自动选择处理模式的规则
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModeRule {
+    /// 应用名称关键字（不区分大小写的子串匹配）
+    pub pattern: String,
+    /// 匹配时使用的模式
+    pub mode: PostProcessMode,
+}
+
+impl ModeRule {
+    fn new(pattern: &str, mode: PostProcessMode) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            mode,
+        }
+    }
+}
+
+fn default_mode_rules() -> Vec<ModeRule> {
+    use PostProcessMode::{Code, Meeting};
+    [
+        ("code", Code),
+        ("cursor", Code),
+        ("idea", Code),
+        ("pycharm", Code),
+        ("webstorm", Code),
+        ("clion", Code),
+        ("vim", Code),
+        ("emacs", Code),
+        ("terminal", Code),
+        ("iterm", Code),
+        ("alacritty", Code),
+        ("kitty", Code),
+        ("wezterm", Code),
+        ("konsole", Code),
+        ("zoom", Meeting),
+        ("teams", Meeting),
+        ("meet", Meeting),
+        ("webex", Meeting),
+        ("tencentmeeting", Meeting),
+        ("wemeet", Meeting),
+        ("feishu", Meeting),
+        ("lark", Meeting),
+    ]
+    .into_iter()
+    .map(|(pattern, mode)| ModeRule::new(pattern, mode))
+    .collect()
+}
+
+/// 各模式的输出模板
+///
+/// 在输入/复制前应用，模板中的 `{text}` 替换为最终文本，例如
+/// "```\n{text}\n```" 或 "- {text}"。模板为空时原样输出。
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct OutputTemplates {
+    #[serde(default)]
+    pub general: String,
+    #[serde(default)]
+    pub code: String,
+    #[serde(default)]
+    pub meeting: String,
+}
+
+impl OutputTemplates {
+    fn for_mode(&self, mode: &PostProcessMode) -> &str {
+        match mode {
+            PostProcessMode::General => &self.general,
+            PostProcessMode::Code => &self.code,
+            PostProcessMode::Meeting => &self.meeting,
+        }
+    }
+
+    /// 按模式套用模板
+    pub fn apply(&self, mode: &PostProcessMode, text: &str) -> String {
+        let template = self.for_mode(mode);
+        if template.is_empty() {
+            text.to_string()
+        } else {
+            template.replace("{text}", text)
+        }
+    }
+}
+
+/// 各模式是否在输入后自动按回车发送（聊天软件免手动发送）
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct AutoSendConfig {
+    #[serde(default)]
+    pub general: bool,
+    #[serde(default)]
+    pub code: bool,
+    #[serde(default)]
+    pub meeting: bool,
+    /// 识别置信度低于该值时不自动发送 (0.0 - 1.0)，0 表示不检查；
+    /// Provider 未返回置信度时视为未达标
+    #[serde(default)]
+    pub min_confidence: f32,
+}
+
+impl AutoSendConfig {
+    fn for_mode(&self, mode: &PostProcessMode) -> bool {
+        match mode {
+            PostProcessMode::General => self.general,
+            PostProcessMode::Code => self.code,
+            PostProcessMode::Meeting => self.meeting,
+        }
+    }
+
+    /// 本次结果是否自动发送
+    pub fn should_send(&self, mode: &PostProcessMode, confidence: Option<f32>) -> bool {
+        if !self.for_mode(mode) {
+            return false;
+        }
+        if self.min_confidence > 0.0 && !confidence.is_some_and(|c| c >= self.min_confidence) {
+            log::info!(
+                "Auto send skipped: confidence {:?} below {}",
+                confidence,
+                self.min_confidence
+            );
+            return false;
+        }
+        true
+    }
+}
+
+/// 各模式的专用录音快捷键，为空时不注册
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct ModeShortcuts {
+    #[serde(default)]
+    pub general: String,
+    #[serde(default)]
+    pub code: String,
+    #[serde(default)]
+    pub meeting: String,
+}
+
+impl ModeShortcuts {
+    /// 已配置的 (模式, 快捷键) 列表
+    pub fn entries(&self) -> impl Iterator<Item = (PostProcessMode, &str)> {
+        [
+            (PostProcessMode::General, self.general.as_str()),
+            (PostProcessMode::Code, self.code.as_str()),
+            (PostProcessMode::Meeting, self.meeting.as_str()),
+        ]
+        .into_iter()
+        .filter(|(_, shortcut)| !shortcut.is_empty())
+    }
+}
+
 /// 后处理总配置
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PostProcessConfig {
@@ -35,6 +183,21 @@ pub struct PostProcessConfig {
     pub active_provider_id: String,
     /// 处理模式
     pub mode: PostProcessMode,
+    /// 固定使用 `mode`，不按前台应用自动切换
+    #[serde(default)]
+    pub mode_pinned: bool,
+    /// 自动切换规则，按顺序匹配，均不匹配时使用 `mode`
+    #[serde(default = "default_mode_rules")]
+    pub mode_rules: Vec<ModeRule>,
+    /// 输出模板
+    #[serde(default)]
+    pub templates: OutputTemplates,
+    /// 按模式自动发送
+    #[serde(default)]
+    pub auto_send: AutoSendConfig,
+    /// 按模式的录音快捷键（按下时本次录音使用对应模式）
+    #[serde(default)]
+    pub shortcuts: ModeShortcuts,
 }
 
 impl Default for PostProcessConfig {
@@ -51,6 +214,11 @@ impl Default for PostProcessConfig {
             providers: vec![default_provider],
             active_provider_id: "default".to_string(),
             mode: PostProcessMode::General,
+            mode_pinned: false,
+            mode_rules: default_mode_rules(),
+            templates: OutputTemplates::default(),
+            auto_send: AutoSendConfig::default(),
+            shortcuts: ModeShortcuts::default(),
         }
     }
 }
@@ -62,4 +230,19 @@ impl PostProcessConfig {
             .iter()
             .find(|p| p.id == self.active_provider_id)
     }
+
+    /// 根据前台应用名称确定本次会话的处理模式
+    pub fn resolve_mode(&self, app_name: Option<&str>) -> PostProcessMode {
+        if self.mode_pinned {
+            return self.mode.clone();
+        }
+        let Some(app_name) = app_name.map(str::to_lowercase) else {
+            return self.mode.clone();
+        };
+        self.mode_rules
+            .iter()
+            .find(|rule| !rule.pattern.is_empty() && app_name.contains(&rule.pattern.to_lowercase()))
+            .map(|rule| rule.mode.clone())
+            .unwrap_or_else(|| self.mode.clone())
+    }
 }
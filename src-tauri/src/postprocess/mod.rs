@@ -5,7 +5,7 @@ pub mod prompts;
 use std::time::Duration;
 use tokio::time::timeout;
 
-pub use config::{LlmProvider, PostProcessConfig};
+pub use config::{LlmProvider, PostProcessConfig, PostProcessMode};
 
 use client::LlmClient;
 use prompts::get_prompt;
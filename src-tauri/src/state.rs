@@ -1,12 +1,42 @@
+use chrono::{DateTime, Datelike, Local, NaiveTime};
 use directories::ProjectDirs;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::asr::providers::{DoubaoConfig, WhisperApiConfig, WhisperLocalConfig};
-use crate::postprocess::PostProcessConfig;
+use crate::asr::dual::DualSelection;
+use crate::asr::providers::{
+    AlibabaNlsConfig, AwsTranscribeConfig, AzureSpeechConfig, BaiduConfig, CustomWsConfig,
+    DeepgramConfig, DoubaoConfig, IflytekConfig, MockConfig, OpenAiRealtimeConfig, SonioxConfig,
+    SpeachesConfig, TencentAsrConfig, WhisperApiConfig, WhisperLocalConfig, WhisperServerConfig,
+};
+#[cfg(feature = "sherpa")]
+use crate::asr::providers::{FunAsrLocalConfig, MoonshineLocalConfig, SenseVoiceLocalConfig};
+#[cfg(feature = "vosk")]
+use crate::asr::providers::VoskConfig;
+use crate::postprocess::{PostProcessConfig, PostProcessMode};
+use crate::audio::capture::{AudioDevice, DualSourceConfig};
+use crate::audio::dsp;
+use crate::input::keyboard::{KeyboardBackend, RealtimeReplace, TrailingKey, TypingConfig};
+use crate::text::profanity::ProfanityFilterConfig;
+use crate::text::sensitive::SensitiveFilterConfig;
+use crate::text::spoken::SpokenCommandsConfig;
+use crate::text::terminal::TerminalSafeConfig;
+
+/// 未编译进当前构建的 Provider 配置，原样保留以免保存配置时丢失
+#[cfg(not(all(feature = "vosk", feature = "sherpa")))]
+type DisabledProviderConfig = toml::Value;
+#[cfg(not(feature = "vosk"))]
+type VoskConfig = DisabledProviderConfig;
+#[cfg(not(feature = "sherpa"))]
+type FunAsrLocalConfig = DisabledProviderConfig;
+#[cfg(not(feature = "sherpa"))]
+type SenseVoiceLocalConfig = DisabledProviderConfig;
+#[cfg(not(feature = "sherpa"))]
+type MoonshineLocalConfig = DisabledProviderConfig;
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum RecordingState {
@@ -15,12 +45,114 @@ pub enum RecordingState {
     Processing,
 }
 
+/// 录音快捷键的触发方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutMode {
+    /// 按住录音，松开停止
+    #[default]
+    PushToTalk,
+    /// 按一下开始，再按一下停止
+    Toggle,
+}
+
+/// 录音期间切换了窗口时的输出处理
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusLock {
+    /// 输出到当前焦点窗口
+    #[default]
+    Off,
+    /// 输出前重新激活开始录音时的窗口
+    Refocus,
+    /// 焦点变化时不做键盘输出（仍复制到剪贴板），并发出提醒
+    Suppress,
+}
+
+/// 免打扰时段，期间自动暂停语音输入
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuietHours {
+    /// 开始时间 ("HH:MM")
+    pub start: String,
+    /// 结束时间 ("HH:MM")，早于开始时间表示跨越午夜
+    pub end: String,
+    /// 生效的星期 (1 = 周一 ... 7 = 周日)，为空表示每天，跨午夜时段按开始日计算
+    #[serde(default)]
+    pub days: Vec<u32>,
+}
+
+impl QuietHours {
+    /// 判断给定时刻是否处于该时段（时间格式无效时视为不在时段内）
+    pub fn contains(&self, now: DateTime<Local>) -> bool {
+        let (Ok(start), Ok(end)) = (
+            NaiveTime::parse_from_str(&self.start, "%H:%M"),
+            NaiveTime::parse_from_str(&self.end, "%H:%M"),
+        ) else {
+            return false;
+        };
+
+        let time = now.time();
+        let weekday = now.weekday().number_from_monday();
+        let day_matches = |day: u32| self.days.is_empty() || self.days.contains(&day);
+
+        if start <= end {
+            start <= time && time < end && day_matches(weekday)
+        } else if time >= start {
+            // 跨午夜：开始当天的后半段
+            day_matches(weekday)
+        } else if time < end {
+            // 跨午夜：次日凌晨，按前一天判断
+            day_matches(if weekday == 1 { 7 } else { weekday - 1 })
+        } else {
+            false
+        }
+    }
+}
+
+/// 静音自动停止录音
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AutoStopConfig {
+    /// 是否启用（启用后快捷键改为按一下开始，说完后静音自动停止，再按一下可手动停止）
+    #[serde(default)]
+    pub enabled: bool,
+    /// 说话后连续静音多少秒自动停止
+    #[serde(default = "default_silence_secs")]
+    pub silence_secs: f32,
+    /// 低于该音量（dBFS）视为静音
+    #[serde(default = "default_silence_threshold_db")]
+    pub threshold_db: f32,
+}
+
+fn default_silence_secs() -> f32 {
+    2.0
+}
+
+fn default_silence_threshold_db() -> f32 {
+    -45.0
+}
+
+impl Default for AutoStopConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            silence_secs: default_silence_secs(),
+            threshold_db: default_silence_threshold_db(),
+        }
+    }
+}
+
 /// ASR 配置
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AsrConfig {
-    /// 当前激活的 Provider ID ("doubao", "whisper_local", "whisper_api")
+    /// 当前激活的 Provider ID（与各 Provider 的 `id()` 一致，如 "doubao"、"whisper_local"、"mock"）
     #[serde(default = "default_active_provider")]
     pub active_provider: String,
+    /// 对比模式：同时识别同一段音频的第二个 Provider（None 表示关闭）
+    #[serde(default)]
+    pub secondary_provider: Option<String>,
+    /// 对比模式下最终结果的选择策略
+    #[serde(default)]
+    pub dual_selection: DualSelection,
     /// 豆包配置
     #[serde(default)]
     pub doubao: Option<DoubaoConfig>,
@@ -30,6 +162,69 @@ pub struct AsrConfig {
     /// Whisper API 配置
     #[serde(default)]
     pub whisper_api: Option<WhisperApiConfig>,
+    /// OpenAI Realtime 配置
+    #[serde(default)]
+    pub openai_realtime: Option<OpenAiRealtimeConfig>,
+    /// Azure Speech 配置
+    #[serde(default)]
+    pub azure: Option<AzureSpeechConfig>,
+    /// Amazon Transcribe 配置
+    #[serde(default)]
+    pub aws_transcribe: Option<AwsTranscribeConfig>,
+    /// Deepgram 配置
+    #[serde(default)]
+    pub deepgram: Option<DeepgramConfig>,
+    /// Vosk 本地配置
+    #[serde(default)]
+    pub vosk: Option<VoskConfig>,
+    /// FunASR 本地配置
+    #[serde(default)]
+    pub funasr_local: Option<FunAsrLocalConfig>,
+    /// SenseVoice 本地配置
+    #[serde(default)]
+    pub sense_voice: Option<SenseVoiceLocalConfig>,
+    /// Moonshine 本地配置
+    #[serde(default)]
+    pub moonshine: Option<MoonshineLocalConfig>,
+    /// 讯飞配置
+    #[serde(default)]
+    pub iflytek: Option<IflytekConfig>,
+    /// 腾讯云配置
+    #[serde(default)]
+    pub tencent: Option<TencentAsrConfig>,
+    /// 阿里云 NLS 配置
+    #[serde(default)]
+    pub alibaba: Option<AlibabaNlsConfig>,
+    /// 自定义 WebSocket 配置
+    #[serde(default)]
+    pub custom_ws: Option<CustomWsConfig>,
+    /// whisper.cpp 服务器配置
+    #[serde(default)]
+    pub whisper_server: Option<WhisperServerConfig>,
+    /// faster-whisper (speaches) 配置
+    #[serde(default)]
+    pub speaches: Option<SpeachesConfig>,
+    /// Soniox 配置
+    #[serde(default)]
+    pub soniox: Option<SonioxConfig>,
+    /// 百度语音配置
+    #[serde(default)]
+    pub baidu: Option<BaiduConfig>,
+    /// 演示模式配置
+    #[serde(default)]
+    pub mock: Option<MockConfig>,
+    /// 绑定 Provider 的录音快捷键（按下时本次录音改用对应 Provider）
+    #[serde(default)]
+    pub shortcuts: Vec<ProviderShortcut>,
+}
+
+/// 绑定到指定 Provider 的录音快捷键
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProviderShortcut {
+    /// 快捷键，如 "Alt+W"
+    pub shortcut: String,
+    /// Provider ID
+    pub provider: String,
 }
 
 fn default_active_provider() -> String {
@@ -40,9 +235,29 @@ impl Default for AsrConfig {
     fn default() -> Self {
         Self {
             active_provider: default_active_provider(),
+            secondary_provider: None,
+            dual_selection: DualSelection::default(),
             doubao: Some(DoubaoConfig::default()),
             whisper_local: None,
             whisper_api: None,
+            openai_realtime: None,
+            azure: None,
+            aws_transcribe: None,
+            deepgram: None,
+            vosk: None,
+            funasr_local: None,
+            sense_voice: None,
+            moonshine: None,
+            iflytek: None,
+            tencent: None,
+            alibaba: None,
+            custom_ws: None,
+            whisper_server: None,
+            speaches: None,
+            soniox: None,
+            baidu: None,
+            mock: None,
+            shortcuts: Vec::new(),
         }
     }
 }
@@ -60,6 +275,9 @@ pub struct AppConfig {
     #[serde(default, skip_serializing)]
     pub secret_key: String,
     pub shortcut: String,
+    /// 快捷键触发方式
+    #[serde(default)]
+    pub shortcut_mode: ShortcutMode,
     pub auto_type: bool,
     pub auto_copy: bool,
     #[serde(default)]
@@ -70,23 +288,177 @@ pub struct AppConfig {
     pub show_indicator: bool,
     #[serde(default)]
     pub realtime_input: bool,
+    /// 实时输入替换上一次文本的方式
+    #[serde(default)]
+    pub realtime_replace: RealtimeReplace,
+    /// 把输出锁定到开始录音时的窗口
+    #[serde(default)]
+    pub focus_lock: FocusLock,
+    /// 同时写入主选区，可用鼠标中键粘贴（仅 Linux）
+    #[serde(default)]
+    pub copy_to_primary: bool,
+    /// 输入结果后追加的按键（实时输入模式下不追加）
+    #[serde(default)]
+    pub trailing_key: TrailingKey,
+    /// 预览模式：不输入也不复制，结果显示在可关闭的预览窗口中
+    #[serde(default)]
+    pub review_output: bool,
+    /// 输出到终端时把多行文本合并为一行，避免逐行执行
+    #[serde(default)]
+    pub terminal_safe: TerminalSafeConfig,
+    /// 键盘输入后端（Wayland 下无法模拟输入时可改用 ydotool）
+    #[serde(default)]
+    pub keyboard_backend: KeyboardBackend,
+    /// SendInput 分批输入时每批之间的间隔（毫秒）
+    #[serde(default = "default_send_input_delay_ms")]
+    pub send_input_delay_ms: u32,
+    /// 输入速度和按键间隔
+    #[serde(default)]
+    pub typing: TypingConfig,
+    /// 自动粘贴后恢复原来的剪贴板内容
+    #[serde(default)]
+    pub restore_clipboard: bool,
     #[serde(default)]
     pub postprocess: PostProcessConfig,
-    /// 选择的音频设备名称，空字符串表示使用系统默认设备
+    /// 选择的音频设备 ID，空字符串表示使用系统默认设备（旧配置中为设备名称，启动时迁移）
     #[serde(default)]
     pub audio_device: String,
+    /// 各输入设备的软件增益倍数（键为设备 ID，空字符串表示系统默认设备）
+    #[serde(default)]
+    pub input_gains: HashMap<String, f32>,
+    /// 麦克风软件增益（dB），叠加在各设备的增益倍数之上，用于放大音量过低的麦克风
+    #[serde(default)]
+    pub input_gain_db: f32,
+    /// 是否启用回声消除（以系统输出回环作为参考信号）
+    #[serde(default)]
+    pub echo_cancellation: bool,
+    /// 回声消除参考设备名称，空字符串表示自动选择系统输出回环/monitor 设备
+    #[serde(default)]
+    pub echo_reference_device: String,
+    /// 高通滤波截止频率（Hz，建议 80~120），0 表示关闭
+    #[serde(default)]
+    pub high_pass_hz: u32,
+    /// 是否启用 RNNoise 降噪（抑制风扇声等背景噪声）
+    #[serde(default)]
+    pub noise_suppression: bool,
     /// 是否启用日志记录到文件
     #[serde(default = "default_enable_logging")]
     pub enable_logging: bool,
     /// ASR 识别语言 ("auto", "zh", "en", "ja", "ko", etc.)
     #[serde(default = "default_asr_language")]
     pub asr_language: String,
+    /// 按 Provider 覆盖识别语言（provider id -> 语言），未设置时使用 asr_language
+    #[serde(default)]
+    pub provider_languages: HashMap<String, String>,
+    /// 热词（产品名、人名等专有名词），用于提升识别准确率
+    #[serde(default)]
+    pub hotwords: Vec<String>,
+    /// 最低置信度阈值 (0.0 - 1.0)，低于该值时不自动输入，仅复制到剪贴板；0 表示不检查
+    #[serde(default)]
+    pub min_confidence: f32,
+    /// 是否对英文结果做大小写和空格规整（不依赖 LLM）
+    #[serde(default = "default_english_formatting")]
+    pub english_formatting: bool,
+    /// 敏感内容过滤（命中时不自动输入）
+    #[serde(default)]
+    pub sensitive_filter: SensitiveFilterConfig,
+    /// 脏话过滤（遮盖或删除后再输出）
+    #[serde(default)]
+    pub profanity_filter: ProfanityFilterConfig,
+    /// 口述格式指令（“逗号”“new line” 等替换为标点和换行）
+    #[serde(default)]
+    pub spoken_commands: SpokenCommandsConfig,
+    /// 录音期间是否降低系统输出音量
+    #[serde(default)]
+    pub ducking_enabled: bool,
+    /// 录音期间输出音量降低的百分比 (0-100)
+    #[serde(default = "default_ducking_percent")]
+    pub ducking_percent: u8,
+    /// 是否在历史记录中保存录音波形
+    #[serde(default = "default_history_waveform")]
+    pub history_waveform: bool,
+    /// 已启用的文本变换插件名称
+    #[serde(default)]
+    pub enabled_plugins: Vec<String>,
+    /// 输出脚本路径（Rhai），为空时不执行
+    #[serde(default)]
+    pub output_script: String,
+    /// LLM 改写文本时保留原始识别结果（Linux 写入主选区，可通过 copy_raw_transcript 取回）
+    #[serde(default)]
+    pub keep_raw_transcript: bool,
+    /// 暂停/恢复快捷键，为空时不注册
+    #[serde(default)]
+    pub pause_shortcut: String,
+    /// 录音中取消本次录音的快捷键（仅录音期间注册），为空时不注册
+    #[serde(default = "default_cancel_shortcut")]
+    pub cancel_shortcut: String,
+    /// 对最近一条历史记录重新执行后处理的快捷键，为空时不注册
+    #[serde(default)]
+    pub reprocess_shortcut: String,
+    /// 重新后处理后把结果输入到当前窗口（否则只复制到剪贴板）
+    #[serde(default)]
+    pub reprocess_retype: bool,
+    /// 免打扰时段
+    #[serde(default)]
+    pub quiet_hours: Vec<QuietHours>,
+    /// 麦克风与系统音频混音
+    #[serde(default)]
+    pub dual_source: DualSourceConfig,
+    /// 丢弃录音开头的时长（毫秒），去除按下快捷键的按键声
+    #[serde(default)]
+    pub start_trim_ms: u32,
+    /// 录音开头的淡入时长（毫秒）
+    #[serde(default)]
+    pub start_fade_ms: u32,
+    /// 开始录音前预缓冲的时长（毫秒），0 表示关闭；开启后麦克风保持常开
+    #[serde(default)]
+    pub preroll_ms: u32,
+    /// 静音自动停止录音
+    #[serde(default)]
+    pub auto_stop: AutoStopConfig,
+    /// 是否把每次录音保存为 WAV 文件（数据目录下的 recordings）
+    #[serde(default)]
+    pub save_recordings: bool,
+    /// 最多保留的录音文件数，0 表示不限制
+    #[serde(default = "default_max_recordings")]
+    pub max_recordings: usize,
+    /// 录音文件保留天数，0 表示不限制
+    #[serde(default = "default_recording_retention_days")]
+    pub recording_retention_days: u32,
+}
+
+fn default_english_formatting() -> bool {
+    false
+}
+
+fn default_ducking_percent() -> u8 {
+    70
+}
+
+fn default_history_waveform() -> bool {
+    true
 }
 
 fn default_asr_language() -> String {
     "zh".to_string()
 }
 
+fn default_send_input_delay_ms() -> u32 {
+    10
+}
+
+fn default_cancel_shortcut() -> String {
+    "Escape".to_string()
+}
+
+fn default_max_recordings() -> usize {
+    200
+}
+
+fn default_recording_retention_days() -> u32 {
+    30
+}
+
 fn default_show_indicator() -> bool {
     true
 }
@@ -103,16 +475,59 @@ impl Default for AppConfig {
             access_token: String::new(),
             secret_key: String::new(),
             shortcut: "Alt+Space".to_string(),
+            shortcut_mode: ShortcutMode::default(),
             auto_type: true,
             auto_copy: true,
             auto_start: false,
             silent_start: false,
             show_indicator: true,
             realtime_input: false,
+            realtime_replace: RealtimeReplace::default(),
+            focus_lock: FocusLock::default(),
+            copy_to_primary: false,
+            trailing_key: TrailingKey::default(),
+            review_output: false,
+            terminal_safe: TerminalSafeConfig::default(),
+            keyboard_backend: KeyboardBackend::default(),
+            send_input_delay_ms: default_send_input_delay_ms(),
+            typing: TypingConfig::default(),
+            restore_clipboard: false,
             postprocess: PostProcessConfig::default(),
             audio_device: String::new(),
+            input_gains: HashMap::new(),
+            input_gain_db: 0.0,
+            echo_cancellation: false,
+            echo_reference_device: String::new(),
+            high_pass_hz: 0,
+            noise_suppression: false,
             enable_logging: true,
             asr_language: default_asr_language(),
+            provider_languages: HashMap::new(),
+            hotwords: Vec::new(),
+            min_confidence: 0.0,
+            english_formatting: default_english_formatting(),
+            sensitive_filter: SensitiveFilterConfig::default(),
+            profanity_filter: ProfanityFilterConfig::default(),
+            spoken_commands: SpokenCommandsConfig::default(),
+            ducking_enabled: false,
+            ducking_percent: default_ducking_percent(),
+            history_waveform: default_history_waveform(),
+            enabled_plugins: Vec::new(),
+            output_script: String::new(),
+            keep_raw_transcript: false,
+            pause_shortcut: String::new(),
+            cancel_shortcut: default_cancel_shortcut(),
+            reprocess_shortcut: String::new(),
+            reprocess_retype: false,
+            quiet_hours: Vec::new(),
+            dual_source: DualSourceConfig::default(),
+            start_trim_ms: 0,
+            start_fade_ms: 0,
+            preroll_ms: 0,
+            auto_stop: AutoStopConfig::default(),
+            save_recordings: false,
+            max_recordings: default_max_recordings(),
+            recording_retention_days: default_recording_retention_days(),
         }
     }
 }
@@ -149,6 +564,57 @@ impl AppConfig {
         Self::default()
     }
 
+    /// 获取指定设备的输入增益倍数（设备增益与全局 dB 增益相乘，未配置时为 1.0）
+    pub fn input_gain_for(&self, device_id: &str) -> f32 {
+        let device_gain = self
+            .input_gains
+            .get(device_id)
+            .copied()
+            .filter(|g| g.is_finite() && *g > 0.0)
+            .unwrap_or(1.0);
+        let boost = if self.input_gain_db.is_finite() {
+            dsp::db_to_gain(self.input_gain_db)
+        } else {
+            1.0
+        };
+        device_gain * boost
+    }
+
+    /// 获取指定 Provider 的识别语言（未单独设置时使用全局语言）
+    pub fn language_for(&self, provider_id: &str) -> &str {
+        self.provider_languages
+            .get(provider_id)
+            .map(|l| l.as_str())
+            .filter(|l| !l.is_empty())
+            .unwrap_or(&self.asr_language)
+    }
+
+    /// 判断当前是否处于任一免打扰时段
+    pub fn in_quiet_hours(&self, now: DateTime<Local>) -> bool {
+        self.quiet_hours.iter().any(|q| q.contains(now))
+    }
+
+    /// 判断识别置信度是否低于阈值（Provider 未报告置信度时不拦截）
+    pub fn is_low_confidence(&self, confidence: Option<f32>) -> bool {
+        self.min_confidence > 0.0 && confidence.is_some_and(|c| c < self.min_confidence)
+    }
+
+    /// 去除空白项后的热词列表
+    pub fn hotword_list(&self) -> Vec<String> {
+        self.hotwords
+            .iter()
+            .map(|w| w.trim())
+            .filter(|w| !w.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    /// 热词拼成的 Whisper 提示词（没有热词时为 None）
+    pub fn hotword_prompt(&self) -> Option<String> {
+        let hotwords = self.hotword_list();
+        (!hotwords.is_empty()).then(|| hotwords.join(", "))
+    }
+
     /// 迁移旧的 ASR 配置到新结构
     fn migrate_legacy_asr_config(&mut self) {
         // 如果旧字段有值，迁移到新的 asr.doubao 配置
@@ -157,6 +623,7 @@ impl AppConfig {
                 app_id: std::mem::take(&mut self.app_id),
                 access_token: std::mem::take(&mut self.access_token),
                 secret_key: std::mem::take(&mut self.secret_key),
+                ..Default::default()
             };
 
             // 只有当 doubao 配置为空或未配置时才迁移
@@ -167,6 +634,41 @@ impl AppConfig {
         }
     }
 
+    /// 把按设备名称保存的麦克风和增益迁移为设备 ID，有改动时返回 true
+    ///
+    /// 只迁移当前已连接的设备，未连接的设备保留名称，下次启动再迁移（采集时仍可按名称匹配）。
+    pub fn migrate_audio_device_ids(&mut self, devices: &[AudioDevice]) -> bool {
+        let id_for_name = |name: &str| {
+            devices
+                .iter()
+                .find(|d| !d.name.is_empty() && d.name == name && d.id != name)
+                .map(|d| d.id.clone())
+        };
+        let mut migrated = false;
+
+        if !devices.iter().any(|d| d.id == self.audio_device) {
+            if let Some(id) = id_for_name(&self.audio_device) {
+                log::info!("Migrated audio device '{}' to id {}", self.audio_device, id);
+                self.audio_device = id;
+                migrated = true;
+            }
+        }
+
+        let renamed: Vec<(String, String)> = self
+            .input_gains
+            .keys()
+            .filter(|key| !devices.iter().any(|d| &d.id == *key))
+            .filter_map(|key| id_for_name(key).map(|id| (key.clone(), id)))
+            .collect();
+        for (name, id) in renamed {
+            if let Some(gain) = self.input_gains.remove(&name) {
+                self.input_gains.insert(id, gain);
+                migrated = true;
+            }
+        }
+        migrated
+    }
+
     /// 保存配置到文件
     pub fn save(&self) -> Result<(), String> {
         let path = Self::config_path().ok_or("Failed to get config path")?;
@@ -189,7 +691,15 @@ impl AppConfig {
 
 pub struct AppState {
     pub recording_state: Arc<RwLock<RecordingState>>,
+    /// 是否处于暂停（免打扰）状态
+    pub paused: Arc<RwLock<bool>>,
     pub current_transcript: Arc<RwLock<String>>,
+    /// LLM 后处理前的原始识别结果
+    pub raw_transcript: Arc<RwLock<String>>,
+    /// 当前识别结果的置信度
+    pub current_confidence: Arc<RwLock<Option<f32>>>,
+    /// 本次会话使用的后处理模式（录音开始时按前台应用确定）
+    pub session_mode: Arc<RwLock<PostProcessMode>>,
     pub config: Arc<RwLock<AppConfig>>,
 }
 
@@ -199,7 +709,11 @@ impl AppState {
         let config = AppConfig::load();
         Self {
             recording_state: Arc::new(RwLock::new(RecordingState::Idle)),
+            paused: Arc::new(RwLock::new(false)),
             current_transcript: Arc::new(RwLock::new(String::new())),
+            raw_transcript: Arc::new(RwLock::new(String::new())),
+            current_confidence: Arc::new(RwLock::new(None)),
+            session_mode: Arc::new(RwLock::new(PostProcessMode::default())),
             config: Arc::new(RwLock::new(config)),
         }
     }
@@ -212,6 +726,14 @@ impl AppState {
         self.recording_state.read().clone()
     }
 
+    pub fn set_paused(&self, paused: bool) {
+        *self.paused.write() = paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.read()
+    }
+
     pub fn set_transcript(&self, text: String) {
         *self.current_transcript.write() = text;
     }
@@ -222,6 +744,32 @@ impl AppState {
 
     pub fn clear_transcript(&self) {
         self.current_transcript.write().clear();
+        self.raw_transcript.write().clear();
+        *self.current_confidence.write() = None;
+    }
+
+    pub fn set_raw_transcript(&self, text: String) {
+        *self.raw_transcript.write() = text;
+    }
+
+    pub fn get_raw_transcript(&self) -> String {
+        self.raw_transcript.read().clone()
+    }
+
+    pub fn set_confidence(&self, confidence: Option<f32>) {
+        *self.current_confidence.write() = confidence;
+    }
+
+    pub fn get_confidence(&self) -> Option<f32> {
+        *self.current_confidence.read()
+    }
+
+    pub fn set_session_mode(&self, mode: PostProcessMode) {
+        *self.session_mode.write() = mode;
+    }
+
+    pub fn get_session_mode(&self) -> PostProcessMode {
+        self.session_mode.read().clone()
     }
 
     pub fn get_config(&self) -> AppConfig {
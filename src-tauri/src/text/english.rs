@@ -0,0 +1,179 @@
+//! 英文文本规整
+//!
+//! 确定性的大小写和空格修正：句首大写、独立的 "i" 大写、去掉标点前的空格、
+//! 逗号等标点后补空格。不依赖 LLM，用于修正本地 Whisper 大小写不一致的输出。
+
+/// 句末标点
+const SENTENCE_END: [char; 3] = ['.', '!', '?'];
+/// 需要紧贴前一个词的标点
+const ATTACHED_PUNCT: [char; 7] = [',', '.', '!', '?', ';', ':', '%'];
+
+/// 规整英文文本（不含英文字母时原样返回）
+pub fn format_english(text: &str) -> String {
+    if !text.chars().any(|c| c.is_ascii_alphabetic()) {
+        return text.to_string();
+    }
+
+    let spaced = fix_spacing(text);
+    capitalize(&spaced)
+}
+
+/// 修正空格：合并连续空格、去掉标点前空格、标点后缺少空格时补上
+fn fix_spacing(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' {
+            let next = chars.get(i + 1).copied();
+            // 连续空格、标点前的空格都丢弃
+            if out.ends_with(' ') || next.is_some_and(|n| ATTACHED_PUNCT.contains(&n)) {
+                continue;
+            }
+            out.push(c);
+            continue;
+        }
+
+        out.push(c);
+
+        // 标点后紧跟字母时补空格。句点不处理，避免拆开 "main.rs"、"example.com"、"e.g." 这类写法；
+        // 网址和邮箱中的 "?"、";" 等也保持原样
+        if matches!(c, ',' | '!' | '?' | ';')
+            && chars.get(i + 1).is_some_and(|n| n.is_ascii_alphabetic())
+            && !in_link(&out)
+        {
+            out.push(' ');
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// 判断当前词是否是网址或邮箱
+fn in_link(out: &str) -> bool {
+    let word = out.rsplit(char::is_whitespace).next().unwrap_or_default();
+    word.contains("://") || word.contains('@') || word.starts_with("www.")
+}
+
+/// 句首字母大写，独立的 "i"（以及 i'm / i'll 等）大写
+fn capitalize(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut sentence_start = true;
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_alphabetic() {
+            let prev = if i > 0 { chars[i - 1] } else { ' ' };
+            let next = chars.get(i + 1).copied().unwrap_or(' ');
+            let standalone_i = c == 'i'
+                && !prev.is_alphanumeric()
+                && (!next.is_alphanumeric() || next == '\'')
+                && (next != '\'' || is_i_contraction(&chars[i + 1..]))
+                && !(next == '.' && chars.get(i + 2).is_some_and(|c| c.is_ascii_alphabetic()));
+
+            // 词内已有大写字母（如 iPhone、eBay）时保持原样
+            let word_has_upper = chars[i + 1..]
+                .iter()
+                .take_while(|c| c.is_alphanumeric())
+                .any(|c| c.is_uppercase());
+
+            if (sentence_start && !word_has_upper) || standalone_i {
+                out.push(c.to_ascii_uppercase());
+            } else {
+                out.push(c);
+            }
+            sentence_start = false;
+            continue;
+        }
+
+        out.push(c);
+        if SENTENCE_END.contains(&c) {
+            // 仅当后面是空白且不是缩写时才视为句末（排除 3.14、e.g. 等）
+            sentence_start = chars.get(i + 1).is_none_or(|n| n.is_whitespace())
+                && !(c == '.' && is_abbreviation(&chars[..i]));
+        } else if c.is_alphanumeric() {
+            // 非 ASCII 文字（如中文）不影响句首状态之外的判断
+            sentence_start = false;
+        }
+    }
+
+    out
+}
+
+/// 判断句点前是否为单字母缩写（如 "e.g"、"U.S"）
+fn is_abbreviation(before: &[char]) -> bool {
+    match before {
+        [.., a, b] => b.is_ascii_alphabetic() && (*a == '.' || !a.is_alphanumeric()),
+        [b] => b.is_ascii_alphabetic(),
+        [] => false,
+    }
+}
+
+/// 判断 "i" 后是否为常见缩写 ('m, 'll, 've, 'd)
+fn is_i_contraction(rest: &[char]) -> bool {
+    let suffix: String = rest
+        .iter()
+        .skip(1)
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    matches!(suffix.as_str(), "m" | "ll" | "ve" | "d")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_file_names() {
+        assert_eq!(
+            format_english("open main.rs and config.json"),
+            "Open main.rs and config.json"
+        );
+    }
+
+    #[test]
+    fn keeps_urls() {
+        assert_eq!(format_english("visit example.com"), "Visit example.com");
+        assert_eq!(
+            format_english("see https://example.com/search?q=rust;lang=en for details"),
+            "See https://example.com/search?q=rust;lang=en for details"
+        );
+        assert_eq!(format_english("go to www.rust-lang.org"), "Go to www.rust-lang.org");
+    }
+
+    #[test]
+    fn keeps_emails() {
+        assert_eq!(
+            format_english("mail john.doe@example.com today"),
+            "Mail john.doe@example.com today"
+        );
+    }
+
+    #[test]
+    fn keeps_version_numbers() {
+        assert_eq!(format_english("upgrade to v1.2.3 now"), "Upgrade to v1.2.3 now");
+        assert_eq!(format_english("pi is 3.14"), "Pi is 3.14");
+    }
+
+    #[test]
+    fn keeps_links_in_cjk_text() {
+        assert_eq!(format_english("代码在github.com上"), "代码在github.com上");
+        assert_eq!(format_english("打开 github.com 看看"), "打开 github.com 看看");
+    }
+
+    #[test]
+    fn capitalizes_sentences_and_i() {
+        assert_eq!(
+            format_english("hello world. i think i'm done"),
+            "Hello world. I think I'm done"
+        );
+        assert_eq!(format_english("e.g. this works"), "E.g. this works");
+    }
+
+    #[test]
+    fn fixes_punctuation_spacing() {
+        assert_eq!(format_english("yes ,no  maybe"), "Yes, no maybe");
+        assert_eq!(format_english("wait,what?really"), "Wait, what? Really");
+    }
+}
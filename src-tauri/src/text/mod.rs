@@ -0,0 +1,9 @@
+//! 识别文本处理
+//!
+//! 在键盘输出前对最终识别结果做本地检查和规整。
+
+pub mod english;
+pub mod profanity;
+pub mod sensitive;
+pub mod spoken;
+pub mod terminal;
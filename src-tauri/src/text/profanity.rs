@@ -0,0 +1,112 @@
+//! 脏话过滤
+//!
+//! 在键盘输出前按词表遮盖或删除不雅用语，适用于直播、共享屏幕等场景。
+//! 支持的云服务（Azure、Deepgram、腾讯云）同时开启服务端过滤，本地词表作为兜底。
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// 内置英文词表（按整词匹配，不区分大小写）
+const BUILTIN_EN: &[&str] = &[
+    "fuck", "fucking", "fucked", "fucker", "shit", "shitty", "bitch", "bastard", "asshole",
+    "dick", "cunt", "motherfucker", "bullshit", "damn",
+];
+/// 内置中文词表（按子串匹配）
+const BUILTIN_ZH: &[&str] = &[
+    "他妈的", "他妈", "妈的", "卧槽", "我操", "操你", "傻逼", "煞笔", "傻B", "狗日的",
+    "王八蛋", "混蛋", "滚蛋", "贱人",
+];
+
+/// 处理方式
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfanityMode {
+    /// 用 * 遮盖
+    #[default]
+    Mask,
+    /// 直接删除
+    Remove,
+}
+
+/// 脏话过滤配置
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProfanityFilterConfig {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 处理方式
+    #[serde(default)]
+    pub mode: ProfanityMode,
+    /// 自定义词（追加到内置词表）
+    #[serde(default)]
+    pub words: Vec<String>,
+}
+
+impl ProfanityFilterConfig {
+    /// 启用时返回处理方式，供支持服务端过滤的 Provider 使用
+    pub fn provider_mode(&self) -> Option<ProfanityMode> {
+        self.enabled.then_some(self.mode)
+    }
+
+    /// 过滤文本，未启用时原样返回
+    pub fn apply(&self, text: &str) -> String {
+        if !self.enabled || text.is_empty() {
+            return text.to_string();
+        }
+        match self.pattern() {
+            Some(re) => {
+                let filtered = re.replace_all(text, |caps: &regex::Captures| match self.mode {
+                    ProfanityMode::Mask => "*".repeat(caps[0].chars().count()),
+                    ProfanityMode::Remove => String::new(),
+                });
+                match self.mode {
+                    // 删除后可能留下连续空格
+                    ProfanityMode::Remove => filtered
+                        .split(' ')
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    ProfanityMode::Mask => filtered.into_owned(),
+                }
+            }
+            None => text.to_string(),
+        }
+    }
+
+    /// 由内置词表和自定义词构建匹配表达式：ASCII 词按整词匹配，其他按子串匹配
+    fn pattern(&self) -> Option<Regex> {
+        let custom = self.words.iter().map(|w| w.trim()).filter(|w| !w.is_empty());
+        let mut words: Vec<&str> = BUILTIN_EN
+            .iter()
+            .chain(BUILTIN_ZH)
+            .copied()
+            .chain(custom)
+            .collect();
+        // 长词优先，避免 "他妈的" 只匹配到 "他妈"
+        words.sort_by_key(|w| std::cmp::Reverse(w.chars().count()));
+        words.dedup();
+
+        let alternatives: Vec<String> = words
+            .iter()
+            .map(|w| {
+                let escaped = regex::escape(w);
+                if w.is_ascii() {
+                    format!(r"\b{}\b", escaped)
+                } else {
+                    escaped
+                }
+            })
+            .collect();
+
+        match RegexBuilder::new(&alternatives.join("|"))
+            .case_insensitive(true)
+            .build()
+        {
+            Ok(re) => Some(re),
+            Err(e) => {
+                log::warn!("Invalid profanity word list: {}", e);
+                None
+            }
+        }
+    }
+}
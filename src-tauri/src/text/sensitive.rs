@@ -0,0 +1,118 @@
+//! 敏感内容检测
+//!
+//! 检测信用卡号、密码口述以及用户自定义的正则表达式。命中时不自动输入，
+//! 仅复制到剪贴板并提醒用户，适用于屏幕共享等场景。
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// 口述密码的关键字模式（关键字后紧跟内容）
+const PASSWORD_PATTERN: &str =
+    r"(?i)(password|passwd|passcode|pwd|pin\s*code|密码|口令|验证码)\s*(is|是|为|:|：)?\s*\S+";
+
+/// 敏感内容过滤配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SensitiveFilterConfig {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 检测信用卡号（Luhn 校验）
+    #[serde(default = "default_true")]
+    pub detect_credit_cards: bool,
+    /// 检测口述密码
+    #[serde(default = "default_true")]
+    pub detect_passwords: bool,
+    /// 自定义正则表达式
+    #[serde(default)]
+    pub custom_patterns: Vec<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for SensitiveFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            detect_credit_cards: true,
+            detect_passwords: true,
+            custom_patterns: Vec::new(),
+        }
+    }
+}
+
+impl SensitiveFilterConfig {
+    /// 检测文本中的敏感内容，返回命中的类型描述
+    pub fn detect(&self, text: &str) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+
+        if self.detect_credit_cards && contains_credit_card(text) {
+            return Some("credit_card".to_string());
+        }
+
+        if self.detect_passwords {
+            if let Ok(re) = Regex::new(PASSWORD_PATTERN) {
+                if re.is_match(text) {
+                    return Some("password".to_string());
+                }
+            }
+        }
+
+        for pattern in &self.custom_patterns {
+            match Regex::new(pattern) {
+                Ok(re) if re.is_match(text) => return Some(format!("custom:{}", pattern)),
+                Ok(_) => {}
+                Err(e) => log::warn!("Invalid sensitive pattern '{}': {}", pattern, e),
+            }
+        }
+
+        None
+    }
+}
+
+/// 检测文本中是否包含通过 Luhn 校验的 13-19 位卡号（允许空格和连字符分隔）
+fn contains_credit_card(text: &str) -> bool {
+    let mut digits: Vec<u8> = Vec::with_capacity(19);
+
+    for c in text.chars().chain(std::iter::once('\n')) {
+        if let Some(d) = c.to_digit(10) {
+            digits.push(d as u8);
+            continue;
+        }
+        if (c == ' ' || c == '-') && !digits.is_empty() {
+            continue;
+        }
+        if (13..=19).contains(&digits.len()) && luhn_valid(&digits) {
+            return true;
+        }
+        digits.clear();
+    }
+
+    false
+}
+
+/// Luhn 校验
+fn luhn_valid(digits: &[u8]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            let d = d as u32;
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
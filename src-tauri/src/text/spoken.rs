@@ -0,0 +1,118 @@
+//! 口述格式指令
+//!
+//! 把识别结果中口述的标点和换行（如“逗号”“句号”“new line”）替换为实际字符，
+//! 在键盘输出和保存历史记录之前执行。内置常用指令，可在配置中追加或覆盖。
+
+use regex::{Captures, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 内置指令（中文按子串匹配，英文按整词匹配，不区分大小写）
+const BUILTIN: &[(&str, &str)] = &[
+    ("逗号", "，"),
+    ("句号", "。"),
+    ("问号", "？"),
+    ("感叹号", "！"),
+    ("冒号", "："),
+    ("分号", "；"),
+    ("顿号", "、"),
+    ("换行", "\n"),
+    ("新段落", "\n\n"),
+    ("comma", ","),
+    ("full stop", "."),
+    ("question mark", "?"),
+    ("exclamation mark", "!"),
+    ("exclamation point", "!"),
+    ("new line", "\n"),
+    ("newline", "\n"),
+    ("new paragraph", "\n\n"),
+];
+
+/// 指令后紧跟的标点（识别服务自动补的标点，替换时一并去掉）
+const TRAILING_PUNCT: &str = r"[,.;:!?，。；：！？、]?";
+
+/// 单条指令
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpokenCommand {
+    /// 口述的词
+    pub phrase: String,
+    /// 替换成的文本（可以包含换行）
+    pub replacement: String,
+}
+
+/// 口述格式指令配置
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SpokenCommandsConfig {
+    /// 是否启用
+    #[serde(default)]
+    pub enabled: bool,
+    /// 自定义指令（与内置指令同名时覆盖）
+    #[serde(default)]
+    pub commands: Vec<SpokenCommand>,
+}
+
+impl SpokenCommandsConfig {
+    /// 替换文本中的指令，未启用时原样返回
+    pub fn apply(&self, text: &str) -> String {
+        if !self.enabled || text.is_empty() {
+            return text.to_string();
+        }
+
+        let mut table: HashMap<String, String> = BUILTIN
+            .iter()
+            .map(|(phrase, replacement)| (phrase.to_string(), replacement.to_string()))
+            .collect();
+        for command in &self.commands {
+            let phrase = command.phrase.trim().to_lowercase();
+            if !phrase.is_empty() {
+                table.insert(phrase, command.replacement.clone());
+            }
+        }
+
+        // 长词优先，避免 "new paragraph" 被拆开匹配
+        let mut phrases: Vec<&String> = table.keys().collect();
+        phrases.sort_by_key(|p| std::cmp::Reverse(p.chars().count()));
+        let alternatives: Vec<String> = phrases
+            .iter()
+            .map(|p| {
+                // 词组内的空格允许识别结果中有多个空格
+                let escaped = regex::escape(p).replace(' ', r"\s+");
+                if p.is_ascii() {
+                    format!(r"\b{}\b", escaped)
+                } else {
+                    escaped
+                }
+            })
+            .collect();
+        let pattern = format!(
+            r"[ \t]*(?P<cmd>{}){}(?P<trail>[ \t]*)",
+            alternatives.join("|"),
+            TRAILING_PUNCT
+        );
+        let re = match RegexBuilder::new(&pattern).case_insensitive(true).build() {
+            Ok(re) => re,
+            Err(e) => {
+                log::warn!("Invalid spoken command list: {}", e);
+                return text.to_string();
+            }
+        };
+
+        re.replace_all(text, |caps: &Captures| {
+            let spoken = caps["cmd"].split_whitespace().collect::<Vec<_>>().join(" ");
+            let replacement = table
+                .get(&spoken.to_lowercase())
+                .map(String::as_str)
+                .unwrap_or_default();
+            // 英文标点后保留一个空格，换行和中文标点后不留空格
+            let keep_space = !caps["trail"].is_empty()
+                && replacement.is_ascii()
+                && !replacement.ends_with('\n');
+            if keep_space {
+                format!("{} ", replacement)
+            } else {
+                replacement.to_string()
+            }
+        })
+        .into_owned()
+    }
+}
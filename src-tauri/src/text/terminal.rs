@@ -0,0 +1,89 @@
+//! 终端安全输出
+//!
+//! 多行文本粘贴或输入到终端时每个换行都会立即执行一条命令。焦点应用是终端模拟器时，
+//! 把换行合并为空格后再输出。
+
+use serde::{Deserialize, Serialize};
+
+/// 内置终端应用名（与 active_app 返回的名称比较，不区分大小写）
+const BUILTIN_TERMINALS: &[&str] = &[
+    "terminal",
+    "iterm2",
+    "alacritty",
+    "kitty",
+    "wezterm",
+    "wezterm-gui",
+    "ghostty",
+    "warp",
+    "hyper",
+    "tabby",
+    "rio",
+    "foot",
+    "gnome-terminal",
+    "gnome-terminal-server",
+    // Linux 进程名最长 15 个字符
+    "gnome-terminal-",
+    "konsole",
+    "xterm",
+    "urxvt",
+    "tilix",
+    "terminator",
+    "xfce4-terminal",
+    "windowsterminal",
+    "cmd",
+    "powershell",
+    "pwsh",
+    "conhost",
+];
+
+/// 终端安全输出配置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TerminalSafeConfig {
+    /// 是否启用
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 额外视为终端的应用名（追加到内置列表）
+    #[serde(default)]
+    pub apps: Vec<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Default for TerminalSafeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            apps: Vec::new(),
+        }
+    }
+}
+
+impl TerminalSafeConfig {
+    /// 应用名是否为终端
+    pub fn is_terminal(&self, app_name: &str) -> bool {
+        let name = app_name.trim().to_lowercase();
+        BUILTIN_TERMINALS.contains(&name.as_str())
+            || self
+                .apps
+                .iter()
+                .any(|app| app.trim().to_lowercase() == name)
+    }
+
+    /// 启用且目标是终端时把多行文本合并为一行，否则原样返回
+    pub fn apply(&self, text: &str, app_name: Option<&str>) -> String {
+        if !self.enabled
+            || !text.contains(['\n', '\r'])
+            || !app_name.is_some_and(|name| self.is_terminal(name))
+        {
+            return text.to_string();
+        }
+        log::info!("Terminal detected ({:?}), joining lines", app_name);
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
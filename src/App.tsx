@@ -8,14 +8,14 @@ import {
   MicIcon, SunIcon, MoonIcon, SettingsIcon,
   ChevronLeftIcon, ChevronDownIcon, ChevronUpIcon, CloseIcon,
   GeneralIcon, PostProcessIcon, HistoryIcon, ConfigFileIcon,
-  TrashIcon, CopyIcon, LogsIcon, AsrIcon
+  TrashIcon, CopyIcon, LogsIcon, AsrIcon, RedoIcon
 } from "./components/Icons";
 import {
   type RecordingState, type SettingsTab, type ViewMode,
   type WindowSizes, type Config, type WhisperModel, type DownloadProgress,
   type LlmProvider, type HistoryEntry, type AudioDevice, type LogInfo, type Toast,
-  type PostProcessMode,
-  PROVIDER_PRESETS, DEFAULT_SHORTCUT, calculateWindowSizes
+  type PostProcessMode, type SessionFailure, type ActiveInputDevice, type AudioWarning, type WhisperGpuBackend, type WhisperBenchmark, type KeyboardBackend, type TypingConfig, type RealtimeReplace, type FocusLock, type ShortcutMode, type TrailingKey, type KeyboardTestReport, type AutoSendConfig, type ModeShortcuts, type ProviderShortcut,
+  PROVIDER_PRESETS, DEFAULT_SHORTCUT, DOUBLE_TAP_WINDOW_MS, DEFAULT_AUTO_STOP, DEFAULT_DUAL_SOURCE, DEFAULT_TYPING, DEFAULT_AUTO_SEND, DEFAULT_MODE_SHORTCUTS, AUDIO_WARNING_MESSAGES, VAD_MODEL_ID, calculateWindowSizes, formatTimestamp
 } from "./components/types";
 
 // 设置类别配置
@@ -31,6 +31,8 @@ const settingsTabs: { id: SettingsTab; label: string; icon: React.ReactNode }[]
 export default function App() {
   const [state, setState] = useState<RecordingState>("idle");
   const [transcript, setTranscript] = useState("");
+  const [failedSession, setFailedSession] = useState<SessionFailure | null>(null);
+  const [isRetrying, setIsRetrying] = useState(false);
   const [viewMode, setViewMode] = useState<ViewMode>("main");
   const [isAnimating, setIsAnimating] = useState(false);
   const [settingsTab, setSettingsTab] = useState<SettingsTab>("general");
@@ -45,6 +47,8 @@ export default function App() {
     access_token: "",
     secret_key: "",
     shortcut: DEFAULT_SHORTCUT,
+    shortcut_mode: "push_to_talk",
+    cancel_shortcut: "Escape",
     auto_type: true,
     auto_copy: true,
     auto_start: false,
@@ -71,16 +75,27 @@ export default function App() {
     asr_language: "zh",
   });
   const [isRecordingShortcut, setIsRecordingShortcut] = useState(false);
+  // 录制快捷键时上一次单独按下的修饰键（识别双击）
+  const lastModifierTapRef = useRef<{ key: string; time: number } | null>(null);
+  // 后端无法监听全局按键时改为在窗口内录制快捷键
+  const [shortcutKeyFallback, setShortcutKeyFallback] = useState(false);
+  // 每次录制的编号，取消后忽略旧的录制结果
+  const shortcutCaptureRef = useRef(0);
   const animationFrameRef = useRef<number | null>(null);
 
   // Whisper 模型列表和下载进度
   const [whisperModels, setWhisperModels] = useState<WhisperModel[]>([]);
+  const [gpuBackends, setGpuBackends] = useState<WhisperGpuBackend[]>(["cpu"]);
+  const [importModelPath, setImportModelPath] = useState("");
   const [downloadingModel, setDownloadingModel] = useState<string | null>(null);
   const [downloadProgress, setDownloadProgress] = useState<number>(0);
   const [deletingModel, setDeletingModel] = useState<string | null>(null);
+  const [benchmarkingModel, setBenchmarkingModel] = useState<string | null>(null);
+  const [whisperBenchmarks, setWhisperBenchmarks] = useState<Record<string, WhisperBenchmark>>({});
 
   // 音频设备列表
   const [audioDevices, setAudioDevices] = useState<AudioDevice[]>([]);
+  const [activeInputDevice, setActiveInputDevice] = useState<ActiveInputDevice | null>(null);
 
   // 历史记录
   const [historyEntries, setHistoryEntries] = useState<HistoryEntry[]>([]);
@@ -95,6 +110,12 @@ export default function App() {
   const [logEntries, setLogEntries] = useState<string[]>([]);
   const logContainerRef = useRef<HTMLDivElement>(null);
 
+  // 键盘输出自检
+  const [keyboardTest, setKeyboardTest] = useState<KeyboardTestReport | null>(null);
+  const [keyboardTestTyped, setKeyboardTestTyped] = useState<boolean | null>(null);
+  const [isTestingKeyboard, setIsTestingKeyboard] = useState(false);
+  const keyboardTestInputRef = useRef<HTMLTextAreaElement>(null);
+
   // 初始化窗口尺寸（基于屏幕分辨率）
   useEffect(() => {
     const initWindowSize = async () => {
@@ -130,6 +151,11 @@ export default function App() {
     return typeof navigator !== 'undefined' && /Mac|iPhone|iPad|iPod/.test(navigator.platform);
   }, []);
 
+  // 主选区（中键粘贴）只有 Linux 有
+  const isLinux = useMemo(() => {
+    return typeof navigator !== 'undefined' && /Linux/.test(navigator.platform);
+  }, []);
+
   const statusText = isRecording
     ? "Listening..."
     : isProcessing
@@ -222,6 +248,21 @@ export default function App() {
     }, 4000);
   }, []);
 
+  // 用缓存的录音重新识别
+  const retryFailedSession = async () => {
+    setIsRetrying(true);
+    try {
+      const text = await invoke<string>("retry_last_session");
+      setTranscript(text);
+      setFailedSession(null);
+      showToast("Retry succeeded, copied to clipboard", "success");
+    } catch (e) {
+      showToast(`Retry failed: ${e}`);
+    } finally {
+      setIsRetrying(false);
+    }
+  };
+
   const dismissToast = useCallback((id: number) => {
     setToasts((prev) => prev.filter((t) => t.id !== id));
   }, []);
@@ -272,9 +313,16 @@ export default function App() {
   useEffect(() => {
     let unlistenStarted: UnlistenFn | null = null;
     let unlistenStopped: UnlistenFn | null = null;
+    let unlistenCancelled: UnlistenFn | null = null;
     let unlistenUpdate: UnlistenFn | null = null;
     let unlistenError: UnlistenFn | null = null;
     let unlistenDownloadProgress: UnlistenFn | null = null;
+    let unlistenSessionFailed: UnlistenFn | null = null;
+    let unlistenChecksumMismatch: UnlistenFn | null = null;
+    let unlistenDeviceActive: UnlistenFn | null = null;
+    let unlistenAudioWarning: UnlistenFn | null = null;
+    let unlistenAudioTruncated: UnlistenFn | null = null;
+    let unlistenOutputSuppressed: UnlistenFn | null = null;
 
     const setup = async () => {
       try {
@@ -296,6 +344,7 @@ export default function App() {
       unlistenStarted = await listen("recording-started", () => {
         setState("recording");
         setTranscript("");
+        setFailedSession(null);
       });
 
       unlistenStopped = await listen("recording-stopped", (event) => {
@@ -303,6 +352,12 @@ export default function App() {
         setTranscript(event.payload as string);
       });
 
+      // 录音被取消：丢弃识别结果
+      unlistenCancelled = await listen("recording-cancelled", () => {
+        setState("idle");
+        setTranscript("");
+      });
+
       unlistenUpdate = await listen("transcript-update", (event) => {
         setTranscript(event.payload as string);
       });
@@ -311,6 +366,45 @@ export default function App() {
         showToast(event.payload as string);
       });
 
+      // Provider 出错时保留录音，提供重试入口
+      unlistenSessionFailed = await listen("asr-session-failed", (event) => {
+        const failure = event.payload as SessionFailure;
+        setFailedSession(failure);
+        showToast(`Recognition failed: ${failure.error}`);
+      });
+
+      // 实际使用的麦克风（所选设备被拔出时回退到默认设备）
+      unlistenDeviceActive = await listen("audio-device-active", (event) => {
+        const device = event.payload as ActiveInputDevice;
+        setActiveInputDevice(device);
+        if (device.fallback) {
+          showToast(`Selected microphone not found, using ${device.name}`);
+        }
+      });
+
+      // 录音中持续削波或音量过低
+      unlistenAudioWarning = await listen("audio-warning", (event) => {
+        showToast(AUDIO_WARNING_MESSAGES[event.payload as AudioWarning]);
+      });
+
+      // 录音超过缓存上限，超出部分不能重试或保存
+      unlistenAudioTruncated = await listen("session-audio-truncated", (event) => {
+        const minutes = Math.round((event.payload as number) / 60);
+        showToast(`Recording longer than ${minutes} minutes, later audio won't be kept for retry`);
+      });
+
+      // 录音期间切换了窗口，结果只复制到剪贴板
+      unlistenOutputSuppressed = await listen("output-suppressed", () => {
+        showToast("Window changed during recording, text was not typed");
+      });
+
+      // 模型下载完成但校验失败：文件已删除，需要重新下载
+      unlistenChecksumMismatch = await listen("model-checksum-mismatch", (event) => {
+        const { model_id } = event.payload as { model_id: string; message: string };
+        setDownloadingModel(null);
+        showToast(`Checksum mismatch for ${model_id}, please download again`);
+      });
+
       // 监听模型下载进度
       unlistenDownloadProgress = await listen("model-download-progress", (event) => {
         const progress = event.payload as DownloadProgress;
@@ -330,9 +424,16 @@ export default function App() {
     return () => {
       unlistenStarted?.();
       unlistenStopped?.();
+      unlistenCancelled?.();
       unlistenUpdate?.();
       unlistenError?.();
       unlistenDownloadProgress?.();
+      unlistenSessionFailed?.();
+      unlistenChecksumMismatch?.();
+      unlistenDeviceActive?.();
+      unlistenAudioWarning?.();
+      unlistenAudioTruncated?.();
+      unlistenOutputSuppressed?.();
     };
   }, [showToast]);
 
@@ -380,6 +481,36 @@ export default function App() {
     }
   };
 
+  // 键盘输出自检：向下方的测试框输入测试文本，再对比实际收到的内容
+  const runKeyboardTest = async () => {
+    const input = keyboardTestInputRef.current;
+    if (!input) return;
+    input.value = "";
+    input.focus();
+    setIsTestingKeyboard(true);
+    setKeyboardTestTyped(null);
+    try {
+      const report = await invoke<KeyboardTestReport>("test_keyboard_output", { typeMarker: true });
+      setKeyboardTest(report);
+      setKeyboardTestTyped(report.marker !== null && input.value.includes(report.marker));
+    } catch (e) {
+      showToast(`Keyboard test failed: ${e}`);
+    } finally {
+      setIsTestingKeyboard(false);
+    }
+  };
+
+  // 用保存的录音文件重新识别
+  const retranscribeRecording = async (path: string) => {
+    try {
+      await invoke<string>("transcribe_recording", { path });
+      await loadHistory();
+      showToast("Re-transcribed, copied to clipboard", "success");
+    } catch (e) {
+      showToast(`Re-transcribe failed: ${e}`);
+    }
+  };
+
   const deleteHistoryEntry = async (id: string) => {
     try {
       await invoke("delete_history_entry", { id });
@@ -494,6 +625,17 @@ export default function App() {
     }
   }, [state, showToast]);
 
+  // 对最近一条记录重新执行后处理（设置页中手动触发时不输入到窗口）
+  const reprocessLastTranscript = async () => {
+    try {
+      const text = await invoke<string>("reprocess_last_transcript", { retype: false });
+      setTranscript(text);
+      showToast("Re-processed and copied to clipboard", "success");
+    } catch (e) {
+      showToast(`Re-process failed: ${e}`, "error");
+    }
+  };
+
   const saveConfig = async () => {
     try {
       await invoke("update_config", { config });
@@ -508,6 +650,30 @@ export default function App() {
     setConfig((prev) => ({ ...prev, [key]: value }));
   };
 
+  const updateAutoSend = (key: keyof AutoSendConfig, value: boolean | number) => {
+    setConfig((prev) => ({
+      ...prev,
+      postprocess: {
+        ...prev.postprocess,
+        auto_send: { ...(prev.postprocess.auto_send ?? DEFAULT_AUTO_SEND), [key]: value },
+      },
+    }));
+  };
+
+  const updateModeShortcut = (key: keyof ModeShortcuts, value: string) => {
+    setConfig((prev) => ({
+      ...prev,
+      postprocess: {
+        ...prev.postprocess,
+        shortcuts: { ...(prev.postprocess.shortcuts ?? DEFAULT_MODE_SHORTCUTS), [key]: value },
+      },
+    }));
+  };
+
+  const updateTyping = (key: keyof TypingConfig, value: number) => {
+    setConfig((prev) => ({ ...prev, typing: { ...(prev.typing ?? DEFAULT_TYPING), [key]: value } }));
+  };
+
   // 快捷键录入
   const handleShortcutKeyDown = (e: React.KeyboardEvent) => {
     e.preventDefault();
@@ -521,7 +687,26 @@ export default function App() {
     if (e.metaKey) parts.push(isMacOS ? "Cmd" : "Super");
 
     const key = e.key;
-    if (!["Control", "Alt", "Shift", "Meta"].includes(key)) {
+    const now = Date.now();
+    if (["Control", "Alt", "Shift", "Meta"].includes(key)) {
+      if (e.repeat) return;
+      // 同一修饰键快速按两次：双击触发
+      const last = lastModifierTapRef.current;
+      if (last && last.key === key && now - last.time <= DOUBLE_TAP_WINDOW_MS) {
+        const names: Record<string, string> = {
+          Control: "Ctrl",
+          Alt: isMacOS ? "Option" : "Alt",
+          Shift: "Shift",
+          Meta: isMacOS ? "Cmd" : "Super",
+        };
+        lastModifierTapRef.current = null;
+        updateConfig("shortcut", `DoubleTap+${names[key]}`);
+        setIsRecordingShortcut(false);
+      } else {
+        lastModifierTapRef.current = { key, time: now };
+      }
+    } else {
+      lastModifierTapRef.current = null;
       // 格式化按键名称
       let keyName = key;
       if (key === " ") keyName = "Space";
@@ -541,6 +726,46 @@ export default function App() {
     }
   };
 
+  // 窗口内录制鼠标侧键（按钮 3/4 对应 Mouse4/Mouse5）
+  const handleShortcutMouseDown = (e: React.MouseEvent) => {
+    if (e.button !== 3 && e.button !== 4) return;
+    e.preventDefault();
+    const parts: string[] = [];
+    if (e.ctrlKey) parts.push("Ctrl");
+    if (e.altKey) parts.push("Alt");
+    if (e.shiftKey) parts.push("Shift");
+    if (e.metaKey) parts.push("Super");
+    parts.push(e.button === 3 ? "Mouse4" : "Mouse5");
+    updateConfig("shortcut", parts.join("+"));
+    setIsRecordingShortcut(false);
+  };
+
+  // 由后端录制下一次按下的组合键
+  const captureShortcut = async () => {
+    if (isRecordingShortcut) return;
+    const id = ++shortcutCaptureRef.current;
+    setIsRecordingShortcut(true);
+    setShortcutKeyFallback(false);
+    try {
+      const shortcut = await invoke<string>("capture_shortcut");
+      if (shortcutCaptureRef.current !== id) return;
+      updateConfig("shortcut", shortcut);
+      setIsRecordingShortcut(false);
+    } catch (e) {
+      if (shortcutCaptureRef.current !== id) return;
+      if (String(e).includes("无法监听")) {
+        setShortcutKeyFallback(true);
+      } else {
+        setIsRecordingShortcut(false);
+      }
+    }
+  };
+
+  const cancelShortcutCapture = () => {
+    shortcutCaptureRef.current++;
+    setIsRecordingShortcut(false);
+  };
+
   // General 设置内容
   const renderGeneralSettings = () => (
     <div className="space-y-6">
@@ -551,7 +776,7 @@ export default function App() {
         </h3>
         <div className="bg-bg-secondary rounded-xl border border-border-light overflow-hidden">
           <div className="p-4">
-            <label className="block text-sm text-text-primary mb-2">Press to Talk Shortcut</label>
+            <label className="block text-sm text-text-primary mb-2">Recording Shortcut</label>
             <div className="flex gap-2">
               <div
                 tabIndex={0}
@@ -560,15 +785,16 @@ export default function App() {
                     ? "bg-accent/10 border-accent text-accent ring-2 ring-accent/30"
                     : "bg-bg-input border-border text-text-primary hover:border-accent/50"
                 }`}
-                onClick={() => setIsRecordingShortcut(true)}
-                onKeyDown={isRecordingShortcut ? handleShortcutKeyDown : undefined}
-                onBlur={() => setIsRecordingShortcut(false)}
+                onClick={captureShortcut}
+                onKeyDown={isRecordingShortcut && shortcutKeyFallback ? handleShortcutKeyDown : undefined}
+                onMouseDown={isRecordingShortcut && shortcutKeyFallback ? handleShortcutMouseDown : undefined}
+                onBlur={cancelShortcutCapture}
               >
                 {isRecordingShortcut ? "Press keys..." : config.shortcut}
               </div>
               {isRecordingShortcut ? (
                 <button
-                  onClick={() => setIsRecordingShortcut(false)}
+                  onClick={cancelShortcutCapture}
                   className="px-3 py-2 text-sm text-text-muted hover:text-text-primary transition-colors"
                 >
                   Cancel
@@ -586,7 +812,44 @@ export default function App() {
               )}
             </div>
             <p className="text-xs text-text-muted mt-2">
-              Click and press your desired key combination. Default: {DEFAULT_SHORTCUT}
+              Click and press your desired key combination, tap a modifier twice (e.g. Ctrl Ctrl), or press a mouse side button. Default: {DEFAULT_SHORTCUT}
+            </p>
+          </div>
+          <div className="p-4 border-t border-border-light">
+            <label className="block text-sm text-text-primary mb-2">Shortcut Mode</label>
+            <select
+              value={config.shortcut_mode ?? "push_to_talk"}
+              onChange={(e) => updateConfig("shortcut_mode", e.target.value as ShortcutMode)}
+              className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+              style={{ colorScheme: 'dark' }}
+            >
+              <option value="push_to_talk" className="bg-bg-secondary text-text-primary">Push to talk (hold to record)</option>
+              <option value="toggle" className="bg-bg-secondary text-text-primary">Toggle (tap to start, tap again to stop)</option>
+            </select>
+            {config.cancel_shortcut && (
+              <p className="text-xs text-text-muted mt-2">
+                Press {config.cancel_shortcut} while recording to cancel and discard the transcript
+              </p>
+            )}
+          </div>
+          <div className="p-4 border-t border-border-light">
+            <label className="block text-sm text-text-primary mb-2">Mode Shortcuts</label>
+            <div className="space-y-2">
+              {([["general", "General", "Alt+G"], ["code", "Code", "Alt+C"], ["meeting", "Meeting", "Alt+M"]] as const).map(([key, label, placeholder]) => (
+                <div key={key} className="flex items-center gap-2">
+                  <span className="w-16 text-xs text-text-secondary">{label}</span>
+                  <input
+                    type="text"
+                    value={(config.postprocess.shortcuts ?? DEFAULT_MODE_SHORTCUTS)[key]}
+                    onChange={(e) => updateModeShortcut(key, e.target.value)}
+                    placeholder={placeholder}
+                    className="flex-1 px-3 py-2 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+                  />
+                </div>
+              ))}
+            </div>
+            <p className="text-xs text-text-muted mt-2">
+              Record with a specific post-processing mode instead of picking it from the active app. Leave empty to disable
             </p>
           </div>
         </div>
@@ -606,10 +869,10 @@ export default function App() {
               className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
               style={{ colorScheme: 'dark' }}
             >
-              {audioDevices.map((device, index) => (
+              {audioDevices.map((device) => (
                 <option
-                  key={index}
-                  value={device.name}
+                  key={device.id}
+                  value={device.id}
                   className="bg-bg-secondary text-text-primary"
                 >
                   {device.name === "" ? "System Default" : device.name}
@@ -619,8 +882,199 @@ export default function App() {
             </select>
             <p className="text-xs text-text-muted mt-2">
               Select the microphone to use for recording
+              {activeInputDevice && ` · Last used: ${activeInputDevice.name}`}
+              {activeInputDevice?.fallback && " (fallback)"}
             </p>
           </div>
+          <div className="p-4 border-t border-border-light">
+            <div className="flex items-center justify-between mb-2">
+              <label className="text-sm text-text-primary">Input Gain</label>
+              <span className="text-xs text-text-muted">
+                {(config.input_gain_db ?? 0) > 0 ? "+" : ""}{config.input_gain_db ?? 0} dB
+              </span>
+            </div>
+            <input
+              type="range"
+              min={-12}
+              max={24}
+              step={1}
+              value={config.input_gain_db ?? 0}
+              onChange={(e) => setConfig((prev) => ({ ...prev, input_gain_db: Number(e.target.value) }))}
+              className="w-full accent-accent"
+            />
+            <p className="text-xs text-text-muted mt-2">
+              Boost quiet microphones in software (loud peaks are clipped)
+            </p>
+          </div>
+          <div className="p-4 border-t border-border-light">
+            <label className="block text-sm text-text-primary mb-2">Pre-roll Buffer</label>
+            <select
+              value={config.preroll_ms ?? 0}
+              onChange={(e) => setConfig((prev) => ({ ...prev, preroll_ms: Number(e.target.value) }))}
+              className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+              style={{ colorScheme: 'dark' }}
+            >
+              <option value={0} className="bg-bg-secondary text-text-primary">Off</option>
+              <option value={1000} className="bg-bg-secondary text-text-primary">1 second</option>
+              <option value={1500} className="bg-bg-secondary text-text-primary">1.5 seconds</option>
+              <option value={2000} className="bg-bg-secondary text-text-primary">2 seconds</option>
+            </select>
+            <p className="text-xs text-text-muted mt-2">
+              Keeps the microphone open to capture audio from just before the shortcut is pressed
+            </p>
+          </div>
+          <div className="p-4 border-t border-border-light">
+            <label className="block text-sm text-text-primary mb-2">High-pass Filter</label>
+            <select
+              value={config.high_pass_hz ?? 0}
+              onChange={(e) => setConfig((prev) => ({ ...prev, high_pass_hz: Number(e.target.value) }))}
+              className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+              style={{ colorScheme: 'dark' }}
+            >
+              <option value={0} className="bg-bg-secondary text-text-primary">Off</option>
+              <option value={80} className="bg-bg-secondary text-text-primary">80 Hz</option>
+              <option value={100} className="bg-bg-secondary text-text-primary">100 Hz</option>
+              <option value={120} className="bg-bg-secondary text-text-primary">120 Hz</option>
+            </select>
+            <p className="text-xs text-text-muted mt-2">
+              Remove desk thumps and HVAC rumble below the cutoff
+            </p>
+          </div>
+          <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors border-t border-border-light">
+            <div>
+              <span className="text-sm text-text-primary font-medium">Noise Suppression</span>
+              <p className="text-xs text-text-muted mt-1">Reduce fan and background noise with RNNoise</p>
+            </div>
+            <div className="relative shrink-0 ml-4">
+              <input
+                type="checkbox"
+                checked={config.noise_suppression ?? false}
+                onChange={(e) => updateConfig("noise_suppression", e.target.checked)}
+                className="sr-only peer"
+              />
+              <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+              <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+            </div>
+          </label>
+          <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors border-t border-border-light">
+            <div>
+              <span className="text-sm text-text-primary font-medium">Include System Audio</span>
+              <p className="text-xs text-text-muted mt-1">Mix speaker output with the microphone, e.g. for meeting notes</p>
+            </div>
+            <div className="relative shrink-0 ml-4">
+              <input
+                type="checkbox"
+                checked={config.dual_source?.enabled ?? false}
+                onChange={(e) =>
+                  setConfig((prev) => ({
+                    ...prev,
+                    dual_source: { ...(prev.dual_source ?? DEFAULT_DUAL_SOURCE), enabled: e.target.checked },
+                  }))
+                }
+                className="sr-only peer"
+              />
+              <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+              <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+            </div>
+          </label>
+          {config.dual_source?.enabled && (
+            <div className="p-4 border-t border-border-light space-y-3">
+              {(["mic_gain", "system_gain"] as const).map((key) => (
+                <div key={key}>
+                  <div className="flex items-center justify-between mb-2">
+                    <label className="text-sm text-text-primary">
+                      {key === "mic_gain" ? "Microphone Level" : "System Audio Level"}
+                    </label>
+                    <span className="text-xs text-text-muted">
+                      {Math.round((config.dual_source?.[key] ?? 1) * 100)}%
+                    </span>
+                  </div>
+                  <input
+                    type="range"
+                    min={0}
+                    max={2}
+                    step={0.1}
+                    value={config.dual_source?.[key] ?? 1}
+                    onChange={(e) =>
+                      setConfig((prev) => ({
+                        ...prev,
+                        dual_source: {
+                          ...(prev.dual_source ?? DEFAULT_DUAL_SOURCE),
+                          [key]: Number(e.target.value),
+                        },
+                      }))
+                    }
+                    className="w-full accent-accent"
+                  />
+                </div>
+              ))}
+            </div>
+          )}
+          <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors border-t border-border-light">
+            <div>
+              <span className="text-sm text-text-primary font-medium">Auto Stop on Silence</span>
+              <p className="text-xs text-text-muted mt-1">Tap the shortcut to start, recording stops after you finish speaking</p>
+            </div>
+            <div className="relative shrink-0 ml-4">
+              <input
+                type="checkbox"
+                checked={config.auto_stop?.enabled ?? false}
+                onChange={(e) =>
+                  setConfig((prev) => ({
+                    ...prev,
+                    auto_stop: { ...(prev.auto_stop ?? DEFAULT_AUTO_STOP), enabled: e.target.checked },
+                  }))
+                }
+                className="sr-only peer"
+              />
+              <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+              <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+            </div>
+          </label>
+          {config.auto_stop?.enabled && (
+            <div className="p-4 border-t border-border-light">
+              <label className="block text-sm text-text-primary mb-2">Silence Timeout</label>
+              <select
+                value={config.auto_stop.silence_secs}
+                onChange={(e) =>
+                  setConfig((prev) => ({
+                    ...prev,
+                    auto_stop: {
+                      ...(prev.auto_stop ?? DEFAULT_AUTO_STOP),
+                      silence_secs: Number(e.target.value),
+                    },
+                  }))
+                }
+                className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+                style={{ colorScheme: 'dark' }}
+              >
+                {[1, 1.5, 2, 3, 5, 8].map((secs) => (
+                  <option key={secs} value={secs} className="bg-bg-secondary text-text-primary">
+                    {secs} seconds
+                  </option>
+                ))}
+              </select>
+              <p className="text-xs text-text-muted mt-2">
+                Press the shortcut again to stop early
+              </p>
+            </div>
+          )}
+          <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors border-t border-border-light">
+            <div>
+              <span className="text-sm text-text-primary font-medium">Save Recordings</span>
+              <p className="text-xs text-text-muted mt-1">Keep each session as a WAV file for re-transcription</p>
+            </div>
+            <div className="relative shrink-0 ml-4">
+              <input
+                type="checkbox"
+                checked={config.save_recordings ?? false}
+                onChange={(e) => updateConfig("save_recordings", e.target.checked)}
+                className="sr-only peer"
+              />
+              <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+              <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+            </div>
+          </label>
         </div>
       </div>
 
@@ -649,7 +1103,40 @@ export default function App() {
               <option value="ru" className="bg-bg-secondary text-text-primary">Russian (Русский)</option>
             </select>
             <p className="text-xs text-text-muted mt-2">
-              Select the language you will be speaking. This applies to all ASR engines.
+              Select the language you will be speaking. Engines without an override below use this setting.
+            </p>
+          </div>
+          <div className="p-4 border-t border-border-light">
+            <label className="block text-sm text-text-primary mb-2">
+              Override for {config.asr.active_provider}
+            </label>
+            <select
+              value={config.provider_languages?.[config.asr.active_provider] ?? ""}
+              onChange={(e) => {
+                const languages = { ...(config.provider_languages ?? {}) };
+                if (e.target.value) {
+                  languages[config.asr.active_provider] = e.target.value;
+                } else {
+                  delete languages[config.asr.active_provider];
+                }
+                setConfig((prev) => ({ ...prev, provider_languages: languages }));
+              }}
+              className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+              style={{ colorScheme: 'dark' }}
+            >
+              <option value="" className="bg-bg-secondary text-text-primary">Use global language</option>
+              <option value="auto" className="bg-bg-secondary text-text-primary">Auto Detect</option>
+              <option value="zh" className="bg-bg-secondary text-text-primary">Chinese (中文)</option>
+              <option value="en" className="bg-bg-secondary text-text-primary">English</option>
+              <option value="ja" className="bg-bg-secondary text-text-primary">Japanese (日本語)</option>
+              <option value="ko" className="bg-bg-secondary text-text-primary">Korean (한국어)</option>
+              <option value="es" className="bg-bg-secondary text-text-primary">Spanish (Español)</option>
+              <option value="fr" className="bg-bg-secondary text-text-primary">French (Français)</option>
+              <option value="de" className="bg-bg-secondary text-text-primary">German (Deutsch)</option>
+              <option value="ru" className="bg-bg-secondary text-text-primary">Russian (Русский)</option>
+            </select>
+            <p className="text-xs text-text-muted mt-2">
+              Language used only by the current engine. Region codes (e.g. zh-CN) are added automatically for Azure and Amazon Transcribe.
             </p>
           </div>
         </div>
@@ -756,6 +1243,302 @@ export default function App() {
               <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
             </div>
           </label>
+          {config.auto_type && config.realtime_input && (
+            <div className="p-4 border-b border-border-light">
+              <label className="block text-sm text-text-primary mb-2">Realtime Correction</label>
+              <select
+                value={config.realtime_replace ?? "backspace"}
+                onChange={(e) => updateConfig("realtime_replace", e.target.value as RealtimeReplace)}
+                className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+                style={{ colorScheme: 'dark' }}
+              >
+                <option value="backspace" className="bg-bg-secondary text-text-primary">Backspace</option>
+                <option value="select" className="bg-bg-secondary text-text-primary">Select and replace</option>
+              </select>
+              <p className="text-xs text-text-muted mt-2">
+                Select the previous text with Shift+Arrow instead of repeated Backspace, for editors with autocomplete or bracket pairing
+              </p>
+            </div>
+          )}
+          <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors border-b border-border-light">
+            <div>
+              <span className="text-sm text-text-primary font-medium">Review Before Inserting</span>
+              <p className="text-xs text-text-muted mt-1">Show the result in a popup with a copy button instead of typing or pasting</p>
+            </div>
+            <div className="relative shrink-0 ml-4">
+              <input
+                type="checkbox"
+                checked={config.review_output ?? false}
+                onChange={(e) => updateConfig("review_output", e.target.checked)}
+                className="sr-only peer"
+              />
+              <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+              <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+            </div>
+          </label>
+          <label className={`flex items-center justify-between p-4 cursor-pointer transition-colors border-b border-border-light ${
+            config.auto_type ? "hover:bg-bg-tertiary" : "opacity-50 cursor-not-allowed"
+          }`}>
+            <div>
+              <span className="text-sm text-text-primary font-medium">Restore Clipboard</span>
+              <p className="text-xs text-text-muted mt-1">Put back the previous clipboard contents after pasting</p>
+            </div>
+            <div className="relative shrink-0 ml-4">
+              <input
+                type="checkbox"
+                checked={config.restore_clipboard ?? false}
+                onChange={(e) => updateConfig("restore_clipboard", e.target.checked)}
+                disabled={!config.auto_type}
+                className="sr-only peer"
+              />
+              <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+              <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+            </div>
+          </label>
+          {isLinux && (
+            <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors border-b border-border-light">
+              <div>
+                <span className="text-sm text-text-primary font-medium">Primary Selection</span>
+                <p className="text-xs text-text-muted mt-1">Also set the primary selection for middle-click paste</p>
+              </div>
+              <div className="relative shrink-0 ml-4">
+                <input
+                  type="checkbox"
+                  checked={config.copy_to_primary ?? false}
+                  onChange={(e) => updateConfig("copy_to_primary", e.target.checked)}
+                  className="sr-only peer"
+                />
+                <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+                <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+              </div>
+            </label>
+          )}
+          <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors border-b border-border-light">
+            <div>
+              <span className="text-sm text-text-primary font-medium">Spoken Punctuation</span>
+              <p className="text-xs text-text-muted mt-1">Turn "逗号", "句号", "new line" into punctuation and line breaks (add your own in the config file)</p>
+            </div>
+            <div className="relative shrink-0 ml-4">
+              <input
+                type="checkbox"
+                checked={config.spoken_commands?.enabled ?? false}
+                onChange={(e) => setConfig((prev) => ({
+                  ...prev,
+                  spoken_commands: { commands: [], ...prev.spoken_commands, enabled: e.target.checked },
+                }))}
+                className="sr-only peer"
+              />
+              <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+              <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+            </div>
+          </label>
+          <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors border-b border-border-light">
+            <div>
+              <span className="text-sm text-text-primary font-medium">Terminal-safe Output</span>
+              <p className="text-xs text-text-muted mt-1">Join lines into one when typing into a terminal so nothing runs early</p>
+            </div>
+            <div className="relative shrink-0 ml-4">
+              <input
+                type="checkbox"
+                checked={config.terminal_safe?.enabled ?? true}
+                onChange={(e) => setConfig((prev) => ({
+                  ...prev,
+                  terminal_safe: { apps: [], ...prev.terminal_safe, enabled: e.target.checked },
+                }))}
+                className="sr-only peer"
+              />
+              <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+              <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+            </div>
+          </label>
+          <div className="p-4 border-b border-border-light">
+            <label className="block text-sm text-text-primary mb-2">After Inserting</label>
+            <select
+              value={config.trailing_key ?? "none"}
+              onChange={(e) => updateConfig("trailing_key", e.target.value as TrailingKey)}
+              className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+              style={{ colorScheme: 'dark' }}
+            >
+              <option value="none" className="bg-bg-secondary text-text-primary">Do nothing</option>
+              <option value="space" className="bg-bg-secondary text-text-primary">Press Space</option>
+              <option value="enter" className="bg-bg-secondary text-text-primary">Press Enter</option>
+            </select>
+            <p className="text-xs text-text-muted mt-2">
+              Press Enter to send chat messages hands-free (not used with realtime input)
+            </p>
+          </div>
+          <div className="p-4 border-b border-border-light">
+            <label className="block text-sm text-text-primary mb-2">Auto Send by Mode</label>
+            <div className="flex gap-2">
+              {([["general", "General"], ["code", "Code"], ["meeting", "Meeting"]] as const).map(([key, label]) => {
+                const enabled = (config.postprocess.auto_send ?? DEFAULT_AUTO_SEND)[key];
+                return (
+                  <button
+                    key={key}
+                    onClick={() => updateAutoSend(key, !enabled)}
+                    className={`flex-1 px-3 py-2 text-xs rounded-lg transition-all ${
+                      enabled
+                        ? "bg-accent text-white shadow-sm"
+                        : "bg-bg-tertiary text-text-secondary hover:text-text-primary"
+                    }`}
+                  >
+                    {label}
+                  </button>
+                );
+              })}
+            </div>
+            <select
+              value={(config.postprocess.auto_send ?? DEFAULT_AUTO_SEND).min_confidence}
+              onChange={(e) => updateAutoSend("min_confidence", Number(e.target.value))}
+              className="w-full mt-3 px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+              style={{ colorScheme: 'dark' }}
+            >
+              <option value={0} className="bg-bg-secondary text-text-primary">Always send</option>
+              <option value={0.7} className="bg-bg-secondary text-text-primary">Only if confidence ≥ 70%</option>
+              <option value={0.8} className="bg-bg-secondary text-text-primary">Only if confidence ≥ 80%</option>
+              <option value={0.9} className="bg-bg-secondary text-text-primary">Only if confidence ≥ 90%</option>
+            </select>
+            <p className="text-xs text-text-muted mt-2">
+              Press Enter after inserting in the selected modes, e.g. General for Slack or WeChat replies
+            </p>
+          </div>
+          <div className="p-4 border-b border-border-light">
+            <label className="block text-sm text-text-primary mb-2">Window Lock</label>
+            <select
+              value={config.focus_lock ?? "off"}
+              onChange={(e) => updateConfig("focus_lock", e.target.value as FocusLock)}
+              className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+              style={{ colorScheme: 'dark' }}
+            >
+              <option value="off" className="bg-bg-secondary text-text-primary">Off</option>
+              <option value="refocus" className="bg-bg-secondary text-text-primary">Switch back to original window</option>
+              <option value="suppress" className="bg-bg-secondary text-text-primary">Don't type if window changed</option>
+            </select>
+            <p className="text-xs text-text-muted mt-2">
+              Keep output in the window that was focused when recording started
+            </p>
+          </div>
+          <div className="p-4 border-b border-border-light">
+            <label className="block text-sm text-text-primary mb-2">Keyboard Backend</label>
+            <select
+              value={config.keyboard_backend ?? "enigo"}
+              onChange={(e) => updateConfig("keyboard_backend", e.target.value as KeyboardBackend)}
+              className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+              style={{ colorScheme: 'dark' }}
+            >
+              <option value="enigo" className="bg-bg-secondary text-text-primary">Default</option>
+              <option value="ydotool" className="bg-bg-secondary text-text-primary">ydotool (Wayland)</option>
+              <option value="accessibility" className="bg-bg-secondary text-text-primary">Accessibility API (macOS)</option>
+              <option value="send_input" className="bg-bg-secondary text-text-primary">SendInput Unicode (Windows)</option>
+            </select>
+            <p className="text-xs text-text-muted mt-2">
+              Use ydotool when the compositor blocks simulated input (requires ydotoold running); on macOS the Accessibility API inserts text directly, bypassing input methods
+            </p>
+            {config.keyboard_backend === "send_input" && (
+              <div className="mt-3">
+                <label className="block text-sm text-text-primary mb-2">
+                  Chunk Delay: {config.send_input_delay_ms ?? 10} ms
+                </label>
+                <input
+                  type="range"
+                  min={0}
+                  max={100}
+                  step={5}
+                  value={config.send_input_delay_ms ?? 10}
+                  onChange={(e) => setConfig((prev) => ({ ...prev, send_input_delay_ms: Number(e.target.value) }))}
+                  className="w-full accent-accent"
+                />
+                <p className="text-xs text-text-muted mt-2">
+                  Pause between batches of characters; raise it if an app still drops characters
+                </p>
+              </div>
+            )}
+          </div>
+          <div className="p-4 border-b border-border-light space-y-3">
+            <div>
+              <label className="block text-sm text-text-primary mb-2">Typing Speed</label>
+              <select
+                value={(config.typing ?? DEFAULT_TYPING).chars_per_second}
+                onChange={(e) => updateTyping("chars_per_second", Number(e.target.value))}
+                className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+                style={{ colorScheme: 'dark' }}
+              >
+                <option value={0} className="bg-bg-secondary text-text-primary">Unlimited</option>
+                <option value={100} className="bg-bg-secondary text-text-primary">100 chars/s</option>
+                <option value={50} className="bg-bg-secondary text-text-primary">50 chars/s</option>
+                <option value={20} className="bg-bg-secondary text-text-primary">20 chars/s</option>
+              </select>
+            </div>
+            <div>
+              <label className="block text-sm text-text-primary mb-2">
+                Key Delay: {(config.typing ?? DEFAULT_TYPING).key_delay_ms} ms
+              </label>
+              <input
+                type="range"
+                min={0}
+                max={50}
+                step={5}
+                value={(config.typing ?? DEFAULT_TYPING).key_delay_ms}
+                onChange={(e) => updateTyping("key_delay_ms", Number(e.target.value))}
+                className="w-full accent-accent"
+              />
+            </div>
+            <div>
+              <label className="block text-sm text-text-primary mb-2">
+                Paste Settle Time: {(config.typing ?? DEFAULT_TYPING).paste_settle_ms} ms
+              </label>
+              <input
+                type="range"
+                min={0}
+                max={500}
+                step={25}
+                value={(config.typing ?? DEFAULT_TYPING).paste_settle_ms}
+                onChange={(e) => updateTyping("paste_settle_ms", Number(e.target.value))}
+                className="w-full accent-accent"
+              />
+            </div>
+            <p className="text-xs text-text-muted">
+              Slow down typing for remote desktop sessions that drop characters
+            </p>
+          </div>
+          <div className="p-4 border-b border-border-light">
+            <div className="flex items-center justify-between">
+              <div>
+                <span className="text-sm text-text-primary font-medium">Test Keyboard Output</span>
+                <p className="text-xs text-text-muted mt-1">Types a test string below with the saved backend and checks the environment</p>
+              </div>
+              <button
+                onClick={runKeyboardTest}
+                disabled={isTestingKeyboard}
+                className="shrink-0 ml-4 px-3 py-1.5 text-xs rounded-lg border border-border text-text-secondary hover:text-text-primary hover:bg-bg-tertiary transition-colors disabled:opacity-50"
+              >
+                {isTestingKeyboard ? "Testing..." : "Run Test"}
+              </button>
+            </div>
+            <textarea
+              ref={keyboardTestInputRef}
+              rows={2}
+              placeholder="Test output appears here"
+              className="w-full mt-3 px-3 py-2 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary resize-none"
+            />
+            {keyboardTest && (
+              <div className="mt-2 space-y-1">
+                {keyboardTestTyped !== null && (
+                  <p className={`text-xs font-medium ${keyboardTestTyped ? "text-green-500" : "text-red-500"}`}>
+                    {keyboardTestTyped
+                      ? "Test string received"
+                      : `Test string not received${keyboardTest.error ? `: ${keyboardTest.error}` : ""}`}
+                  </p>
+                )}
+                {keyboardTest.checks.map((check) => (
+                  <p key={check.name} className="text-xs text-text-muted">
+                    <span className={check.ok ? "text-green-500" : "text-red-500"}>{check.ok ? "✓" : "✗"}</span>{" "}
+                    {check.name}: {check.detail}
+                  </p>
+                ))}
+              </div>
+            )}
+          </div>
           <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors">
             <div>
               <span className="text-sm text-text-primary font-medium">Show Indicator</span>
@@ -867,11 +1650,38 @@ export default function App() {
     try {
       const models = await invoke<WhisperModel[]>("get_whisper_models");
       setWhisperModels(models);
+      setGpuBackends(await invoke<WhisperGpuBackend[]>("get_whisper_gpu_backends"));
     } catch (e) {
       console.error("Failed to load whisper models:", e);
     }
   }, []);
 
+  // 导入本地 Whisper 模型文件
+  const handleImportModel = async () => {
+    if (!importModelPath.trim()) return;
+    try {
+      const model = await invoke<WhisperModel>("import_whisper_model", { path: importModelPath.trim() });
+      setImportModelPath("");
+      showToast(`Imported ${model.id}`, "success");
+      loadWhisperModels();
+    } catch (e) {
+      showToast(`Import failed: ${e}`);
+    }
+  };
+
+  // 测试 Whisper 模型在本机的推理速度
+  const handleBenchmarkModel = async (modelId: string) => {
+    setBenchmarkingModel(modelId);
+    try {
+      const result = await invoke<WhisperBenchmark>("benchmark_whisper_model", { modelId });
+      setWhisperBenchmarks((prev) => ({ ...prev, [modelId]: result }));
+    } catch (e) {
+      showToast(`Benchmark failed: ${e}`);
+    } finally {
+      setBenchmarkingModel(null);
+    }
+  };
+
   // 下载 Whisper 模型
   const handleDownloadModel = async (modelId: string) => {
     setDownloadingModel(modelId);
@@ -910,6 +1720,14 @@ export default function App() {
     }));
   };
 
+  // 更新 Provider 快捷键列表
+  const updateProviderShortcuts = (update: (shortcuts: ProviderShortcut[]) => ProviderShortcut[]) => {
+    setConfig(prev => ({
+      ...prev,
+      asr: { ...prev.asr, shortcuts: update(prev.asr.shortcuts ?? []) }
+    }));
+  };
+
   // ASR 设置内容
   const renderAsrSettings = () => (
     <div className="space-y-6">
@@ -949,6 +1767,56 @@ export default function App() {
         </div>
       </div>
 
+      {/* 绑定 Provider 的快捷键 */}
+      <div className="space-y-3">
+        <h3 className="text-xs font-medium text-text-muted uppercase tracking-wider">
+          Provider Shortcuts
+        </h3>
+        <div className="bg-bg-secondary rounded-xl border border-border-light p-4 space-y-2">
+          {(config.asr.shortcuts ?? []).map((binding, index) => (
+            <div key={index} className="flex items-center gap-2">
+              <input
+                type="text"
+                value={binding.shortcut}
+                onChange={(e) => updateProviderShortcuts(list =>
+                  list.map((b, i) => (i === index ? { ...b, shortcut: e.target.value } : b))
+                )}
+                placeholder="Alt+W"
+                className="flex-1 px-3 py-2 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+              />
+              <select
+                value={binding.provider}
+                onChange={(e) => updateProviderShortcuts(list =>
+                  list.map((b, i) => (i === index ? { ...b, provider: e.target.value as ProviderShortcut["provider"] } : b))
+                )}
+                className="flex-1 px-3 py-2 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+                style={{ colorScheme: 'dark' }}
+              >
+                <option value="doubao" className="bg-bg-secondary text-text-primary">Doubao</option>
+                <option value="whisper_local" className="bg-bg-secondary text-text-primary">Whisper Local</option>
+                <option value="whisper_api" className="bg-bg-secondary text-text-primary">Whisper API</option>
+              </select>
+              <button
+                onClick={() => updateProviderShortcuts(list => list.filter((_, i) => i !== index))}
+                className="p-2 text-text-muted hover:text-red-500 transition-colors"
+                title="Remove"
+              >
+                <TrashIcon />
+              </button>
+            </div>
+          ))}
+          <button
+            onClick={() => updateProviderShortcuts(list => [...list, { shortcut: "", provider: "whisper_local" }])}
+            className="w-full px-3 py-2 text-sm text-text-secondary hover:text-text-primary border border-dashed border-border rounded-lg transition-colors"
+          >
+            Add Shortcut
+          </button>
+          <p className="text-xs text-text-muted">
+            Record with a different engine without changing the default, e.g. Whisper Local when offline
+          </p>
+        </div>
+      </div>
+
       {/* 豆包配置 */}
       {config.asr.active_provider === "doubao" && (
         <div className="space-y-3">
@@ -1008,6 +1876,61 @@ export default function App() {
               </p>
             </div>
           </div>
+          <div className="bg-bg-secondary rounded-xl border border-border-light overflow-hidden">
+            {([
+              ["enable_punc", "Punctuation", "Add punctuation on the server", true],
+              ["enable_itn", "Inverse Text Normalization", "Convert spoken numbers to digits (一百二十 → 120)", true],
+              ["show_utterances", "Utterance Details", "Return sentence and word timings", false],
+            ] as const).map(([key, title, description, defaultValue]) => (
+              <label
+                key={key}
+                className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors border-b border-border-light"
+              >
+                <div>
+                  <span className="text-sm text-text-primary font-medium">{title}</span>
+                  <p className="text-xs text-text-muted mt-1">{description}</p>
+                </div>
+                <div className="relative shrink-0 ml-4">
+                  <input
+                    type="checkbox"
+                    checked={config.asr.doubao?.[key] ?? defaultValue}
+                    onChange={(e) => setConfig(prev => ({
+                      ...prev,
+                      asr: {
+                        ...prev.asr,
+                        doubao: { ...prev.asr.doubao!, [key]: e.target.checked }
+                      }
+                    }))}
+                    className="sr-only peer"
+                  />
+                  <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+                  <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+                </div>
+              </label>
+            ))}
+            <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors">
+              <div>
+                <span className="text-sm text-text-primary font-medium">Opus Compression</span>
+                <p className="text-xs text-text-muted mt-1">Upload Opus instead of raw PCM (~10x less bandwidth)</p>
+              </div>
+              <div className="relative shrink-0 ml-4">
+                <input
+                  type="checkbox"
+                  checked={config.asr.doubao?.audio_codec === "opus"}
+                  onChange={(e) => setConfig(prev => ({
+                    ...prev,
+                    asr: {
+                      ...prev.asr,
+                      doubao: { ...prev.asr.doubao!, audio_codec: e.target.checked ? "opus" : "pcm" }
+                    }
+                  }))}
+                  className="sr-only peer"
+                />
+                <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+                <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+              </div>
+            </label>
+          </div>
         </div>
       )}
 
@@ -1023,21 +1946,74 @@ export default function App() {
                 Select and download a Whisper model. Larger models are more accurate but slower.
               </p>
             </div>
+            <div className="p-4 border-b border-border-light">
+              <label className="block text-sm text-text-primary mb-2">Inference Backend</label>
+              <select
+                value={config.asr.whisper_local?.gpu_backend ?? "cpu"}
+                onChange={(e) => setConfig((prev) => ({
+                  ...prev,
+                  asr: {
+                    ...prev.asr,
+                    whisper_local: {
+                      ...prev.asr.whisper_local!,
+                      gpu_backend: e.target.value as WhisperGpuBackend,
+                    },
+                  },
+                }))}
+                className="w-full px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+                style={{ colorScheme: 'dark' }}
+              >
+                {gpuBackends.map((backend) => (
+                  <option key={backend} value={backend} className="bg-bg-secondary text-text-primary">
+                    {backend === "auto" ? "GPU (Auto)" : backend.toUpperCase()}
+                  </option>
+                ))}
+              </select>
+              <p className="text-xs text-text-muted mt-2">
+                GPU backends are only listed when this build was compiled with CUDA, Metal or Vulkan support
+              </p>
+            </div>
             <div className="divide-y divide-border-light">
               {whisperModels.map((model) => (
                 <div key={model.id} className="p-4 flex items-center justify-between">
                   <div className="flex items-center gap-3">
-                    <input
-                      type="radio"
-                      checked={model.is_selected}
-                      onChange={() => invoke("set_whisper_model", { modelId: model.id })}
-                      disabled={!model.is_downloaded}
-                      className="w-4 h-4 accent-accent"
-                    />
+                    {model.id === VAD_MODEL_ID ? (
+                      // VAD 模型与 Whisper 模型搭配使用，单独开关
+                      <input
+                        type="checkbox"
+                        checked={!!config.asr.whisper_local?.vad_segmentation}
+                        onChange={(e) => setConfig((prev) => ({
+                          ...prev,
+                          asr: {
+                            ...prev.asr,
+                            whisper_local: {
+                              ...prev.asr.whisper_local!,
+                              vad_segmentation: e.target.checked,
+                            },
+                          },
+                        }))}
+                        disabled={!model.is_downloaded}
+                        className="w-4 h-4 accent-accent"
+                      />
+                    ) : (
+                      <input
+                        type="radio"
+                        checked={model.is_selected}
+                        onChange={() => invoke("set_whisper_model", { modelId: model.id })}
+                        disabled={!model.is_downloaded}
+                        className="w-4 h-4 accent-accent"
+                      />
+                    )}
                     <div>
                       <div className="text-sm font-medium text-text-primary">{model.name}</div>
                       <div className="text-xs text-text-muted">
                         {(model.size_bytes / 1_000_000).toFixed(0)} MB
+                        {whisperBenchmarks[model.id] && (
+                          <span title="Inference time / audio duration on a built-in 10s sample">
+                            {` · load ${(whisperBenchmarks[model.id].load_ms / 1000).toFixed(1)}s`}
+                            {` · ${whisperBenchmarks[model.id].realtime_factor.toFixed(2)}x realtime`}
+                          </span>
+                        )}
                       </div>
                     </div>
                   </div>
@@ -1045,6 +2021,17 @@ export default function App() {
                     {model.is_downloaded ? (
                       <>
                         <span className="text-xs text-green-500 font-medium">Downloaded</span>
+                        {model.id !== VAD_MODEL_ID && (
+                          <button
+                            type="button"
+                            onClick={() => handleBenchmarkModel(model.id)}
+                            disabled={benchmarkingModel !== null}
+                            className="px-2 py-1 text-xs text-text-muted hover:text-text-primary hover:bg-bg-tertiary rounded-lg transition-colors disabled:opacity-40 disabled:cursor-not-allowed"
+                            title="Measure load time and realtime factor"
+                          >
+                            {benchmarkingModel === model.id ? "Testing..." : "Benchmark"}
+                          </button>
+                        )}
                         <button
                           type="button"
                           onClick={() => handleDeleteModel(model.id)}
@@ -1084,6 +2071,28 @@ export default function App() {
                 </div>
               ))}
             </div>
+            <div className="p-4 border-t border-border-light">
+              <label className="block text-sm text-text-primary mb-2">Import Model File</label>
+              <div className="flex gap-2">
+                <input
+                  type="text"
+                  value={importModelPath}
+                  onChange={(e) => setImportModelPath(e.target.value)}
+                  placeholder="/path/to/ggml-model.bin"
+                  className="flex-1 px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+                />
+                <button
+                  onClick={handleImportModel}
+                  disabled={!importModelPath.trim()}
+                  className="px-3 py-1.5 text-xs bg-accent text-white rounded-lg hover:bg-accent-hover transition-colors disabled:opacity-50"
+                >
+                  Import
+                </button>
+              </div>
+              <p className="text-xs text-text-muted mt-2">
+                Sideload a ggml / gguf model for offline machines; it is copied into the models folder
+              </p>
+            </div>
           </div>
         </div>
       )}
@@ -1192,6 +2201,52 @@ export default function App() {
             </p>
           </div>
 
+          {/* 重新后处理最近一条记录 */}
+          <div className="space-y-3">
+            <h3 className="text-xs font-medium text-text-muted uppercase tracking-wider">
+              Re-run on Last Transcript
+            </h3>
+            <div className="bg-bg-secondary rounded-xl border border-border-light overflow-hidden">
+              <div className="p-4 border-b border-border-light">
+                <label className="block text-sm text-text-primary mb-2">Shortcut</label>
+                <div className="flex gap-2">
+                  <input
+                    type="text"
+                    value={config.reprocess_shortcut ?? ""}
+                    onChange={(e) => updateConfig("reprocess_shortcut", e.target.value)}
+                    placeholder="Alt+R"
+                    className="flex-1 px-3 py-2.5 text-sm border border-border rounded-lg focus:outline-none focus:border-accent transition-colors bg-bg-input text-text-primary"
+                  />
+                  <button
+                    onClick={() => reprocessLastTranscript()}
+                    className="px-3 py-2 text-sm text-text-secondary hover:text-text-primary border border-border rounded-lg transition-colors"
+                  >
+                    Run Now
+                  </button>
+                </div>
+                <p className="text-xs text-text-muted mt-2">
+                  Polish the latest history entry again (e.g. after the LLM timed out) and copy the result
+                </p>
+              </div>
+              <label className="flex items-center justify-between p-4 cursor-pointer hover:bg-bg-tertiary transition-colors">
+                <div>
+                  <span className="text-sm text-text-primary font-medium">Type Result</span>
+                  <p className="text-xs text-text-muted mt-1">Also type the improved text into the focused window</p>
+                </div>
+                <div className="relative shrink-0 ml-4">
+                  <input
+                    type="checkbox"
+                    checked={config.reprocess_retype ?? false}
+                    onChange={(e) => updateConfig("reprocess_retype", e.target.checked)}
+                    className="sr-only peer"
+                  />
+                  <div className="w-11 h-6 bg-bg-tertiary rounded-full peer peer-checked:bg-accent transition-colors" />
+                  <div className="absolute top-0.5 left-0.5 w-5 h-5 bg-white rounded-full shadow-sm transition-transform peer-checked:translate-x-5" />
+                </div>
+              </label>
+            </div>
+          </div>
+
           {/* API Providers */}
           <div className="space-y-3">
             <div className="flex items-center justify-between">
@@ -1453,6 +2508,15 @@ export default function App() {
                   >
                     <CopyIcon />
                   </button>
+                  {entry.recording && (
+                    <button
+                      onClick={() => retranscribeRecording(entry.recording!)}
+                      className="p-1.5 text-text-muted hover:text-text-primary hover:bg-bg-tertiary rounded transition-colors"
+                      title="Re-transcribe recording"
+                    >
+                      <RedoIcon />
+                    </button>
+                  )}
                   <button
                     onClick={() => deleteHistoryEntry(entry.id)}
                     className="p-1.5 text-text-muted hover:text-red-500 hover:bg-red-500/10 rounded transition-colors"
@@ -1464,7 +2528,34 @@ export default function App() {
               </div>
               <p className="text-xs text-text-muted mt-2">
                 {new Date(entry.timestamp).toLocaleString()}
+                {entry.language && ` · ${entry.language.toUpperCase()}`}
+                {entry.audio_stats?.warnings?.map((warning) => (
+                  <span
+                    key={warning}
+                    className="ml-1 text-amber-500"
+                    title={`${AUDIO_WARNING_MESSAGES[warning]} (peak ${entry.audio_stats!.peak_dbfs.toFixed(1)} dBFS)`}
+                  >
+                    · {warning === "clipping" ? "Clipping" : "Too quiet"}
+                  </span>
+                ))}
               </p>
+              {entry.segments && entry.segments.length > 1 && (
+                <details className="mt-2">
+                  <summary className="text-xs text-text-muted cursor-pointer hover:text-text-primary">
+                    {entry.segments.length} segments
+                  </summary>
+                  <div className="mt-2 space-y-1">
+                    {entry.segments.map((segment, i) => (
+                      <div key={i} className="flex gap-2 text-xs">
+                        <span className="text-text-muted font-mono shrink-0">
+                          {formatTimestamp(segment.start)}
+                        </span>
+                        <span className="text-text-primary break-words">{segment.text}</span>
+                      </div>
+                    ))}
+                  </div>
+                </details>
+              )}
             </div>
           ))}
         </div>
@@ -1870,6 +2961,18 @@ export default function App() {
               {transcript || "Transcription will appear here"}
             </p>
           </div>
+
+          {failedSession && (
+            <button
+              onClick={retryFailedSession}
+              disabled={isRetrying}
+              className="mt-3 px-3 py-1.5 text-xs rounded-lg border border-border text-text-secondary hover:text-text-primary hover:bg-bg-tertiary transition-colors disabled:opacity-50"
+            >
+              {isRetrying
+                ? "Retrying..."
+                : `Retry last ${Math.round(failedSession.audio_secs)}s recording`}
+            </button>
+          )}
         </div>
       )}
     </div>
@@ -3,7 +3,45 @@
 export type RecordingState = "idle" | "recording" | "processing";
 export type SettingsTab = "general" | "asr" | "postprocess" | "history" | "config" | "logs";
 export type ViewMode = "main" | "settings";
-export type AsrProviderType = "doubao" | "whisper_local" | "whisper_api";
+export type AsrProviderType =
+  | "doubao"
+  | "whisper_local"
+  | "whisper_api"
+  | "openai_realtime"
+  | "azure"
+  | "aws_transcribe"
+  | "deepgram"
+  | "vosk"
+  | "funasr_local"
+  | "sense_voice"
+  | "moonshine"
+  | "iflytek"
+  | "tencent"
+  | "alibaba"
+  | "custom_ws"
+  | "whisper_server"
+  | "speaches"
+  | "soniox"
+  | "baidu"
+  | "mock";
+export type TrailingKey = "none" | "space" | "enter";
+export type FocusLock = "off" | "refocus" | "suppress";
+export type ShortcutMode = "push_to_talk" | "toggle";
+export interface KeyboardCheck {
+  name: string;
+  ok: boolean;
+  detail: string;
+}
+
+export interface KeyboardTestReport {
+  backend: KeyboardBackend;
+  checks: KeyboardCheck[];
+  marker: string | null;
+  error: string | null;
+}
+
+export type RealtimeReplace = "backspace" | "select";
+export type KeyboardBackend = "enigo" | "ydotool" | "accessibility" | "send_input";
 export type PostProcessMode = "General" | "Code" | "Meeting";
 
 export interface WindowSizes {
@@ -11,40 +49,175 @@ export interface WindowSizes {
   settings: { width: number; height: number };
 }
 
+export interface DualSourceConfig {
+  enabled: boolean;
+  system_device: string;
+  mic_gain: number;
+  system_gain: number;
+}
+
+export const DEFAULT_DUAL_SOURCE: DualSourceConfig = {
+  enabled: false,
+  system_device: "",
+  mic_gain: 1,
+  system_gain: 1,
+};
+
+export interface AutoStopConfig {
+  enabled: boolean;
+  silence_secs: number;
+  threshold_db: number;
+}
+
+export const DEFAULT_AUTO_STOP: AutoStopConfig = {
+  enabled: false,
+  silence_secs: 2,
+  threshold_db: -45,
+};
+
+export interface TypingConfig {
+  chars_per_second: number;
+  key_delay_ms: number;
+  paste_settle_ms: number;
+  focus_settle_ms: number;
+}
+
+export const DEFAULT_TYPING: TypingConfig = {
+  chars_per_second: 0,
+  key_delay_ms: 0,
+  paste_settle_ms: 50,
+  focus_settle_ms: 100,
+};
+
 export interface Config {
   app_id: string;
   access_token: string;
   secret_key: string;
   shortcut: string;
+  shortcut_mode?: ShortcutMode;
+  cancel_shortcut?: string;
+  reprocess_shortcut?: string;
+  reprocess_retype?: boolean;
   auto_type: boolean;
   auto_copy: boolean;
   auto_start: boolean;
   silent_start: boolean;
   show_indicator: boolean;
   realtime_input: boolean;
+  realtime_replace?: RealtimeReplace;
+  focus_lock?: FocusLock;
+  copy_to_primary?: boolean;
+  trailing_key?: TrailingKey;
+  review_output?: boolean;
+  terminal_safe?: TerminalSafeConfig;
+  keyboard_backend?: KeyboardBackend;
+  send_input_delay_ms?: number;
+  typing?: TypingConfig;
+  restore_clipboard?: boolean;
   postprocess: PostProcessConfig;
   audio_device: string;
+  input_gain_db?: number;
+  high_pass_hz?: number;
+  noise_suppression?: boolean;
+  preroll_ms?: number;
+  dual_source?: DualSourceConfig;
+  auto_stop?: AutoStopConfig;
+  save_recordings?: boolean;
+  max_recordings?: number;
+  recording_retention_days?: number;
   asr: AsrConfig;
   asr_language: string;
+  provider_languages?: Record<string, string>;
+  hotwords: string[];
+  profanity_filter?: ProfanityFilterConfig;
+  spoken_commands?: SpokenCommandsConfig;
+}
+
+export type ProfanityMode = "mask" | "remove";
+
+export interface TerminalSafeConfig {
+  enabled: boolean;
+  apps: string[];
+}
+
+export interface SpokenCommand {
+  phrase: string;
+  replacement: string;
+}
+
+export interface SpokenCommandsConfig {
+  enabled: boolean;
+  commands: SpokenCommand[];
+}
+
+export interface ProfanityFilterConfig {
+  enabled: boolean;
+  mode: ProfanityMode;
+  words: string[];
+}
+
+export type DualSelection = "auto" | "primary";
+
+export interface ProviderShortcut {
+  shortcut: string;
+  provider: AsrProviderType;
 }
 
 export interface AsrConfig {
   active_provider: AsrProviderType;
+  secondary_provider?: AsrProviderType;
+  dual_selection: DualSelection;
+  shortcuts?: ProviderShortcut[];
   doubao?: DoubaoConfig;
   whisper_local?: WhisperLocalConfig;
   whisper_api?: WhisperApiConfig;
+  azure?: AzureSpeechConfig;
+  aws_transcribe?: AwsTranscribeConfig;
+  deepgram?: DeepgramConfig;
+  vosk?: VoskConfig;
+  funasr_local?: FunAsrLocalConfig;
+  sense_voice?: SenseVoiceLocalConfig;
+  moonshine?: MoonshineLocalConfig;
+  iflytek?: IflytekConfig;
+  tencent?: TencentAsrConfig;
+  alibaba?: AlibabaNlsConfig;
+  custom_ws?: CustomWsConfig;
+  whisper_server?: WhisperServerConfig;
+  speaches?: SpeachesConfig;
+  soniox?: SonioxConfig;
+  baidu?: BaiduConfig;
 }
 
 export interface DoubaoConfig {
   app_id: string;
   access_token: string;
   secret_key: string;
+  enable_punc?: boolean;
+  enable_itn?: boolean;
+  show_utterances?: boolean;
+  result_type?: "single" | "full";
+  audio_codec?: AudioCodec;
 }
 
+export type AudioCodec = "pcm" | "opus";
+
 export interface WhisperLocalConfig {
   model_size: string;
   language: string;
   translate_to_english: boolean;
+  partial_interval_ms?: number;
+  vad_segmentation?: boolean;
+  gpu_backend?: WhisperGpuBackend;
+}
+
+export type WhisperGpuBackend = "cpu" | "auto" | "cuda" | "metal" | "vulkan";
+
+export interface WhisperBenchmark {
+  model_id: string;
+  load_ms: number;
+  inference_ms: number;
+  audio_ms: number;
+  realtime_factor: number;
 }
 
 export interface WhisperApiConfig {
@@ -54,6 +227,113 @@ export interface WhisperApiConfig {
   language?: string;
 }
 
+export interface AzureSpeechConfig {
+  subscription_key: string;
+  region: string;
+  endpoint?: string;
+  language?: string;
+}
+
+export interface AwsTranscribeConfig {
+  region: string;
+  access_key_id: string;
+  secret_access_key: string;
+  session_token?: string;
+  endpoint?: string;
+  language?: string;
+}
+
+export interface DeepgramConfig {
+  api_key: string;
+  endpoint: string;
+  model: string;
+  language?: string;
+}
+
+export interface VoskConfig {
+  model: "small_cn" | "small_en_us";
+  model_path?: string;
+}
+
+export interface FunAsrLocalConfig {
+  model: "paraformer_zh" | "paraformer_zh_small";
+  model_path?: string;
+}
+
+export interface SenseVoiceLocalConfig {
+  model: "small";
+  model_path?: string;
+  language?: string;
+}
+
+export interface MoonshineLocalConfig {
+  model: "tiny_en" | "base_en";
+  model_path?: string;
+}
+
+export interface IflytekConfig {
+  app_id: string;
+  api_key: string;
+  api_secret: string;
+  endpoint: string;
+  language?: string;
+}
+
+export interface TencentAsrConfig {
+  app_id: string;
+  secret_id: string;
+  secret_key: string;
+  engine_model_type?: string;
+  language?: string;
+}
+
+export interface AlibabaNlsConfig {
+  appkey: string;
+  token: string;
+  endpoint: string;
+}
+
+export interface CustomWsConfig {
+  url: string;
+  headers: Record<string, string>;
+  start_message?: string;
+  audio_message?: string;
+  end_message?: string;
+  text_field: string;
+  final_field?: string;
+  error_field?: string;
+  language?: string;
+}
+
+export interface WhisperServerConfig {
+  base_url: string;
+  timeout_secs: number;
+  language?: string;
+}
+
+export interface SpeachesConfig {
+  base_url: string;
+  model: string;
+  api_key?: string;
+  vad_filter: boolean;
+  word_timestamps: boolean;
+  language?: string;
+}
+
+export interface SonioxConfig {
+  api_key: string;
+  endpoint: string;
+  model: string;
+  language?: string;
+}
+
+export interface BaiduConfig {
+  api_key: string;
+  secret_key: string;
+  dev_pid?: number;
+  language?: string;
+}
+
 export interface WhisperModel {
   id: string;
   name: string;
@@ -82,19 +362,120 @@ export interface PostProcessConfig {
   providers: LlmProvider[];
   active_provider_id: string;
   mode: PostProcessMode;
+  mode_pinned?: boolean;
+  mode_rules?: ModeRule[];
+  templates?: OutputTemplates;
+  auto_send?: AutoSendConfig;
+  shortcuts?: ModeShortcuts;
+}
+
+export interface ModeShortcuts {
+  general: string;
+  code: string;
+  meeting: string;
+}
+
+export const DEFAULT_MODE_SHORTCUTS: ModeShortcuts = {
+  general: "",
+  code: "",
+  meeting: "",
+};
+
+export interface AutoSendConfig {
+  general: boolean;
+  code: boolean;
+  meeting: boolean;
+  min_confidence: number;
+}
+
+export const DEFAULT_AUTO_SEND: AutoSendConfig = {
+  general: false,
+  code: false,
+  meeting: false,
+  min_confidence: 0,
+};
+
+export interface OutputTemplates {
+  general: string;
+  code: string;
+  meeting: string;
+}
+
+export interface ModeRule {
+  pattern: string;
+  mode: PostProcessMode;
 }
 
 export interface HistoryEntry {
   id: string;
   text: string;
   timestamp: string;
+  raw_text?: string;
+  confidence?: number;
+  low_confidence?: boolean;
+  waveform?: number[];
+  metrics?: SessionMetrics;
+  candidates?: TranscriptCandidate[];
+  segments?: AsrSegment[];
+  language?: string;
+  recording?: string;
+  audio_stats?: AudioStats;
+}
+
+export interface AsrSegment {
+  text: string;
+  start: number;
+  end: number;
+  confidence?: number;
+}
+
+export interface TranscriptCandidate {
+  provider: string;
+  text: string;
+  confidence?: number;
+  selected: boolean;
+}
+
+export interface SessionMetrics {
+  provider: string;
+  audio_ms: number;
+  decode_ms: number;
+  rtf: number;
+  first_partial_ms?: number;
+  bytes_uploaded: number;
+}
+
+export interface SessionFailure {
+  error: string;
+  audio_secs: number;
 }
 
 export interface AudioDevice {
+  id: string;
   name: string;
   is_default: boolean;
 }
 
+export interface ActiveInputDevice {
+  id: string;
+  name: string;
+  fallback: boolean;
+}
+
+export type AudioWarning = "clipping" | "too_quiet";
+
+export interface AudioStats {
+  peak_dbfs: number;
+  rms_dbfs: number;
+  clipped_ratio: number;
+  warnings?: AudioWarning[];
+}
+
+export const AUDIO_WARNING_MESSAGES: Record<AudioWarning, string> = {
+  clipping: "Input is clipping, lower the input gain or move away from the microphone",
+  too_quiet: "Input is very quiet, check the microphone or raise the input gain",
+};
+
 export interface LogInfo {
   path: string;
   size: number;
@@ -156,6 +537,18 @@ export const PROVIDER_PRESETS: Record<string, ProviderPreset> = {
 
 // 默认配置
 export const DEFAULT_SHORTCUT = "Alt+Space";
+// 双击修饰键的最大间隔（与后端一致）
+export const DOUBLE_TAP_WINDOW_MS = 400;
+
+// Whisper 模型列表中的 Silero VAD 条目
+export const VAD_MODEL_ID = "silero_vad.onnx";
+
+// 秒数格式化为 mm:ss（用于分段时间戳）
+export const formatTimestamp = (seconds: number): string => {
+  const total = Math.max(0, Math.floor(seconds));
+  const minutes = Math.floor(total / 60);
+  return `${String(minutes).padStart(2, "0")}:${String(total % 60).padStart(2, "0")}`;
+};
 
 // 计算窗口尺寸（基于屏幕分辨率百分比）
 export const calculateWindowSizes = (screenWidth: number, screenHeight: number): WindowSizes => ({
@@ -168,3 +561,9 @@ export const calculateWindowSizes = (screenWidth: number, screenHeight: number):
     height: Math.max(380, Math.round(screenHeight * 0.32)),
   },
 });
+
+export interface ShortcutConflict {
+  shortcut: string;
+  reason: string;
+  suggestions: string[];
+}
@@ -6,11 +6,14 @@ type IndicatorState = "recording" | "processing" | "not-configured";
 
 function Indicator() {
   const [state, setState] = useState<IndicatorState>("recording");
+  // 切换模式：松开快捷键后继续录音，再按一次停止
+  const [toggle, setToggle] = useState(false);
 
   useEffect(() => {
     const setupListeners = async () => {
-      const unlistenRecording = await listen("recording-started", () => {
+      const unlistenRecording = await listen<{ toggle: boolean }>("recording-started", (event) => {
         setState("recording");
+        setToggle(event.payload?.toggle ?? false);
       });
 
       const unlistenProcessing = await listen("recording-stopped", () => {
@@ -67,6 +70,14 @@ function Indicator() {
               <div className="w-1 bg-white/90 rounded-full animate-wave-1" style={{ height: '40%' }} />
             </div>
             <span className="text-xs font-medium tracking-wide">Listening</span>
+            {toggle && (
+              <>
+                {/* 锁定图标：再按一次快捷键停止 */}
+                <svg className="w-3.5 h-3.5 text-white/80" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2.5">
+                  <path strokeLinecap="round" strokeLinejoin="round" d="M12 15v2m-6 4h12a2 2 0 002-2v-6a2 2 0 00-2-2H6a2 2 0 00-2 2v6a2 2 0 002 2zm10-10V7a4 4 0 00-8 0v4h8z" />
+                </svg>
+              </>
+            )}
           </>
         ) : (
           <>
@@ -0,0 +1,87 @@
+import { createRoot } from "react-dom/client";
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { writeText } from "@tauri-apps/plugin-clipboard-manager";
+
+// 预览模式：显示识别结果，由用户决定复制或关闭
+function Review() {
+  const [text, setText] = useState("");
+  const [copied, setCopied] = useState(false);
+
+  const dismiss = () => {
+    getCurrentWindow().hide();
+  };
+
+  const copy = async () => {
+    try {
+      await writeText(text);
+      setCopied(true);
+      setTimeout(dismiss, 400);
+    } catch (e) {
+      console.error("Failed to copy transcript:", e);
+    }
+  };
+
+  useEffect(() => {
+    const unlisten = listen<string>("review-transcript", (event) => {
+      setText(event.payload);
+      setCopied(false);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") {
+        dismiss();
+      } else if (e.key === "Enter" && (e.metaKey || e.ctrlKey)) {
+        copy();
+      }
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  });
+
+  return (
+    <div className="w-screen h-screen p-2">
+      <div className="h-full flex flex-col rounded-xl bg-slate-800/95 text-slate-100 shadow-lg shadow-slate-900/40 border border-slate-700">
+        <div
+          data-tauri-drag-region
+          className="flex items-center justify-between px-4 pt-3 pb-2 text-xs text-slate-400"
+        >
+          <span data-tauri-drag-region>Transcript</span>
+          <button
+            onClick={dismiss}
+            className="px-1.5 text-slate-400 hover:text-slate-100 transition-colors"
+            title="Dismiss (Esc)"
+          >
+            ✕
+          </button>
+        </div>
+        <div className="flex-1 overflow-y-auto px-4 text-sm leading-relaxed whitespace-pre-wrap select-text">
+          {text}
+        </div>
+        <div className="flex justify-end gap-2 px-4 py-3">
+          <button
+            onClick={dismiss}
+            className="px-3 py-1.5 text-xs rounded-lg text-slate-300 hover:bg-slate-700 transition-colors"
+          >
+            Dismiss
+          </button>
+          <button
+            onClick={copy}
+            className="px-3 py-1.5 text-xs font-medium rounded-lg bg-sky-500 text-white hover:bg-sky-600 transition-colors"
+          >
+            {copied ? "Copied" : "Copy"}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+const root = createRoot(document.getElementById("root")!);
+root.render(<Review />);
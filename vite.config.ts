@@ -16,6 +16,7 @@ export default defineConfig(async () => ({
       input: {
         main: resolve(__dirname, "index.html"),
         indicator: resolve(__dirname, "indicator.html"),
+        review: resolve(__dirname, "review.html"),
       },
       output: {
         // 优化 chunk 分割